@@ -2,16 +2,7 @@
 
 set -e
 
-cargo run -- --crate-name "$1" "../programs/$1.cl"
-echo
+crate_name="$1"
+shift
 
-echo 'Compiling LLVM IR...'
-llc --filetype=obj -o ../programs/bin/main.o ../programs/bin/main.ll
-
-echo 'Linking program...'
-gcc -std=c17 -lm -o ../programs/bin/main ../programs/bin/main.o
-
-echo 'Running program...'
-echo
-
-../programs/bin/main
+cargo run -- run --crate-name "$crate_name" "../programs/$crate_name.cl" -- "$@"
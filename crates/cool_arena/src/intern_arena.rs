@@ -11,6 +11,16 @@ pub struct InternedValue<'a, T>(&'a T)
 where
     T: ?Sized;
 
+impl<'a, T> InternedValue<'a, T>
+where
+    T: ?Sized,
+{
+    #[inline]
+    pub fn into_ref(self) -> &'a T {
+        self.0
+    }
+}
+
 impl<T> Clone for InternedValue<'_, T>
 where
     T: ?Sized,
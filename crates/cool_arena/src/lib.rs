@@ -1,7 +1,9 @@
 mod arena;
 mod intern_arena;
+mod shared_intern_arena;
 mod unsafe_bump;
 
 pub use self::arena::*;
 pub use self::intern_arena::*;
+pub use self::shared_intern_arena::*;
 pub use self::unsafe_bump::*;
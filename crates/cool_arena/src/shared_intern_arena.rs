@@ -0,0 +1,104 @@
+use crate::{InternArena, InternedValue};
+use rustc_hash::FxHashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+/// Process-wide storage of interned values. The values are never freed, but equal values are
+/// stored only once, so memory grows with the number of distinct values rather than with the
+/// number of contexts interning them.
+pub type SharedInterner<T> = Mutex<InternArena<'static, T>>;
+
+/// Values interned by a single context. The values themselves are stored in a
+/// `SharedInterner`, so they stay valid after the arena is dropped, while lookups only see the
+/// values inserted through this arena.
+pub struct SharedInternArena<T>
+where
+    T: ?Sized + 'static,
+{
+    interner: &'static SharedInterner<T>,
+    values: FxHashSet<&'static T>,
+}
+
+impl<T> SharedInternArena<T>
+where
+    T: ?Sized + 'static,
+{
+    pub fn new(interner: &'static SharedInterner<T>) -> Self {
+        Self {
+            interner,
+            values: Default::default(),
+        }
+    }
+}
+
+impl<T> SharedInternArena<T>
+where
+    T: ?Sized + Eq + Hash + 'static,
+{
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.values.contains(value)
+    }
+
+    #[must_use]
+    pub fn get(&self, value: &T) -> Option<InternedValue<'static, T>> {
+        self.values
+            .get(value)
+            .map(|&value| InternedValue::from(value))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = InternedValue<'static, T>> + '_ {
+        self.values.iter().copied().map(InternedValue::from)
+    }
+}
+
+impl<T> SharedInternArena<T>
+where
+    T: Eq + Hash + 'static,
+{
+    pub fn insert(&mut self, value: T) -> InternedValue<'static, T> {
+        if let Some(value) = self.get(&value) {
+            return value;
+        }
+
+        let value = self.interner.lock().unwrap().insert(value);
+        self.values.insert(value.into_ref());
+        value
+    }
+}
+
+impl<E> SharedInternArena<[E]>
+where
+    E: Copy + Eq + Hash + 'static,
+{
+    pub fn insert_slice(&mut self, value: &[E]) -> InternedValue<'static, [E]> {
+        if let Some(value) = self.get(value) {
+            return value;
+        }
+
+        let value = self.interner.lock().unwrap().insert_slice(value);
+        self.values.insert(value.into_ref());
+        value
+    }
+
+    pub fn insert_slice_if_not_exists(
+        &mut self,
+        value: &[E],
+    ) -> Option<InternedValue<'static, [E]>> {
+        if self.values.contains(value) {
+            return None;
+        }
+
+        Some(self.insert_slice(value))
+    }
+}
+
+impl<T> fmt::Debug for SharedInternArena<T>
+where
+    T: ?Sized + fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.values, f)
+    }
+}
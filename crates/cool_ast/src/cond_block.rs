@@ -1,11 +1,12 @@
-use crate::{AstGenerator, AstResult, ExprAst};
-use cool_parser::CondBlock;
-use cool_resolve::{FrameId, TyId};
+use crate::{AstError, AstGenerator, AstResult, ExprAst};
+use cool_parser::{CondBlock, Expr, Ty};
+use cool_resolve::{BindingId, FrameId, TyId, VariantTy};
 use cool_span::{Section, Span};
 
 #[derive(Clone, Debug)]
 pub struct CondBlockAst {
     pub cond: Box<ExprAst>,
+    pub binding_id: Option<BindingId>,
     pub expr: Box<ExprAst>,
 }
 
@@ -23,11 +24,61 @@ impl AstGenerator<'_> {
         expected_ty_id: TyId,
         block: &CondBlock,
     ) -> AstResult<CondBlockAst> {
-        let cond = self.gen_expr(frame_id, self.tys().bool, &block.cond)?;
+        let Expr::Cast(cast_expr) = &block.cond else {
+            let cond = self.gen_expr(frame_id, self.tys().bool, &block.cond)?;
+            let expr = self.gen_block_expr(frame_id, expected_ty_id, &block.expr)?;
+
+            return Ok(CondBlockAst {
+                cond: Box::new(cond),
+                binding_id: None,
+                expr: Box::new(expr),
+            });
+        };
+
+        // `if x as p { ... }` unwraps the optional `x` into the binding `p`
+        let ident = match cast_expr.ty.as_ref() {
+            Ty::Path(path_ty) if path_ty.ty_args.is_none() => {
+                match path_ty.idents.as_slice() {
+                    [ident] => Some(*ident),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let base = self.gen_expr(frame_id, self.tys().infer, &cast_expr.base)?;
+
+        let inner_ty_id = base
+            .expr_id()
+            .ty_id
+            .as_variant()
+            .and_then(VariantTy::get_optional_inner);
+
+        let (Some(ident), Some(inner_ty_id)) = (ident, inner_ty_id) else {
+            let cond = self.continue_gen_cast_expr(frame_id, self.tys().bool, base, cast_expr)?;
+            let expr = self.gen_block_expr(frame_id, expected_ty_id, &block.expr)?;
+
+            return Ok(CondBlockAst {
+                cond: Box::new(cond),
+                binding_id: None,
+                expr: Box::new(expr),
+            });
+        };
+
+        let frame_id = self.resolve.add_frame(frame_id.into());
+
+        let binding_id = self
+            .resolve
+            .insert_local_binding(frame_id, false, ident.symbol, Some(inner_ty_id))
+            .map_err(|error| AstError::new(ident.span, error))?;
+
+        self.index_local_binding(binding_id, ident.span);
+
         let expr = self.gen_block_expr(frame_id, expected_ty_id, &block.expr)?;
 
         Ok(CondBlockAst {
-            cond: Box::new(cond),
+            cond: Box::new(base),
+            binding_id: Some(binding_id),
             expr: Box::new(expr),
         })
     }
@@ -0,0 +1,738 @@
+use crate::{
+    AstError, AstGenerator, AstResult, AstResultExt, BinaryExprAst, CastExprAst, CastKind,
+    ConstEvalError, ExprAst, LiteralExprAst, LiteralExprValue, UnaryExprAst, UnitExprAst,
+};
+use cool_parser::{
+    ArithmeticOp, BinOp, BitwiseOp, ComparisonOp, Expr, LogicalOp, StaticAssertDecl, Ty,
+    UnaryOpKind,
+};
+use cool_resolve::{ConstValue, FloatTy, ItemId, ModuleId, ResolveExpr, Scope, TyId};
+use cool_span::{Section, Span};
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug)]
+struct IntLayout {
+    ty_id: TyId,
+    bits: u32,
+    is_signed: bool,
+}
+
+impl IntLayout {
+    fn min(&self) -> i128 {
+        if self.is_signed {
+            i128::MIN >> (128 - self.bits)
+        } else {
+            0
+        }
+    }
+
+    fn max(&self) -> u128 {
+        if self.is_signed {
+            (i128::MAX >> (128 - self.bits)) as u128
+        } else {
+            u128::MAX >> (128 - self.bits)
+        }
+    }
+
+    /// Truncates the bits of a value to this layout.
+    fn wrap(&self, value: u128) -> u128 {
+        let shift = 128 - self.bits;
+
+        if self.is_signed {
+            (((value << shift) as i128) >> shift) as u128
+        } else {
+            (value << shift) >> shift
+        }
+    }
+
+    fn check_signed(&self, value: Option<i128>) -> Result<u128, ConstEvalError> {
+        value
+            .filter(|&value| value >= self.min() && (value < 0 || value as u128 <= self.max()))
+            .map(|value| value as u128)
+            .ok_or(ConstEvalError::Overflow { ty_id: self.ty_id })
+    }
+
+    fn check_unsigned(&self, value: Option<u128>) -> Result<u128, ConstEvalError> {
+        value
+            .filter(|&value| value <= self.max())
+            .ok_or(ConstEvalError::Overflow { ty_id: self.ty_id })
+    }
+
+    fn saturate_signed(&self, value: i128) -> u128 {
+        if value < self.min() {
+            self.min() as u128
+        } else if value >= 0 && value as u128 > self.max() {
+            self.max()
+        } else {
+            value as u128
+        }
+    }
+
+    fn compare(&self, lhs: u128, rhs: u128) -> Ordering {
+        if self.is_signed {
+            (lhs as i128).cmp(&(rhs as i128))
+        } else {
+            lhs.cmp(&rhs)
+        }
+    }
+}
+
+impl AstGenerator<'_> {
+    pub fn define_const(
+        &mut self,
+        module_id: ModuleId,
+        item_id: ItemId,
+        ty: Option<&Ty>,
+        expr: &Expr,
+    ) -> AstResult {
+        let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+
+        let expected_ty_id = ty
+            .map(|ty| self.resolve_ty(module_id, ty))
+            .transpose()?
+            .unwrap_or(self.tys().infer);
+
+        let frame_id = self.resolve.add_frame(module_id.into());
+        let expr = self.gen_expr(frame_id, expected_ty_id, expr)?;
+        let value = self.eval_const_expr(&expr)?;
+
+        self.resolve
+            .define_const(binding_id, expr.expr_id().ty_id, value);
+
+        Ok(())
+    }
+
+    pub fn check_static_assert(
+        &mut self,
+        module_id: ModuleId,
+        decl: &StaticAssertDecl,
+    ) -> AstResult {
+        let frame_id = self.resolve.add_frame(module_id.into());
+        let cond = self.gen_expr(frame_id, self.tys().bool, &decl.cond)?;
+
+        if self.eval_const_expr(&cond)?.as_bool() != Some(true) {
+            return AstResult::error(decl.span(), ConstEvalError::StaticAssertFailed);
+        }
+
+        Ok(())
+    }
+
+    pub fn resolve_array_len(&mut self, scope: Scope, len: &Expr) -> AstResult<u64> {
+        let frame_id = match scope {
+            Scope::Frame(frame_id) => frame_id,
+            Scope::Module(_) => self.resolve.add_frame(scope),
+        };
+
+        let len_expr = self.gen_expr(frame_id, self.tys().infer_int, len)?;
+        let len_ty_id = len_expr.expr_id().ty_id;
+        let value = self.eval_const_expr(&len_expr)?.as_int().unwrap();
+
+        if len_ty_id.is_signed_int() && (value as i128) < 0 || value > u64::MAX as u128 {
+            return AstResult::error(len.span(), ConstEvalError::InvalidArrayLen);
+        }
+
+        Ok(value as u64)
+    }
+
+    pub fn gen_const_value_expr(
+        &mut self,
+        span: Span,
+        found_ty_id: TyId,
+        expected_ty_id: TyId,
+        value: ConstValue,
+    ) -> AstResult<ExprAst> {
+        self.resolve_expr(
+            span,
+            found_ty_id,
+            expected_ty_id,
+            |resolve, span, ty_id| -> ExprAst {
+                let expr_id = resolve.add_expr(ResolveExpr::rvalue(ty_id));
+
+                let value = match value {
+                    ConstValue::Unit => return UnitExprAst { span, expr_id }.into(),
+                    ConstValue::Bool(value) => LiteralExprValue::Bool(value),
+                    ConstValue::Char(value) => LiteralExprValue::Char(value),
+                    ConstValue::Int(value) => LiteralExprValue::Int(value),
+                    ConstValue::Float(value) => LiteralExprValue::Float(value),
+                    ConstValue::Cstr(value) => LiteralExprValue::Cstr(value),
+                };
+
+                LiteralExprAst {
+                    span,
+                    expr_id,
+                    value,
+                }
+                .into()
+            },
+        )
+    }
+
+    pub fn eval_const_expr(&self, expr: &ExprAst) -> AstResult<ConstValue> {
+        let value = match expr {
+            ExprAst::Literal(expr) => self.eval_const_literal_expr(expr),
+            ExprAst::Unit(_) => Ok(ConstValue::Unit),
+            ExprAst::Binding(binding_expr) => {
+                self.resolve
+                    .get_const_value(binding_expr.binding_id)
+                    .cloned()
+                    .ok_or(ConstEvalError::NotConstant)
+            }
+            ExprAst::SizeOf(expr) => Ok(ConstValue::Int(expr.value as u128)),
+            ExprAst::AlignOf(expr) => Ok(ConstValue::Int(expr.value as u128)),
+            ExprAst::OffsetOf(expr) => Ok(ConstValue::Int(expr.value as u128)),
+            ExprAst::Unary(expr) => return self.eval_const_unary_expr(expr),
+            ExprAst::Binary(expr) => return self.eval_const_binary_expr(expr),
+            ExprAst::Cast(expr) => return self.eval_const_cast_expr(expr),
+            _ => Err(ConstEvalError::NotConstant),
+        };
+
+        value.map_err(|error| AstError::new(expr.span(), error))
+    }
+
+    fn eval_const_literal_expr(&self, expr: &LiteralExprAst) -> Result<ConstValue, ConstEvalError> {
+        let value = match &expr.value {
+            LiteralExprValue::Int(value) => {
+                // Substituted constants are already stored in their canonical form
+                let layout = self.get_int_layout(expr.expr_id.ty_id);
+
+                if layout.wrap(*value) != *value {
+                    return Err(ConstEvalError::Overflow {
+                        ty_id: layout.ty_id,
+                    });
+                }
+
+                ConstValue::Int(*value)
+            }
+            LiteralExprValue::Float(value) => {
+                ConstValue::Float(round_float(expr.expr_id.ty_id, *value))
+            }
+            LiteralExprValue::Bool(value) => ConstValue::Bool(*value),
+            LiteralExprValue::Char(value) => ConstValue::Char(*value),
+            LiteralExprValue::Cstr(value) => ConstValue::Cstr(value.clone()),
+        };
+
+        Ok(value)
+    }
+
+    fn eval_const_unary_expr(&self, expr: &UnaryExprAst) -> AstResult<ConstValue> {
+        let value = self.eval_const_expr(&expr.expr)?;
+        let ty_id = expr.expr_id.ty_id;
+
+        let value = match (expr.op.kind, value) {
+            (UnaryOpKind::Minus, ConstValue::Int(value)) => {
+                let layout = self.get_int_layout(ty_id);
+
+                if layout.is_signed {
+                    layout.check_signed((value as i128).checked_neg())
+                } else {
+                    layout.check_unsigned((value == 0).then_some(0))
+                }
+                .map(ConstValue::Int)
+            }
+            (UnaryOpKind::Minus, ConstValue::Float(value)) => Ok(ConstValue::Float(-value)),
+            (UnaryOpKind::Not, ConstValue::Bool(value)) => Ok(ConstValue::Bool(!value)),
+            (UnaryOpKind::Not, ConstValue::Int(value)) => {
+                Ok(ConstValue::Int(self.get_int_layout(ty_id).wrap(!value)))
+            }
+            _ => Err(ConstEvalError::NotConstant),
+        };
+
+        value.map_err(|error| AstError::new(expr.span(), error))
+    }
+
+    fn eval_const_binary_expr(&self, expr: &BinaryExprAst) -> AstResult<ConstValue> {
+        let lhs = self.eval_const_expr(&expr.lhs)?;
+
+        // Logical operators short-circuit
+        if let (BinOp::Logical(op), ConstValue::Bool(lhs)) = (expr.bin_op, &lhs) {
+            match (op, lhs) {
+                (LogicalOp::And, false) => return Ok(ConstValue::Bool(false)),
+                (LogicalOp::Or, true) => return Ok(ConstValue::Bool(true)),
+                _ => return self.eval_const_expr(&expr.rhs),
+            }
+        }
+
+        let rhs = self.eval_const_expr(&expr.rhs)?;
+        let operand_ty_id = expr.lhs.expr_id().ty_id;
+
+        let value = match (expr.bin_op, lhs, rhs) {
+            (BinOp::Arithmetic(op), ConstValue::Int(lhs), ConstValue::Int(rhs)) => {
+                fold_int_arithmetic(self.get_int_layout(operand_ty_id), op, lhs, rhs)
+                    .map(ConstValue::Int)
+            }
+            (BinOp::Arithmetic(op), ConstValue::Float(lhs), ConstValue::Float(rhs)) => {
+                let value = match op {
+                    ArithmeticOp::Add => lhs + rhs,
+                    ArithmeticOp::Sub => lhs - rhs,
+                    ArithmeticOp::Mul => lhs * rhs,
+                    ArithmeticOp::Div => lhs / rhs,
+                    ArithmeticOp::Rem => lhs % rhs,
+                    _ => return AstResult::error(expr.span(), ConstEvalError::NotConstant),
+                };
+
+                Ok(ConstValue::Float(round_float(operand_ty_id, value)))
+            }
+            (BinOp::Bitwise(op), ConstValue::Int(lhs), ConstValue::Int(rhs)) => {
+                fold_int_bitwise(self.get_int_layout(operand_ty_id), op, lhs, rhs)
+                    .map(ConstValue::Int)
+            }
+            (BinOp::Bitwise(op), ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => {
+                match op {
+                    BitwiseOp::And => Ok(ConstValue::Bool(lhs & rhs)),
+                    BitwiseOp::Or => Ok(ConstValue::Bool(lhs | rhs)),
+                    BitwiseOp::Xor => Ok(ConstValue::Bool(lhs ^ rhs)),
+                    _ => Err(ConstEvalError::NotConstant),
+                }
+            }
+            (BinOp::Comparison(op), lhs, rhs) => {
+                let ordering = match (lhs, rhs) {
+                    (ConstValue::Int(lhs), ConstValue::Int(rhs)) => {
+                        Some(self.get_int_layout(operand_ty_id).compare(lhs, rhs))
+                    }
+                    (ConstValue::Float(lhs), ConstValue::Float(rhs)) => lhs.partial_cmp(&rhs),
+                    (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => Some(lhs.cmp(&rhs)),
+                    (ConstValue::Char(lhs), ConstValue::Char(rhs)) => Some(lhs.cmp(&rhs)),
+                    (ConstValue::Unit, ConstValue::Unit) => Some(Ordering::Equal),
+                    _ => return AstResult::error(expr.span(), ConstEvalError::NotConstant),
+                };
+
+                Ok(ConstValue::Bool(fold_comparison(op, ordering)))
+            }
+            _ => Err(ConstEvalError::NotConstant),
+        };
+
+        value.map_err(|error| AstError::new(expr.span(), error))
+    }
+
+    fn eval_const_cast_expr(&self, expr: &CastExprAst) -> AstResult<ConstValue> {
+        let value = self.eval_const_expr(&expr.base)?;
+        let from_ty_id = expr.from_ty_id();
+        let to_ty_id = expr.to_ty_id();
+
+        let value = match (expr.kind, value) {
+            (CastKind::IntToInt, ConstValue::Int(value)) => {
+                ConstValue::Int(self.get_int_layout(to_ty_id).wrap(value))
+            }
+            (CastKind::IntToFloat, ConstValue::Int(value)) => {
+                let value = if from_ty_id.is_signed_int() {
+                    value as i128 as f64
+                } else {
+                    value as f64
+                };
+
+                ConstValue::Float(round_float(to_ty_id, value))
+            }
+            (CastKind::FloatToFloat, ConstValue::Float(value)) => {
+                ConstValue::Float(round_float(to_ty_id, value))
+            }
+            (CastKind::FloatToInt, ConstValue::Float(value)) => {
+                let layout = self.get_int_layout(to_ty_id);
+
+                // Out of range values saturate, like Rust's 'as'
+                let value = if layout.is_signed {
+                    layout.saturate_signed(value as i128)
+                } else {
+                    (value as u128).min(layout.max())
+                };
+
+                ConstValue::Int(value)
+            }
+            _ => return AstResult::error(expr.span(), ConstEvalError::NotConstant),
+        };
+
+        Ok(value)
+    }
+
+    fn get_int_layout(&self, ty_id: TyId) -> IntLayout {
+        IntLayout {
+            ty_id,
+            bits: (self.resolve.get_ty_def(ty_id).unwrap().size * 8) as u32,
+            is_signed: ty_id.is_signed_int(),
+        }
+    }
+}
+
+fn fold_int_arithmetic(
+    layout: IntLayout,
+    op: ArithmeticOp,
+    lhs: u128,
+    rhs: u128,
+) -> Result<u128, ConstEvalError> {
+    if matches!(op, ArithmeticOp::Div | ArithmeticOp::Rem) && rhs == 0 {
+        return Err(ConstEvalError::DivisionByZero);
+    }
+
+    if layout.is_signed {
+        let (lhs, rhs) = (lhs as i128, rhs as i128);
+
+        // `MIN % -1` traps at runtime just like `MIN / -1`, even though the result fits.
+        if op == ArithmeticOp::Rem && lhs == layout.min() && rhs == -1 {
+            return Err(ConstEvalError::Overflow {
+                ty_id: layout.ty_id,
+            });
+        }
+
+        let value = match op {
+            ArithmeticOp::Add => lhs.checked_add(rhs),
+            ArithmeticOp::Sub => lhs.checked_sub(rhs),
+            ArithmeticOp::Mul => lhs.checked_mul(rhs),
+            ArithmeticOp::Div => lhs.checked_div(rhs),
+            ArithmeticOp::Rem => lhs.checked_rem(rhs),
+            ArithmeticOp::WrappingAdd => return Ok(layout.wrap(lhs.wrapping_add(rhs) as u128)),
+            ArithmeticOp::WrappingSub => return Ok(layout.wrap(lhs.wrapping_sub(rhs) as u128)),
+            ArithmeticOp::WrappingMul => return Ok(layout.wrap(lhs.wrapping_mul(rhs) as u128)),
+            ArithmeticOp::SaturatingAdd => {
+                return Ok(layout.saturate_signed(lhs.saturating_add(rhs)))
+            }
+            ArithmeticOp::SaturatingSub => {
+                return Ok(layout.saturate_signed(lhs.saturating_sub(rhs)))
+            }
+            ArithmeticOp::SaturatingMul => {
+                return Ok(layout.saturate_signed(lhs.saturating_mul(rhs)))
+            }
+        };
+
+        layout.check_signed(value)
+    } else {
+        let value = match op {
+            ArithmeticOp::Add => lhs.checked_add(rhs),
+            ArithmeticOp::Sub => lhs.checked_sub(rhs),
+            ArithmeticOp::Mul => lhs.checked_mul(rhs),
+            ArithmeticOp::Div => lhs.checked_div(rhs),
+            ArithmeticOp::Rem => lhs.checked_rem(rhs),
+            ArithmeticOp::WrappingAdd => return Ok(layout.wrap(lhs.wrapping_add(rhs))),
+            ArithmeticOp::WrappingSub => return Ok(layout.wrap(lhs.wrapping_sub(rhs))),
+            ArithmeticOp::WrappingMul => return Ok(layout.wrap(lhs.wrapping_mul(rhs))),
+            ArithmeticOp::SaturatingAdd => return Ok(lhs.saturating_add(rhs).min(layout.max())),
+            ArithmeticOp::SaturatingSub => return Ok(lhs.saturating_sub(rhs)),
+            ArithmeticOp::SaturatingMul => return Ok(lhs.saturating_mul(rhs).min(layout.max())),
+        };
+
+        layout.check_unsigned(value)
+    }
+}
+
+fn fold_int_bitwise(
+    layout: IntLayout,
+    op: BitwiseOp,
+    lhs: u128,
+    rhs: u128,
+) -> Result<u128, ConstEvalError> {
+    let value = match op {
+        BitwiseOp::And => lhs & rhs,
+        BitwiseOp::Or => lhs | rhs,
+        BitwiseOp::Xor => lhs ^ rhs,
+        BitwiseOp::Shl | BitwiseOp::Shr => {
+            if rhs >= layout.bits as u128 {
+                return Err(ConstEvalError::Overflow {
+                    ty_id: layout.ty_id,
+                });
+            }
+
+            match op {
+                BitwiseOp::Shl => lhs << rhs,
+                _ if layout.is_signed => ((lhs as i128) >> rhs) as u128,
+                _ => lhs >> rhs,
+            }
+        }
+    };
+
+    Ok(layout.wrap(value))
+}
+
+fn fold_comparison(op: ComparisonOp, ordering: Option<Ordering>) -> bool {
+    match op {
+        ComparisonOp::Eq => ordering == Some(Ordering::Equal),
+        ComparisonOp::Ne => ordering != Some(Ordering::Equal),
+        ComparisonOp::Lt => ordering == Some(Ordering::Less),
+        ComparisonOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        ComparisonOp::Gt => ordering == Some(Ordering::Greater),
+        ComparisonOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+fn round_float(ty_id: TyId, value: f64) -> f64 {
+    if ty_id.as_float() == Some(&FloatTy::F32) {
+        value as f32 as f64
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cool_lexer::SymbolTable;
+    use cool_resolve::{PrimitiveTyData, ResolveContext, TyConsts};
+
+    fn tys() -> TyConsts {
+        let primitives = PrimitiveTyData {
+            i8_align: 1,
+            i16_align: 2,
+            i32_align: 4,
+            i64_align: 8,
+            i128_align: 16,
+            ptr_size: 8,
+            ptr_align: 8,
+            f32_align: 4,
+            f64_align: 8,
+        };
+
+        SymbolTable::default().enter(|| *ResolveContext::new(primitives).ty_consts())
+    }
+
+    fn layout(ty_id: TyId, bits: u32) -> IntLayout {
+        IntLayout {
+            ty_id,
+            bits,
+            is_signed: ty_id.is_signed_int(),
+        }
+    }
+
+    fn signed(layout: IntLayout, value: i128) -> u128 {
+        layout.wrap(value as u128)
+    }
+
+    fn is_overflow(result: Result<u128, ConstEvalError>) -> bool {
+        matches!(result, Err(ConstEvalError::Overflow { .. }))
+    }
+
+    #[test]
+    fn int_layout_bounds() {
+        let tys = tys();
+        let layouts = [
+            (layout(tys.i8, 8), i8::MIN as i128, i8::MAX as u128),
+            (layout(tys.i16, 16), i16::MIN as i128, i16::MAX as u128),
+            (layout(tys.i32, 32), i32::MIN as i128, i32::MAX as u128),
+            (layout(tys.i64, 64), i64::MIN as i128, i64::MAX as u128),
+            (layout(tys.i128, 128), i128::MIN, i128::MAX as u128),
+            (layout(tys.u8, 8), 0, u8::MAX as u128),
+            (layout(tys.u16, 16), 0, u16::MAX as u128),
+            (layout(tys.u32, 32), 0, u32::MAX as u128),
+            (layout(tys.u64, 64), 0, u64::MAX as u128),
+            (layout(tys.u128, 128), 0, u128::MAX),
+        ];
+
+        for (layout, min, max) in layouts {
+            assert_eq!(layout.min(), min);
+            assert_eq!(layout.max(), max);
+        }
+    }
+
+    #[test]
+    fn int_layout_wrap() {
+        let tys = tys();
+
+        assert_eq!(layout(tys.u8, 8).wrap(300), 44);
+        assert_eq!(layout(tys.i8, 8).wrap(200), -56_i128 as u128);
+        assert_eq!(layout(tys.u16, 16).wrap(u128::MAX), u16::MAX as u128);
+        assert_eq!(layout(tys.i32, 32).wrap(u32::MAX as u128), -1_i128 as u128);
+        assert_eq!(layout(tys.i64, 64).wrap(1 << 63), i64::MIN as i128 as u128);
+        assert_eq!(layout(tys.u128, 128).wrap(u128::MAX), u128::MAX);
+    }
+
+    #[test]
+    fn checked_arithmetic_overflows_at_each_width() {
+        let tys = tys();
+
+        for (ty_id, bits) in [
+            (tys.i8, 8),
+            (tys.i16, 16),
+            (tys.i32, 32),
+            (tys.i64, 64),
+            (tys.i128, 128),
+        ] {
+            let layout = layout(ty_id, bits);
+            let max = layout.max();
+            let min = signed(layout, layout.min());
+
+            assert!(is_overflow(fold_int_arithmetic(
+                layout,
+                ArithmeticOp::Add,
+                max,
+                1
+            )));
+            assert!(is_overflow(fold_int_arithmetic(
+                layout,
+                ArithmeticOp::Sub,
+                min,
+                1
+            )));
+            assert!(is_overflow(fold_int_arithmetic(
+                layout,
+                ArithmeticOp::Mul,
+                max,
+                2
+            )));
+            assert_eq!(
+                fold_int_arithmetic(layout, ArithmeticOp::Add, max - 1, 1).unwrap(),
+                max
+            );
+        }
+
+        for (ty_id, bits) in [
+            (tys.u8, 8),
+            (tys.u16, 16),
+            (tys.u32, 32),
+            (tys.u64, 64),
+            (tys.u128, 128),
+        ] {
+            let layout = layout(ty_id, bits);
+            let max = layout.max();
+
+            assert!(is_overflow(fold_int_arithmetic(
+                layout,
+                ArithmeticOp::Add,
+                max,
+                1
+            )));
+            assert!(is_overflow(fold_int_arithmetic(
+                layout,
+                ArithmeticOp::Sub,
+                0,
+                1
+            )));
+            assert!(is_overflow(fold_int_arithmetic(
+                layout,
+                ArithmeticOp::Mul,
+                max,
+                2
+            )));
+            assert_eq!(
+                fold_int_arithmetic(layout, ArithmeticOp::Sub, max, max).unwrap(),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn signed_division_traps() {
+        let tys = tys();
+
+        for (ty_id, bits) in [(tys.i8, 8), (tys.i32, 32), (tys.i128, 128)] {
+            let layout = layout(ty_id, bits);
+            let min = signed(layout, layout.min());
+            let minus_one = signed(layout, -1);
+
+            for op in [ArithmeticOp::Div, ArithmeticOp::Rem] {
+                assert!(matches!(
+                    fold_int_arithmetic(layout, op, 1, 0),
+                    Err(ConstEvalError::DivisionByZero),
+                ));
+                assert!(is_overflow(fold_int_arithmetic(layout, op, min, minus_one)));
+            }
+
+            assert_eq!(
+                fold_int_arithmetic(layout, ArithmeticOp::Div, signed(layout, -7), 2).unwrap(),
+                signed(layout, -3),
+            );
+            assert_eq!(
+                fold_int_arithmetic(layout, ArithmeticOp::Rem, signed(layout, -7), 2).unwrap(),
+                signed(layout, -1),
+            );
+        }
+    }
+
+    #[test]
+    fn wrapping_and_saturating_arithmetic() {
+        let tys = tys();
+        let i8 = layout(tys.i8, 8);
+        let u8 = layout(tys.u8, 8);
+        let i8_min = signed(i8, -128);
+
+        assert_eq!(
+            fold_int_arithmetic(i8, ArithmeticOp::WrappingAdd, 127, 1).unwrap(),
+            i8_min
+        );
+        assert_eq!(
+            fold_int_arithmetic(i8, ArithmeticOp::WrappingMul, i8_min, signed(i8, -1)).unwrap(),
+            i8_min,
+        );
+        assert_eq!(
+            fold_int_arithmetic(i8, ArithmeticOp::SaturatingAdd, 127, 1).unwrap(),
+            127
+        );
+        assert_eq!(
+            fold_int_arithmetic(i8, ArithmeticOp::SaturatingSub, i8_min, 1).unwrap(),
+            i8_min
+        );
+        assert_eq!(
+            fold_int_arithmetic(i8, ArithmeticOp::SaturatingMul, i8_min, 2).unwrap(),
+            i8_min
+        );
+
+        assert_eq!(
+            fold_int_arithmetic(u8, ArithmeticOp::WrappingAdd, 255, 1).unwrap(),
+            0
+        );
+        assert_eq!(
+            fold_int_arithmetic(u8, ArithmeticOp::WrappingSub, 0, 1).unwrap(),
+            255
+        );
+        assert_eq!(
+            fold_int_arithmetic(u8, ArithmeticOp::SaturatingAdd, 255, 1).unwrap(),
+            255
+        );
+        assert_eq!(
+            fold_int_arithmetic(u8, ArithmeticOp::SaturatingSub, 0, 1).unwrap(),
+            0
+        );
+        assert_eq!(
+            fold_int_arithmetic(u8, ArithmeticOp::SaturatingMul, 16, 16).unwrap(),
+            255
+        );
+    }
+
+    #[test]
+    fn shifts_reject_bit_width() {
+        let tys = tys();
+
+        for (ty_id, bits) in [
+            (tys.i8, 8),
+            (tys.u16, 16),
+            (tys.i32, 32),
+            (tys.u64, 64),
+            (tys.i128, 128),
+        ] {
+            let layout = layout(ty_id, bits);
+
+            for op in [BitwiseOp::Shl, BitwiseOp::Shr] {
+                assert!(is_overflow(fold_int_bitwise(layout, op, 1, bits as u128)));
+                assert!(is_overflow(fold_int_bitwise(
+                    layout,
+                    op,
+                    1,
+                    bits as u128 + 1
+                )));
+                assert!(fold_int_bitwise(layout, op, 1, bits as u128 - 1).is_ok());
+            }
+        }
+
+        let i8 = layout(tys.i8, 8);
+        let u8 = layout(tys.u8, 8);
+
+        assert_eq!(
+            fold_int_bitwise(i8, BitwiseOp::Shl, 1, 7).unwrap(),
+            signed(i8, -128)
+        );
+        assert_eq!(
+            fold_int_bitwise(i8, BitwiseOp::Shr, signed(i8, -16), 2).unwrap(),
+            signed(i8, -4)
+        );
+        assert_eq!(fold_int_bitwise(u8, BitwiseOp::Shl, 255, 4).unwrap(), 240);
+        assert_eq!(fold_int_bitwise(u8, BitwiseOp::Shr, 240, 4).unwrap(), 15);
+    }
+
+    #[test]
+    fn signed_comparison() {
+        let tys = tys();
+        let i64 = layout(tys.i64, 64);
+        let u64 = layout(tys.u64, 64);
+
+        assert_eq!(i64.compare(signed(i64, -1), 1), Ordering::Less);
+        assert_eq!(u64.compare(u64::MAX as u128, 1), Ordering::Greater);
+        assert!(fold_comparison(ComparisonOp::Le, Some(Ordering::Equal)));
+        assert!(!fold_comparison(ComparisonOp::Lt, None));
+        assert!(fold_comparison(ComparisonOp::Ne, None));
+    }
+}
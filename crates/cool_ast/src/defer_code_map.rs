@@ -18,4 +18,9 @@ impl DeferStmtMap {
     pub fn get(&self, frame_id: FrameId) -> Option<&StmtAst> {
         self.defer_stmts.get(&frame_id).map(Arc::as_ref)
     }
+
+    #[inline]
+    pub fn append(&mut self, other: DeferStmtMap) {
+        self.defer_stmts.extend(other.defer_stmts);
+    }
 }
@@ -89,6 +89,7 @@ impl From<AstErrorKind> for AstError {
 
 #[derive(Clone, From, Error, Display, Debug)]
 pub enum AstErrorKind {
+    ConstEval(ConstEvalError),
     Expr(ExprError),
     Literal(LiteralError),
     Logic(LogicError),
@@ -113,6 +114,41 @@ pub enum TyDefError {
 
     #[display(fmt = "type hint missing from parameter '{param}'")]
     TyHintMissing { param: Symbol },
+
+    #[display(fmt = "type argument count mismatch")]
+    TyArgCountMismatch { found: u32, expected: u32 },
+
+    #[display(fmt = "could not infer type argument for '{param}'")]
+    TyArgInferFailed { param: Symbol },
+
+    #[display(fmt = "type parameters are not allowed here")]
+    TyParamsNotAllowed,
+
+    #[display(fmt = "type '{ty_id}' could not be defined")]
+    TyNotDefinable { ty_id: TyId },
+
+    #[display(fmt = "generic item is not a function")]
+    GenericNotFn,
+
+    #[display(fmt = "generic item is not a struct")]
+    GenericNotStruct,
+
+    #[display(
+        fmt = "interface method '{method}' must take '*Self' or '*mut Self' as its first parameter and cannot use 'Self' anywhere else"
+    )]
+    InvalidInterfaceMethod { method: Symbol },
+
+    #[display(fmt = "type '{ty_id}' does not implement interface '{interface_ty_id}'")]
+    InterfaceNotImplemented { ty_id: TyId, interface_ty_id: TyId },
+
+    #[display(
+        fmt = "type '{ty_id}' is missing method '{method}' of interface '{interface_ty_id}'"
+    )]
+    InterfaceMethodMissing {
+        ty_id: TyId,
+        interface_ty_id: TyId,
+        method: Symbol,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -123,6 +159,8 @@ pub enum TyErrorKind {
     TyNotComparable,
     TyNotDereferenceable,
     UnsupportedCast { to_ty_id: TyId },
+    UnsupportedVariadicArgument,
+    ZeroSizedPtrDifference,
 }
 
 #[derive(Clone, Error, Debug)]
@@ -164,10 +202,42 @@ impl fmt::Display for TyError {
                     self.ty_id, to_ty_id,
                 )
             }
+            TyErrorKind::UnsupportedVariadicArgument => {
+                write!(
+                    f,
+                    "values of type '{}' cannot be passed as variadic arguments",
+                    self.ty_id,
+                )
+            }
+            TyErrorKind::ZeroSizedPtrDifference => {
+                write!(
+                    f,
+                    "cannot subtract pointers of type '{}' to zero-sized elements",
+                    self.ty_id,
+                )
+            }
         }
     }
 }
 
+#[derive(Clone, Error, Display, Debug)]
+pub enum ConstEvalError {
+    #[display(fmt = "expression is not constant")]
+    NotConstant,
+
+    #[display(fmt = "constant evaluation overflowed type '{ty_id}'")]
+    Overflow { ty_id: TyId },
+
+    #[display(fmt = "division by zero in constant expression")]
+    DivisionByZero,
+
+    #[display(fmt = "array length must be a non-negative integer")]
+    InvalidArrayLen,
+
+    #[display(fmt = "static assertion failed")]
+    StaticAssertFailed,
+}
+
 #[derive(Clone, Error, Display, Debug)]
 pub enum ExprError {
     #[display(fmt = "Expression is not addressable.")]
@@ -176,8 +246,21 @@ pub enum ExprError {
     #[display(fmt = "expression is not mutably addressable")]
     NotAddressableMutably,
 
+    #[display(fmt = "expression may be unaligned inside a packed struct and is not addressable")]
+    NotAddressableUnaligned,
+
     #[display(fmt = "expression is not assignable")]
     NotAssignable,
+
+    #[display(
+        fmt = "values of type '{ty_id}' may hold a closure that captures locals and cannot outlive its function"
+    )]
+    ClosureEscapes { ty_id: TyId },
+
+    #[display(
+        fmt = "function '{symbol}' has a non-default calling convention and can only be called directly"
+    )]
+    DirectCallOnly { symbol: Symbol },
 }
 
 #[derive(Clone, Debug)]
@@ -225,6 +308,24 @@ pub enum LogicError {
     #[display(fmt = "missing variants for type '{ty_id}'")]
     MissingVariants { ty_id: TyId },
 
+    #[display(fmt = "method '{method}' of type '{ty_id}' has no receiver")]
+    MethodWithoutReceiver { ty_id: TyId, method: Symbol },
+
+    #[display(fmt = "cannot determine the success variant of type '{ty_id}'")]
+    AmbiguousTrySuccessVariant { ty_id: TyId },
+
+    #[display(fmt = "struct literals must start with a struct or union type")]
+    InvalidStructBase,
+
+    #[display(fmt = "field '{field}' is initialized more than once")]
+    DuplicateFieldInitializer { field: Symbol },
+
+    #[display(fmt = "missing field initializers for type '{ty_id}'")]
+    MissingFieldInitializers { ty_id: TyId },
+
+    #[display(fmt = "union '{ty_id}' must be initialized with exactly one field")]
+    InvalidUnionInitializer { ty_id: TyId },
+
     #[display(fmt = "statement cannot be used as an expression")]
     StmtNotPromotableToExpr,
 }
@@ -1,6 +1,6 @@
 use crate::{
     AstError, AstGenerator, AstResult, AstResultExt, BindingExprAst, DerefExprAst, ExprAst,
-    ModuleExprAst, TyExprAst,
+    GenericExprAst, ModuleExprAst, TyExprAst,
 };
 use cool_lexer::sym;
 use cool_parser::{AccessExpr, Ident};
@@ -42,7 +42,18 @@ impl AstGenerator<'_> {
         expected_ty_id: TyId,
         access_expr: &AccessExpr,
     ) -> AstResult<ExprAst> {
-        match self.gen_expr(frame_id, self.tys().infer, &access_expr.base)? {
+        let base = self.gen_expr(frame_id, self.tys().infer, &access_expr.base)?;
+        self.continue_gen_access_expr(frame_id, expected_ty_id, base, access_expr)
+    }
+
+    pub fn continue_gen_access_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        base: ExprAst,
+        access_expr: &AccessExpr,
+    ) -> AstResult<ExprAst> {
+        match base {
             ExprAst::Module(module_expr) => {
                 let parent_module_id = self.resolve.resolve_parent_module(frame_id.into());
 
@@ -57,7 +68,17 @@ impl AstGenerator<'_> {
 
                 match item {
                     ItemKind::Binding(binding_id) => {
+                        self.check_fn_use(access_expr.span(), binding_id)?;
                         let found_ty_id = self.resolve[binding_id].ty_id;
+
+                        if let Some(value) = self.resolve.get_const_value(binding_id).cloned() {
+                            return self.gen_const_value_expr(
+                                access_expr.span(),
+                                found_ty_id,
+                                expected_ty_id,
+                                value,
+                            );
+                        }
                         let is_mutable = self.resolve[binding_id].is_mutable();
 
                         self.resolve_expr(
@@ -102,8 +123,59 @@ impl AstGenerator<'_> {
                             },
                         )
                     }
+                    ItemKind::Generic(generic_id) => {
+                        self.resolve_expr(
+                            access_expr.span(),
+                            self.tys().generic,
+                            expected_ty_id,
+                            |resolve, span, ty_id| {
+                                GenericExprAst {
+                                    span,
+                                    expr_id: resolve.add_expr(ResolveExpr::lvalue(ty_id, false)),
+                                    generic_id,
+                                }
+                            },
+                        )
+                    }
                 }
             }
+            ExprAst::Ty(ty_expr) => {
+                let method_item_id = self
+                    .struct_item_id(ty_expr.item_ty_id)
+                    .ok_or_else(|| {
+                        AstError::field_not_found(
+                            access_expr.span(),
+                            ty_expr.item_ty_id,
+                            access_expr.ident.symbol,
+                        )
+                    })
+                    .and_then(|struct_item_id| {
+                        self.resolve
+                            .resolve_method(
+                                frame_id.into(),
+                                struct_item_id,
+                                access_expr.ident.symbol,
+                            )
+                            .map_err(|error| AstError::new(access_expr.span(), error))
+                    })?;
+
+                let binding_id = self.resolve[method_item_id].as_binding_id().unwrap();
+                self.check_fn_use(access_expr.span(), binding_id)?;
+                let found_ty_id = self.resolve_method_ty(access_expr.span(), method_item_id)?;
+
+                self.resolve_expr(
+                    access_expr.span(),
+                    found_ty_id,
+                    expected_ty_id,
+                    |resolve, span, ty_id| {
+                        BindingExprAst {
+                            span,
+                            expr_id: resolve.add_expr(ResolveExpr::lvalue(ty_id, false)),
+                            binding_id,
+                        }
+                    },
+                )
+            }
             base => {
                 if base.expr_id().ty_id.is_ptr() {
                     let new_base = self.gen_implicit_deref_expr(Box::new(base))?;
@@ -120,7 +192,7 @@ impl AstGenerator<'_> {
         }
     }
 
-    fn gen_implicit_deref_expr(&mut self, base: Box<ExprAst>) -> AstResult<DerefExprAst> {
+    pub fn gen_implicit_deref_expr(&mut self, base: Box<ExprAst>) -> AstResult<DerefExprAst> {
         let base_ty_id = base.expr_id().ty_id;
         let base_ptr_ty = base_ty_id.as_ptr().unwrap();
 
@@ -171,7 +243,7 @@ impl AstGenerator<'_> {
                 .resolve
                 .get_ty_def(base_expr_id.ty_id)
                 .unwrap()
-                .get_aggregate_field(ident.symbol)
+                .get_field(ident.symbol)
                 .ok_or_else(|| {
                     AstError::field_not_found(access_expr.span(), base_expr_id.ty_id, ident.symbol)
                 })?;
@@ -1,4 +1,4 @@
-use crate::{AstGenerator, AstResult, ExprAst};
+use crate::{AstError, AstGenerator, AstResult, ExprAst, TyDefError};
 use cool_parser::AlignOfExpr;
 use cool_resolve::{ExprId, FrameId, ResolveExpr, TyId};
 use cool_span::{Section, Span};
@@ -25,7 +25,14 @@ impl AstGenerator<'_> {
         expr: &AlignOfExpr,
     ) -> AstResult<ExprAst> {
         let arg_ty_id = self.resolve_ty(frame_id, &expr.ty)?;
-        let value = self.resolve.get_ty_def(arg_ty_id).unwrap().align;
+        let value = self
+            .resolve
+            .get_ty_def(arg_ty_id)
+            .ok_or(AstError::new(
+                expr.span(),
+                TyDefError::TyNotDefinable { ty_id: arg_ty_id },
+            ))?
+            .align;
 
         self.resolve_expr(
             expr.span(),
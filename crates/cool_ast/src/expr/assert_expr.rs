@@ -0,0 +1,42 @@
+use crate::{AstGenerator, AstResult, ExprAst};
+use cool_parser::AssertExpr;
+use cool_resolve::{ExprId, FrameId, ResolveExpr, TyId};
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct AssertExprAst {
+    pub span: Span,
+    pub expr_id: ExprId,
+    pub cond: Box<ExprAst>,
+}
+
+impl Section for AssertExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstGenerator<'_> {
+    pub fn gen_assert_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        expr: &AssertExpr,
+    ) -> AstResult<ExprAst> {
+        let cond = self.gen_expr(frame_id, self.tys().bool, &expr.cond)?;
+
+        self.resolve_expr(
+            expr.span(),
+            self.tys().unit,
+            expected_ty_id,
+            |resolve, span, ty_id| {
+                AssertExprAst {
+                    span,
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                    cond: Box::new(cond),
+                }
+            },
+        )
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{AstGenerator, AstResult, AstResultExt, ExprAst, TyError, TyErrorKind};
-use cool_parser::{BinOp, BinaryExpr, BitwiseOp};
+use cool_parser::{ArithmeticOp, BinOp, BinaryExpr, BitwiseOp, Expr};
 use cool_resolve::{ExprId, FrameId, ResolveExpr, TyId};
 use cool_span::{Section, Span};
 
@@ -28,19 +28,64 @@ impl AstGenerator<'_> {
         let bin_op = binary_expr.bin_op;
 
         let (found_ty_id, lhs, rhs) = match binary_expr.bin_op {
-            BinOp::Arithmetic(_) => {
-                let lhs = self.gen_expr(frame_id, expected_ty_id, &binary_expr.lhs)?;
+            BinOp::Arithmetic(arithmetic_op) => {
+                let lhs =
+                    if arithmetic_op == ArithmeticOp::Sub && expected_ty_id == self.tys().isize {
+                        self.gen_ptr_difference_lhs(frame_id, expected_ty_id, &binary_expr.lhs)?
+                    } else {
+                        self.gen_expr(frame_id, expected_ty_id, &binary_expr.lhs)?
+                    };
+
                 let lhs_ty_id = lhs.expr_id().ty_id;
+
+                if lhs_ty_id.is_many_ptr() {
+                    let (found_ty_id, rhs) = self.gen_ptr_arithmetic_rhs(
+                        frame_id,
+                        binary_expr.span(),
+                        lhs_ty_id,
+                        arithmetic_op,
+                        &binary_expr.rhs,
+                    )?;
+
+                    return self.resolve_expr(
+                        binary_expr.span(),
+                        found_ty_id,
+                        expected_ty_id,
+                        |resolve, _, ty_id| {
+                            BinaryExprAst {
+                                expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                                bin_op,
+                                lhs: Box::new(lhs),
+                                rhs: Box::new(rhs),
+                            }
+                        },
+                    );
+                }
+
+                if arithmetic_op.is_int_only() && !lhs_ty_id.is_int() {
+                    return AstResult::ty_mismatch(
+                        binary_expr.span(),
+                        lhs_ty_id,
+                        self.tys().infer_int,
+                    );
+                }
+
                 let rhs = self.gen_expr(frame_id, lhs_ty_id, &binary_expr.rhs)?;
 
                 (lhs_ty_id, lhs, rhs)
             }
-            BinOp::Comparison(_) => {
+            BinOp::Comparison(comparison_op) => {
                 let lhs = self.gen_expr(frame_id, self.tys().infer, &binary_expr.lhs)?;
                 let lhs_ty_id = lhs.expr_id().ty_id;
                 let rhs = self.gen_expr(frame_id, lhs_ty_id, &binary_expr.rhs)?;
 
-                if !lhs_ty_id.is_comparable() {
+                let is_comparable = if comparison_op.is_equality() {
+                    self.resolve.is_ty_equatable(lhs_ty_id)
+                } else {
+                    lhs_ty_id.is_comparable()
+                };
+
+                if !is_comparable {
                     return AstResult::error(
                         binary_expr.span(),
                         TyError {
@@ -103,4 +148,79 @@ impl AstGenerator<'_> {
             },
         )
     }
+
+    /// Generates the left-hand side of a subtraction whose result is expected to be an
+    /// `isize`. The difference of two many-pointers is an `isize`, so the left-hand side is
+    /// typed on its own first and only falls back to the expected type for numbers.
+    fn gen_ptr_difference_lhs(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        lhs: &Expr,
+    ) -> AstResult<ExprAst> {
+        let indexed_expr_count = self
+            .source_index
+            .as_deref()
+            .map(|source_index| source_index.exprs.len());
+
+        if let Ok(lhs) = self.gen_expr(frame_id, self.tys().infer, lhs) {
+            let lhs_ty_id = lhs.expr_id().ty_id;
+
+            if lhs_ty_id.is_many_ptr() || lhs_ty_id == expected_ty_id {
+                return Ok(lhs);
+            }
+        }
+
+        // Drop the expressions indexed by the discarded attempt
+        if let (Some(source_index), Some(indexed_expr_count)) =
+            (self.source_index.as_deref_mut(), indexed_expr_count)
+        {
+            source_index.exprs.truncate(indexed_expr_count);
+        }
+
+        self.gen_expr(frame_id, expected_ty_id, lhs)
+    }
+
+    /// Generates the right-hand side of an arithmetic operation on a many-pointer. Integers can
+    /// be added to or subtracted from many-pointers and subtracting two many-pointers of the
+    /// same type yields the number of elements between them, which is only defined for elements
+    /// that have a size. Returns the type of the result.
+    pub(crate) fn gen_ptr_arithmetic_rhs(
+        &mut self,
+        frame_id: FrameId,
+        span: Span,
+        lhs_ty_id: TyId,
+        arithmetic_op: ArithmeticOp,
+        rhs: &Expr,
+    ) -> AstResult<(TyId, ExprAst)> {
+        let rhs_expected_ty_id = match arithmetic_op {
+            ArithmeticOp::Add => self.tys().infer_int,
+            ArithmeticOp::Sub => self.tys().infer,
+            _ => return AstResult::ty_mismatch(span, lhs_ty_id, self.tys().infer_number),
+        };
+
+        let rhs = self.gen_expr(frame_id, rhs_expected_ty_id, rhs)?;
+        let rhs_ty_id = rhs.expr_id().ty_id;
+
+        if rhs_ty_id.is_int() {
+            Ok((lhs_ty_id, rhs))
+        } else if rhs_ty_id == lhs_ty_id {
+            if self
+                .resolve
+                .is_ty_zero_sized(lhs_ty_id.get_many_ptr().pointee)
+            {
+                return AstResult::error(
+                    span,
+                    TyError {
+                        ty_id: lhs_ty_id,
+                        kind: TyErrorKind::ZeroSizedPtrDifference,
+                    },
+                );
+            }
+
+            Ok((self.tys().isize, rhs))
+        } else {
+            AstResult::ty_mismatch(rhs.span(), rhs_ty_id, self.tys().infer_int)
+        }
+    }
 }
@@ -52,6 +52,16 @@ impl AstGenerator<'_> {
         expr: &CastExpr,
     ) -> AstResult<ExprAst> {
         let base = self.gen_expr(frame_id, self.tys().infer, &expr.base)?;
+        self.continue_gen_cast_expr(frame_id, expected_ty_id, base, expr)
+    }
+
+    pub fn continue_gen_cast_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        base: ExprAst,
+        expr: &CastExpr,
+    ) -> AstResult<ExprAst> {
         let base_ty_id = base.expr_id().ty_id;
         let expr_ty_id = self.resolve_ty(frame_id, &expr.ty)?;
 
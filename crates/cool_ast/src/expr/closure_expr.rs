@@ -0,0 +1,350 @@
+use crate::{AstError, AstGenerator, AstResult, AstResultExt, ExprAst, ExprError, TyDefError};
+use cool_lexer::Symbol;
+use cool_parser::ClosureExpr;
+use cool_resolve::{BindingId, ExprId, FrameId, ResolveExpr, TyId, TyKind, ValueTy};
+use cool_span::{Section, Span};
+use smallvec::SmallVec;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ClosureCaptureAst {
+    pub binding_id: BindingId,
+    pub capture_binding_id: BindingId,
+    /// Only set for mutable bindings of sized types, which always live in stack memory.
+    pub is_by_ptr: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ClosureExprAst {
+    pub span: Span,
+    pub expr_id: ExprId,
+    pub frame_id: FrameId,
+    pub binding_ids: SmallVec<[BindingId; 4]>,
+    pub captures: Vec<ClosureCaptureAst>,
+    pub body: Box<ExprAst>,
+}
+
+impl Section for ClosureExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstGenerator<'_> {
+    pub fn gen_closure_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        expr: &ClosureExpr,
+    ) -> AstResult<ExprAst> {
+        let expected_closure_ty = expected_ty_id.as_closure();
+
+        if let Some(closure_ty) = expected_closure_ty {
+            if closure_ty.params.len() != expr.param_list.params.len() {
+                return AstResult::error(
+                    expr.param_list.span(),
+                    TyDefError::ParamCountMismatch {
+                        found: expr.param_list.params.len() as _,
+                        expected: closure_ty.params.len() as _,
+                    },
+                );
+            }
+        }
+
+        if expr.param_list.is_variadic {
+            return AstResult::error(
+                expr.param_list.span(),
+                TyDefError::VariadicMismatch {
+                    found: true,
+                    expected: false,
+                },
+            );
+        }
+
+        let mut param_ty_ids = SmallVec::<[TyId; 4]>::new();
+
+        for (i, param) in expr.param_list.params.iter().enumerate() {
+            let expected_param_ty_id = expected_closure_ty.map(|closure_ty| closure_ty.params[i]);
+
+            let param_ty_id = match (param.ty.as_ref(), expected_param_ty_id) {
+                (Some(param_ty), expected_param_ty_id) => {
+                    let param_ty_id = self.resolve_ty(frame_id, param_ty)?;
+
+                    if let Some(expected_param_ty_id) = expected_param_ty_id {
+                        if param_ty_id != expected_param_ty_id {
+                            return AstResult::ty_mismatch(
+                                param.span(),
+                                param_ty_id,
+                                expected_param_ty_id,
+                            );
+                        }
+                    }
+
+                    param_ty_id
+                }
+                (None, Some(expected_param_ty_id)) => expected_param_ty_id,
+                (None, None) => {
+                    return AstResult::error(
+                        param.span(),
+                        TyDefError::TyHintMissing {
+                            param: param.ident.symbol,
+                        },
+                    );
+                }
+            };
+
+            param_ty_ids.push(param_ty_id);
+        }
+
+        let ret_ty_id = match (expr.ret_ty.as_ref(), expected_closure_ty) {
+            (Some(ret_ty), _) => self.resolve_ty(frame_id, ret_ty)?,
+            (None, Some(closure_ty)) => closure_ty.ret,
+            (None, None) => self.tys().unit,
+        };
+
+        let closure_frame_id = self.resolve.add_frame(frame_id.into());
+        let mut binding_ids = SmallVec::new();
+
+        for (param, &param_ty_id) in expr.param_list.params.iter().zip(param_ty_ids.iter()) {
+            let binding_id = self
+                .resolve
+                .insert_local_binding(
+                    closure_frame_id,
+                    param.is_mutable,
+                    param.ident.symbol,
+                    Some(param_ty_id),
+                )
+                .map_err(|error| AstError::new(param.span(), error))?;
+
+            self.index_local_binding(binding_id, param.ident.span);
+            binding_ids.push(binding_id);
+        }
+
+        self.push_closure_fn_state(ret_ty_id, closure_frame_id);
+        self.mark_params_non_capturing(&binding_ids);
+
+        let body = self
+            .gen_block_expr(closure_frame_id, ret_ty_id, &expr.body)
+            .and_then(|body| {
+                self.check_closure_not_escaping(&body)?;
+                Ok(body)
+            });
+        let captures = self.pop_fn_state().captures;
+        let body = body?;
+
+        let found_ty_id = self.resolve.mk_closure(param_ty_ids, ret_ty_id);
+
+        self.resolve_expr(
+            expr.span(),
+            found_ty_id,
+            expected_ty_id,
+            |resolve, span, ty_id| {
+                ClosureExprAst {
+                    span,
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                    frame_id: closure_frame_id,
+                    binding_ids,
+                    captures,
+                    body: Box::new(body),
+                }
+            },
+        )
+    }
+
+    /// Returns whether values of type `ty_id` hold a closure.
+    pub fn ty_contains_closure(&self, ty_id: TyId) -> bool {
+        let Some(value_ty) = ty_id.as_value() else {
+            return false;
+        };
+
+        match value_ty {
+            ValueTy::Closure(_) => true,
+            ValueTy::Array(array_ty) => self.ty_contains_closure(array_ty.elem),
+            ValueTy::Tuple(tuple_ty) => {
+                tuple_ty
+                    .elems()
+                    .iter()
+                    .any(|&elem| self.ty_contains_closure(elem))
+            }
+            ValueTy::Variant(variant_ty) => {
+                variant_ty
+                    .variants()
+                    .iter()
+                    .any(|&variant| self.ty_contains_closure(variant))
+            }
+            ValueTy::Struct(_) | ValueTy::Union(_) => {
+                let fields = match self.resolve.get_ty_def(ty_id).map(|ty_def| &ty_def.kind) {
+                    Some(TyKind::Aggregate(aggregate_ty)) => aggregate_ty.fields(),
+                    Some(TyKind::UntaggedUnion(untagged_union_ty)) => untagged_union_ty.fields(),
+                    _ => return false,
+                };
+
+                fields
+                    .iter()
+                    .any(|field| self.ty_contains_closure(field.ty_id))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the span of the part of `expr` whose value may hold a closure that captured
+    /// locals. Only closures without captures may leave the function they were created in.
+    pub fn find_capturing_closure(&self, expr: &ExprAst) -> Option<Span> {
+        if !self.ty_contains_closure(expr.expr_id().ty_id) {
+            return None;
+        }
+
+        match expr {
+            ExprAst::Closure(closure_expr) => {
+                (!closure_expr.captures.is_empty()).then_some(closure_expr.span)
+            }
+            ExprAst::Binding(binding_expr) => {
+                (!self
+                    .fn_state()
+                    .non_capturing_binding_ids
+                    .contains(&binding_expr.binding_id))
+                .then_some(binding_expr.span)
+            }
+            ExprAst::Block(block_expr) => self.find_capturing_closure(&block_expr.expr),
+            ExprAst::Tuple(tuple_expr) => {
+                tuple_expr
+                    .elems
+                    .iter()
+                    .find_map(|elem| self.find_capturing_closure(elem))
+            }
+            ExprAst::Array(array_expr) => {
+                array_expr
+                    .elems
+                    .iter()
+                    .find_map(|elem| self.find_capturing_closure(elem))
+            }
+            ExprAst::ArrayRepeat(array_repeat_expr) => {
+                self.find_capturing_closure(&array_repeat_expr.elem)
+            }
+            ExprAst::Struct(struct_expr) => {
+                struct_expr
+                    .initializers
+                    .iter()
+                    .find_map(|initializer| self.find_capturing_closure(&initializer.expr))
+            }
+            ExprAst::VariantWrap(variant_wrap_expr) => {
+                self.find_capturing_closure(&variant_wrap_expr.inner)
+            }
+            // Functions cannot return closures that captured their own locals, so a call can
+            // only return capturing closures that were passed to it by value
+            ExprAst::FnCall(fn_call_expr) => {
+                fn_call_expr
+                    .arg_exprs
+                    .iter()
+                    .find_map(|arg_expr| self.find_capturing_closure(arg_expr))
+            }
+            ExprAst::Cond(cond_expr) => {
+                cond_expr
+                    .cond_blocks
+                    .iter()
+                    .map(|cond_block| &cond_block.expr)
+                    .chain(cond_expr.else_block.iter())
+                    .find_map(|expr| self.find_capturing_closure(expr))
+            }
+            _ => Some(expr.span()),
+        }
+    }
+
+    /// Parameters hold values owned by the caller, so the function may return them as long as
+    /// they are not reassigned.
+    pub fn mark_params_non_capturing(&mut self, binding_ids: &[BindingId]) {
+        for &binding_id in binding_ids {
+            if !self.resolve[binding_id].is_mutable() {
+                self.fn_state_mut()
+                    .non_capturing_binding_ids
+                    .insert(binding_id);
+            }
+        }
+    }
+
+    /// Rejects `expr` if its value may hold a closure that captured locals, whose environment
+    /// lives in the stack frame of the current function.
+    pub fn check_closure_not_escaping(&self, expr: &ExprAst) -> AstResult<()> {
+        if let Some(span) = self.find_capturing_closure(expr) {
+            return AstResult::error(
+                span,
+                ExprError::ClosureEscapes {
+                    ty_id: expr.expr_id().ty_id,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether `expr` is a place in the stack frame of the current function. Captured
+    /// bindings live in the frame of an enclosing function.
+    pub fn is_local_place(&self, expr: &ExprAst) -> bool {
+        match expr {
+            ExprAst::Binding(binding_expr) => {
+                !self
+                    .fn_state()
+                    .captures
+                    .iter()
+                    .any(|capture| capture.capture_binding_id == binding_expr.binding_id)
+            }
+            ExprAst::Access(access_expr) => self.is_local_place(&access_expr.base),
+            ExprAst::Index(index_expr) => {
+                index_expr.base.expr_id().ty_id.is_array() && self.is_local_place(&index_expr.base)
+            }
+            _ => false,
+        }
+    }
+
+    /// Captures `binding_id` into every enclosing closure that does not already see it.
+    /// Mutable bindings are captured by pointer, all others by value. Zero-sized bindings have
+    /// no storage to point to and are always captured by value.
+    pub fn capture_binding(
+        &mut self,
+        span: Span,
+        frame_id: FrameId,
+        symbol: Symbol,
+        mut binding_id: BindingId,
+    ) -> AstResult<BindingId> {
+        let Some(mut binding_frame_id) = self.resolve.find_binding_frame(frame_id, symbol) else {
+            return Ok(binding_id);
+        };
+
+        for i in 0..self.fn_states.len() {
+            let Some(closure_frame_id) = self.fn_states[i].closure_frame_id else {
+                continue;
+            };
+
+            if self
+                .resolve
+                .is_frame_within(binding_frame_id, closure_frame_id)
+            {
+                continue;
+            }
+
+            let binding = self.resolve[binding_id];
+            let is_by_ptr = binding.is_mutable() && !self.resolve.is_ty_zero_sized(binding.ty_id);
+
+            let capture_binding_id = self
+                .resolve
+                .insert_local_binding(
+                    closure_frame_id,
+                    binding.is_mutable(),
+                    symbol,
+                    Some(binding.ty_id),
+                )
+                .map_err(|error| AstError::new(span, error))?;
+
+            self.fn_states[i].captures.push(ClosureCaptureAst {
+                binding_id,
+                capture_binding_id,
+                is_by_ptr,
+            });
+
+            binding_id = capture_binding_id;
+            binding_frame_id = closure_frame_id;
+        }
+
+        Ok(binding_id)
+    }
+}
@@ -1,7 +1,11 @@
-use crate::{AstGenerator, AstResult, AstResultExt, ExprAst, TyError, TyErrorKind};
-use cool_parser::FnCallExpr;
-use cool_resolve::{ExprId, FrameId, ResolveExpr, TyId};
+use crate::{
+    ty_mentions_ty_params, AstGenerator, AstResult, AstResultExt, BindingExprAst, ExprAst,
+    ExprError, GenericExprAst, GenericItem, TyError, TyErrorKind,
+};
+use cool_parser::{Expr, FnCallExpr, Ty};
+use cool_resolve::{BindingId, ExprId, FrameId, ResolveExpr, Scope, TyId, ValueTy};
 use cool_span::{Section, Span};
+use smallvec::SmallVec;
 
 #[derive(Clone, Debug)]
 pub struct FnCallExprAst {
@@ -25,30 +29,207 @@ impl AstGenerator<'_> {
         expected_ty_id: TyId,
         fn_call_expr: &FnCallExpr,
     ) -> AstResult<ExprAst> {
-        let fn_expr = self.gen_expr(frame_id, self.tys().infer, &fn_call_expr.base)?;
+        self.callee_span = Some(fn_call_expr.base.span());
+
+        let fn_expr = match fn_call_expr.base.as_ref() {
+            Expr::Access(access_expr) => {
+                let base = self.gen_expr(frame_id, self.tys().infer, &access_expr.base)?;
+
+                if let Some(method_item_id) =
+                    self.get_receiver_method(frame_id, &base, access_expr.ident)?
+                {
+                    return self.gen_method_call_expr(
+                        frame_id,
+                        expected_ty_id,
+                        base,
+                        method_item_id,
+                        fn_call_expr,
+                    );
+                }
+
+                if let Some(method_index) =
+                    self.get_interface_method_index(&base, access_expr.ident.symbol)
+                {
+                    return self.gen_interface_method_call_expr(
+                        frame_id,
+                        expected_ty_id,
+                        base,
+                        access_expr.ident.span(),
+                        method_index,
+                        fn_call_expr,
+                    );
+                }
+
+                self.continue_gen_access_expr(frame_id, self.tys().infer, base, access_expr)?
+            }
+            base => self.gen_expr(frame_id, self.tys().infer, base)?,
+        };
+
+        let fn_expr = match fn_expr {
+            ExprAst::Generic(generic_expr) => {
+                return self.gen_generic_fn_call_expr(
+                    frame_id,
+                    expected_ty_id,
+                    &generic_expr,
+                    fn_call_expr,
+                );
+            }
+            fn_expr => fn_expr,
+        };
+
         let fn_expr_ty_id = fn_expr.expr_id().ty_id;
 
-        let Some(fn_ty) = fn_expr_ty_id.as_fn() else {
+        let (params, is_variadic, ret) = match fn_expr_ty_id.get_value() {
+            ValueTy::Fn(fn_ty) => (fn_ty.params.as_slice(), fn_ty.is_variadic, fn_ty.ret),
+            ValueTy::Closure(closure_ty) => (closure_ty.params.as_slice(), false, closure_ty.ret),
+            _ => {
+                return AstResult::error(
+                    fn_call_expr.span(),
+                    TyError {
+                        ty_id: fn_expr_ty_id,
+                        kind: TyErrorKind::TyNotCallable,
+                    },
+                );
+            }
+        };
+
+        let is_argument_count_valid = if is_variadic {
+            fn_call_expr.args.len() >= params.len()
+        } else {
+            fn_call_expr.args.len() == params.len()
+        };
+
+        if !is_argument_count_valid {
             return AstResult::error(
                 fn_call_expr.span(),
                 TyError {
                     ty_id: fn_expr_ty_id,
+                    kind: TyErrorKind::InvalidArgumentCount {
+                        found: fn_call_expr.args.len() as _,
+                    },
+                },
+            );
+        }
+
+        let arg_exprs = self.gen_fn_call_args(frame_id, params, &fn_call_expr.args)?;
+
+        self.resolve_expr(
+            fn_call_expr.span(),
+            ret,
+            expected_ty_id,
+            |resolve, span, ty_id| {
+                FnCallExprAst {
+                    span,
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                    fn_expr: Box::new(fn_expr),
+                    arg_exprs,
+                }
+            },
+        )
+    }
+
+    /// Generates the arguments of a function call, with the ones past the parameters being
+    /// variadic.
+    pub(crate) fn gen_fn_call_args(
+        &mut self,
+        frame_id: FrameId,
+        params: &[TyId],
+        args: &[Expr],
+    ) -> AstResult<Vec<ExprAst>> {
+        args.iter()
+            .enumerate()
+            .map(|(i, arg_expr)| {
+                match params.get(i) {
+                    Some(&param_ty_id) => self.gen_expr(frame_id, param_ty_id, arg_expr),
+                    None => self.gen_variadic_arg_expr(frame_id, arg_expr),
+                }
+            })
+            .collect()
+    }
+
+    /// Variadic arguments must be scalars, as aggregates are not lowered to the C calling
+    /// convention of variadic functions.
+    fn gen_variadic_arg_expr(&mut self, frame_id: FrameId, arg_expr: &Expr) -> AstResult<ExprAst> {
+        let arg_expr = self.gen_expr(frame_id, self.tys().infer, arg_expr)?;
+        let arg_ty_id = arg_expr.expr_id().ty_id;
+
+        let is_scalar = arg_ty_id.is_diverge()
+            || arg_ty_id.as_value().is_some_and(|value_ty| {
+                matches!(
+                    value_ty,
+                    ValueTy::Bool
+                        | ValueTy::Char
+                        | ValueTy::Int(_)
+                        | ValueTy::Float(_)
+                        | ValueTy::Ptr(_)
+                        | ValueTy::ManyPtr(_)
+                        | ValueTy::Fn(_),
+                )
+            });
+
+        if !is_scalar {
+            return AstResult::error(
+                arg_expr.span(),
+                TyError {
+                    ty_id: arg_ty_id,
+                    kind: TyErrorKind::UnsupportedVariadicArgument,
+                },
+            );
+        }
+
+        Ok(arg_expr)
+    }
+
+    /// Rejects uses of a function that can only be called directly other than as the callee of
+    /// a function call.
+    pub fn check_fn_use(&self, span: Span, binding_id: BindingId) -> AstResult<()> {
+        if self.resolve.is_direct_call_only(binding_id) && self.callee_span != Some(span) {
+            return AstResult::error(
+                span,
+                ExprError::DirectCallOnly {
+                    symbol: self.resolve[binding_id].symbol,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Infers the type arguments of a generic function from the expected type and the call
+    /// arguments.
+    fn gen_generic_fn_call_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        generic_expr: &GenericExprAst,
+        fn_call_expr: &FnCallExpr,
+    ) -> AstResult<ExprAst> {
+        let generic_id = generic_expr.generic_id;
+        let generics = self.generics;
+
+        let Some(GenericItem::Fn { expr: fn_expr, .. }) = generics.get(&generic_id) else {
+            return AstResult::error(
+                fn_call_expr.span(),
+                TyError {
+                    ty_id: self.tys().generic,
                     kind: TyErrorKind::TyNotCallable,
                 },
             );
         };
 
-        let is_argument_count_valid = if fn_ty.is_variadic {
-            fn_call_expr.args.len() >= fn_ty.params.len()
+        let param_list = &fn_expr.prototype.param_list;
+
+        let is_argument_count_valid = if param_list.is_variadic {
+            fn_call_expr.args.len() >= param_list.params.len()
         } else {
-            fn_call_expr.args.len() == fn_ty.params.len()
+            fn_call_expr.args.len() == param_list.params.len()
         };
 
         if !is_argument_count_valid {
             return AstResult::error(
                 fn_call_expr.span(),
                 TyError {
-                    ty_id: fn_expr_ty_id,
+                    ty_id: self.tys().generic,
                     kind: TyErrorKind::InvalidArgumentCount {
                         found: fn_call_expr.args.len() as _,
                     },
@@ -56,13 +237,79 @@ impl AstGenerator<'_> {
             );
         }
 
+        let module_id = self.resolve[generic_id].module_id;
+        let ty_params = self.resolve[generic_id].ty_params.clone();
+        let mut ty_args = SmallVec::<[Option<TyId>; 2]>::from_elem(None, ty_params.len());
         let mut arg_exprs = Vec::<ExprAst>::new();
 
+        if let Some(ret_ty) = fn_expr.prototype.ret_ty.as_ref() {
+            if expected_ty_id.is_value() {
+                self.infer_ty_args(generic_id, ret_ty, expected_ty_id, &mut ty_args)
+                    .map_err(|error| error.with_span(fn_call_expr.span()))?;
+            }
+        }
+
         for (i, arg_expr) in fn_call_expr.args.iter().enumerate() {
-            let param_ty_id = fn_ty.params.get(i).copied().unwrap_or(self.tys().infer);
-            arg_exprs.push(self.gen_expr(frame_id, param_ty_id, arg_expr)?);
+            let Some(param) = param_list.params.get(i) else {
+                arg_exprs.push(self.gen_variadic_arg_expr(frame_id, arg_expr)?);
+                continue;
+            };
+
+            let Some(param_ty) = param.ty.as_ref() else {
+                arg_exprs.push(self.gen_expr(frame_id, self.tys().infer, arg_expr)?);
+                continue;
+            };
+
+            if !ty_mentions_ty_params(param_ty, &ty_params) {
+                let param_ty_id = self.resolve_ty(Scope::Module(module_id), param_ty)?;
+                arg_exprs.push(self.gen_expr(frame_id, param_ty_id, arg_expr)?);
+                continue;
+            }
+
+            let arg_expected_ty_id = match param_ty {
+                Ty::Path(path_ty) if path_ty.ty_args.is_none() && path_ty.idents.len() == 1 => {
+                    ty_params
+                        .iter()
+                        .position(|&ty_param| ty_param == path_ty.idents[0].symbol)
+                        .and_then(|index| ty_args[index])
+                        .unwrap_or(self.tys().infer)
+                }
+                _ => self.tys().infer,
+            };
+
+            let arg_expr_ast = self.gen_expr(frame_id, arg_expected_ty_id, arg_expr)?;
+
+            self.infer_ty_args(
+                generic_id,
+                param_ty,
+                arg_expr_ast.expr_id().ty_id,
+                &mut ty_args,
+            )
+            .map_err(|error| error.with_span(arg_expr.span()))?;
+
+            arg_exprs.push(arg_expr_ast);
         }
 
+        let ty_args = self.collect_ty_args(fn_call_expr.span(), generic_id, &ty_args)?;
+        let item_id = self.instantiate_generic(fn_call_expr.span(), generic_id, &ty_args)?;
+        let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+        let fn_ty_id = self.resolve[binding_id].ty_id;
+        let fn_ty = fn_ty_id.as_fn().unwrap().clone();
+
+        for (arg_expr, &param_ty_id) in arg_exprs.iter().zip(fn_ty.params.iter()) {
+            let arg_ty_id = arg_expr.expr_id().ty_id;
+
+            if arg_ty_id != param_ty_id && !arg_ty_id.is_diverge() {
+                return AstResult::ty_mismatch(arg_expr.span(), arg_ty_id, param_ty_id);
+            }
+        }
+
+        let fn_expr = BindingExprAst {
+            span: generic_expr.span,
+            expr_id: self.resolve.add_expr(ResolveExpr::lvalue(fn_ty_id, false)),
+            binding_id,
+        };
+
         self.resolve_expr(
             fn_call_expr.span(),
             fn_ty.ret,
@@ -71,7 +318,7 @@ impl AstGenerator<'_> {
                 FnCallExprAst {
                     span,
                     expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
-                    fn_expr: Box::new(fn_expr),
+                    fn_expr: Box::new(fn_expr.into()),
                     arg_exprs,
                 }
             },
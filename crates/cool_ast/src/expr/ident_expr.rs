@@ -1,6 +1,6 @@
 use crate::{AstError, AstGenerator, AstResult, ExprAst, UnitExprAst};
 use cool_parser::IdentExpr;
-use cool_resolve::{BindingId, ExprId, FrameId, ItemKind, ModuleId, ResolveExpr, TyId};
+use cool_resolve::{BindingId, ExprId, FrameId, GenericId, ItemKind, ModuleId, ResolveExpr, TyId};
 use cool_span::{Section, Span};
 
 #[derive(Clone, Debug)]
@@ -17,6 +17,20 @@ impl Section for BindingExprAst {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct GenericExprAst {
+    pub span: Span,
+    pub expr_id: ExprId,
+    pub generic_id: GenericId,
+}
+
+impl Section for GenericExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ModuleExprAst {
     pub span: Span,
@@ -59,6 +73,26 @@ impl AstGenerator<'_> {
 
         match item {
             ItemKind::Binding(binding_id) => {
+                if let Some(value) = self.resolve.get_const_value(binding_id).cloned() {
+                    let found_ty_id = self.resolve[binding_id].ty_id;
+
+                    return self.gen_const_value_expr(
+                        ident_expr.span(),
+                        found_ty_id,
+                        expected_ty_id,
+                        value,
+                    );
+                }
+
+                self.check_fn_use(ident_expr.span(), binding_id)?;
+
+                let binding_id = self.capture_binding(
+                    ident_expr.span(),
+                    frame_id,
+                    ident_expr.ident.symbol,
+                    binding_id,
+                )?;
+
                 let found_ty_id = self.resolve[binding_id].ty_id;
                 let is_mutable = self.resolve[binding_id].is_mutable();
 
@@ -117,6 +151,20 @@ impl AstGenerator<'_> {
                     },
                 )
             }
+            ItemKind::Generic(generic_id) => {
+                self.resolve_expr(
+                    ident_expr.span(),
+                    self.tys().generic,
+                    expected_ty_id,
+                    |resolve, span, ty_id| {
+                        GenericExprAst {
+                            span,
+                            expr_id: resolve.add_expr(ResolveExpr::lvalue(ty_id, false)),
+                            generic_id,
+                        }
+                    },
+                )
+            }
         }
     }
 }
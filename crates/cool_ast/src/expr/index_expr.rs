@@ -1,6 +1,6 @@
-use crate::{AstGenerator, AstResult, ExprAst};
+use crate::{AstGenerator, AstResult, BindingExprAst, ExprAst, GenericExprAst, TyExprAst};
 use cool_parser::IndexExpr;
-use cool_resolve::{ExprId, FrameId, ResolveExpr, ResolveExprKind, TyId, ValueTy};
+use cool_resolve::{ExprId, FrameId, ItemKind, ResolveExpr, ResolveExprKind, TyId, ValueTy};
 use cool_span::{Section, Span};
 
 #[derive(Clone, Debug)]
@@ -25,7 +25,18 @@ impl AstGenerator<'_> {
         expected_ty_id: TyId,
         expr: &IndexExpr,
     ) -> AstResult<ExprAst> {
-        let base = self.gen_expr(frame_id, self.tys().infer, &expr.base)?;
+        let base = match self.gen_expr(frame_id, self.tys().infer, &expr.base)? {
+            ExprAst::Generic(generic_expr) => {
+                return self.gen_generic_instance_expr(
+                    frame_id,
+                    expected_ty_id,
+                    &generic_expr,
+                    expr,
+                );
+            }
+            base => base,
+        };
+
         let index = self.gen_expr(frame_id, self.tys().usize, &expr.index)?;
 
         let base_expr = base.expr_id();
@@ -68,4 +79,56 @@ impl AstGenerator<'_> {
             },
         )
     }
+
+    /// Instantiates a generic item with a single explicit type argument, as in `Vec[i32]`.
+    fn gen_generic_instance_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        generic_expr: &GenericExprAst,
+        expr: &IndexExpr,
+    ) -> AstResult<ExprAst> {
+        let ty_arg = self
+            .gen_expr(frame_id, self.tys().ty, &expr.index)?
+            .as_ty()
+            .expect("type argument is not a type")
+            .item_ty_id;
+
+        let item_id = self.instantiate_generic(expr.span(), generic_expr.generic_id, &[ty_arg])?;
+
+        match self.resolve[item_id] {
+            ItemKind::Binding(binding_id) => {
+                self.check_fn_use(expr.span(), binding_id)?;
+                let found_ty_id = self.resolve[binding_id].ty_id;
+
+                self.resolve_expr(
+                    expr.span(),
+                    found_ty_id,
+                    expected_ty_id,
+                    |resolve, span, ty_id| {
+                        BindingExprAst {
+                            span,
+                            expr_id: resolve.add_expr(ResolveExpr::lvalue(ty_id, false)),
+                            binding_id,
+                        }
+                    },
+                )
+            }
+            ItemKind::Ty(item_ty_id) => {
+                self.resolve_expr(
+                    expr.span(),
+                    self.tys().ty,
+                    expected_ty_id,
+                    |resolve, span, ty_id| {
+                        TyExprAst {
+                            span,
+                            expr_id: resolve.add_expr(ResolveExpr::lvalue(ty_id, false)),
+                            item_ty_id,
+                        }
+                    },
+                )
+            }
+            _ => unreachable!("generic instance is not a type or a binding"),
+        }
+    }
 }
@@ -0,0 +1,158 @@
+use crate::{AstGenerator, AstResult, AstResultExt, ExprAst, FnCallExprAst, TyError, TyErrorKind};
+use cool_lexer::Symbol;
+use cool_parser::FnCallExpr;
+use cool_resolve::{ExprId, FrameId, ItemId, ResolveExpr, TyId};
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct InterfaceObjectExprAst {
+    pub expr_id: ExprId,
+    pub expr: Box<ExprAst>,
+}
+
+impl InterfaceObjectExprAst {
+    #[must_use]
+    pub fn struct_item_id(&self) -> ItemId {
+        self.expr
+            .expr_id()
+            .ty_id
+            .get_ptr()
+            .pointee
+            .get_struct()
+            .item_id
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn interface_item_id(&self) -> ItemId {
+        self.expr_id.ty_id.get_interface().item_id
+    }
+}
+
+impl Section for InterfaceObjectExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.expr.span()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InterfaceMethodExprAst {
+    pub span: Span,
+    pub expr_id: ExprId,
+    pub base: Box<ExprAst>,
+    pub method_index: u32,
+}
+
+impl Section for InterfaceMethodExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstGenerator<'_> {
+    /// Returns the index of `method` in the interface of `base` if `base` is an interface
+    /// object or a pointer to one.
+    pub fn get_interface_method_index(&self, base: &ExprAst, method: Symbol) -> Option<usize> {
+        let base_ty_id = base.expr_id().ty_id;
+
+        let interface_ty_id = match base_ty_id.as_ptr() {
+            Some(ptr_ty) => ptr_ty.pointee,
+            None => base_ty_id,
+        };
+
+        let interface_item_id = interface_ty_id.as_interface()?.item_id;
+
+        self.resolve
+            .get_interface(interface_item_id)?
+            .get_method_index(method)
+    }
+
+    pub fn continue_gen_interface_object_expr(
+        &mut self,
+        expr: Box<ExprAst>,
+        interface_ty_id: TyId,
+    ) -> AstResult<InterfaceObjectExprAst> {
+        let expr = InterfaceObjectExprAst {
+            expr_id: self.resolve.add_expr(ResolveExpr::rvalue(interface_ty_id)),
+            expr,
+        };
+
+        self.define_interface_impl_if_needed(
+            expr.span(),
+            expr.struct_item_id(),
+            expr.interface_item_id(),
+        )?;
+
+        Ok(expr)
+    }
+
+    /// Generates a call to the method at `method_index` through the vtable of `base`, which is
+    /// an interface object or a pointer to one.
+    pub fn gen_interface_method_call_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        base: ExprAst,
+        method_span: Span,
+        method_index: usize,
+        fn_call_expr: &FnCallExpr,
+    ) -> AstResult<ExprAst> {
+        let base = if base.expr_id().ty_id.is_ptr() {
+            self.gen_implicit_deref_expr(Box::new(base))?.into()
+        } else {
+            base
+        };
+
+        let interface_item_id = base.expr_id().ty_id.get_interface().item_id;
+        let method = self
+            .resolve
+            .get_interface(interface_item_id)
+            .unwrap()
+            .methods[method_index];
+        let fn_ty = method.ty_id.get_fn().clone();
+        let params = &fn_ty.params[1..];
+
+        let is_argument_count_valid = if fn_ty.is_variadic {
+            fn_call_expr.args.len() >= params.len()
+        } else {
+            fn_call_expr.args.len() == params.len()
+        };
+
+        if !is_argument_count_valid {
+            return AstResult::error(
+                fn_call_expr.span(),
+                TyError {
+                    ty_id: method.ty_id,
+                    kind: TyErrorKind::InvalidArgumentCount {
+                        found: fn_call_expr.args.len() as _,
+                    },
+                },
+            );
+        }
+
+        let arg_exprs = self.gen_fn_call_args(frame_id, params, &fn_call_expr.args)?;
+
+        let fn_expr = InterfaceMethodExprAst {
+            span: method_span,
+            expr_id: self.resolve.add_expr(ResolveExpr::rvalue(method.ty_id)),
+            base: Box::new(base),
+            method_index: method_index as _,
+        };
+
+        self.resolve_expr(
+            fn_call_expr.span(),
+            fn_ty.ret,
+            expected_ty_id,
+            |resolve, span, ty_id| {
+                FnCallExprAst {
+                    span,
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                    fn_expr: Box::new(fn_expr.into()),
+                    arg_exprs,
+                }
+            },
+        )
+    }
+}
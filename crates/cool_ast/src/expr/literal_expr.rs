@@ -1,5 +1,6 @@
 use crate::{
     AstError, AstGenerator, AstResult, AstResultExt, ExprAst, LiteralError, LiteralErrorKind,
+    UnitExprAst,
 };
 use cool_collections::SmallString;
 use cool_lexer::{sym, IntBase, LiteralKind, Symbol};
@@ -138,6 +139,19 @@ impl AstGenerator<'_> {
                     },
                 )
             }
+            LiteralKind::Null => {
+                self.resolve_expr(
+                    expr.span(),
+                    tys.unit,
+                    expected_ty_id,
+                    |resolve, span, ty_id| {
+                        UnitExprAst {
+                            span,
+                            expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                        }
+                    },
+                )
+            }
         }
     }
 }
@@ -128,6 +128,8 @@ impl AstGenerator<'_> {
                                 )
                                 .map_err(|error| AstError::new(expr.span(), error))?;
 
+                            self.index_local_binding(binding_id, pattern.ident.span);
+
                             let expr =
                                 self.gen_stmt_expr_or_expr(frame_id, found_ty_id, &arm.code)?;
 
@@ -1,21 +1,27 @@
 mod access_expr;
 mod align_of_expr;
 mod array_expr;
+mod assert_expr;
 mod binary_expr;
 mod block_expr;
 mod cast_expr;
+mod closure_expr;
 mod cond_expr;
 mod deref_expr;
 mod fn_call_expr;
 mod ident_expr;
 mod index_expr;
+mod interface_expr;
 mod literal_expr;
 mod match_expr;
 mod offset_of_expr;
+mod or_else_expr;
+mod panic_expr;
 mod range_expr;
 mod size_of_expr;
 mod stmt_expr;
 mod struct_expr;
+mod try_expr;
 mod tuple_expr;
 mod unary_expr;
 mod unit_expr;
@@ -24,21 +30,27 @@ mod variant_wrap_expr;
 pub use self::access_expr::*;
 pub use self::align_of_expr::*;
 pub use self::array_expr::*;
+pub use self::assert_expr::*;
 pub use self::binary_expr::*;
 pub use self::block_expr::*;
 pub use self::cast_expr::*;
+pub use self::closure_expr::*;
 pub use self::cond_expr::*;
 pub use self::deref_expr::*;
 pub use self::fn_call_expr::*;
 pub use self::ident_expr::*;
 pub use self::index_expr::*;
+pub use self::interface_expr::*;
 pub use self::literal_expr::*;
 pub use self::match_expr::*;
 pub use self::offset_of_expr::*;
+pub use self::or_else_expr::*;
+pub use self::panic_expr::*;
 pub use self::range_expr::*;
 pub use self::size_of_expr::*;
 pub use self::stmt_expr::*;
 pub use self::struct_expr::*;
+pub use self::try_expr::*;
 pub use self::tuple_expr::*;
 pub use self::unary_expr::*;
 pub use self::unit_expr::*;
@@ -104,26 +116,35 @@ define_expr_ast! {
     Array,
     ArrayLen,
     ArrayRepeat,
+    Assert,
     Binary,
     Binding,
     Block,
     Cast,
+    Closure,
     Cond,
     Deref,
     FnCall,
+    Generic,
     Index,
+    InterfaceMethod,
+    InterfaceObject,
     Literal,
     Match,
     Module,
     OffsetOf,
+    OrElse,
+    Panic,
     Range,
     SizeOf,
     Stmt,
     Struct,
+    Try,
     Tuple,
     Ty,
     Unary,
     Unit,
+    Unreachable,
     VariantWrap,
 }
 
@@ -149,6 +170,10 @@ macro_rules! impl_gen_expr {
                     };
                 }
 
+                if let Some(source_index) = self.source_index.as_deref_mut() {
+                    source_index.add_expr(&expr);
+                }
+
                 Ok(expr)
             }
         }
@@ -160,9 +185,11 @@ impl_gen_expr! {
     AlignOf,
     Array,
     ArrayRepeat,
+    Assert,
     Binary,
     Block,
     Cast,
+    Closure,
     Cond,
     Deref,
     FnCall,
@@ -171,12 +198,16 @@ impl_gen_expr! {
     Literal,
     Match,
     OffsetOf,
+    OrElse,
+    Panic,
     Paren,
     Range,
     SizeOf,
     Struct,
+    Try,
     Tuple,
     Unary,
+    Unreachable,
 }
 
 impl AstGenerator<'_> {
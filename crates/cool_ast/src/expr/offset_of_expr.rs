@@ -31,7 +31,7 @@ impl AstGenerator<'_> {
             let field = self
                 .resolve
                 .get_ty_def(arg_ty_id)
-                .and_then(|def| def.get_aggregate_field(symbol))
+                .and_then(|def| def.get_field(symbol))
                 .ok_or(AstError::field_not_found(expr.span, arg_ty_id, symbol))?;
 
             arg_ty_id = field.ty_id;
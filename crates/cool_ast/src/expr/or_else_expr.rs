@@ -0,0 +1,59 @@
+use crate::{AstGenerator, AstResult, AstResultExt, ExprAst};
+use cool_parser::OrElseExpr;
+use cool_resolve::{ExprId, FrameId, ResolveExpr, TyId, VariantTy};
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct OrElseExprAst {
+    pub expr_id: ExprId,
+    pub lhs: Box<ExprAst>,
+    pub rhs: Box<ExprAst>,
+}
+
+impl OrElseExprAst {
+    #[inline]
+    pub fn optional_ty_id(&self) -> TyId {
+        self.lhs.expr_id().ty_id
+    }
+}
+
+impl Section for OrElseExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.lhs.span().to(self.rhs.span())
+    }
+}
+
+impl AstGenerator<'_> {
+    pub fn gen_or_else_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        expr: &OrElseExpr,
+    ) -> AstResult<ExprAst> {
+        let lhs = self.gen_expr(frame_id, self.tys().infer, &expr.lhs)?;
+        let lhs_ty_id = lhs.expr_id().ty_id;
+
+        let Some(inner_ty_id) = lhs_ty_id
+            .as_variant()
+            .and_then(VariantTy::get_optional_inner)
+        else {
+            return AstResult::ty_mismatch(expr.lhs.span(), lhs_ty_id, self.tys().infer_variant);
+        };
+
+        let rhs = self.gen_expr(frame_id, inner_ty_id, &expr.rhs)?;
+
+        self.resolve_expr(
+            expr.span(),
+            inner_ty_id,
+            expected_ty_id,
+            |resolve, _, ty_id| {
+                OrElseExprAst {
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{AstGenerator, AstResult, ExprAst};
+use cool_parser::{PanicExpr, UnreachableExpr};
+use cool_resolve::{ExprId, FrameId, ResolveExpr, TyId};
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct PanicExprAst {
+    pub span: Span,
+    pub expr_id: ExprId,
+    pub message: Box<ExprAst>,
+}
+
+impl Section for PanicExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UnreachableExprAst {
+    pub span: Span,
+    pub expr_id: ExprId,
+}
+
+impl Section for UnreachableExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstGenerator<'_> {
+    pub fn gen_panic_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        expr: &PanicExpr,
+    ) -> AstResult<ExprAst> {
+        let message = self.gen_expr(frame_id, self.tys().c_str, &expr.message)?;
+
+        self.resolve_expr(
+            expr.span(),
+            self.tys().diverge,
+            expected_ty_id,
+            |resolve, span, ty_id| {
+                PanicExprAst {
+                    span,
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                    message: Box::new(message),
+                }
+            },
+        )
+    }
+
+    pub fn gen_unreachable_expr(
+        &mut self,
+        _frame_id: FrameId,
+        expected_ty_id: TyId,
+        expr: &UnreachableExpr,
+    ) -> AstResult<ExprAst> {
+        self.resolve_expr(
+            expr.span(),
+            self.tys().diverge,
+            expected_ty_id,
+            |resolve, span, ty_id| {
+                UnreachableExprAst {
+                    span,
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                }
+            },
+        )
+    }
+}
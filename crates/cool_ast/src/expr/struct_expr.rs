@@ -1,9 +1,10 @@
-use crate::{AstGenerator, AstResult, ExprAst};
+use crate::{AstError, AstGenerator, AstResult, AstResultExt, ExprAst, LogicError, TyDefError};
 use cool_lexer::Symbol;
 use cool_parser::{Ident, StructExpr};
 use cool_resolve::{ExprId, FrameId, ResolveExpr, TyId};
 use cool_span::{Section, Span};
 use rustc_hash::FxHashSet;
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub struct StructFieldInitializerAst {
@@ -40,36 +41,71 @@ impl AstGenerator<'_> {
         expected_ty_id: TyId,
         expr: &StructExpr,
     ) -> AstResult<ExprAst> {
-        let ty_id = self
-            .gen_expr(frame_id, self.tys().ty, &expr.base)?
-            .as_ty()
-            .unwrap()
-            .item_ty_id;
+        let ty_id = match self.gen_expr(frame_id, self.tys().infer, &expr.base)? {
+            ExprAst::Generic(generic_expr) => {
+                // Generic struct literals take their type arguments from the expected type
+                let instance = expected_ty_id
+                    .as_struct()
+                    .and_then(|struct_ty| self.resolve.get_generic_instance_of(struct_ty.item_id))
+                    .filter(|instance| instance.generic_id == generic_expr.generic_id);
 
-        ty_id.as_struct().expect("struct base is not a struct type");
+                match instance {
+                    Some(instance) => self.resolve[instance.item_id].as_ty_id().unwrap(),
+                    None => {
+                        return AstResult::error(
+                            expr.span(),
+                            TyDefError::TyArgInferFailed {
+                                param: self.resolve[generic_expr.generic_id].ty_params[0],
+                            },
+                        );
+                    }
+                }
+            }
+            base => {
+                match base.as_ty() {
+                    Some(ty_expr) => ty_expr.item_ty_id,
+                    None => return AstResult::error(expr.span(), LogicError::InvalidStructBase),
+                }
+            }
+        };
+
+        let Some(ty_def) = self.resolve.get_ty_def(ty_id) else {
+            return AstResult::error(expr.span(), LogicError::InvalidStructBase);
+        };
+
+        // Unions are initialized through a single field
+        let (fields, is_union) = match ty_def.kind.as_untagged_union() {
+            Some(untagged_union_ty) => (Arc::<[_]>::from(untagged_union_ty.fields()), true),
+            None => {
+                match ty_def.get_aggregate_fields().filter(|_| ty_id.is_struct()) {
+                    Some(fields) => (fields.clone(), false),
+                    None => return AstResult::error(expr.span(), LogicError::InvalidStructBase),
+                }
+            }
+        };
+
+        if is_union && expr.initializers.len() != 1 {
+            return AstResult::error(expr.span(), LogicError::InvalidUnionInitializer { ty_id });
+        }
 
         let mut initializers = Vec::<StructFieldInitializerAst>::new();
         let mut used_fields = FxHashSet::<Symbol>::default();
 
-        let fields = self
-            .resolve
-            .get_ty_def(ty_id)
-            .unwrap()
-            .get_aggregate_fields()
-            .unwrap()
-            .clone();
-
         for initializer in expr.initializers.iter() {
-            let field_ty_id = fields
+            let field = initializer.ident.symbol;
+
+            let Some(field_ty_id) = fields
                 .iter()
-                .find(|field| field.symbol == initializer.ident.symbol)
-                .map(|field| field.ty_id)
-                .expect("unknown struct field in initializer");
-
-            if !used_fields.insert(initializer.ident.symbol) {
-                panic!(
-                    "duplicate field initializer: {}",
-                    initializer.ident.symbol.as_str()
+                .find(|ty_field| ty_field.symbol == field)
+                .map(|ty_field| ty_field.ty_id)
+            else {
+                return Err(AstError::field_not_found(initializer.span(), ty_id, field));
+            };
+
+            if !used_fields.insert(field) {
+                return AstResult::error(
+                    initializer.span(),
+                    LogicError::DuplicateFieldInitializer { field },
                 );
             }
 
@@ -82,8 +118,8 @@ impl AstGenerator<'_> {
             });
         }
 
-        if initializers.len() < fields.len() {
-            panic!("missing struct fields");
+        if !is_union && initializers.len() < fields.len() {
+            return AstResult::error(expr.span(), LogicError::MissingFieldInitializers { ty_id });
         }
 
         self.resolve_expr(
@@ -0,0 +1,132 @@
+use crate::{AstError, AstGenerator, AstResult, AstResultExt, BindingExprAst, ExprAst, LogicError};
+use cool_lexer::sym;
+use cool_parser::TryExpr;
+use cool_resolve::{BindingId, ExprId, FrameId, ResolveExpr, TyId};
+use cool_span::{Section, Span};
+use smallvec::SmallVec;
+
+#[derive(Clone, Debug)]
+pub struct TryErrorArmAst {
+    pub error_ty_id: TyId,
+    pub binding_id: BindingId,
+    pub ret_expr: Box<ExprAst>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TryExprAst {
+    pub span: Span,
+    pub frame_id: FrameId,
+    pub expr_id: ExprId,
+    pub expr: Box<ExprAst>,
+    pub error_arms: Vec<TryErrorArmAst>,
+}
+
+impl TryExprAst {
+    #[inline]
+    pub fn variant_ty_id(&self) -> TyId {
+        self.expr.expr_id().ty_id
+    }
+}
+
+impl Section for TryExprAst {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstGenerator<'_> {
+    pub fn gen_try_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        expr: &TryExpr,
+    ) -> AstResult<ExprAst> {
+        let inner_expr = self.gen_expr(frame_id, self.tys().infer, &expr.expr)?;
+        let variant_ty_id = inner_expr.expr_id().ty_id;
+
+        let Some(variant_ty) = variant_ty_id.as_variant() else {
+            return AstResult::ty_mismatch(
+                expr.expr.span(),
+                variant_ty_id,
+                self.tys().infer_variant,
+            );
+        };
+
+        let ret_ty_id = self.fn_ret_ty_id();
+
+        // The success variant is the expected type if the expression can hold it, otherwise the
+        // only variant that cannot be returned from the enclosing function
+        let success_ty_id = if variant_ty.has_variant(expected_ty_id) {
+            expected_ty_id
+        } else {
+            let unreturnable_ty_ids = variant_ty
+                .variants()
+                .iter()
+                .copied()
+                .filter(|&ty_id| self.resolve.resolve_ty_id(ty_id, ret_ty_id).is_none())
+                .collect::<SmallVec<[TyId; 2]>>();
+
+            match unreturnable_ty_ids.as_slice() {
+                &[success_ty_id] => success_ty_id,
+                _ => {
+                    return AstResult::error(
+                        expr.span(),
+                        LogicError::AmbiguousTrySuccessVariant {
+                            ty_id: variant_ty_id,
+                        },
+                    );
+                }
+            }
+        };
+
+        let mut error_arms = Vec::<TryErrorArmAst>::new();
+
+        for &error_ty_id in variant_ty.variants() {
+            if error_ty_id == success_ty_id {
+                continue;
+            }
+
+            let arm_frame_id = self.resolve.add_frame(frame_id.into());
+
+            let binding_id = self
+                .resolve
+                .insert_local_binding(arm_frame_id, false, sym::EMPTY, Some(error_ty_id))
+                .map_err(|error| AstError::new(expr.span(), error))?;
+
+            let ret_expr = self.resolve_expr(
+                expr.span(),
+                error_ty_id,
+                ret_ty_id,
+                |resolve, span, ty_id| {
+                    BindingExprAst {
+                        span,
+                        expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                        binding_id,
+                    }
+                },
+            )?;
+
+            error_arms.push(TryErrorArmAst {
+                error_ty_id,
+                binding_id,
+                ret_expr: Box::new(ret_expr),
+            });
+        }
+
+        self.resolve_expr(
+            expr.span(),
+            success_ty_id,
+            expected_ty_id,
+            |resolve, span, ty_id| {
+                TryExprAst {
+                    span,
+                    frame_id,
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                    expr: Box::new(inner_expr),
+                    error_arms,
+                }
+            },
+        )
+    }
+}
@@ -87,6 +87,10 @@ impl AstGenerator<'_> {
                     }
                 }
 
+                if self.is_place_unaligned(&inner_expr) {
+                    return AstResult::error(unary_expr.span(), ExprError::NotAddressableUnaligned);
+                }
+
                 let found_ty_id = self.resolve.mk_ptr(inner_expr_id.ty_id, is_mutable);
 
                 self.resolve_expr(
@@ -104,4 +108,54 @@ impl AstGenerator<'_> {
             }
         }
     }
+
+    /// Returns whether the address of `expr` may be less aligned than its type, which happens
+    /// for places inside fields of packed structs. Pointers do not carry that information.
+    pub fn is_place_unaligned(&self, expr: &ExprAst) -> bool {
+        self.get_place_align(expr) < self.get_ty_align(expr.expr_id().ty_id)
+    }
+
+    fn get_place_align(&self, expr: &ExprAst) -> u64 {
+        let (base, offset_align) = match expr {
+            ExprAst::Access(access_expr) => {
+                let field = self
+                    .resolve
+                    .get_ty_def(access_expr.base.expr_id().ty_id)
+                    .and_then(|ty_def| ty_def.get_field(access_expr.ident.symbol));
+
+                match field {
+                    Some(field) => (&access_expr.base, get_offset_align(field.offset)),
+                    None => return self.get_ty_align(expr.expr_id().ty_id),
+                }
+            }
+            ExprAst::Index(index_expr) if index_expr.base.expr_id().ty_id.is_array() => {
+                let elem_size = self
+                    .resolve
+                    .get_ty_def(expr.expr_id().ty_id)
+                    .map(|ty_def| ty_def.size)
+                    .unwrap_or(0);
+
+                (&index_expr.base, get_offset_align(elem_size))
+            }
+            _ => return self.get_ty_align(expr.expr_id().ty_id),
+        };
+
+        self.get_place_align(base).min(offset_align)
+    }
+
+    fn get_ty_align(&self, ty_id: TyId) -> u64 {
+        self.resolve
+            .get_ty_def(ty_id)
+            .map(|ty_def| ty_def.align)
+            .unwrap_or(1)
+    }
+}
+
+/// Returns the largest alignment satisfied by `offset` and all of its multiples.
+fn get_offset_align(offset: u64) -> u64 {
+    if offset == 0 {
+        u64::MAX
+    } else {
+        1 << offset.trailing_zeros()
+    }
 }
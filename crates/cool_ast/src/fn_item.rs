@@ -1,13 +1,51 @@
-use crate::{AstGenerator, AstResult, AstResultExt, ExprAst, TyError, TyErrorKind};
-use cool_parser::FnExpr;
-use cool_resolve::{BindingId, FrameId, ItemId, ModuleId, TyId};
+use crate::{
+    AstGenerator, AstResult, AstResultExt, BlockExprAst, ExprAst, StmtAst, TyError, TyErrorKind,
+    UnitExprAst,
+};
+use cool_lexer::Symbol;
+use cool_parser::{Expr, FnExpr};
+use cool_resolve::{BindingId, FnAbi, FrameId, ItemId, ResolveExpr, Scope, TyId};
 use cool_span::{Section, Span};
 use smallvec::SmallVec;
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InlineAttr {
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CallConv {
+    C,
+    Fast,
+    Cold,
+}
+
+/// Code generation attributes of a function.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct FnAttrs {
+    pub inline: Option<InlineAttr>,
+    pub is_cold: bool,
+    pub is_weak: bool,
+    pub link_name: Option<Symbol>,
+    pub section: Option<Symbol>,
+    pub call_conv: Option<CallConv>,
+}
+
+impl FnAttrs {
+    /// Functions with a non-default calling convention can only be called directly, as
+    /// function pointer types do not carry a calling convention.
+    #[inline]
+    pub fn is_direct_call_only(&self) -> bool {
+        matches!(self.call_conv, Some(CallConv::Fast | CallConv::Cold))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ExternFnAst {
     pub item_id: ItemId,
     pub ty_id: TyId,
+    pub attrs: FnAttrs,
 }
 
 #[derive(Clone, Debug)]
@@ -18,21 +56,35 @@ pub struct FnAst {
     pub frame_id: FrameId,
     pub binding_ids: SmallVec<[BindingId; 4]>,
     pub body: ExprAst,
+    pub attrs: FnAttrs,
 }
 
 impl AstGenerator<'_> {
-    pub fn gen_extern_fn(&mut self, item_id: ItemId, ty_id: TyId) -> AstResult<ExternFnAst> {
-        Ok(ExternFnAst { item_id, ty_id })
+    pub fn gen_extern_fn(
+        &mut self,
+        item_id: ItemId,
+        ty_id: TyId,
+        attrs: FnAttrs,
+    ) -> AstResult<ExternFnAst> {
+        Ok(ExternFnAst {
+            item_id,
+            ty_id,
+            attrs,
+        })
     }
 
-    pub fn gen_fn(
+    pub fn gen_fn<S>(
         &mut self,
         item_id: ItemId,
-        module_id: ModuleId,
+        scope: S,
         ty_id: TyId,
         fn_expr: &FnExpr,
-    ) -> AstResult<FnAst> {
-        let frame_id = self.resolve.add_frame(module_id.into());
+        attrs: FnAttrs,
+    ) -> AstResult<FnAst>
+    where
+        S: Into<Scope>,
+    {
+        let frame_id = self.resolve.add_frame(scope.into());
 
         let Some(fn_ty) = ty_id.as_fn().cloned() else {
             return AstResult::error(
@@ -64,11 +116,19 @@ impl AstGenerator<'_> {
                 )
                 .unwrap();
 
+            self.index_local_binding(binding_id, param.ident.span);
             binding_ids.push(binding_id);
         }
 
         self.push_fn_state(fn_ty.ret);
-        let body = self.gen_block_expr(frame_id, fn_ty.ret, &fn_expr.body);
+        self.mark_params_non_capturing(&binding_ids);
+
+        let body = self
+            .gen_block_expr(frame_id, fn_ty.ret, &fn_expr.body)
+            .and_then(|body| {
+                self.check_closure_not_escaping(&body)?;
+                Ok(body)
+            });
         self.pop_fn_state();
 
         Ok(FnAst {
@@ -78,6 +138,66 @@ impl AstGenerator<'_> {
             frame_id,
             binding_ids,
             body: body?,
+            attrs,
+        })
+    }
+
+    /// Generates a C function without parameters that evaluates an expression. The function
+    /// returns the value of the expression if `keep_value` accepts its type and discards it
+    /// otherwise.
+    pub fn gen_expr_fn<S, F>(
+        &mut self,
+        item_id: ItemId,
+        scope: S,
+        expr: &Expr,
+        attrs: FnAttrs,
+        keep_value: F,
+    ) -> AstResult<FnAst>
+    where
+        S: Into<Scope>,
+        F: FnOnce(TyId) -> bool,
+    {
+        let frame_id = self.resolve.add_frame(scope.into());
+
+        self.push_fn_state(self.tys().infer);
+        let body = self.gen_expr(frame_id, self.tys().infer, expr);
+        self.pop_fn_state();
+
+        let body = body?;
+        let body_ty_id = body.expr_id().ty_id;
+
+        let (ret_ty_id, body) = if keep_value(body_ty_id) {
+            (body_ty_id, body)
+        } else {
+            let unit_ty_id = self.tys().unit;
+
+            let body = ExprAst::from(BlockExprAst {
+                span: expr.span(),
+                first_frame_id: frame_id,
+                last_frame_id: frame_id,
+                expr_id: self.resolve.add_expr(ResolveExpr::rvalue(unit_ty_id)),
+                stmts: vec![StmtAst::Expr(body)],
+                expr: Box::new(ExprAst::from(UnitExprAst {
+                    span: Span::new(expr.span().end(), 0),
+                    expr_id: self.implicit_unit_expr_id,
+                })),
+            });
+
+            (unit_ty_id, body)
+        };
+
+        let ty_id = self.resolve.mk_fn(FnAbi::C, [], false, ret_ty_id);
+        let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+        self.resolve.set_binding_ty(binding_id, ty_id);
+
+        Ok(FnAst {
+            span: expr.span(),
+            item_id,
+            ty_id,
+            frame_id,
+            binding_ids: SmallVec::new(),
+            body,
+            attrs,
         })
     }
 }
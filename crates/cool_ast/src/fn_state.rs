@@ -1,11 +1,16 @@
-use crate::{AstError, AstGenerator, AstResult, LogicError};
-use cool_resolve::TyId;
+use crate::{AstError, AstGenerator, AstResult, ClosureCaptureAst, LogicError};
+use cool_resolve::{BindingId, FrameId, TyId};
 use cool_span::Span;
+use rustc_hash::FxHashSet;
 
 #[derive(Clone, Debug)]
 pub struct FnState {
     pub ret_ty_id: TyId,
     pub main_block_ty_ids: Vec<TyId>,
+    pub closure_frame_id: Option<FrameId>,
+    pub captures: Vec<ClosureCaptureAst>,
+    /// Immutable bindings whose values hold no closure that captured locals.
+    pub non_capturing_binding_ids: FxHashSet<BindingId>,
 }
 
 impl AstGenerator<'_> {
@@ -29,12 +34,26 @@ impl AstGenerator<'_> {
         self.fn_states.push(FnState {
             ret_ty_id,
             main_block_ty_ids: vec![],
+            closure_frame_id: None,
+            captures: vec![],
+            non_capturing_binding_ids: Default::default(),
         });
     }
 
     #[inline]
-    pub fn pop_fn_state(&mut self) {
-        self.fn_states.pop();
+    pub fn push_closure_fn_state(&mut self, ret_ty_id: TyId, closure_frame_id: FrameId) {
+        self.fn_states.push(FnState {
+            ret_ty_id,
+            main_block_ty_ids: vec![],
+            closure_frame_id: Some(closure_frame_id),
+            captures: vec![],
+            non_capturing_binding_ids: Default::default(),
+        });
+    }
+
+    #[inline]
+    pub fn pop_fn_state(&mut self) -> FnState {
+        self.fn_states.pop().unwrap()
     }
 
     #[inline]
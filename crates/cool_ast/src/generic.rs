@@ -0,0 +1,412 @@
+use crate::{
+    find_generic_method, AstError, AstErrorKind, AstGenerator, AstResult, AstResultExt, FnAst,
+    FnAttrs, TyDefError,
+};
+use cool_lexer::Symbol;
+use cool_parser::{FnExpr, StructItem, Ty};
+use cool_resolve::{
+    GenericId, GenericInstance, GenericKind, ItemId, ItemKind, ItemPathBuf, Scope, TyArgs, TyId,
+    VariantTy,
+};
+use cool_span::{Section, Span};
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
+
+#[derive(Clone, Debug)]
+pub enum GenericItem {
+    Struct(StructItem),
+    Fn {
+        ty: Option<Ty>,
+        expr: FnExpr,
+        attrs: FnAttrs,
+    },
+}
+
+impl Section for GenericItem {
+    #[inline]
+    fn span(&self) -> Span {
+        match self {
+            Self::Struct(struct_item) => struct_item.span(),
+            Self::Fn { expr, .. } => expr.span(),
+        }
+    }
+}
+
+pub type GenericItemMap = FxHashMap<GenericId, GenericItem>;
+
+impl AstGenerator<'_> {
+    pub fn instantiate_generic(
+        &mut self,
+        span: Span,
+        generic_id: GenericId,
+        ty_args: &[TyId],
+    ) -> AstResult<ItemId> {
+        let ty_param_count = self.resolve[generic_id].ty_params.len();
+
+        if ty_args.len() != ty_param_count {
+            return AstResult::error(
+                span,
+                TyDefError::TyArgCountMismatch {
+                    found: ty_args.len() as _,
+                    expected: ty_param_count as _,
+                },
+            );
+        }
+
+        if let Some(item_id) = self.resolve.get_generic_instance(generic_id, ty_args) {
+            return Ok(item_id);
+        }
+
+        let generics = self.generics;
+        let ty_params = match generics.get(&generic_id) {
+            Some(GenericItem::Struct(struct_item)) => struct_item.ty_params.as_ref(),
+            Some(GenericItem::Fn { expr, .. }) => expr.prototype.ty_params.as_ref(),
+            None => None,
+        };
+
+        if let Some(ty_params) = ty_params {
+            self.check_ty_param_bounds(span, generic_id, ty_params, ty_args)?;
+        }
+
+        let item_id = self.resolve.insert_generic_instance(generic_id, ty_args);
+
+        match self.resolve[generic_id].kind {
+            GenericKind::Struct { .. } => {
+                self.declare_generic_methods(generic_id, item_id)
+                    .map_err(|error| error.with_span(span))?;
+                self.define_generic_struct(item_id)
+                    .map_err(|error| error.with_span(span))?;
+            }
+            GenericKind::Fn => {
+                let (ty, expr, attrs) =
+                    get_generic_fn(generics, generic_id).map_err(|error| error.with_span(span))?;
+
+                let frame_id = self.resolve.add_generic_frame(generic_id, ty_args);
+                let fn_ty_id = self
+                    .resolve_fn_prototype(frame_id, ty, &expr.prototype)
+                    .map_err(|error| error.with_span(span))?;
+
+                let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+                self.resolve.set_binding_ty(binding_id, fn_ty_id);
+
+                if attrs.is_direct_call_only() {
+                    self.resolve.mark_direct_call_only(binding_id);
+                }
+            }
+        }
+
+        Ok(item_id)
+    }
+
+    /// Defines the fields of a generic struct instance. Fails if any field type is still
+    /// undefined, in which case the definition can be retried later.
+    pub fn define_generic_struct(&mut self, item_id: ItemId) -> AstResult<()> {
+        let instance = self
+            .resolve
+            .get_generic_instance_of(item_id)
+            .unwrap()
+            .clone();
+
+        let struct_item = get_generic_struct(self.generics, instance.generic_id)?;
+
+        if !struct_item.has_body {
+            return Ok(());
+        }
+
+        let frame_id = self
+            .resolve
+            .add_generic_frame(instance.generic_id, &instance.ty_args);
+
+        let fields = struct_item
+            .fields
+            .iter()
+            .map(|field| {
+                self.resolve_ty(frame_id, &field.ty)
+                    .map(|ty_id| (field.ident.symbol, ty_id))
+            })
+            .collect::<Result<SmallVec<[_; 7]>, _>>()?;
+
+        let ty_id = self.resolve[item_id].as_ty_id().unwrap();
+
+        self.resolve
+            .define_struct(item_id, fields)
+            .map_err(|_| AstError::new(struct_item.span(), TyDefError::TyNotDefinable { ty_id }))?;
+
+        Ok(())
+    }
+
+    pub fn gen_generic_fn(&mut self, instance: &GenericInstance) -> AstResult<FnAst> {
+        let generics = self.generics;
+
+        let (frame_id, expr, attrs) = match generics.get(&instance.generic_id) {
+            Some(GenericItem::Fn { expr, attrs, .. }) => {
+                let frame_id = self
+                    .resolve
+                    .add_generic_frame(instance.generic_id, &instance.ty_args);
+
+                (frame_id, expr, *attrs)
+            }
+            _ => {
+                let (_, expr) = find_generic_method(
+                    generics,
+                    instance.generic_id,
+                    *instance.item_id.last().unwrap(),
+                )
+                .ok_or(AstError::from(AstErrorKind::from(TyDefError::GenericNotFn)))?;
+
+                let frame_id = self.resolve.add_method_frame(instance.item_id);
+                (frame_id, expr, FnAttrs::default())
+            }
+        };
+
+        let binding_id = self.resolve[instance.item_id].as_binding_id().unwrap();
+        let fn_ty_id = self.resolve[binding_id].ty_id;
+
+        self.gen_fn(instance.item_id, frame_id, fn_ty_id, expr, attrs)
+    }
+
+    /// Binds the type parameters of `generic_id` that appear in `ty` by matching `ty` against
+    /// `found_ty_id`. Structural mismatches are ignored and left for the type checker.
+    pub fn infer_ty_args(
+        &mut self,
+        generic_id: GenericId,
+        ty: &Ty,
+        found_ty_id: TyId,
+        ty_args: &mut [Option<TyId>],
+    ) -> AstResult<()> {
+        if found_ty_id.is_diverge() || found_ty_id.is_infer() {
+            return Ok(());
+        }
+
+        match ty {
+            Ty::Array(array_ty) => {
+                if let Some(found_array_ty) = found_ty_id.as_array() {
+                    self.infer_ty_args(generic_id, &array_ty.elem, found_array_ty.elem, ty_args)?;
+                }
+            }
+            Ty::Closure(closure_ty) => {
+                if let Some(found_closure_ty) = found_ty_id.as_closure().cloned() {
+                    let param_iter = closure_ty
+                        .param_list
+                        .params
+                        .iter()
+                        .zip(found_closure_ty.params.iter());
+
+                    for (param_ty, &found_param_ty_id) in param_iter {
+                        self.infer_ty_args(generic_id, param_ty, found_param_ty_id, ty_args)?;
+                    }
+
+                    if let Some(ret_ty) = closure_ty.ret_ty.as_ref() {
+                        self.infer_ty_args(generic_id, ret_ty, found_closure_ty.ret, ty_args)?;
+                    }
+                }
+            }
+            Ty::Fn(fn_ty) => {
+                if let Some(found_fn_ty) = found_ty_id.as_fn().cloned() {
+                    let param_iter = fn_ty
+                        .param_list
+                        .params
+                        .iter()
+                        .zip(found_fn_ty.params.iter());
+
+                    for (param_ty, &found_param_ty_id) in param_iter {
+                        self.infer_ty_args(generic_id, param_ty, found_param_ty_id, ty_args)?;
+                    }
+
+                    if let Some(ret_ty) = fn_ty.ret_ty.as_ref() {
+                        self.infer_ty_args(generic_id, ret_ty, found_fn_ty.ret, ty_args)?;
+                    }
+                }
+            }
+            Ty::ManyPtr(many_ptr_ty) => {
+                if let Some(found_many_ptr_ty) = found_ty_id.as_many_ptr() {
+                    self.infer_ty_args(
+                        generic_id,
+                        &many_ptr_ty.pointee,
+                        found_many_ptr_ty.pointee,
+                        ty_args,
+                    )?;
+                }
+            }
+            Ty::Optional(optional_ty) => {
+                let found_inner_ty_id = found_ty_id
+                    .as_variant()
+                    .and_then(VariantTy::get_optional_inner);
+
+                if let Some(found_inner_ty_id) = found_inner_ty_id {
+                    self.infer_ty_args(generic_id, &optional_ty.inner, found_inner_ty_id, ty_args)?;
+                }
+            }
+            Ty::Paren(paren_ty) => {
+                self.infer_ty_args(generic_id, &paren_ty.inner, found_ty_id, ty_args)?;
+            }
+            Ty::Path(path_ty) => {
+                match (path_ty.idents.as_slice(), path_ty.ty_args.as_ref()) {
+                    ([ident], None) => {
+                        let Some(index) = self.resolve[generic_id]
+                            .ty_params
+                            .iter()
+                            .position(|&ty_param| ty_param == ident.symbol)
+                        else {
+                            return Ok(());
+                        };
+
+                        match ty_args[index] {
+                            Some(ty_arg) if ty_arg != found_ty_id => {
+                                return AstResult::ty_mismatch(path_ty.span(), found_ty_id, ty_arg);
+                            }
+                            _ => ty_args[index] = Some(found_ty_id),
+                        }
+                    }
+                    (_, Some(path_ty_args)) => {
+                        let Some(found_struct_ty) = found_ty_id.as_struct() else {
+                            return Ok(());
+                        };
+
+                        let Some(found_instance) = self
+                            .resolve
+                            .get_generic_instance_of(found_struct_ty.item_id)
+                            .cloned()
+                        else {
+                            return Ok(());
+                        };
+
+                        let path = path_ty
+                            .idents
+                            .iter()
+                            .map(|ident| ident.symbol)
+                            .collect::<ItemPathBuf>();
+
+                        let scope = Scope::Module(self.resolve[generic_id].module_id);
+                        let is_same_generic =
+                            self.resolve
+                                .resolve_global(scope, &path)
+                                .is_ok_and(|item_id| {
+                                    matches!(
+                                        self.resolve[item_id],
+                                        ItemKind::Generic(generic_id)
+                                            if generic_id == found_instance.generic_id
+                                    )
+                                });
+
+                        if !is_same_generic {
+                            return Ok(());
+                        }
+
+                        let arg_iter = path_ty_args.args.iter().zip(found_instance.ty_args.iter());
+
+                        for (arg_ty, &found_arg_ty_id) in arg_iter {
+                            self.infer_ty_args(generic_id, arg_ty, found_arg_ty_id, ty_args)?;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            Ty::Ptr(ptr_ty) => {
+                if let Some(found_ptr_ty) = found_ty_id.as_ptr() {
+                    self.infer_ty_args(generic_id, &ptr_ty.pointee, found_ptr_ty.pointee, ty_args)?;
+                }
+            }
+            Ty::Slice(slice_ty) => {
+                if let Some(found_slice_ty) = found_ty_id.as_slice() {
+                    self.infer_ty_args(generic_id, &slice_ty.elem, found_slice_ty.elem, ty_args)?;
+                }
+            }
+            Ty::Tuple(tuple_ty) => {
+                if let Some(found_tuple_ty) = found_ty_id.as_tuple().cloned() {
+                    let elem_iter = tuple_ty.elems.iter().zip(found_tuple_ty.elems().iter());
+
+                    for (elem_ty, &found_elem_ty_id) in elem_iter {
+                        self.infer_ty_args(generic_id, elem_ty, found_elem_ty_id, ty_args)?;
+                    }
+                }
+            }
+            Ty::Item(_) | Ty::Variant(_) => (),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the inferred type arguments, or an error naming the first type parameter that
+    /// could not be inferred.
+    pub fn collect_ty_args(
+        &self,
+        span: Span,
+        generic_id: GenericId,
+        ty_args: &[Option<TyId>],
+    ) -> AstResult<TyArgs> {
+        ty_args
+            .iter()
+            .zip(self.resolve[generic_id].ty_params.iter())
+            .map(|(ty_arg, &ty_param)| {
+                ty_arg.ok_or(AstError::new(
+                    span,
+                    TyDefError::TyArgInferFailed { param: ty_param },
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Returns whether any of `ty_params` appears in `ty`.
+pub fn ty_mentions_ty_params(ty: &Ty, ty_params: &[Symbol]) -> bool {
+    let mentions = |ty: &Ty| ty_mentions_ty_params(ty, ty_params);
+
+    match ty {
+        Ty::Array(array_ty) => mentions(&array_ty.elem),
+        Ty::Closure(closure_ty) => {
+            closure_ty.param_list.params.iter().any(mentions)
+                || closure_ty.ret_ty.as_deref().is_some_and(mentions)
+        }
+        Ty::Fn(fn_ty) => {
+            fn_ty.param_list.params.iter().any(mentions)
+                || fn_ty.ret_ty.as_deref().is_some_and(mentions)
+        }
+        Ty::Item(_) => false,
+        Ty::ManyPtr(many_ptr_ty) => mentions(&many_ptr_ty.pointee),
+        Ty::Optional(optional_ty) => mentions(&optional_ty.inner),
+        Ty::Paren(paren_ty) => mentions(&paren_ty.inner),
+        Ty::Path(path_ty) => {
+            let is_ty_param = match (path_ty.idents.as_slice(), path_ty.ty_args.as_ref()) {
+                ([ident], None) => ty_params.contains(&ident.symbol),
+                _ => false,
+            };
+
+            is_ty_param
+                || path_ty
+                    .ty_args
+                    .as_ref()
+                    .is_some_and(|ty_args| ty_args.args.iter().any(mentions))
+        }
+        Ty::Ptr(ptr_ty) => mentions(&ptr_ty.pointee),
+        Ty::Slice(slice_ty) => mentions(&slice_ty.elem),
+        Ty::Tuple(tuple_ty) => tuple_ty.elems.iter().any(mentions),
+        Ty::Variant(variant_ty) => variant_ty.variants.iter().any(mentions),
+    }
+}
+
+/// Returns the prototype type, expression and attributes of a generic function.
+pub(crate) fn get_generic_fn(
+    generics: &GenericItemMap,
+    generic_id: GenericId,
+) -> AstResult<(&Option<Ty>, &FnExpr, FnAttrs)> {
+    match generics.get(&generic_id) {
+        Some(GenericItem::Fn { ty, expr, attrs }) => Ok((ty, expr, *attrs)),
+        _ => Err(AstError::from(AstErrorKind::from(TyDefError::GenericNotFn))),
+    }
+}
+
+/// Returns the item a generic struct was declared with.
+pub(crate) fn get_generic_struct(
+    generics: &GenericItemMap,
+    generic_id: GenericId,
+) -> AstResult<&StructItem> {
+    match generics.get(&generic_id) {
+        Some(GenericItem::Struct(struct_item)) => Ok(struct_item),
+        _ => {
+            Err(AstError::from(AstErrorKind::from(
+                TyDefError::GenericNotStruct,
+            )))
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{
+    ty_mentions_ty_params, AstError, AstGenerator, AstResult, AstResultExt, ExprError, TyDefError,
+};
+use cool_lexer::sym;
+use cool_parser::{ImplBlock, InterfaceItem, Ty, TyParamList};
+use cool_resolve::{
+    GenericId, InterfaceImpl, InterfaceMethod, ItemId, ItemPathBuf, ModuleId, ResolveError,
+    ResolveErrorKind, Scope, TyId,
+};
+use cool_span::{Section, Span};
+use smallvec::SmallVec;
+
+impl AstGenerator<'_> {
+    /// Resolves the method signatures of an interface. `Self` can only appear as the pointee of
+    /// the receiver, which must be the first parameter.
+    pub fn define_interface(
+        &mut self,
+        module_id: ModuleId,
+        item_id: ItemId,
+        interface_item: &InterfaceItem,
+    ) -> AstResult<()> {
+        let frame_id = self.resolve.add_interface_frame(module_id, item_id);
+        let mut methods = SmallVec::<[InterfaceMethod; 4]>::new();
+
+        for method in interface_item.methods.iter() {
+            let prototype = &method.prototype;
+
+            if let Some(ty_params) = prototype.ty_params.as_ref() {
+                return AstResult::error(ty_params.span(), TyDefError::TyParamsNotAllowed);
+            }
+
+            if methods.iter().any(|m| m.symbol == method.ident.symbol) {
+                return AstResult::error(
+                    method.ident.span(),
+                    ResolveError {
+                        symbol: method.ident.symbol,
+                        kind: ResolveErrorKind::SymbolAlreadyDefined,
+                    },
+                );
+            }
+
+            let params = &prototype.param_list.params;
+            let receiver_ty = params.first().and_then(|param| param.ty.as_ref());
+
+            let is_receiver_mutable = match receiver_ty {
+                Some(Ty::Ptr(ptr_ty)) if is_self_ty(&ptr_ty.pointee) => Some(ptr_ty.is_mutable),
+                _ => None,
+            };
+
+            let mentions_self = |ty: &Ty| ty_mentions_ty_params(ty, &[sym::SELF_TY]);
+
+            let mentions_self_elsewhere = params
+                .iter()
+                .skip(1)
+                .filter_map(|param| param.ty.as_ref())
+                .any(mentions_self)
+                || prototype.ret_ty.as_ref().is_some_and(mentions_self);
+
+            let Some(is_receiver_mutable) =
+                is_receiver_mutable.filter(|_| !mentions_self_elsewhere)
+            else {
+                return AstResult::error(
+                    method.span(),
+                    TyDefError::InvalidInterfaceMethod {
+                        method: method.ident.symbol,
+                    },
+                );
+            };
+
+            let ty_id = self.resolve_fn_prototype(frame_id, &None, prototype)?;
+
+            methods.push(InterfaceMethod {
+                symbol: method.ident.symbol,
+                ty_id,
+                is_receiver_mutable,
+            });
+        }
+
+        self.resolve.define_interface(item_id, methods);
+        Ok(())
+    }
+
+    /// Resolves the interfaces named by the impl blocks of a struct, in order.
+    pub fn resolve_impl_interfaces(
+        &self,
+        module_id: ModuleId,
+        impl_blocks: &[ImplBlock],
+    ) -> AstResult<SmallVec<[ItemId; 2]>> {
+        let mut interface_item_ids = SmallVec::<[ItemId; 2]>::new();
+
+        for interface in impl_blocks
+            .iter()
+            .filter_map(|block| block.interface.as_ref())
+        {
+            let path = interface
+                .idents
+                .iter()
+                .map(|ident| ident.symbol)
+                .collect::<ItemPathBuf>();
+
+            let interface_item_id = self
+                .resolve
+                .resolve_interface(Scope::Module(module_id), &path)
+                .map_err(|error| AstError::new(interface.span(), error))?;
+
+            if interface_item_ids.contains(&interface_item_id) {
+                return AstResult::error(
+                    interface.span(),
+                    ResolveError {
+                        symbol: path.last(),
+                        kind: ResolveErrorKind::SymbolAlreadyDefined,
+                    },
+                );
+            }
+
+            interface_item_ids.push(interface_item_id);
+        }
+
+        Ok(interface_item_ids)
+    }
+
+    /// Declares the interfaces named by the impl blocks of a struct. The method signatures are
+    /// checked later by `define_interface_impl`.
+    pub fn declare_interface_impls(
+        &mut self,
+        module_id: ModuleId,
+        struct_item_id: ItemId,
+        impl_blocks: &[ImplBlock],
+    ) -> AstResult<()> {
+        for interface_item_id in self.resolve_impl_interfaces(module_id, impl_blocks)? {
+            self.resolve
+                .declare_interface_impl(struct_item_id, interface_item_id);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the methods of a struct match the signatures of an interface it implements,
+    /// with `Self` replaced by the struct.
+    pub fn define_interface_impl(
+        &mut self,
+        span: Span,
+        struct_item_id: ItemId,
+        interface_item_id: ItemId,
+    ) -> AstResult<()> {
+        let struct_ty_id = self.resolve[struct_item_id].as_ty_id().unwrap();
+        let interface_ty_id = self.resolve[interface_item_id].as_ty_id().unwrap();
+        let interface = self
+            .resolve
+            .get_interface(interface_item_id)
+            .unwrap()
+            .clone();
+
+        let mut method_item_ids = InterfaceImpl::new();
+
+        for method in interface.methods.iter() {
+            let Some(method_item_id) = self
+                .resolve
+                .get_method_table(struct_item_id)
+                .and_then(|method_table| method_table.get(&method.symbol))
+                .copied()
+            else {
+                return AstResult::error(
+                    span,
+                    TyDefError::InterfaceMethodMissing {
+                        ty_id: struct_ty_id,
+                        interface_ty_id,
+                        method: method.symbol,
+                    },
+                );
+            };
+
+            // Interface objects call their methods through function pointers
+            let binding_id = self.resolve[method_item_id].as_binding_id().unwrap();
+
+            if self.resolve.is_direct_call_only(binding_id) {
+                return AstResult::error(
+                    span,
+                    ExprError::DirectCallOnly {
+                        symbol: method.symbol,
+                    },
+                );
+            }
+
+            // Methods are always function items, so a matching function type also guarantees
+            // that the vtable can point to the method
+            let found_ty_id = self.resolve_method_ty(span, method_item_id)?;
+            let expected_ty_id = self.mk_interface_method_impl_ty(method, struct_ty_id);
+
+            if found_ty_id != expected_ty_id {
+                return AstResult::ty_mismatch(span, found_ty_id, expected_ty_id);
+            }
+
+            method_item_ids.push(method_item_id);
+        }
+
+        self.resolve
+            .define_interface_impl(struct_item_id, interface_item_id, method_item_ids);
+
+        Ok(())
+    }
+
+    /// Checks the implementation of a generic struct instance the first time one of its
+    /// objects is created.
+    pub fn define_interface_impl_if_needed(
+        &mut self,
+        span: Span,
+        struct_item_id: ItemId,
+        interface_item_id: ItemId,
+    ) -> AstResult<()> {
+        let method_count = self
+            .resolve
+            .get_interface(interface_item_id)
+            .unwrap()
+            .methods
+            .len();
+
+        let is_defined = self
+            .resolve
+            .get_interface_impl(struct_item_id, interface_item_id)
+            .is_some_and(|interface_impl| interface_impl.len() == method_count);
+
+        if is_defined {
+            return Ok(());
+        }
+
+        self.define_interface_impl(span, struct_item_id, interface_item_id)
+    }
+
+    /// Checks that the type arguments of a generic implement the interfaces their parameters
+    /// are bounded by.
+    pub fn check_ty_param_bounds(
+        &mut self,
+        span: Span,
+        generic_id: GenericId,
+        ty_params: &TyParamList,
+        ty_args: &[TyId],
+    ) -> AstResult<()> {
+        let module_id = self.resolve[generic_id].module_id;
+
+        for (ty_param, &ty_arg) in ty_params.params.iter().zip(ty_args) {
+            let Some(bound) = ty_param.bound.as_ref() else {
+                continue;
+            };
+
+            let path = bound
+                .idents
+                .iter()
+                .map(|ident| ident.symbol)
+                .collect::<ItemPathBuf>();
+
+            let interface_item_id = self
+                .resolve
+                .resolve_interface(Scope::Module(module_id), &path)
+                .map_err(|error| AstError::new(bound.span(), error))?;
+
+            let implements_interface = self.struct_item_id(ty_arg).is_some_and(|struct_item_id| {
+                self.resolve
+                    .implements_interface(struct_item_id, interface_item_id)
+            });
+
+            if !implements_interface {
+                return AstResult::error(
+                    span,
+                    TyDefError::InterfaceNotImplemented {
+                        ty_id: ty_arg,
+                        interface_ty_id: self.resolve[interface_item_id].as_ty_id().unwrap(),
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn mk_interface_method_impl_ty(
+        &mut self,
+        method: &InterfaceMethod,
+        struct_ty_id: TyId,
+    ) -> TyId {
+        let fn_ty = method.ty_id.get_fn().clone();
+        let receiver_ty_id = self
+            .resolve
+            .mk_ptr(struct_ty_id, method.is_receiver_mutable);
+
+        let params = std::iter::once(receiver_ty_id).chain(fn_ty.params.iter().skip(1).copied());
+
+        self.resolve
+            .mk_fn(fn_ty.abi, params, fn_ty.is_variadic, fn_ty.ret)
+    }
+}
+
+fn is_self_ty(ty: &Ty) -> bool {
+    match ty {
+        Ty::Path(path_ty) => {
+            matches!(
+                (path_ty.idents.as_slice(), path_ty.ty_args.as_ref()),
+                ([ident], None) if ident.symbol == sym::SELF_TY,
+            )
+        }
+        Ty::Paren(paren_ty) => is_self_ty(&paren_ty.inner),
+        _ => false,
+    }
+}
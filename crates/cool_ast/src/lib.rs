@@ -1,12 +1,17 @@
 mod cond_block;
+mod const_eval;
 mod defer_code_map;
 mod error;
 mod expr;
 mod expr_or_stmt;
 mod fn_item;
 mod fn_state;
+mod generic;
+mod interface;
+mod method;
 mod package;
 mod resolve;
+mod source_index;
 mod stmt;
 
 pub use self::cond_block::*;
@@ -16,33 +21,53 @@ pub use self::expr::*;
 pub use self::expr_or_stmt::*;
 pub use self::fn_item::*;
 pub use self::fn_state::*;
+pub use self::generic::*;
+pub use self::method::*;
 pub use self::package::*;
 pub use self::resolve::*;
+pub use self::source_index::*;
 pub use self::stmt::*;
-use cool_resolve::{ExprId, ResolveContext, ResolveExpr, TyConsts, TyId, TyResolutionMethod};
+use cool_resolve::{
+    BindingId, ExprId, ResolveContext, ResolveExpr, TyConsts, TyId, TyResolutionMethod,
+};
 use cool_span::Span;
 
 pub struct AstGenerator<'a> {
     pub resolve: &'a mut ResolveContext,
+    pub generics: &'a GenericItemMap,
     pub defer_stmts: DeferStmtMap,
     pub fn_states: Vec<FnState>,
+    /// Records the spans of the generated expressions and local bindings when set.
+    pub source_index: Option<&'a mut SourceIndex>,
+    /// Span of the callee of the innermost function call being generated.
+    callee_span: Option<Span>,
     implicit_unit_expr_id: ExprId,
 }
 
 impl<'a> AstGenerator<'a> {
     #[inline]
-    pub fn new(resolve: &'a mut ResolveContext) -> Self {
+    pub fn new(resolve: &'a mut ResolveContext, generics: &'a GenericItemMap) -> Self {
         let unit_ty_id = resolve.ty_consts().unit;
         let implicit_unit_expr_id = resolve.add_expr(ResolveExpr::rvalue(unit_ty_id));
 
         Self {
             resolve,
+            generics,
             defer_stmts: Default::default(),
             fn_states: Default::default(),
+            source_index: None,
+            callee_span: None,
             implicit_unit_expr_id,
         }
     }
 
+    #[inline]
+    pub fn index_local_binding(&mut self, binding_id: BindingId, span: Span) {
+        if let Some(source_index) = self.source_index.as_deref_mut() {
+            source_index.add_local_binding(binding_id, span);
+        }
+    }
+
     #[inline]
     pub fn tys(&self) -> &TyConsts {
         self.resolve.ty_consts()
@@ -97,6 +122,11 @@ impl<'a> AstGenerator<'a> {
                 self.continue_gen_variant_wrap_expr(Box::new(inner), ty_id)?
                     .into()
             }
+            TyResolutionMethod::IntoInterface { data_ty_id } => {
+                let data = expr_builder(self.resolve, span, data_ty_id).into();
+                self.continue_gen_interface_object_expr(Box::new(data), ty_id)?
+                    .into()
+            }
             _ => expr_builder(self.resolve, span, ty_id).into(),
         };
 
@@ -0,0 +1,297 @@
+use crate::{
+    get_generic_struct, AstGenerator, AstResult, AstResultExt, BindingExprAst, ExprAst, ExprError,
+    FnCallExprAst, GenericItem, GenericItemMap, LogicError, TyError, TyErrorKind, UnaryExprAst,
+};
+use cool_lexer::Symbol;
+use cool_parser::{DeclKind, Expr, FnCallExpr, FnExpr, Ident, Item, Ty, UnaryOp, UnaryOpKind};
+use cool_resolve::{FrameId, GenericId, ItemId, ResolveErrorKind, ResolveExpr, Scope, TyId};
+use cool_span::{Section, Span};
+
+impl AstGenerator<'_> {
+    pub fn struct_item_id(&self, ty_id: TyId) -> Option<ItemId> {
+        ty_id
+            .as_struct()
+            .map(|struct_ty| struct_ty.item_id)
+            .or_else(|| ty_id.as_empty_struct().map(|struct_ty| struct_ty.item_id))
+    }
+
+    /// Declares the methods and interface implementations of a generic struct instance. The
+    /// method types are only resolved once they are used.
+    pub fn declare_generic_methods(
+        &mut self,
+        generic_id: GenericId,
+        struct_item_id: ItemId,
+    ) -> AstResult<()> {
+        let struct_item = get_generic_struct(self.generics, generic_id)?;
+
+        let module_id = self.resolve[generic_id].module_id;
+
+        for decl in struct_item
+            .impl_blocks
+            .iter()
+            .flat_map(|block| block.decls.iter())
+        {
+            let DeclKind::Item(item_decl) = &decl.kind else {
+                continue;
+            };
+
+            // Duplicate methods are reported when the generic struct is declared
+            let _ = self.resolve.declare_method(
+                struct_item_id,
+                module_id,
+                decl.is_exported,
+                item_decl.ident.symbol,
+            );
+        }
+
+        // Invalid interfaces are reported when the generic struct is declared
+        let _ = self.declare_interface_impls(module_id, struct_item_id, &struct_item.impl_blocks);
+        Ok(())
+    }
+
+    /// Returns the type of a method, resolving its prototype first if it belongs to a generic
+    /// struct instance and was not used before.
+    pub fn resolve_method_ty(&mut self, span: Span, item_id: ItemId) -> AstResult<TyId> {
+        let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+        let ty_id = self.resolve[binding_id].ty_id;
+
+        if !ty_id.is_infer() {
+            return Ok(ty_id);
+        }
+
+        let struct_item_id = self.resolve.get_method(item_id).unwrap().struct_item_id;
+
+        let Some(generic_id) = self
+            .resolve
+            .get_generic_instance_of(struct_item_id)
+            .map(|instance| instance.generic_id)
+        else {
+            return Ok(ty_id);
+        };
+
+        let generics = self.generics;
+        let Some((ty, fn_expr)) =
+            find_generic_method(generics, generic_id, *item_id.last().unwrap())
+        else {
+            return Ok(ty_id);
+        };
+
+        let frame_id = self.resolve.add_method_frame(item_id);
+        let fn_ty_id = self
+            .resolve_fn_prototype(frame_id, ty, &fn_expr.prototype)
+            .map_err(|error| error.with_span(span))?;
+
+        self.resolve.set_binding_ty(binding_id, fn_ty_id);
+        self.resolve.insert_generic_method_instance(item_id);
+        Ok(fn_ty_id)
+    }
+
+    /// Returns the method named `ident` if `base` is a struct or a pointer to a struct that has
+    /// no field with the same name.
+    pub fn get_receiver_method(
+        &self,
+        frame_id: FrameId,
+        base: &ExprAst,
+        ident: Ident,
+    ) -> AstResult<Option<ItemId>> {
+        let base_ty_id = base.expr_id().ty_id;
+
+        let struct_ty_id = match base_ty_id.as_ptr() {
+            Some(ptr_ty) => ptr_ty.pointee,
+            None => base_ty_id,
+        };
+
+        let Some(struct_item_id) = self.struct_item_id(struct_ty_id) else {
+            return Ok(None);
+        };
+
+        let has_field = self
+            .resolve
+            .get_ty_def(struct_ty_id)
+            .and_then(|ty_def| ty_def.get_aggregate_field(ident.symbol))
+            .is_some();
+
+        if has_field {
+            return Ok(None);
+        }
+
+        match self
+            .resolve
+            .resolve_method(Scope::Frame(frame_id), struct_item_id, ident.symbol)
+        {
+            Ok(item_id) => Ok(Some(item_id)),
+            Err(error) if error.kind == ResolveErrorKind::SymbolNotFound => Ok(None),
+            Err(error) => AstResult::error(ident.span(), error),
+        }
+    }
+
+    /// Generates a call to a method with `base` as the receiver, taking its address or
+    /// dereferencing it to match the receiver type.
+    pub fn gen_method_call_expr(
+        &mut self,
+        frame_id: FrameId,
+        expected_ty_id: TyId,
+        base: ExprAst,
+        method_item_id: ItemId,
+        fn_call_expr: &FnCallExpr,
+    ) -> AstResult<ExprAst> {
+        let method_span = match &fn_call_expr.base.as_ref() {
+            Expr::Access(access_expr) => access_expr.span(),
+            base => base.span(),
+        };
+
+        let fn_ty_id = self.resolve_method_ty(method_span, method_item_id)?;
+
+        let Some(fn_ty) = fn_ty_id.as_fn().cloned() else {
+            return AstResult::error(
+                fn_call_expr.span(),
+                TyError {
+                    ty_id: fn_ty_id,
+                    kind: TyErrorKind::TyNotCallable,
+                },
+            );
+        };
+
+        let base_ty_id = base.expr_id().ty_id;
+        let struct_ty_id = match base_ty_id.as_ptr() {
+            Some(ptr_ty) => ptr_ty.pointee,
+            None => base_ty_id,
+        };
+
+        let receiver_ty_id = fn_ty.params.first().copied();
+        let receiver = match receiver_ty_id.map(|ty_id| (ty_id, ty_id.as_ptr().copied())) {
+            Some((receiver_ty_id, None)) if receiver_ty_id == struct_ty_id => {
+                if base_ty_id.is_ptr() {
+                    self.gen_implicit_deref_expr(Box::new(base))?.into()
+                } else {
+                    base
+                }
+            }
+            Some((_, Some(receiver_ptr_ty))) if receiver_ptr_ty.pointee == struct_ty_id => {
+                if let Some(base_ptr_ty) = base_ty_id.as_ptr() {
+                    if receiver_ptr_ty.is_mutable && !base_ptr_ty.is_mutable {
+                        return AstResult::ty_mismatch(
+                            base.span(),
+                            base_ty_id,
+                            receiver_ty_id.unwrap(),
+                        );
+                    }
+
+                    base
+                } else {
+                    self.gen_receiver_addr_expr(base, receiver_ptr_ty.is_mutable)?
+                }
+            }
+            _ => {
+                return AstResult::error(
+                    method_span,
+                    LogicError::MethodWithoutReceiver {
+                        ty_id: struct_ty_id,
+                        method: *method_item_id.last().unwrap(),
+                    },
+                );
+            }
+        };
+
+        let params = &fn_ty.params[1..];
+
+        let is_argument_count_valid = if fn_ty.is_variadic {
+            fn_call_expr.args.len() >= params.len()
+        } else {
+            fn_call_expr.args.len() == params.len()
+        };
+
+        if !is_argument_count_valid {
+            return AstResult::error(
+                fn_call_expr.span(),
+                TyError {
+                    ty_id: fn_ty_id,
+                    kind: TyErrorKind::InvalidArgumentCount {
+                        found: fn_call_expr.args.len() as _,
+                    },
+                },
+            );
+        }
+
+        let mut arg_exprs = vec![receiver];
+        arg_exprs.extend(self.gen_fn_call_args(frame_id, params, &fn_call_expr.args)?);
+
+        let binding_id = self.resolve[method_item_id].as_binding_id().unwrap();
+        let fn_expr = BindingExprAst {
+            span: method_span,
+            expr_id: self.resolve.add_expr(ResolveExpr::lvalue(fn_ty_id, false)),
+            binding_id,
+        };
+
+        self.resolve_expr(
+            fn_call_expr.span(),
+            fn_ty.ret,
+            expected_ty_id,
+            |resolve, span, ty_id| {
+                FnCallExprAst {
+                    span,
+                    expr_id: resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+                    fn_expr: Box::new(fn_expr.into()),
+                    arg_exprs,
+                }
+            },
+        )
+    }
+
+    fn gen_receiver_addr_expr(&mut self, base: ExprAst, is_mutable: bool) -> AstResult<ExprAst> {
+        let base_expr_id = base.expr_id();
+
+        if is_mutable && !base_expr_id.is_mutably_addressable() {
+            return AstResult::error(base.span(), ExprError::NotAddressableMutably);
+        }
+
+        if !base_expr_id.is_addressable() {
+            return AstResult::error(base.span(), ExprError::NotAddressable);
+        }
+
+        if self.is_place_unaligned(&base) {
+            return AstResult::error(base.span(), ExprError::NotAddressableUnaligned);
+        }
+
+        let ty_id = self.resolve.mk_ptr(base_expr_id.ty_id, is_mutable);
+
+        Ok(UnaryExprAst {
+            expr_id: self.resolve.add_expr(ResolveExpr::rvalue(ty_id)),
+            op: UnaryOp {
+                span: base.span(),
+                kind: UnaryOpKind::Addr { is_mutable },
+            },
+            expr: Box::new(base),
+        }
+        .into())
+    }
+}
+
+/// Finds the template of a method declared in the impl block of a generic struct.
+pub fn find_generic_method(
+    generics: &GenericItemMap,
+    generic_id: GenericId,
+    symbol: Symbol,
+) -> Option<(&Option<Ty>, &FnExpr)> {
+    let GenericItem::Struct(struct_item) = generics.get(&generic_id)? else {
+        return None;
+    };
+
+    struct_item
+        .impl_blocks
+        .iter()
+        .flat_map(|impl_block| impl_block.decls.iter())
+        .filter_map(|decl| decl.kind.as_item_decl())
+        .find(|item_decl| item_decl.ident.symbol == symbol)
+        .and_then(|item_decl| {
+            match &item_decl.item {
+                Item::Const(const_item) => {
+                    match &const_item.expr {
+                        Expr::Fn(fn_expr) => Some((&item_decl.ty, fn_expr)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        })
+}
@@ -1,8 +1,13 @@
 use crate::{DeferStmtMap, ExternFnAst, FnAst};
+use cool_resolve::ItemId;
 
 #[derive(Clone, Default, Debug)]
 pub struct PackageAst {
     pub fns: Vec<FnAst>,
     pub extern_fns: Vec<ExternFnAst>,
+    /// Functions defined by a previously generated module, which are only declared.
+    pub declared_fns: Vec<ExternFnAst>,
     pub defer_stmts: DeferStmtMap,
+    /// Functions run by the test harness, in declaration order.
+    pub tests: Vec<ItemId>,
 }
@@ -1,17 +1,20 @@
 use crate::{AstError, AstGenerator, AstResult, AstResultExt, TyDefError, TyError, TyErrorKind};
 use cool_parser::{FnExternDecl, FnPrototype, Ty};
-use cool_resolve::{FnAbi, ModuleId, Scope, TyId};
+use cool_resolve::{FnAbi, Scope, TyId};
 use cool_span::Section;
 use smallvec::SmallVec;
 
 impl AstGenerator<'_> {
-    pub fn resolve_fn_prototype(
+    pub fn resolve_fn_prototype<S>(
         &mut self,
-        module_id: ModuleId,
+        scope: S,
         explicit_ty: &Option<Ty>,
         prototype: &FnPrototype,
-    ) -> AstResult<TyId> {
-        let scope = Scope::Module(module_id);
+    ) -> AstResult<TyId>
+    where
+        S: Into<Scope>,
+    {
+        let scope: Scope = scope.into();
 
         let explicit_ty_id = explicit_ty
             .as_ref()
@@ -1,7 +1,7 @@
 use crate::resolve::fn_ty::resolve_fn_abi;
-use crate::{AstError, AstGenerator, AstResult};
+use crate::{AstError, AstGenerator, AstResult, AstResultExt, TyDefError};
 use cool_parser::{ItemKind, Ty};
-use cool_resolve::{FrameId, ItemPathBuf, ResolveError, ResolveErrorKind, Scope, TyId};
+use cool_resolve::{ItemPathBuf, ResolveError, ResolveErrorKind, Scope, TyArgs, TyId};
 mod fn_ty;
 use cool_span::Section;
 use smallvec::SmallVec;
@@ -19,17 +19,38 @@ impl AstGenerator<'_> {
     fn resolve_ty_inner(&mut self, scope: Scope, ty: &Ty) -> AstResult<TyId> {
         let ty_id = match ty {
             Ty::Array(array_ty) => {
-                let len = self
-                    .gen_literal_expr(FrameId::dummy(), self.tys().usize, &array_ty.len)
-                    .unwrap()
-                    .as_literal()
-                    .unwrap()
-                    .as_int_value()
-                    .unwrap() as u64;
+                let len = self.resolve_array_len(scope, &array_ty.len)?;
 
                 let elem = self.resolve_ty_inner(scope, &array_ty.elem)?;
                 self.resolve.mk_array(len, elem)
             }
+            Ty::Closure(closure_ty) => {
+                if closure_ty.param_list.is_variadic {
+                    return AstResult::error(
+                        closure_ty.span(),
+                        TyDefError::VariadicMismatch {
+                            found: true,
+                            expected: false,
+                        },
+                    );
+                }
+
+                let param_ty_ids = closure_ty
+                    .param_list
+                    .params
+                    .iter()
+                    .map(|ty| self.resolve_ty_inner(scope, ty))
+                    .collect::<Result<TySmallVec, _>>()?;
+
+                let ret_ty_id = closure_ty
+                    .ret_ty
+                    .as_ref()
+                    .map(|ty| self.resolve_ty_inner(scope, ty))
+                    .transpose()?
+                    .unwrap_or(self.tys().unit);
+
+                self.resolve.mk_closure(param_ty_ids, ret_ty_id)
+            }
             Ty::Fn(fn_ty) => {
                 let abi = resolve_fn_abi(&fn_ty.extern_decl)?;
 
@@ -60,8 +81,18 @@ impl AstGenerator<'_> {
                 let pointee = self.resolve_ty_inner(scope, &many_ptr_ty.pointee)?;
                 self.resolve.mk_many_ptr(pointee, many_ptr_ty.is_mutable)
             }
+            Ty::Optional(optional_ty) => {
+                let inner = self.resolve_ty_inner(scope, &optional_ty.inner)?;
+                self.resolve.mk_optional(inner)
+            }
             Ty::Paren(paren_ty) => self.resolve_ty_inner(scope, &paren_ty.inner)?,
             Ty::Path(path_ty) => {
+                if let ([ident], None) = (path_ty.idents.as_slice(), path_ty.ty_args.as_ref()) {
+                    if let Some(ty_id) = self.resolve.resolve_ty_param(scope, ident.symbol) {
+                        return Ok(ty_id);
+                    }
+                }
+
                 let path = path_ty
                     .idents
                     .iter()
@@ -73,6 +104,40 @@ impl AstGenerator<'_> {
                     .resolve_global(scope, &path)
                     .map_err(|error| AstError::new(path_ty.span(), error))?;
 
+                let item_id = match (
+                    self.resolve[item_id].as_generic_id(),
+                    path_ty.ty_args.as_ref(),
+                ) {
+                    (Some(generic_id), Some(ty_args)) => {
+                        let ty_args = ty_args
+                            .args
+                            .iter()
+                            .map(|ty| self.resolve_ty_inner(scope, ty))
+                            .collect::<Result<TyArgs, _>>()?;
+
+                        self.instantiate_generic(path_ty.span(), generic_id, &ty_args)?
+                    }
+                    (Some(_), None) => {
+                        return AstResult::error(
+                            path_ty.span(),
+                            ResolveError {
+                                symbol: path.last(),
+                                kind: ResolveErrorKind::SymbolIsGeneric,
+                            },
+                        );
+                    }
+                    (None, Some(ty_args)) => {
+                        return AstResult::error(
+                            ty_args.span(),
+                            TyDefError::TyArgCountMismatch {
+                                found: ty_args.args.len() as _,
+                                expected: 0,
+                            },
+                        );
+                    }
+                    (None, None) => item_id,
+                };
+
                 self.resolve[item_id]
                     .as_ty_id()
                     .filter(|ty_id| !ty_id.is_infer())
@@ -0,0 +1,72 @@
+use crate::ExprAst;
+use cool_resolve::{BindingId, ExprId, ModuleId, TyId};
+use cool_span::{Section, Span};
+use rustc_hash::FxHashMap;
+
+/// Item or binding named by an expression.
+#[derive(Clone, Copy, Debug)]
+pub enum SourceRef {
+    Binding(BindingId),
+    Module(ModuleId),
+    Ty(TyId),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IndexedExpr {
+    pub span: Span,
+    pub expr_id: ExprId,
+    pub source_ref: Option<SourceRef>,
+}
+
+/// Spans of the expressions and local bindings generated by an `AstGenerator`, used by editor
+/// tooling to map source locations back to types and definitions.
+#[derive(Clone, Default, Debug)]
+pub struct SourceIndex {
+    pub exprs: Vec<IndexedExpr>,
+    pub local_bindings: FxHashMap<BindingId, Span>,
+}
+
+impl SourceIndex {
+    pub fn add_expr(&mut self, expr: &ExprAst) {
+        let source_ref = match expr {
+            ExprAst::Binding(expr) => Some(SourceRef::Binding(expr.binding_id)),
+            ExprAst::Module(expr) => Some(SourceRef::Module(expr.module_id)),
+            ExprAst::Ty(expr) => Some(SourceRef::Ty(expr.item_ty_id)),
+            _ => None,
+        };
+
+        self.exprs.push(IndexedExpr {
+            span: expr.span(),
+            expr_id: expr.expr_id(),
+            source_ref,
+        });
+    }
+
+    #[inline]
+    pub fn add_local_binding(&mut self, binding_id: BindingId, span: Span) {
+        self.local_bindings.insert(binding_id, span);
+    }
+
+    /// Returns the innermost expression whose span contains the offset.
+    pub fn find_expr(&self, offset: u32) -> Option<&IndexedExpr> {
+        self.find_expr_by(offset, |_| true)
+    }
+
+    /// Returns the innermost expression whose span contains the offset and that names an item
+    /// or a binding.
+    pub fn find_source_ref(&self, offset: u32) -> Option<SourceRef> {
+        self.find_expr_by(offset, |expr| expr.source_ref.is_some())
+            .and_then(|expr| expr.source_ref)
+    }
+
+    fn find_expr_by<F>(&self, offset: u32, filter: F) -> Option<&IndexedExpr>
+    where
+        F: Fn(&IndexedExpr) -> bool,
+    {
+        self.exprs
+            .iter()
+            .filter(|expr| expr.span.start <= offset && offset <= expr.span.end())
+            .filter(|expr| filter(expr))
+            .min_by_key(|expr| expr.span.len)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{AstGenerator, AstResult, AstResultExt, ExprAst, ExprError};
-use cool_parser::{AssignOp, AssignStmt};
+use cool_parser::{AssignOp, AssignStmt, BinOp};
 use cool_resolve::FrameId;
 use cool_span::{Section, Span};
 
@@ -29,7 +29,37 @@ impl AstGenerator<'_> {
         }
 
         let ty_id = lhs.expr_id().ty_id;
-        let rhs = self.gen_expr(frame_id, ty_id, &stmt.rhs)?;
+
+        let is_int_only = stmt.assign_op.bin_op().is_some_and(|bin_op| {
+            matches!(bin_op, BinOp::Arithmetic(arithmetic_op) if arithmetic_op.is_int_only())
+        });
+
+        if is_int_only && !ty_id.is_int() {
+            return AstResult::ty_mismatch(stmt.span(), ty_id, self.tys().infer_int);
+        }
+
+        let rhs = match stmt.assign_op.bin_op() {
+            Some(BinOp::Arithmetic(arithmetic_op)) if ty_id.is_many_ptr() => {
+                let (found_ty_id, rhs) = self.gen_ptr_arithmetic_rhs(
+                    frame_id,
+                    stmt.span(),
+                    ty_id,
+                    arithmetic_op,
+                    &stmt.rhs,
+                )?;
+
+                if found_ty_id != ty_id {
+                    return AstResult::ty_mismatch(stmt.span(), found_ty_id, ty_id);
+                }
+
+                rhs
+            }
+            _ => self.gen_expr(frame_id, ty_id, &stmt.rhs)?,
+        };
+
+        if !self.is_local_place(&lhs) {
+            self.check_closure_not_escaping(&rhs)?;
+        }
 
         Ok(AssignStmtAst {
             assign_op: stmt.assign_op,
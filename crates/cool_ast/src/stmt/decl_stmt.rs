@@ -45,6 +45,14 @@ impl AstGenerator<'_> {
             )
             .map_err(|error| AstError::new(decl_stmt.span(), error))?;
 
+        self.index_local_binding(binding_id, decl_stmt.pattern.ident.span);
+
+        if !decl_stmt.pattern.is_mutable && self.find_capturing_closure(&expr).is_none() {
+            self.fn_state_mut()
+                .non_capturing_binding_ids
+                .insert(binding_id);
+        }
+
         Ok(DeclStmtAst {
             span: decl_stmt.span(),
             frame_id,
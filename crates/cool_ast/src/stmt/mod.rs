@@ -52,7 +52,11 @@ impl StmtAst {
 
     #[inline]
     pub fn diverges(&self) -> bool {
-        matches!(self, Self::Break(_) | Self::Continue(_) | Self::Return(_))
+        match self {
+            Self::Break(_) | Self::Continue(_) | Self::Return(_) => true,
+            Self::Expr(expr) => expr.expr_id().ty_id.is_diverge(),
+            _ => false,
+        }
     }
 }
 
@@ -31,6 +31,8 @@ impl AstGenerator<'_> {
             .map(|expr| self.gen_expr(frame_id, expr_ty_id, expr))
             .unwrap_or_else(|| self.implicit_unit_expr(stmt.span().end(), expr_ty_id))?;
 
+        self.check_closure_not_escaping(&expr)?;
+
         Ok(ReturnStmtAst {
             span: stmt.span,
             frame_id,
@@ -1,9 +1,115 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use cool_driver::CompileOptions;
 use std::path::PathBuf;
 
 #[derive(Parser)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Compile the package described by a manifest along with its dependencies.
+    Build(BuildArgs),
+    /// Compile a crate and run its main function in-process.
+    Run(RunArgs),
+    /// Compile the tests of a crate and run them in-process.
+    Test(TestArgs),
+    /// Read declarations and expressions from the standard input and evaluate them one at a
+    /// time.
+    Repl(ReplArgs),
+    /// Rewrite source files in the canonical style.
+    Fmt(FmtArgs),
+}
+
+#[derive(clap::Args)]
+pub struct BuildArgs {
+    #[arg(long, default_value = "cool.toml")]
+    pub manifest_path: PathBuf,
+    /// Abort with the source location on integer overflow, division by zero and oversized
+    /// shifts.
+    #[arg(long)]
+    pub overflow_checks: bool,
+    /// Write the LLVM IR of the package instead of the output kind from the manifest.
+    #[arg(long)]
+    pub emit_llvm: bool,
+    /// Target triple of the written objects. Defaults to the host.
+    #[arg(long)]
+    pub target: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub struct CompileArgs {
     #[arg(long)]
     pub crate_name: String,
+    /// Abort with the source location on integer overflow, division by zero and oversized
+    /// shifts.
+    #[arg(long)]
+    pub overflow_checks: bool,
     pub crate_root_file: PathBuf,
 }
+
+impl From<CompileArgs> for CompileOptions {
+    #[inline]
+    fn from(args: CompileArgs) -> Self {
+        Self {
+            crate_name: args.crate_name,
+            crate_root_file: args.crate_root_file,
+            dependencies: Vec::new(),
+            packages: Vec::new(),
+            overflow_checks: args.overflow_checks,
+            test: false,
+        }
+    }
+}
+
+#[derive(clap::Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub compile: CompileArgs,
+    /// Arguments passed to the program.
+    #[arg(last = true)]
+    pub program_args: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub struct TestArgs {
+    #[command(flatten)]
+    pub compile: CompileArgs,
+    /// Only run the tests whose name contains this string.
+    pub filter: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub struct ReplArgs {
+    #[arg(long, default_value = "repl")]
+    pub crate_name: String,
+    /// Abort with the source location on integer overflow, division by zero and oversized
+    /// shifts.
+    #[arg(long)]
+    pub overflow_checks: bool,
+}
+
+impl From<ReplArgs> for CompileOptions {
+    #[inline]
+    fn from(args: ReplArgs) -> Self {
+        Self {
+            crate_name: args.crate_name,
+            crate_root_file: PathBuf::from("<repl>"),
+            dependencies: Vec::new(),
+            packages: Vec::new(),
+            overflow_checks: args.overflow_checks,
+            test: false,
+        }
+    }
+}
+
+#[derive(clap::Args)]
+pub struct FmtArgs {
+    /// Only report the files that are not formatted, without changing them.
+    #[arg(long)]
+    pub check: bool,
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+}
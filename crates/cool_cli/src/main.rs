@@ -1,58 +1,102 @@
 mod args;
 
-use crate::args::Args;
+use crate::args::{Args, BuildArgs, Command, FmtArgs, ReplArgs};
 use clap::Parser as _;
 use colored::Colorize;
 use cool_ast::PackageAst;
-use cool_driver::{CompileError, CompileErrorBundle, CompileOptions, Package};
+use cool_driver::{
+    BuildPackage, BuildPlan, CodeGeneratorContext, CompileError, CompileErrorBundle,
+    CompileOptions, CompileResult, Package, PackageInterface, Repl, ReplOutput, SourceMap,
+};
+use cool_lexer::SymbolTable;
+use cool_resolve::ResolveContext;
+use inkwell::module::Module;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
 use std::process::ExitCode;
+use std::sync::Arc;
+use std::{fs, ptr};
 
-fn compile(options: &CompileOptions) -> Result<(), (Package, CompileErrorBundle)> {
-    let (codegen, mut resolve) = match cool_driver::p0_init("x86_64-unknown-linux-gnu") {
-        Ok((codegen, resolve)) => {
-            println!("p0 - init:          success");
-            (codegen, resolve)
+/// Output of the passes, which is handed to the action run after them.
+struct Compilation<'a, 'ctx> {
+    codegen: &'a CodeGeneratorContext,
+    resolve: &'a ResolveContext,
+    package: &'a Package,
+    module: &'a Module<'ctx>,
+}
+
+fn compile<F>(options: &CompileOptions, target_triple: &str, on_module: F) -> ExitCode
+where
+    F: FnOnce(&Compilation) -> CompileResult<ExitCode>,
+{
+    let (codegen, mut session) = match cool_driver::p0_init(target_triple) {
+        Ok((codegen, session)) => {
+            eprintln!("p0 - init:          success");
+            (codegen, session)
         }
         Err(errors) => {
-            println!("p0 - init:          error");
-            return Err((Package::default(), errors));
+            eprintln!("p0 - init:          error");
+            eprintln!();
+            print_errors(&SourceMap::default(), &errors);
+            return ExitCode::FAILURE;
         }
     };
 
-    let (package, mut errors) = match cool_driver::p1_parse(&mut resolve, options) {
+    session.enter(|resolve| {
+        match run_passes(options, &codegen, resolve, on_module) {
+            Ok(exit_code) => exit_code,
+            Err((package, errors_bundle)) => {
+                eprintln!();
+                print_errors(&package.source_map, &errors_bundle);
+                ExitCode::FAILURE
+            }
+        }
+    })
+}
+
+fn run_passes<F, T>(
+    options: &CompileOptions,
+    codegen: &CodeGeneratorContext,
+    resolve: &mut ResolveContext,
+    on_module: F,
+) -> Result<T, (Package, CompileErrorBundle)>
+where
+    F: FnOnce(&Compilation) -> CompileResult<T>,
+{
+    let (package, mut errors) = match cool_driver::p1_parse(resolve, options) {
         Ok(package) => {
-            println!("p1 - parse:         success");
+            eprintln!("p1 - parse:         success");
             (package, vec![])
         }
         Err((package, error_bundle)) => {
-            println!("p1 - parse:         error");
+            eprintln!("p1 - parse:         error");
             (package, error_bundle.errors)
         }
     };
 
-    match cool_driver::p2_define_tys(&package, &mut resolve) {
-        Ok(_) => println!("p2 - define tys:    success"),
+    match cool_driver::p2_define_tys(&package, resolve) {
+        Ok(_) => eprintln!("p2 - define tys:    success"),
         Err(mut error_bundle) => {
-            println!("p2 - define tys:    error");
+            eprintln!("p2 - define tys:    error");
             errors.append(&mut error_bundle.errors);
         }
     }
 
-    match cool_driver::p3_define_fn_tys(&package, &mut resolve) {
-        Ok(_) => println!("p3 - define fn tys: success"),
+    match cool_driver::p3_define_fn_tys(&package, resolve) {
+        Ok(_) => eprintln!("p3 - define fn tys: success"),
         Err(mut error_bundle) => {
-            println!("p3 - define fn tys: error");
+            eprintln!("p3 - define fn tys: error");
             errors.append(&mut error_bundle.errors);
         }
     }
 
-    let package_ast = match cool_driver::p4_gen_ast(&package, &mut resolve) {
+    let package_ast = match cool_driver::p4_gen_ast(&package, resolve) {
         Ok(package_ast) => {
-            println!("p4 - gen ast:       success");
+            eprintln!("p4 - gen ast:       success");
             package_ast
         }
         Err(mut error_bundle) => {
-            println!("p4 - gen ast:       error");
+            eprintln!("p4 - gen ast:       error");
             errors.append(&mut error_bundle.errors);
             PackageAst::default()
         }
@@ -63,34 +107,290 @@ fn compile(options: &CompileOptions) -> Result<(), (Package, CompileErrorBundle)
         return Err((package, CompileErrorBundle { errors }));
     }
 
-    let module = cool_driver::p5_gen_code(&package_ast, &codegen, &resolve, options);
-    module.print_to_file("../programs/bin/main.ll").unwrap();
-    println!("p5 - gen code:      success");
+    let module =
+        cool_driver::p5_gen_code(&package_ast, &package.source_map, codegen, resolve, options);
+    eprintln!("p5 - gen code:      success");
 
-    Ok(())
+    let result = on_module(&Compilation {
+        codegen,
+        resolve,
+        package: &package,
+        module: &module,
+    });
+
+    drop(module);
+    result.map_err(|errors| (package, errors))
 }
 
 fn main() -> ExitCode {
-    let args = Args::parse();
+    match Args::parse().command {
+        Command::Build(args) => build(args),
+        Command::Run(args) => {
+            let options = CompileOptions::from(args.compile);
+            let program_name = options.crate_root_file.to_string_lossy().into_owned();
+
+            let program_args = [program_name.as_str()]
+                .into_iter()
+                .chain(args.program_args.iter().map(String::as_str))
+                .collect::<Vec<_>>();
+
+            // The program runs in-process, so it is compiled for the host
+            let target_triple = cool_driver::host_target_triple();
+
+            compile(&options, &target_triple, |compilation| {
+                eprintln!();
+                let exit_code = cool_driver::run_jit(compilation.module, &program_args)?;
+                Ok(ExitCode::from(exit_code as u8))
+            })
+        }
+        Command::Test(args) => {
+            let options = CompileOptions {
+                test: true,
+                ..CompileOptions::from(args.compile)
+            };
+
+            let program_name = options.crate_root_file.to_string_lossy().into_owned();
+            let program_args = [Some(program_name.as_str()), args.filter.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+
+            // The program runs in-process, so it is compiled for the host
+            let target_triple = cool_driver::host_target_triple();
+
+            compile(&options, &target_triple, |compilation| {
+                eprintln!();
+                let exit_code = cool_driver::run_jit(compilation.module, &program_args)?;
+                Ok(ExitCode::from(exit_code as u8))
+            })
+        }
+        Command::Repl(args) => repl(args),
+        Command::Fmt(args) => fmt(args),
+    }
+}
+
+fn build(args: BuildArgs) -> ExitCode {
+    let plan = match BuildPlan::load(&args.manifest_path) {
+        Ok(plan) => plan,
+        Err(error) => {
+            eprintln!("{}: {}.\n", "Error".red(), error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Dependencies are built first, as packages are compiled against their interfaces
+    for package in plan.packages.iter() {
+        if plan.is_up_to_date(package) {
+            eprintln!("Package '{}' is up to date.\n", package.name());
+            continue;
+        }
+
+        let exit_code = build_package(&plan, package, &args);
+
+        if exit_code != ExitCode::SUCCESS {
+            return exit_code;
+        }
+    }
+
+    build_package(&plan, &plan.root, &args)
+}
+
+fn build_package(plan: &BuildPlan, package: &BuildPackage, args: &BuildArgs) -> ExitCode {
+    eprintln!("Building package '{}'.", package.name());
+
     let options = CompileOptions {
-        crate_name: args.crate_name,
-        crate_root_file: args.crate_root_file,
+        overflow_checks: args.overflow_checks,
+        ..plan.compile_options(package)
     };
 
-    let Err((package, errors_bundle)) = compile(&options) else {
-        return ExitCode::SUCCESS;
+    let target_triple = args
+        .target
+        .clone()
+        .unwrap_or_else(cool_driver::host_target_triple);
+
+    let emit_llvm = args.emit_llvm && ptr::eq(package, &plan.root);
+    let output_dir = package.output_dir();
+
+    // Libraries are linked after the packages that use them
+    let link_inputs = plan
+        .get_link_order(package)
+        .iter()
+        .rev()
+        .map(|dependency| dependency.output_file())
+        .collect::<Vec<_>>();
+
+    compile(&options, &target_triple, |compilation| {
+        let mut paths = Vec::<PathBuf>::new();
+
+        if emit_llvm {
+            paths.push(cool_driver::write_llvm_ir(
+                compilation.module,
+                &output_dir,
+                package.name(),
+            )?);
+        } else {
+            let interface = package
+                .kind()
+                .is_library()
+                .then(|| {
+                    PackageInterface::new(compilation.resolve, compilation.package, package.name())
+                })
+                .transpose()
+                .map_err(CompileError::from)?;
+
+            paths.push(cool_driver::write_output(
+                compilation.codegen,
+                compilation.module,
+                package.kind(),
+                &output_dir,
+                package.name(),
+                &link_inputs,
+            )?);
+
+            if let Some(interface) = interface {
+                let interface_file = package.interface_file();
+                interface.save(&interface_file)?;
+                paths.push(interface_file);
+            }
+        }
+
+        eprintln!();
+
+        for path in paths {
+            eprintln!("Wrote '{}'.", path.display());
+        }
+
+        eprintln!();
+        Ok(ExitCode::SUCCESS)
+    })
+}
+
+fn repl(args: ReplArgs) -> ExitCode {
+    // Evaluated code runs in-process, so it is compiled for the host
+    let (codegen, session) = match cool_driver::p0_init(&cool_driver::host_target_triple()) {
+        Ok((codegen, session)) => (codegen, session),
+        Err(errors) => {
+            print_errors(&SourceMap::default(), &errors);
+            return ExitCode::FAILURE;
+        }
     };
 
+    let mut repl = match Repl::new(&codegen, session, args.into()) {
+        Ok(repl) => repl,
+        Err(errors) => {
+            print_errors(&SourceMap::default(), &errors);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut stdin = io::stdin().lock();
+    let mut source = String::new();
+
+    loop {
+        print!("{}", if source.is_empty() { ">> " } else { ".. " });
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+
+        match stdin.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => (),
+            Err(_) => return ExitCode::FAILURE,
+        }
+
+        if source.is_empty() && line.trim().is_empty() {
+            continue;
+        }
+
+        source.push_str(&line);
+
+        match repl.eval(&source) {
+            Ok(ReplOutput::Incomplete) => continue,
+            Ok(ReplOutput::Empty) => (),
+            Ok(ReplOutput::Value(value)) => println!("{value}"),
+            Err(errors) => {
+                let symbols = Arc::clone(repl.symbols());
+                symbols.enter(|| print_errors(repl.source_map(), &errors));
+            }
+        }
+
+        source.clear();
+    }
+
     println!();
+    ExitCode::SUCCESS
+}
+
+fn fmt(args: FmtArgs) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for path in args.files {
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("{}: {}: {}.\n", "Error".red(), path.display(), error);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        // The symbols of each file are interned into a table that is dropped with it
+        let symbols = SymbolTable::default();
+
+        let formatted = match symbols.enter(|| cool_fmt::format_source(&source)) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                let mut source_map = SourceMap::default();
+                let file = source_map.add_source(path, source);
+
+                symbols.enter(|| {
+                    match error.span() {
+                        Some(span) => {
+                            let position = file.offset_to_position(span.start);
+
+                            eprintln!(
+                                "{}: {}.\n -> '{}', line {}, column {}.\n",
+                                "Error".red(),
+                                error,
+                                file.path.display(),
+                                position.line,
+                                position.column,
+                            );
+                        }
+                        None => {
+                            eprintln!("{}: {}: {}.\n", "Error".red(), file.path.display(), error);
+                        }
+                    }
+                });
 
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        if formatted == source {
+            continue;
+        }
+
+        if args.check {
+            println!("{}", path.display());
+            exit_code = ExitCode::FAILURE;
+        } else if let Err(error) = fs::write(&path, formatted) {
+            eprintln!("{}: {}: {}.\n", "Error".red(), path.display(), error);
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
+
+fn print_errors(source_map: &SourceMap, errors_bundle: &CompileErrorBundle) {
     for error in errors_bundle.errors.iter() {
         match error.span() {
             Some(span) => {
-                let (file, position) = package
-                    .source_map
-                    .get_file_and_position_from_offset(span.start);
+                let (file, position) = source_map.get_file_and_position_from_offset(span.start);
 
-                println!(
+                eprintln!(
                     "{}: {}.\n -> '{}', line {}, column {}.\n",
                     "Error".red(),
                     error,
@@ -100,10 +400,8 @@ fn main() -> ExitCode {
                 );
             }
             None => {
-                println!("{}: {}.\n", "Error".red(), error);
+                eprintln!("{}: {}.\n", "Error".red(), error);
             }
         }
     }
-
-    ExitCode::FAILURE
 }
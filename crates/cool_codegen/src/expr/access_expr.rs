@@ -1,8 +1,9 @@
 use crate::{BuilderExt, CodeGenerator, LoadedValue, Value};
 use cool_ast::{AccessExprAst, ArrayLenExprAst};
 use cool_lexer::Symbol;
-use cool_resolve::TyId;
-use inkwell::values::BasicValue;
+use cool_resolve::{LayoutKind, TyId};
+use inkwell::types::{BasicType, StructType};
+use inkwell::values::{BasicValue, PointerValue};
 
 impl<'a> CodeGenerator<'a> {
     pub fn gen_access_expr(&mut self, expr: &AccessExprAst) -> Value<'a> {
@@ -33,6 +34,10 @@ impl<'a> CodeGenerator<'a> {
         struct_value: Value<'a>,
         field: Symbol,
     ) -> Value<'a> {
+        if struct_ty_id.is_union() {
+            return self.util_gen_union_field_access(struct_ty_id, struct_value, field);
+        }
+
         let field_index = match self.tys.get_field_map(struct_ty_id).get(field) {
             Some(field_index) => field_index,
             None => return Value::Void,
@@ -51,14 +56,79 @@ impl<'a> CodeGenerator<'a> {
                     None => return Value::Void,
                 };
 
-                let field_ptr = self
-                    .builder
-                    .build_struct_gep(struct_ty, memory, field_index, "")
-                    .unwrap();
+                let field_ptr =
+                    self.util_gen_struct_field_ptr(struct_ty_id, struct_ty, memory, field_index);
 
                 Value::Memory(field_ptr)
             }
             _ => Value::Void,
         }
     }
+
+    /// Union fields are read and written through a pointer to the union cast to the type of the
+    /// field. Unions held in registers are spilled to memory first.
+    fn util_gen_union_field_access(
+        &mut self,
+        union_ty_id: TyId,
+        union_value: Value<'a>,
+        field: Symbol,
+    ) -> Value<'a> {
+        let memory = match union_value {
+            Value::Register(value) => self.util_gen_init(value),
+            Value::Memory(memory) => memory,
+            _ => return Value::Void,
+        };
+
+        self.util_gen_union_field_ptr(union_ty_id, memory, field)
+            .map(Value::Memory)
+            .unwrap_or(Value::Void)
+    }
+
+    /// Returns a pointer to a field of a union, or `None` if the field is zero-sized.
+    pub(crate) fn util_gen_union_field_ptr(
+        &mut self,
+        union_ty_id: TyId,
+        memory: PointerValue<'a>,
+        field: Symbol,
+    ) -> Option<PointerValue<'a>> {
+        let field_ty_id = self
+            .resolve
+            .get_ty_def(union_ty_id)?
+            .get_field(field)?
+            .ty_id;
+        let field_ty = self.tys[field_ty_id]?;
+
+        let field_ptr =
+            self.builder
+                .build_pointer_cast(memory, field_ty.ptr_type(Default::default()), "");
+
+        Some(field_ptr)
+    }
+
+    /// Returns a pointer to a field of a struct. Fields of packed structs are recorded, as
+    /// accesses through them must not assume the natural alignment of the field.
+    pub(crate) fn util_gen_struct_field_ptr(
+        &mut self,
+        struct_ty_id: TyId,
+        struct_ty: StructType<'a>,
+        memory: PointerValue<'a>,
+        field_index: u32,
+    ) -> PointerValue<'a> {
+        let field_ptr = self
+            .builder
+            .build_struct_gep(struct_ty, memory, field_index, "")
+            .unwrap();
+
+        let is_packed = self
+            .resolve
+            .get_ty_def(struct_ty_id)
+            .and_then(|ty_def| ty_def.kind.as_aggregate())
+            .is_some_and(|aggregate_ty| aggregate_ty.layout().kind == LayoutKind::Packed);
+
+        if is_packed {
+            self.unaligned_ptrs.insert(field_ptr);
+        }
+
+        field_ptr
+    }
 }
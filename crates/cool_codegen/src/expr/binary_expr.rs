@@ -1,8 +1,12 @@
 use crate::{BuilderExt, CodeGenerator, LoadedValue};
 use cool_ast::{BinaryExprAst, ExprAst};
 use cool_parser::{ArithmeticOp, BinOp, BitwiseOp, ComparisonOp, LogicalOp};
-use cool_resolve::TyId;
-use inkwell::values::{BasicValue, BasicValueEnum, IntValue};
+use cool_resolve::{TaggedUnionKind, TyId, ValueTy};
+use cool_span::{Section, Span};
+use inkwell::basic_block::BasicBlock;
+use inkwell::intrinsics::Intrinsic;
+use inkwell::types::BasicType;
+use inkwell::values::{BasicMetadataValueEnum, BasicValue, BasicValueEnum, IntValue, StructValue};
 use inkwell::{FloatPredicate as FloatP, IntPredicate as IntP};
 use smallvec::SmallVec;
 
@@ -12,170 +16,353 @@ impl<'a> CodeGenerator<'a> {
             return self.gen_logical_expr(&expr.lhs, &expr.rhs, op);
         }
 
-        let lhs = self.gen_loaded_expr(&expr.lhs)?;
-        let rhs = self.gen_loaded_expr(&expr.rhs)?;
+        let lhs = self.gen_loaded_expr(&expr.lhs);
+        if self.builder.current_block_diverges() {
+            return LoadedValue::None;
+        }
+
+        let rhs = self.gen_loaded_expr(&expr.rhs);
+        if self.builder.current_block_diverges() {
+            return LoadedValue::None;
+        }
+
         let lhs_ty_id = expr.lhs.expr_id().ty_id;
+        let rhs_ty_id = expr.rhs.expr_id().ty_id;
+
+        // Zero-sized operands are only valid in comparisons
+        if let BinOp::Comparison(op) = expr.bin_op {
+            return self.gen_comparison_expr(lhs_ty_id, lhs, rhs, op);
+        }
+
+        let lhs = lhs?;
+        let rhs = rhs?;
 
         match expr.bin_op {
-            BinOp::Arithmetic(op) => self.gen_arithmetic_expr(lhs_ty_id, lhs, rhs, op),
-            BinOp::Comparison(op) => self.gen_comparison_expr(lhs_ty_id, lhs, rhs, op),
-            BinOp::Bitwise(op) => self.gen_bitwise_expr(lhs_ty_id, lhs, rhs, op),
+            BinOp::Arithmetic(op) => {
+                self.gen_arithmetic_expr(expr.span(), lhs_ty_id, rhs_ty_id, lhs, rhs, op)
+            }
+            BinOp::Bitwise(op) => self.gen_bitwise_expr(expr.span(), lhs_ty_id, lhs, rhs, op),
             _ => unreachable!(),
         }
     }
 
-    fn gen_arithmetic_expr(
+    pub(crate) fn gen_arithmetic_expr(
         &mut self,
+        span: Span,
         lhs_ty_id: TyId,
+        rhs_ty_id: TyId,
         lhs: BasicValueEnum<'a>,
         rhs: BasicValueEnum<'a>,
         arithmetic_op: ArithmeticOp,
     ) -> LoadedValue<'a> {
+        if lhs_ty_id.is_many_ptr() {
+            return self.gen_ptr_arithmetic_expr(lhs_ty_id, rhs_ty_id, lhs, rhs, arithmetic_op);
+        }
+
+        if !lhs_ty_id.is_int() {
+            return self.gen_float_arithmetic_expr(lhs, rhs, arithmetic_op);
+        }
+
+        let lhs = lhs.into_int_value();
+        let rhs = rhs.into_int_value();
+        let is_signed = lhs_ty_id.is_signed_int();
+
         let value = match arithmetic_op {
-            ArithmeticOp::Add => {
-                if lhs_ty_id.is_int() {
-                    self.builder
-                        .build_int_add(lhs.into_int_value(), rhs.into_int_value(), "")
-                        .as_basic_value_enum()
-                } else {
-                    self.builder
-                        .build_float_add(lhs.into_float_value(), rhs.into_float_value(), "")
-                        .as_basic_value_enum()
-                }
+            ArithmeticOp::Add | ArithmeticOp::Sub | ArithmeticOp::Mul
+                if self.options.overflow_checks =>
+            {
+                self.util_gen_checked_int_op(span, is_signed, lhs, rhs, arithmetic_op)
             }
-            ArithmeticOp::Sub => {
-                if lhs_ty_id.is_int() {
-                    self.builder
-                        .build_int_sub(lhs.into_int_value(), rhs.into_int_value(), "")
-                        .as_basic_value_enum()
-                } else {
-                    self.builder
-                        .build_float_sub(lhs.into_float_value(), rhs.into_float_value(), "")
-                        .as_basic_value_enum()
-                }
+            ArithmeticOp::Add | ArithmeticOp::WrappingAdd => {
+                self.builder.build_int_add(lhs, rhs, "")
             }
-            ArithmeticOp::Mul => {
-                if lhs_ty_id.is_int() {
-                    self.builder
-                        .build_int_mul(lhs.into_int_value(), rhs.into_int_value(), "")
-                        .as_basic_value_enum()
-                } else {
-                    self.builder
-                        .build_float_mul(lhs.into_float_value(), rhs.into_float_value(), "")
-                        .as_basic_value_enum()
-                }
+            ArithmeticOp::Sub | ArithmeticOp::WrappingSub => {
+                self.builder.build_int_sub(lhs, rhs, "")
             }
-            ArithmeticOp::Div => {
-                if lhs_ty_id.is_signed_int() {
-                    self.builder
-                        .build_int_signed_div(lhs.into_int_value(), rhs.into_int_value(), "")
-                        .as_basic_value_enum()
-                } else if lhs_ty_id.is_unsigned_int() {
-                    self.builder
-                        .build_int_unsigned_div(lhs.into_int_value(), rhs.into_int_value(), "")
-                        .as_basic_value_enum()
-                } else {
-                    self.builder
-                        .build_float_div(lhs.into_float_value(), rhs.into_float_value(), "")
-                        .as_basic_value_enum()
-                }
+            ArithmeticOp::Mul | ArithmeticOp::WrappingMul => {
+                self.builder.build_int_mul(lhs, rhs, "")
             }
-            ArithmeticOp::Rem => {
-                if lhs_ty_id.is_signed_int() {
-                    self.builder
-                        .build_int_signed_rem(lhs.into_int_value(), rhs.into_int_value(), "")
-                        .as_basic_value_enum()
-                } else if lhs_ty_id.is_unsigned_int() {
-                    self.builder
-                        .build_int_unsigned_rem(lhs.into_int_value(), rhs.into_int_value(), "")
-                        .as_basic_value_enum()
-                } else {
-                    self.builder
-                        .build_float_rem(lhs.into_float_value(), rhs.into_float_value(), "")
-                        .as_basic_value_enum()
-                }
+            ArithmeticOp::Div | ArithmeticOp::Rem => {
+                self.util_gen_int_div(span, is_signed, lhs, rhs, arithmetic_op)
+            }
+            ArithmeticOp::SaturatingAdd
+            | ArithmeticOp::SaturatingSub
+            | ArithmeticOp::SaturatingMul => {
+                self.util_gen_saturating_int_op(is_signed, lhs, rhs, arithmetic_op)
             }
         };
 
-        value.into()
+        value.as_basic_value_enum().into()
     }
 
-    fn gen_comparison_expr(
+    /// Offsets a many-pointer by a number of elements, or computes the number of elements
+    /// between two many-pointers.
+    fn gen_ptr_arithmetic_expr(
         &mut self,
         lhs_ty_id: TyId,
+        rhs_ty_id: TyId,
         lhs: BasicValueEnum<'a>,
         rhs: BasicValueEnum<'a>,
-        comparison_op: ComparisonOp,
+        arithmetic_op: ArithmeticOp,
     ) -> LoadedValue<'a> {
-        let lhs = match lhs {
-            BasicValueEnum::PointerValue(ptr) => {
-                self.builder
-                    .build_ptr_to_int(ptr, self.tys.isize_ty(), "")
-                    .as_basic_value_enum()
+        let isize_ty = self.tys.isize_ty();
+        let pointee_ty_id = lhs_ty_id.get_many_ptr().pointee;
+
+        // Pointers to zero-sized values are never moved by offsets, and their difference is
+        // rejected during type checking
+        let Some(pointee_ty) = self.tys[pointee_ty_id] else {
+            return lhs.into();
+        };
+
+        let lhs = lhs.into_pointer_value();
+
+        if rhs_ty_id.is_int() {
+            let mut offset = self.builder.build_int_cast_sign_flag(
+                rhs.into_int_value(),
+                isize_ty,
+                rhs_ty_id.is_signed_int(),
+                "",
+            );
+
+            if arithmetic_op == ArithmeticOp::Sub {
+                offset = self.builder.build_int_neg(offset, "");
             }
-            value => value,
+
+            let ptr_value = unsafe { self.builder.build_gep(pointee_ty, lhs, &[offset], "") };
+            return ptr_value.as_basic_value_enum().into();
+        }
+
+        let pointee_size = self.resolve.get_ty_def(pointee_ty_id).unwrap().size;
+        let lhs_addr = self.builder.build_ptr_to_int(lhs, isize_ty, "");
+        let rhs_addr = self
+            .builder
+            .build_ptr_to_int(rhs.into_pointer_value(), isize_ty, "");
+
+        let byte_diff = self.builder.build_int_sub(lhs_addr, rhs_addr, "");
+
+        let diff = self.builder.build_int_exact_signed_div(
+            byte_diff,
+            isize_ty.const_int(pointee_size, false),
+            "",
+        );
+
+        diff.as_basic_value_enum().into()
+    }
+
+    fn gen_float_arithmetic_expr(
+        &mut self,
+        lhs: BasicValueEnum<'a>,
+        rhs: BasicValueEnum<'a>,
+        arithmetic_op: ArithmeticOp,
+    ) -> LoadedValue<'a> {
+        let lhs = lhs.into_float_value();
+        let rhs = rhs.into_float_value();
+
+        let value = match arithmetic_op {
+            ArithmeticOp::Add => self.builder.build_float_add(lhs, rhs, ""),
+            ArithmeticOp::Sub => self.builder.build_float_sub(lhs, rhs, ""),
+            ArithmeticOp::Mul => self.builder.build_float_mul(lhs, rhs, ""),
+            ArithmeticOp::Div => self.builder.build_float_div(lhs, rhs, ""),
+            ArithmeticOp::Rem => self.builder.build_float_rem(lhs, rhs, ""),
+            _ => panic!("operation is only defined for integers"),
         };
 
-        let rhs = match rhs {
-            BasicValueEnum::PointerValue(ptr) => {
-                self.builder
-                    .build_ptr_to_int(ptr, self.tys.isize_ty(), "")
-                    .as_basic_value_enum()
+        value.as_basic_value_enum().into()
+    }
+
+    fn util_gen_checked_int_op(
+        &mut self,
+        span: Span,
+        is_signed: bool,
+        lhs: IntValue<'a>,
+        rhs: IntValue<'a>,
+        arithmetic_op: ArithmeticOp,
+    ) -> IntValue<'a> {
+        let (intrinsic_name, message) = match (arithmetic_op, is_signed) {
+            (ArithmeticOp::Add, true) => {
+                ("llvm.sadd.with.overflow", "attempt to add with overflow")
             }
-            value => value,
+            (ArithmeticOp::Add, false) => {
+                ("llvm.uadd.with.overflow", "attempt to add with overflow")
+            }
+            (ArithmeticOp::Sub, true) => {
+                (
+                    "llvm.ssub.with.overflow",
+                    "attempt to subtract with overflow",
+                )
+            }
+            (ArithmeticOp::Sub, false) => {
+                (
+                    "llvm.usub.with.overflow",
+                    "attempt to subtract with overflow",
+                )
+            }
+            (ArithmeticOp::Mul, true) => {
+                (
+                    "llvm.smul.with.overflow",
+                    "attempt to multiply with overflow",
+                )
+            }
+            (ArithmeticOp::Mul, false) => {
+                (
+                    "llvm.umul.with.overflow",
+                    "attempt to multiply with overflow",
+                )
+            }
+            _ => unreachable!(),
         };
 
-        let value = match comparison_op {
-            ComparisonOp::Eq => {
-                if lhs_ty_id.is_float() {
-                    self.util_gen_float_compare(lhs, rhs, FloatP::OEQ)
-                } else {
-                    self.util_gen_int_compare(lhs, rhs, IntP::EQ)
-                }
+        let result = self
+            .util_gen_int_intrinsic_call(intrinsic_name, &[lhs, rhs])
+            .into_struct_value();
+
+        let value = self
+            .builder
+            .build_extract_value(result, 0, "")
+            .unwrap()
+            .into_int_value();
+
+        let overflow = self
+            .builder
+            .build_extract_value(result, 1, "")
+            .unwrap()
+            .into_int_value();
+
+        self.util_gen_runtime_check(overflow, span, message);
+        value
+    }
+
+    fn util_gen_saturating_int_op(
+        &mut self,
+        is_signed: bool,
+        lhs: IntValue<'a>,
+        rhs: IntValue<'a>,
+        arithmetic_op: ArithmeticOp,
+    ) -> IntValue<'a> {
+        let value = match (arithmetic_op, is_signed) {
+            (ArithmeticOp::SaturatingAdd, true) => {
+                self.util_gen_int_intrinsic_call("llvm.sadd.sat", &[lhs, rhs])
             }
-            ComparisonOp::Ne => {
-                if lhs_ty_id.is_float() {
-                    self.util_gen_float_compare(lhs, rhs, FloatP::ONE)
-                } else {
-                    self.util_gen_int_compare(lhs, rhs, IntP::NE)
-                }
+            (ArithmeticOp::SaturatingAdd, false) => {
+                self.util_gen_int_intrinsic_call("llvm.uadd.sat", &[lhs, rhs])
             }
-            ComparisonOp::Lt => {
-                if lhs_ty_id.is_float() {
-                    self.util_gen_float_compare(lhs, rhs, FloatP::OLE)
-                } else if lhs_ty_id.is_signed_int() {
-                    self.util_gen_int_compare(lhs, rhs, IntP::SLT)
-                } else {
-                    self.util_gen_int_compare(lhs, rhs, IntP::ULT)
-                }
+            (ArithmeticOp::SaturatingSub, true) => {
+                self.util_gen_int_intrinsic_call("llvm.ssub.sat", &[lhs, rhs])
             }
-            ComparisonOp::Le => {
-                if lhs_ty_id.is_float() {
-                    self.util_gen_float_compare(lhs, rhs, FloatP::OLE)
-                } else if lhs_ty_id.is_signed_int() {
-                    self.util_gen_int_compare(lhs, rhs, IntP::SLT)
-                } else {
-                    self.util_gen_int_compare(lhs, rhs, IntP::ULT)
-                }
+            (ArithmeticOp::SaturatingSub, false) => {
+                self.util_gen_int_intrinsic_call("llvm.usub.sat", &[lhs, rhs])
             }
-            ComparisonOp::Gt => {
-                if lhs_ty_id.is_float() {
-                    self.util_gen_float_compare(lhs, rhs, FloatP::OGT)
-                } else if lhs_ty_id.is_signed_int() {
-                    self.util_gen_int_compare(lhs, rhs, IntP::SGT)
+            (ArithmeticOp::SaturatingMul, is_signed) => {
+                // Fixed-point multiplication with a scale of 0 is integer multiplication
+                let intrinsic_name = if is_signed {
+                    "llvm.smul.fix.sat"
                 } else {
-                    self.util_gen_int_compare(lhs, rhs, IntP::UGT)
-                }
+                    "llvm.umul.fix.sat"
+                };
+
+                let scale = self.context.i32_type().const_zero();
+                self.util_gen_int_intrinsic_call(intrinsic_name, &[lhs, rhs, scale])
             }
-            ComparisonOp::Ge => {
-                if lhs_ty_id.is_float() {
-                    self.util_gen_float_compare(lhs, rhs, FloatP::OGE)
-                } else if lhs_ty_id.is_signed_int() {
-                    self.util_gen_int_compare(lhs, rhs, IntP::SGE)
+            _ => unreachable!(),
+        };
+
+        value.into_int_value()
+    }
+
+    fn util_gen_int_div(
+        &mut self,
+        span: Span,
+        is_signed: bool,
+        lhs: IntValue<'a>,
+        rhs: IntValue<'a>,
+        arithmetic_op: ArithmeticOp,
+    ) -> IntValue<'a> {
+        let is_div = arithmetic_op == ArithmeticOp::Div;
+
+        if self.options.overflow_checks {
+            let int_ty = lhs.get_type();
+
+            let is_rhs_zero =
+                self.builder
+                    .build_int_compare(IntP::EQ, rhs, int_ty.const_zero(), "");
+
+            let message = if is_div {
+                "attempt to divide by zero"
+            } else {
+                "attempt to calculate the remainder with a divisor of zero"
+            };
+
+            self.util_gen_runtime_check(is_rhs_zero, span, message);
+
+            if is_signed {
+                let min = int_ty
+                    .const_int(1, false)
+                    .const_shl(int_ty.const_int(int_ty.get_bit_width() as u64 - 1, false));
+
+                let is_lhs_min = self.builder.build_int_compare(IntP::EQ, lhs, min, "");
+
+                let is_rhs_minus_one =
+                    self.builder
+                        .build_int_compare(IntP::EQ, rhs, int_ty.const_all_ones(), "");
+
+                let overflow = self.builder.build_and(is_lhs_min, is_rhs_minus_one, "");
+
+                let message = if is_div {
+                    "attempt to divide with overflow"
                 } else {
-                    self.util_gen_int_compare(lhs, rhs, IntP::UGE)
-                }
+                    "attempt to calculate the remainder with overflow"
+                };
+
+                self.util_gen_runtime_check(overflow, span, message);
             }
+        }
+
+        match (is_div, is_signed) {
+            (true, true) => self.builder.build_int_signed_div(lhs, rhs, ""),
+            (true, false) => self.builder.build_int_unsigned_div(lhs, rhs, ""),
+            (false, true) => self.builder.build_int_signed_rem(lhs, rhs, ""),
+            (false, false) => self.builder.build_int_unsigned_rem(lhs, rhs, ""),
+        }
+    }
+
+    /// Calls an integer intrinsic overloaded on the type of its first argument.
+    pub(crate) fn util_gen_int_intrinsic_call(
+        &mut self,
+        intrinsic_name: &str,
+        args: &[IntValue<'a>],
+    ) -> BasicValueEnum<'a> {
+        let int_ty = args[0].get_type().as_basic_type_enum();
+
+        let intrinsic = Intrinsic::find(intrinsic_name)
+            .and_then(|intrinsic| intrinsic.get_declaration(&self.module, &[int_ty]))
+            .unwrap();
+
+        let args = args
+            .iter()
+            .map(|&arg| BasicMetadataValueEnum::from(arg))
+            .collect::<SmallVec<[_; 3]>>();
+
+        self.builder
+            .build_call(intrinsic, &args, "")
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+    }
+
+    fn gen_comparison_expr(
+        &mut self,
+        lhs_ty_id: TyId,
+        lhs: LoadedValue<'a>,
+        rhs: LoadedValue<'a>,
+        comparison_op: ComparisonOp,
+    ) -> LoadedValue<'a> {
+        let value = match comparison_op {
+            ComparisonOp::Eq => self.util_gen_eq(lhs_ty_id, lhs, rhs),
+            ComparisonOp::Ne => {
+                let value = self.util_gen_eq(lhs_ty_id, lhs, rhs);
+                self.builder.build_not(value, "")
+            }
+            _ => self.util_gen_ordering(lhs_ty_id, lhs.unwrap(), rhs.unwrap(), comparison_op),
         };
 
         self.builder
@@ -184,8 +371,182 @@ impl<'a> CodeGenerator<'a> {
             .into()
     }
 
-    fn gen_bitwise_expr(
+    fn util_gen_ordering(
         &mut self,
+        ty_id: TyId,
+        lhs: BasicValueEnum<'a>,
+        rhs: BasicValueEnum<'a>,
+        comparison_op: ComparisonOp,
+    ) -> IntValue<'a> {
+        if ty_id.is_float() {
+            let predicate = match comparison_op {
+                ComparisonOp::Lt => FloatP::OLT,
+                ComparisonOp::Le => FloatP::OLE,
+                ComparisonOp::Gt => FloatP::OGT,
+                ComparisonOp::Ge => FloatP::OGE,
+                _ => unreachable!(),
+            };
+
+            return self.util_gen_float_compare(lhs, rhs, predicate);
+        }
+
+        let lhs = self.util_gen_ptr_to_int(lhs);
+        let rhs = self.util_gen_ptr_to_int(rhs);
+
+        let predicate = match (comparison_op, ty_id.is_signed_int()) {
+            (ComparisonOp::Lt, true) => IntP::SLT,
+            (ComparisonOp::Lt, false) => IntP::ULT,
+            (ComparisonOp::Le, true) => IntP::SLE,
+            (ComparisonOp::Le, false) => IntP::ULE,
+            (ComparisonOp::Gt, true) => IntP::SGT,
+            (ComparisonOp::Gt, false) => IntP::UGT,
+            (ComparisonOp::Ge, true) => IntP::SGE,
+            (ComparisonOp::Ge, false) => IntP::UGE,
+            _ => unreachable!(),
+        };
+
+        self.util_gen_int_compare(lhs, rhs, predicate)
+    }
+
+    /// Generates an `i1` that is true if both values are equal. Aggregates are compared field by
+    /// field and tagged unions compare the payload only if both have the same active variant.
+    fn util_gen_eq(
+        &mut self,
+        ty_id: TyId,
+        lhs: LoadedValue<'a>,
+        rhs: LoadedValue<'a>,
+    ) -> IntValue<'a> {
+        let (Some(lhs), Some(rhs)) = (lhs, rhs) else {
+            // Values of zero-sized types are always equal
+            return self.context.bool_type().const_all_ones();
+        };
+
+        match ty_id.get_value() {
+            ValueTy::Float(_) => self.util_gen_float_compare(lhs, rhs, FloatP::OEQ),
+            ValueTy::Tuple(_) | ValueTy::Struct(_) => {
+                self.util_gen_aggregate_eq(ty_id, lhs.into_struct_value(), rhs.into_struct_value())
+            }
+            ValueTy::Variant(_) => self.util_gen_variant_eq(ty_id, lhs, rhs),
+            _ => {
+                let lhs = self.util_gen_ptr_to_int(lhs);
+                let rhs = self.util_gen_ptr_to_int(rhs);
+                self.util_gen_int_compare(lhs, rhs, IntP::EQ)
+            }
+        }
+    }
+
+    fn util_gen_aggregate_eq(
+        &mut self,
+        ty_id: TyId,
+        lhs: StructValue<'a>,
+        rhs: StructValue<'a>,
+    ) -> IntValue<'a> {
+        let fields = self
+            .resolve
+            .get_ty_def(ty_id)
+            .unwrap()
+            .get_aggregate_fields()
+            .unwrap()
+            .clone();
+
+        let mut value = self.context.bool_type().const_all_ones();
+
+        for field in fields.iter() {
+            let Some(field_index) = self.tys.get_field_map(ty_id).get(field.symbol) else {
+                continue;
+            };
+
+            let lhs_field = self.builder.build_extract_value(lhs, field_index, "");
+            let rhs_field = self.builder.build_extract_value(rhs, field_index, "");
+            let field_value = self.util_gen_eq(field.ty_id, lhs_field, rhs_field);
+            value = self.builder.build_and(value, field_value, "");
+        }
+
+        value
+    }
+
+    fn util_gen_variant_eq(
+        &mut self,
+        ty_id: TyId,
+        lhs: BasicValueEnum<'a>,
+        rhs: BasicValueEnum<'a>,
+    ) -> IntValue<'a> {
+        let lhs_ptr = self.util_gen_init(lhs);
+        let rhs_ptr = self.util_gen_init(rhs);
+
+        let tagged_union_kind = self
+            .resolve
+            .get_ty_def(ty_id)
+            .unwrap()
+            .kind
+            .as_tagged_union()
+            .unwrap()
+            .kind;
+
+        if tagged_union_kind == TaggedUnionKind::NullablePtr {
+            let lhs = self.builder.build_load(self.tys.isize_ty(), lhs_ptr, "");
+            let rhs = self.builder.build_load(self.tys.isize_ty(), rhs_ptr, "");
+            return self.util_gen_int_compare(lhs, rhs, IntP::EQ);
+        }
+
+        let lhs_index = self.util_gen_variant_index(ty_id, lhs_ptr);
+        let rhs_index = self.util_gen_variant_index(ty_id, rhs_ptr);
+        let index_eq = self
+            .builder
+            .build_int_compare(IntP::EQ, lhs_index, rhs_index, "");
+
+        let index_block = self.builder.current_block();
+        let switch_block = self.append_block_after_current_block();
+        let else_block = self.append_block_after(switch_block);
+        let end_block = self.append_block_after(else_block);
+
+        self.builder
+            .build_conditional_branch(index_eq, switch_block, end_block);
+
+        self.builder.position_at_end(else_block);
+        self.builder.build_unreachable();
+
+        let mut variant_blocks = Vec::<(IntValue<'a>, BasicBlock<'a>)>::new();
+        let mut phi_values = vec![(index_block, self.context.bool_type().const_zero())];
+
+        for (i, &variant_ty_id) in ty_id.get_variant().variants().iter().enumerate() {
+            let block = self.append_block_after_current_block();
+            self.builder.position_at_end(block);
+
+            let (lhs, rhs) = match self.tys[variant_ty_id] {
+                Some(variant_ty) => {
+                    (
+                        Some(self.builder.build_load(variant_ty, lhs_ptr, "")),
+                        Some(self.builder.build_load(variant_ty, rhs_ptr, "")),
+                    )
+                }
+                None => (None, None),
+            };
+
+            let value = self.util_gen_eq(variant_ty_id, lhs, rhs);
+            phi_values.push((self.builder.current_block(), value));
+            self.builder.build_unconditional_branch(end_block);
+
+            variant_blocks.push((self.tys.i8_ty().const_int(i as u64, false), block));
+        }
+
+        self.builder.position_at_end(switch_block);
+        self.builder
+            .build_switch(lhs_index, else_block, &variant_blocks);
+
+        self.builder.position_at_end(end_block);
+        let phi_value = self.builder.build_phi(self.context.bool_type(), "");
+
+        for (block, value) in phi_values {
+            phi_value.add_incoming(&[(&value, block)]);
+        }
+
+        phi_value.as_basic_value().into_int_value()
+    }
+
+    pub(crate) fn gen_bitwise_expr(
+        &mut self,
+        span: Span,
         lhs_ty_id: TyId,
         lhs: BasicValueEnum<'a>,
         rhs: BasicValueEnum<'a>,
@@ -194,6 +555,23 @@ impl<'a> CodeGenerator<'a> {
         let lhs = lhs.into_int_value();
         let rhs = rhs.into_int_value();
 
+        if self.options.overflow_checks && matches!(bitwise_op, BitwiseOp::Shl | BitwiseOp::Shr) {
+            let bit_width = lhs.get_type().get_bit_width() as u64;
+            let max_shift = rhs.get_type().const_int(bit_width, false);
+
+            let overflow = self
+                .builder
+                .build_int_compare(IntP::UGE, rhs, max_shift, "");
+
+            let message = if bitwise_op == BitwiseOp::Shl {
+                "attempt to shift left with overflow"
+            } else {
+                "attempt to shift right with overflow"
+            };
+
+            self.util_gen_runtime_check(overflow, span, message);
+        }
+
         let value = match bitwise_op {
             BitwiseOp::And => self.builder.build_and(lhs, rhs, ""),
             BitwiseOp::Or => self.builder.build_or(lhs, rhs, ""),
@@ -316,6 +694,17 @@ impl<'a> CodeGenerator<'a> {
         phi_value.as_basic_value().as_basic_value_enum().into()
     }
 
+    fn util_gen_ptr_to_int(&mut self, value: BasicValueEnum<'a>) -> BasicValueEnum<'a> {
+        match value {
+            BasicValueEnum::PointerValue(ptr) => {
+                self.builder
+                    .build_ptr_to_int(ptr, self.tys.isize_ty(), "")
+                    .as_basic_value_enum()
+            }
+            value => value,
+        }
+    }
+
     fn util_gen_int_compare(
         &mut self,
         lhs: BasicValueEnum<'a>,
@@ -0,0 +1,203 @@
+use crate::{ArgAbi, BuilderExt, CodeGenerator, FnState, LoadedValue, Value};
+use cool_ast::{ClosureCaptureAst, ClosureExprAst};
+use cool_lexer::sym;
+use cool_resolve::TyId;
+use inkwell::module::Linkage;
+use inkwell::types::{BasicType, BasicTypeEnum, StructType};
+use inkwell::values::{BasicValue, BasicValueEnum, PointerValue};
+
+impl<'a> CodeGenerator<'a> {
+    pub fn gen_closure_expr(&mut self, expr: &ClosureExprAst) -> LoadedValue<'a> {
+        let closure_ty_id = expr.expr_id.ty_id;
+        let fn_ty_id = self.get_closure_fn_ty_id(closure_ty_id);
+
+        let captures = expr
+            .captures
+            .iter()
+            .flat_map(|capture| {
+                let ty = self.tys[self.resolve[capture.binding_id].ty_id]?;
+
+                let field_ty = if capture.is_by_ptr {
+                    ty.ptr_type(Default::default()).as_basic_type_enum()
+                } else {
+                    ty
+                };
+
+                Some((capture, field_ty))
+            })
+            .collect::<Vec<_>>();
+
+        let env_ty = (!captures.is_empty()).then(|| {
+            let field_tys = captures.iter().map(|(_, ty)| *ty).collect::<Vec<_>>();
+            self.context.struct_type(&field_tys, false)
+        });
+
+        // Environment
+        let env_ptr = match env_ty {
+            Some(env_ty) => {
+                let env_ptr = self.util_gen_alloca(env_ty);
+
+                for (i, (capture, _)) in captures.iter().enumerate() {
+                    let binding_value = self.bindings[&capture.binding_id];
+
+                    let value = if capture.is_by_ptr {
+                        // Local bindings of sized types are always stored in memory
+                        let Value::Memory(memory) = binding_value else {
+                            unreachable!("captured binding is not stored in memory");
+                        };
+
+                        memory.as_basic_value_enum()
+                    } else {
+                        let ty_id = self.resolve[capture.binding_id].ty_id;
+                        self.gen_loaded_value(ty_id, binding_value).unwrap()
+                    };
+
+                    let field_ptr = self
+                        .builder
+                        .build_struct_gep(env_ty, env_ptr, i as u32, "")
+                        .unwrap();
+
+                    self.builder.build_store(field_ptr, value);
+                }
+
+                self.builder
+                    .build_pointer_cast(env_ptr, self.tys.i8_ptr_ty(), "")
+            }
+            None => self.tys.i8_ptr_ty().const_null(),
+        };
+
+        // Function
+        let fn_name = format!(
+            "{}__closure",
+            self.fn_state().fn_value.get_name().to_str().unwrap(),
+        );
+
+        let fn_ty = self.tys.get_fn_ty(fn_ty_id);
+        let fn_value = self
+            .module
+            .add_function(&fn_name, fn_ty, Some(Linkage::Private));
+        let current_block = self.builder.current_block();
+
+        self.fn_stack.push(FnState::new(
+            fn_value,
+            expr.frame_id,
+            fn_ty_id.get_fn().ret,
+            ArgAbi::Direct,
+        ));
+
+        let entry_block = self.context.append_basic_block(fn_value, "entry");
+        self.builder.position_at_end(entry_block);
+
+        let mut param_value_iter = fn_value.get_param_iter();
+        let env_param = param_value_iter.next().unwrap().into_pointer_value();
+
+        if let Some(env_ty) = env_ty {
+            self.util_gen_closure_captures(env_ty, env_param, &captures);
+        }
+
+        for capture in expr.captures.iter() {
+            self.bindings
+                .entry(capture.capture_binding_id)
+                .or_insert(Value::Void);
+        }
+
+        self.util_gen_fn_params(param_value_iter, &expr.binding_ids);
+
+        let ret_value = self.gen_loaded_expr(&expr.body);
+
+        if !self.builder.current_block_diverges() {
+            self.util_gen_return(ret_value);
+        }
+
+        self.util_fix_unaligned_accesses(fn_value);
+        self.pass_manager.run_on(&fn_value);
+        self.fn_stack.pop();
+        self.builder.position_at_end(current_block);
+
+        // Closure
+        let closure_ty = self.tys[closure_ty_id].unwrap().into_struct_type();
+        let field_map = self.tys.get_field_map(closure_ty_id);
+        let fn_index = field_map[sym::CLOSURE_FN];
+        let env_index = field_map[sym::CLOSURE_ENV];
+
+        let closure_value = self
+            .builder
+            .build_insert_value(
+                closure_ty.get_undef(),
+                fn_value.as_global_value().as_pointer_value(),
+                fn_index,
+                "",
+            )
+            .unwrap();
+
+        let closure_value = self
+            .builder
+            .build_insert_value(closure_value, env_ptr, env_index, "")
+            .unwrap();
+
+        closure_value.as_basic_value_enum().into()
+    }
+
+    pub fn get_closure_fn_ty_id(&self, closure_ty_id: TyId) -> TyId {
+        self.resolve
+            .get_ty_def(closure_ty_id)
+            .unwrap()
+            .get_aggregate_field(sym::CLOSURE_FN)
+            .unwrap()
+            .ty_id
+    }
+
+    fn util_gen_closure_captures(
+        &mut self,
+        env_ty: StructType<'a>,
+        env_param: PointerValue<'a>,
+        captures: &[(&ClosureCaptureAst, BasicTypeEnum<'a>)],
+    ) {
+        let env_ptr =
+            self.builder
+                .build_pointer_cast(env_param, env_ty.ptr_type(Default::default()), "");
+
+        for (i, (capture, field_ty)) in captures.iter().enumerate() {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(env_ty, env_ptr, i as u32, "")
+                .unwrap();
+
+            let memory = if capture.is_by_ptr {
+                self.builder
+                    .build_load(*field_ty, field_ptr, "")
+                    .into_pointer_value()
+            } else {
+                field_ptr
+            };
+
+            self.bindings
+                .insert(capture.capture_binding_id, Value::Memory(memory));
+        }
+    }
+
+    pub(crate) fn util_gen_closure_call_values(
+        &mut self,
+        closure_ty_id: TyId,
+        closure_value: BasicValueEnum<'a>,
+    ) -> (PointerValue<'a>, PointerValue<'a>) {
+        let closure_value = closure_value.into_struct_value();
+        let field_map = self.tys.get_field_map(closure_ty_id);
+        let fn_index = field_map[sym::CLOSURE_FN];
+        let env_index = field_map[sym::CLOSURE_ENV];
+
+        let fn_ptr = self
+            .builder
+            .build_extract_value(closure_value, fn_index, "")
+            .unwrap()
+            .into_pointer_value();
+
+        let env_ptr = self
+            .builder
+            .build_extract_value(closure_value, env_index, "")
+            .unwrap()
+            .into_pointer_value();
+
+        (fn_ptr, env_ptr)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{BuilderExt, CodeGenerator, LoadedValue};
-use cool_ast::CondExprAst;
+use cool_ast::{CondBlockAst, CondExprAst};
 use inkwell::basic_block::BasicBlock;
-use inkwell::values::BasicValueEnum;
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
 
 impl<'a> CodeGenerator<'a> {
     pub fn gen_cond_expr(&mut self, expr: &CondExprAst) -> LoadedValue<'a> {
@@ -11,9 +11,7 @@ impl<'a> CodeGenerator<'a> {
 
         for (i, cond_block) in expr.cond_blocks.iter().enumerate() {
             // Condition
-            let cond_value = self.gen_loaded_expr(&cond_block.cond);
-
-            if self.builder.current_block_diverges() {
+            let Some((bool_cond_value, optional_ptr)) = self.util_gen_cond(cond_block) else {
                 if i == 0 {
                     end_block.remove_from_function().unwrap();
                     return LoadedValue::None;
@@ -21,10 +19,7 @@ impl<'a> CodeGenerator<'a> {
 
                 else_expr = None;
                 break;
-            }
-
-            let cond_value = cond_value.unwrap().into_int_value();
-            let bool_cond_value = self.builder.build_bool(cond_value);
+            };
 
             let body_block = self.append_block_after_current_block();
             let next_block = if i + 1 == expr.cond_blocks.len() && else_expr.is_none() {
@@ -38,6 +33,7 @@ impl<'a> CodeGenerator<'a> {
 
             // Body
             self.builder.position_at_end(body_block);
+            self.util_gen_cond_binding(cond_block, optional_ptr);
             let body_value = self.gen_loaded_expr(&cond_block.expr);
 
             if !self.builder.current_block_diverges() {
@@ -79,4 +75,45 @@ impl<'a> CodeGenerator<'a> {
 
         phi_value.as_basic_value().into()
     }
+
+    /// Generates the condition of `block` as an `i1`. Blocks that unwrap an optional value
+    /// also return the memory holding it. Returns `None` if the condition diverges.
+    pub(crate) fn util_gen_cond(
+        &mut self,
+        block: &CondBlockAst,
+    ) -> Option<(IntValue<'a>, Option<PointerValue<'a>>)> {
+        if block.binding_id.is_none() {
+            let cond_value = self.gen_loaded_expr(&block.cond);
+            if self.builder.current_block_diverges() {
+                return None;
+            }
+
+            let cond_value = cond_value.unwrap().into_int_value();
+            return Some((self.builder.build_bool(cond_value), None));
+        }
+
+        let optional_value = self.gen_expr(&block.cond, None);
+        if self.builder.current_block_diverges() {
+            return None;
+        }
+
+        let optional_ty_id = block.cond.expr_id().ty_id;
+        let optional_ptr = self.util_gen_variant_ptr(optional_value);
+
+        let is_some_value = self.util_gen_optional_is_some(optional_ty_id, optional_ptr);
+        Some((is_some_value, Some(optional_ptr)))
+    }
+
+    /// Binds the unwrapped value of `block`, if any. Must be called at the start of the body.
+    pub(crate) fn util_gen_cond_binding(
+        &mut self,
+        block: &CondBlockAst,
+        optional_ptr: Option<PointerValue<'a>>,
+    ) {
+        if let (Some(binding_id), Some(optional_ptr)) = (block.binding_id, optional_ptr) {
+            let binding_ty_id = self.resolve[binding_id].ty_id;
+            let binding_value = self.util_gen_variant_payload(binding_ty_id, optional_ptr);
+            self.bindings.insert(binding_id, binding_value);
+        }
+    }
 }
@@ -1,48 +1,128 @@
-use crate::{BuilderExt, CodeGenerator, LoadedValue, Value};
-use cool_ast::FnCallExprAst;
+use crate::{ArgAbi, BuilderExt, CodeGenerator, LoadedValue, Value};
+use cool_ast::{ExprAst, FnCallExprAst};
 use inkwell::values::BasicMetadataValueEnum;
 
 impl<'a> CodeGenerator<'a> {
     pub fn gen_fn_call_expr(&mut self, expr: &FnCallExprAst) -> LoadedValue<'a> {
         // Function
-        let fn_value = self.gen_expr(&expr.fn_expr, None);
+        let (fn_value, receiver) = match expr.fn_expr.as_ref() {
+            ExprAst::InterfaceMethod(method_expr) => {
+                let (fn_pointer, data_pointer) =
+                    self.util_gen_interface_method_values(method_expr)?;
+
+                (Value::Register(fn_pointer.into()), Some(data_pointer))
+            }
+            fn_expr => (self.gen_expr(fn_expr, None), None),
+        };
+
         if self.builder.current_block_diverges() {
             return LoadedValue::None;
         }
 
+        let fn_expr_ty_id = expr.fn_expr.expr_id().ty_id;
+
+        let fn_ty_id = if fn_expr_ty_id.is_closure() {
+            self.get_closure_fn_ty_id(fn_expr_ty_id)
+        } else {
+            fn_expr_ty_id
+        };
+
+        let fn_abi = self.tys.get_fn_abi(fn_ty_id).cloned();
+
         // Arguments
         let mut arg_values = Vec::<BasicMetadataValueEnum<'a>>::new();
-        for arg_expr in expr.arg_exprs.iter() {
+        if let Some(receiver) = receiver {
+            arg_values.push(receiver.into());
+        }
+
+        let param_offset = arg_values.len();
+
+        for (i, arg_expr) in expr.arg_exprs.iter().enumerate() {
             let arg_value = self.gen_loaded_expr(arg_expr);
             if self.builder.current_block_diverges() {
                 return LoadedValue::None;
             }
 
-            if let Some(arg_value) = arg_value {
-                arg_values.push(arg_value.into())
-            }
+            let Some(arg_value) = arg_value else {
+                continue;
+            };
+
+            // Variadic arguments are scalars, which are passed directly
+            let arg_abi = fn_abi
+                .as_ref()
+                .and_then(|fn_abi| fn_abi.params.get(param_offset + i))
+                .copied()
+                .unwrap_or(ArgAbi::Direct);
+
+            let arg_value = match arg_abi {
+                ArgAbi::Coerce(coerced_ty) => {
+                    let arg_ty_id = arg_expr.expr_id().ty_id;
+                    self.util_gen_coerced_value(arg_value, arg_ty_id, coerced_ty)
+                }
+                ArgAbi::Indirect { .. } => self.util_gen_init(arg_value).into(),
+                _ => arg_value,
+            };
+
+            arg_values.push(arg_value.into());
+        }
+
+        let ret_ty_id = fn_ty_id.get_fn().ret;
+        let sret_ptr = fn_abi
+            .as_ref()
+            .filter(|fn_abi| fn_abi.has_sret())
+            .map(|_| self.util_gen_alloca(self.tys[ret_ty_id].unwrap()));
+
+        if let Some(sret_ptr) = sret_ptr {
+            arg_values.insert(0, sret_ptr.into());
         }
 
-        let ret_value = match fn_value {
+        let call_value = match fn_value {
             Value::Fn(fn_value) => {
+                let call_value = self.builder.build_call(fn_value, &arg_values, "");
+                call_value.set_call_convention(fn_value.get_call_conventions());
+                call_value
+            }
+            fn_value if fn_expr_ty_id.is_closure() => {
+                let closure_value = self.gen_loaded_value(fn_expr_ty_id, fn_value).unwrap();
+                let (fn_pointer, env_pointer) =
+                    self.util_gen_closure_call_values(fn_expr_ty_id, closure_value);
+
+                let fn_ty = self.tys.get_fn_ty(fn_ty_id);
+                arg_values.insert(0, env_pointer.into());
+
                 self.builder
-                    .build_call(fn_value, &arg_values, "")
-                    .try_as_basic_value()
+                    .build_indirect_call(fn_ty, fn_pointer, &arg_values, "")
             }
             Value::Register(value) => {
-                let ty_id = expr.fn_expr.expr_id().ty_id;
-                let fn_ty = self.tys.get_fn_ty(ty_id);
+                let fn_ty = self.tys.get_fn_ty(fn_ty_id);
                 let fn_pointer = value.into_pointer_value();
 
                 self.builder
                     .build_indirect_call(fn_ty, fn_pointer, &arg_values, "")
-                    .try_as_basic_value()
             }
             _ => panic!("value is not a function"),
         };
 
-        ret_value
+        for (loc, attribute) in self.util_get_c_abi_attributes(fn_ty_id) {
+            call_value.add_attribute(loc, attribute);
+        }
+
+        let ret_value = call_value
+            .try_as_basic_value()
             .map_left(LoadedValue::Some)
-            .left_or(LoadedValue::None)
+            .left_or(LoadedValue::None);
+
+        match fn_abi.map(|fn_abi| fn_abi.ret) {
+            Some(ArgAbi::Coerce(_)) => {
+                let ret_ptr = self.util_gen_uncoerced_ptr(ret_value.unwrap(), ret_ty_id);
+                let ret_ty = self.tys[ret_ty_id].unwrap();
+                Some(self.builder.build_load(ret_ty, ret_ptr, ""))
+            }
+            Some(ArgAbi::Indirect { .. }) => {
+                let ret_ty = self.tys[ret_ty_id].unwrap();
+                Some(self.builder.build_load(ret_ty, sret_ptr.unwrap(), ""))
+            }
+            _ => ret_value,
+        }
     }
 }
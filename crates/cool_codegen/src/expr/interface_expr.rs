@@ -0,0 +1,131 @@
+use crate::{mangle_item_path, BuilderExt, CodeGenerator, LoadedValue};
+use cool_ast::{InterfaceMethodExprAst, InterfaceObjectExprAst};
+use cool_lexer::sym;
+use cool_resolve::ItemId;
+use inkwell::module::Linkage;
+use inkwell::values::{BasicValue, GlobalValue, PointerValue};
+
+impl<'a> CodeGenerator<'a> {
+    pub fn gen_interface_object_expr(&mut self, expr: &InterfaceObjectExprAst) -> LoadedValue<'a> {
+        let data_value = self.gen_loaded_expr(&expr.expr);
+        if self.builder.current_block_diverges() {
+            return LoadedValue::None;
+        }
+
+        let data_ptr = data_value.unwrap().into_pointer_value();
+        let vtable = self.get_or_insert_vtable(expr.struct_item_id(), expr.interface_item_id());
+
+        let interface_ty_id = expr.expr_id.ty_id;
+        let interface_ty = self.tys[interface_ty_id].unwrap().into_struct_type();
+        let field_map = self.tys.get_field_map(interface_ty_id);
+        let data_index = field_map[sym::INTERFACE_DATA];
+        let vtable_index = field_map[sym::INTERFACE_VTABLE];
+
+        let interface_value = self
+            .builder
+            .build_insert_value(interface_ty.get_undef(), data_ptr, data_index, "")
+            .unwrap();
+
+        let interface_value = self
+            .builder
+            .build_insert_value(interface_value, vtable.as_pointer_value(), vtable_index, "")
+            .unwrap();
+
+        interface_value.as_basic_value_enum().into()
+    }
+
+    /// Returns the function pointer of the method and the data pointer to pass as its receiver.
+    pub(crate) fn util_gen_interface_method_values(
+        &mut self,
+        expr: &InterfaceMethodExprAst,
+    ) -> Option<(PointerValue<'a>, PointerValue<'a>)> {
+        let interface_ty_id = expr.base.expr_id().ty_id;
+        let interface_value = self.gen_loaded_expr(&expr.base);
+        if self.builder.current_block_diverges() {
+            return None;
+        }
+
+        let interface_value = interface_value.unwrap().into_struct_value();
+        let field_map = self.tys.get_field_map(interface_ty_id);
+        let data_index = field_map[sym::INTERFACE_DATA];
+        let vtable_index = field_map[sym::INTERFACE_VTABLE];
+
+        let data_ptr = self
+            .builder
+            .build_extract_value(interface_value, data_index, "")
+            .unwrap()
+            .into_pointer_value();
+
+        let vtable_ptr = self
+            .builder
+            .build_extract_value(interface_value, vtable_index, "")
+            .unwrap()
+            .into_pointer_value();
+
+        let fn_ptr_ty = self.tys.i8_ptr_ty();
+        let method_index = self
+            .tys
+            .isize_ty()
+            .const_int(expr.method_index as u64, false);
+
+        let fn_ptr_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(fn_ptr_ty, vtable_ptr, &[method_index], "")
+        };
+
+        let fn_ptr = self
+            .builder
+            .build_load(fn_ptr_ty, fn_ptr_ptr, "")
+            .into_pointer_value();
+
+        Some((fn_ptr, data_ptr))
+    }
+
+    /// Vtables are arrays of method pointers in the order of the interface methods, emitted
+    /// once per implementation.
+    fn get_or_insert_vtable(
+        &mut self,
+        struct_item_id: ItemId,
+        interface_item_id: ItemId,
+    ) -> GlobalValue<'a> {
+        if let Some(&vtable) = self.vtables.get(&(struct_item_id, interface_item_id)) {
+            return vtable;
+        }
+
+        let fn_ptrs = self
+            .resolve
+            .get_interface_impl(struct_item_id, interface_item_id)
+            .unwrap()
+            .iter()
+            .map(|&method_item_id| {
+                // Methods are always function items, declared before any body is generated
+                let binding_id = self.resolve[method_item_id].as_binding_id().unwrap();
+
+                self.bindings[&binding_id]
+                    .into_function_value()
+                    .as_global_value()
+                    .as_pointer_value()
+            })
+            .collect::<Vec<_>>();
+
+        let fn_ptr_ty = self.tys.i8_ptr_ty();
+        let vtable_ty = fn_ptr_ty.array_type(fn_ptrs.len() as u32);
+
+        let vtable_name = format!(
+            "{}__vtable__{}",
+            mangle_item_path(&*struct_item_id),
+            mangle_item_path(&*interface_item_id),
+        );
+
+        // Every module that converts to the interface emits the same vtable
+        let vtable = self.module.add_global(vtable_ty, None, &vtable_name);
+        vtable.set_linkage(Linkage::LinkOnceODR);
+        vtable.set_constant(true);
+        vtable.set_initializer(&fn_ptr_ty.const_array(&fn_ptrs));
+
+        self.vtables
+            .insert((struct_item_id, interface_item_id), vtable);
+
+        vtable
+    }
+}
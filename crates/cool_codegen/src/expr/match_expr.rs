@@ -1,9 +1,9 @@
 use crate::{BuilderExt, CodeGenerator, LoadedValue, Value};
 use cool_ast::MatchExprAst;
 use cool_lexer::sym;
-use cool_resolve::TaggedUnionKind;
+use cool_resolve::{TaggedUnionKind, TyId};
 use inkwell::basic_block::BasicBlock;
-use inkwell::values::{BasicValueEnum, IntValue};
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
 use inkwell::IntPredicate;
 
 impl<'a> CodeGenerator<'a> {
@@ -14,62 +14,10 @@ impl<'a> CodeGenerator<'a> {
         }
 
         let matched_expr_ty_id = expr.matched_expr.expr_id().ty_id;
-        let matched_expr_ty = self.tys[matched_expr_ty_id].unwrap();
 
-        let matched_expr_ptr = match matched_expr_value {
-            Value::Memory(memory) => memory,
-            Value::Register(value) => self.util_gen_init(value),
-            Value::Void | Value::Fn(_) => panic!("invalid value for matched expression"),
-        };
+        let matched_expr_ptr = self.util_gen_variant_ptr(matched_expr_value);
 
-        let tagged_union_kind = self
-            .resolve
-            .get_ty_def(matched_expr_ty_id)
-            .unwrap()
-            .kind
-            .as_tagged_union()
-            .unwrap()
-            .kind;
-
-        let index_value = match tagged_union_kind {
-            TaggedUnionKind::Basic { .. } => {
-                let index_field_index = self
-                    .tys
-                    .get_field_map(matched_expr_ty_id)
-                    .get(sym::VARIANT_INDEX)
-                    .unwrap();
-
-                let index_field_ptr = self
-                    .builder
-                    .build_struct_gep(matched_expr_ty, matched_expr_ptr, index_field_index, "")
-                    .unwrap();
-
-                self.builder
-                    .build_load(self.tys.i8_ty(), index_field_ptr, "")
-                    .into_int_value()
-            }
-            TaggedUnionKind::NullablePtr => {
-                let value = self
-                    .builder
-                    .build_load(self.tys.isize_ty(), matched_expr_ptr, "")
-                    .into_int_value();
-
-                let zero_isize = self.tys.isize_ty().const_zero();
-
-                let select_value =
-                    self.builder
-                        .build_int_compare(IntPredicate::NE, value, zero_isize, "");
-
-                self.builder
-                    .build_select(
-                        select_value,
-                        self.tys.i8_ty().const_zero(),
-                        self.tys.i8_ty().const_int(1, false),
-                        "",
-                    )
-                    .into_int_value()
-            }
-        };
+        let index_value = self.util_gen_variant_index(matched_expr_ty_id, matched_expr_ptr);
 
         let switch_block = self.builder.current_block();
         let else_block = self.append_block_after_current_block();
@@ -84,14 +32,7 @@ impl<'a> CodeGenerator<'a> {
 
             if let Some(binding_id) = arm.binding_id {
                 let binding_ty_id = self.resolve[binding_id].ty_id;
-                let binding_value = match self.tys[binding_ty_id] {
-                    Some(binding_ty) => {
-                        let value = self.builder.build_load(binding_ty, matched_expr_ptr, "");
-                        Value::Memory(self.util_gen_init(value))
-                    }
-                    None => Value::Void,
-                };
-
+                let binding_value = self.util_gen_variant_payload(binding_ty_id, matched_expr_ptr);
                 self.bindings.insert(binding_id, binding_value);
             }
 
@@ -105,24 +46,7 @@ impl<'a> CodeGenerator<'a> {
                 self.builder.build_unconditional_branch(end_block);
             }
 
-            let arm_index = match tagged_union_kind {
-                TaggedUnionKind::Basic { .. } => {
-                    expr.get_variant_index(arm.arm_ty_id)
-                        .map(|i| self.tys.i8_ty().const_int(i as _, false))
-                        .unwrap()
-                }
-
-                TaggedUnionKind::NullablePtr => {
-                    let value = if arm.arm_ty_id.get_value().is_ptr_like() {
-                        0
-                    } else {
-                        1
-                    };
-
-                    self.tys.i8_ty().const_int(value, false)
-                }
-            };
-
+            let arm_index = self.util_gen_variant_arm_index(matched_expr_ty_id, arm.arm_ty_id);
             arm_blocks.push((arm_index, block));
         }
 
@@ -164,4 +88,139 @@ impl<'a> CodeGenerator<'a> {
             _ => LoadedValue::None,
         }
     }
+
+    /// Returns a pointer to the tagged union held by `value`, spilling it to the stack if needed.
+    /// Tagged unions always store an index or a pointer, so they are never zero-sized and
+    /// non-diverging expressions of their type always produce a value.
+    pub(crate) fn util_gen_variant_ptr(&mut self, value: Value<'a>) -> PointerValue<'a> {
+        match value {
+            Value::Memory(memory) => memory,
+            Value::Register(value) => self.util_gen_init(value),
+            Value::Void | Value::Fn(_) => unreachable!("tagged union value is not stored"),
+        }
+    }
+
+    /// Loads the index of the active variant of the tagged union stored at `ptr`. Nullable
+    /// pointers have the index 0 when not null.
+    pub(crate) fn util_gen_variant_index(
+        &mut self,
+        ty_id: TyId,
+        ptr: PointerValue<'a>,
+    ) -> IntValue<'a> {
+        let ty = self.tys[ty_id].unwrap();
+
+        let tagged_union_kind = self
+            .resolve
+            .get_ty_def(ty_id)
+            .unwrap()
+            .kind
+            .as_tagged_union()
+            .unwrap()
+            .kind;
+
+        match tagged_union_kind {
+            TaggedUnionKind::Basic { .. } => {
+                let index_field_index = self
+                    .tys
+                    .get_field_map(ty_id)
+                    .get(sym::VARIANT_INDEX)
+                    .unwrap();
+
+                let index_field_ptr = self
+                    .builder
+                    .build_struct_gep(ty, ptr, index_field_index, "")
+                    .unwrap();
+
+                self.builder
+                    .build_load(self.tys.i8_ty(), index_field_ptr, "")
+                    .into_int_value()
+            }
+            TaggedUnionKind::NullablePtr => {
+                let value = self
+                    .builder
+                    .build_load(self.tys.isize_ty(), ptr, "")
+                    .into_int_value();
+
+                let zero_isize = self.tys.isize_ty().const_zero();
+
+                let select_value =
+                    self.builder
+                        .build_int_compare(IntPredicate::NE, value, zero_isize, "");
+
+                self.builder
+                    .build_select(
+                        select_value,
+                        self.tys.i8_ty().const_zero(),
+                        self.tys.i8_ty().const_int(1, false),
+                        "",
+                    )
+                    .into_int_value()
+            }
+        }
+    }
+
+    /// Returns the index that `util_gen_variant_index` yields when the tagged union `ty_id`
+    /// holds a value of type `variant_ty_id`.
+    pub(crate) fn util_gen_variant_arm_index(
+        &self,
+        ty_id: TyId,
+        variant_ty_id: TyId,
+    ) -> IntValue<'a> {
+        let tagged_union_kind = self
+            .resolve
+            .get_ty_def(ty_id)
+            .unwrap()
+            .kind
+            .as_tagged_union()
+            .unwrap()
+            .kind;
+
+        let index = match tagged_union_kind {
+            TaggedUnionKind::Basic { .. } => {
+                ty_id
+                    .get_variant()
+                    .get_variant_index(variant_ty_id)
+                    .unwrap()
+            }
+            TaggedUnionKind::NullablePtr => {
+                if variant_ty_id.get_value().is_ptr_like() {
+                    0
+                } else {
+                    1
+                }
+            }
+        };
+
+        self.tys.i8_ty().const_int(index as _, false)
+    }
+
+    /// Copies the value of type `variant_ty_id` held by the tagged union stored at `ptr` into a
+    /// new stack slot.
+    pub(crate) fn util_gen_variant_payload(
+        &mut self,
+        variant_ty_id: TyId,
+        ptr: PointerValue<'a>,
+    ) -> Value<'a> {
+        match self.tys[variant_ty_id] {
+            Some(variant_ty) => {
+                let value = self.builder.build_load(variant_ty, ptr, "");
+                Value::Memory(self.util_gen_init(value))
+            }
+            None => Value::Void,
+        }
+    }
+
+    /// Checks whether the optional value stored at `ptr` holds its inner value.
+    pub(crate) fn util_gen_optional_is_some(
+        &mut self,
+        ty_id: TyId,
+        ptr: PointerValue<'a>,
+    ) -> IntValue<'a> {
+        let inner_ty_id = ty_id.get_variant().get_optional_inner().unwrap();
+        let index_value = self.util_gen_variant_index(ty_id, ptr);
+        let inner_index_value = self.util_gen_variant_arm_index(ty_id, inner_ty_id);
+
+        self.builder
+            .build_int_compare(IntPredicate::EQ, index_value, inner_index_value, "")
+    }
 }
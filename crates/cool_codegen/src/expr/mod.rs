@@ -3,15 +3,20 @@ mod array_expr;
 mod binary_expr;
 mod block_expr;
 mod cast_expr;
+mod closure_expr;
 mod cond_expr;
 mod deref_expr;
 mod fn_call_expr;
 mod index_expr;
+mod interface_expr;
 mod layout_expr;
 mod literal_expr;
 mod match_expr;
+mod or_else_expr;
+mod panic_expr;
 mod range_expr;
 mod struct_expr;
+mod try_expr;
 mod tuple_expr;
 mod unary_expr;
 mod variant_wrap_expr;
@@ -47,24 +52,31 @@ impl<'a> CodeGenerator<'a> {
             ExprAst::Array(e) => self.gen_array_expr(e, memory),
             ExprAst::ArrayLen(e) => self.gen_array_len_expr(e).into(),
             ExprAst::ArrayRepeat(e) => self.gen_array_repeat_expr(e, memory),
+            ExprAst::Assert(e) => self.gen_assert_expr(e).into(),
             ExprAst::Binary(e) => self.gen_binary_expr(e).into(),
             ExprAst::Binding(e) => self.gen_ident_expr(e),
             ExprAst::Block(e) => self.gen_block_expr(e).into(),
             ExprAst::Cast(e) => self.gen_cast_expr(e).into(),
+            ExprAst::Closure(e) => self.gen_closure_expr(e).into(),
             ExprAst::Cond(e) => self.gen_cond_expr(e).into(),
             ExprAst::Deref(e) => self.gen_deref_expr(e),
             ExprAst::FnCall(e) => self.gen_fn_call_expr(e).into(),
             ExprAst::Index(e) => self.gen_index_expr(e),
+            ExprAst::InterfaceObject(e) => self.gen_interface_object_expr(e).into(),
             ExprAst::Literal(e) => self.gen_literal_expr(e).into(),
             ExprAst::Match(e) => self.gen_match_expr(e).into(),
             ExprAst::OffsetOf(e) => self.gen_offset_of_expr(e).as_basic_value_enum().into(),
+            ExprAst::OrElse(e) => self.gen_or_else_expr(e).into(),
+            ExprAst::Panic(e) => self.gen_panic_expr(e).into(),
             ExprAst::Range(e) => self.gen_range_expr(e, memory),
             ExprAst::Stmt(e) => self.gen_stmt_expr(e),
             ExprAst::SizeOf(e) => self.gen_size_of_expr(e).as_basic_value_enum().into(),
             ExprAst::Struct(e) => self.gen_struct_expr(e, memory),
+            ExprAst::Try(e) => self.gen_try_expr(e).into(),
             ExprAst::Tuple(e) => self.gen_tuple_expr(e, memory),
             ExprAst::Unary(e) => self.gen_unary_expr(e),
             ExprAst::Unit(e) => self.gen_unit_expr(e).into(),
+            ExprAst::Unreachable(e) => self.gen_unreachable_expr(e).into(),
             ExprAst::VariantWrap(e) => self.gen_variant_wrap_expr(e, memory),
             _ => panic!("unsupported codegen operation: {:#?}", expr),
         }
@@ -0,0 +1,65 @@
+use crate::{BuilderExt, CodeGenerator, LoadedValue};
+use cool_ast::OrElseExprAst;
+use inkwell::basic_block::BasicBlock;
+use inkwell::values::BasicValueEnum;
+
+impl<'a> CodeGenerator<'a> {
+    pub fn gen_or_else_expr(&mut self, expr: &OrElseExprAst) -> LoadedValue<'a> {
+        let optional_value = self.gen_expr(&expr.lhs, None);
+        if self.builder.current_block_diverges() {
+            return LoadedValue::None;
+        }
+
+        let optional_ty_id = expr.optional_ty_id();
+        let optional_ptr = self.util_gen_variant_ptr(optional_value);
+
+        let is_some_value = self.util_gen_optional_is_some(optional_ty_id, optional_ptr);
+
+        let some_block = self.append_block_after_current_block();
+        let none_block = self.append_block_after(some_block);
+        let end_block = self.append_block_after(none_block);
+
+        self.builder
+            .build_conditional_branch(is_some_value, some_block, none_block);
+
+        let mut phi_values = Vec::<(BasicBlock, BasicValueEnum<'a>)>::new();
+
+        // Some
+        self.builder.position_at_end(some_block);
+        let inner_ty_id = optional_ty_id.get_variant().get_optional_inner().unwrap();
+        let some_value = self.util_gen_variant_payload(inner_ty_id, optional_ptr);
+
+        if let Some(some_value) = self.gen_loaded_value(inner_ty_id, some_value) {
+            phi_values.push((self.builder.current_block(), some_value));
+        }
+
+        self.builder.build_unconditional_branch(end_block);
+
+        // None
+        self.builder.position_at_end(none_block);
+        let none_value = self.gen_loaded_expr(&expr.rhs);
+
+        if !self.builder.current_block_diverges() {
+            if let Some(none_value) = none_value {
+                phi_values.push((self.builder.current_block(), none_value));
+            }
+
+            self.builder.build_unconditional_branch(end_block);
+        }
+
+        self.builder.position_at_end(end_block);
+
+        match self.tys[inner_ty_id] {
+            Some(ty) if !phi_values.is_empty() => {
+                let phi_value = self.builder.build_phi(ty, "");
+
+                for (block, value) in phi_values {
+                    phi_value.add_incoming(&[(&value, block)]);
+                }
+
+                phi_value.as_basic_value().into()
+            }
+            _ => LoadedValue::None,
+        }
+    }
+}
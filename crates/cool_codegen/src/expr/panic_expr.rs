@@ -0,0 +1,35 @@
+use crate::{BuilderExt, CodeGenerator, LoadedValue};
+use cool_ast::{AssertExprAst, PanicExprAst, UnreachableExprAst};
+
+impl<'a> CodeGenerator<'a> {
+    pub fn gen_assert_expr(&mut self, expr: &AssertExprAst) -> LoadedValue<'a> {
+        let cond_value = self.gen_loaded_expr(&expr.cond);
+        if self.builder.current_block_diverges() {
+            return LoadedValue::None;
+        }
+
+        let cond_value = cond_value.unwrap().into_int_value();
+        let failed_value = self
+            .builder
+            .build_not(self.builder.build_bool(cond_value), "");
+
+        self.util_gen_runtime_check(failed_value, expr.span, "assertion failed");
+        LoadedValue::None
+    }
+
+    pub fn gen_panic_expr(&mut self, expr: &PanicExprAst) -> LoadedValue<'a> {
+        let message_value = self.gen_loaded_expr(&expr.message);
+        if self.builder.current_block_diverges() {
+            return LoadedValue::None;
+        }
+
+        let message_ptr = message_value.unwrap().into_pointer_value();
+        self.util_gen_panic(expr.span, message_ptr);
+        LoadedValue::None
+    }
+
+    pub fn gen_unreachable_expr(&mut self, expr: &UnreachableExprAst) -> LoadedValue<'a> {
+        self.util_gen_runtime_error(expr.span, "entered unreachable code");
+        LoadedValue::None
+    }
+}
@@ -15,11 +15,27 @@ impl<'a> CodeGenerator<'a> {
         for initializer in expr.initializers.iter() {
             match (struct_ty, memory) {
                 (Some(struct_ty), Some(memory)) => {
-                    let Some(field_index) = self
-                        .tys
-                        .get_field_map(struct_ty_id)
-                        .get(initializer.ident.symbol)
-                    else {
+                    let field_ptr = if struct_ty_id.is_union() {
+                        self.util_gen_union_field_ptr(
+                            struct_ty_id,
+                            memory,
+                            initializer.ident.symbol,
+                        )
+                    } else {
+                        self.tys
+                            .get_field_map(struct_ty_id)
+                            .get(initializer.ident.symbol)
+                            .map(|field_index| {
+                                self.util_gen_struct_field_ptr(
+                                    struct_ty_id,
+                                    struct_ty.into_struct_type(),
+                                    memory,
+                                    field_index,
+                                )
+                            })
+                    };
+
+                    let Some(field_ptr) = field_ptr else {
                         self.gen_expr(&initializer.expr, None);
                         if self.builder.current_block_diverges() {
                             return Value::Void;
@@ -27,11 +43,6 @@ impl<'a> CodeGenerator<'a> {
                         continue;
                     };
 
-                    let field_ptr = self
-                        .builder
-                        .build_struct_gep(struct_ty, memory, field_index, "")
-                        .unwrap();
-
                     let field_value = self.gen_expr(&initializer.expr, Some(field_ptr));
                     if self.builder.current_block_diverges() {
                         return Value::Void;
@@ -0,0 +1,54 @@
+use crate::{BuilderExt, CodeGenerator, LoadedValue};
+use cool_ast::TryExprAst;
+
+impl<'a> CodeGenerator<'a> {
+    pub fn gen_try_expr(&mut self, expr: &TryExprAst) -> LoadedValue<'a> {
+        let variant_value = self.gen_expr(&expr.expr, None);
+        if self.builder.current_block_diverges() {
+            return LoadedValue::None;
+        }
+
+        let variant_ty_id = expr.variant_ty_id();
+        let variant_ptr = self.util_gen_variant_ptr(variant_value);
+
+        let index_value = self.util_gen_variant_index(variant_ty_id, variant_ptr);
+        let switch_block = self.builder.current_block();
+        let success_block = self.append_block_after_current_block();
+        let else_block = self.append_block_after(success_block);
+
+        let success_ty_id = expr.expr_id.ty_id;
+        let success_index = self.util_gen_variant_arm_index(variant_ty_id, success_ty_id);
+        let mut arm_blocks = vec![(success_index, success_block)];
+
+        // Errors
+        for arm in expr.error_arms.iter() {
+            let block = self.append_block_after_current_block();
+            self.builder.position_at_end(block);
+
+            let binding_value = self.util_gen_variant_payload(arm.error_ty_id, variant_ptr);
+            self.bindings.insert(arm.binding_id, binding_value);
+
+            let value = self.gen_loaded_expr(&arm.ret_expr);
+            self.gen_return_defers(expr.frame_id);
+
+            if !self.builder.current_block_diverges() {
+                self.util_gen_return(value);
+            }
+
+            let arm_index = self.util_gen_variant_arm_index(variant_ty_id, arm.error_ty_id);
+            arm_blocks.push((arm_index, block));
+        }
+
+        self.builder.position_at_end(else_block);
+        self.builder.build_unreachable();
+
+        self.builder.position_at_end(switch_block);
+        self.builder
+            .build_switch(index_value, else_block, &arm_blocks);
+
+        // Success
+        self.builder.position_at_end(success_block);
+        let success_value = self.util_gen_variant_payload(success_ty_id, variant_ptr);
+        self.gen_loaded_value(success_ty_id, success_value)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{BuilderExt, CodeGenerator, Value};
 use cool_ast::UnaryExprAst;
 use cool_parser::UnaryOpKind;
+use cool_span::Section;
 use inkwell::values::BasicValue;
 use inkwell::IntPredicate;
 
@@ -19,7 +20,31 @@ impl<'a> CodeGenerator<'a> {
                     value.unwrap()
                 };
 
-                if ty_id.is_int() {
+                if ty_id.is_signed_int() && self.options.overflow_checks {
+                    let value = value.into_int_value();
+                    let zero = value.get_type().const_zero();
+
+                    let result = self
+                        .util_gen_int_intrinsic_call("llvm.ssub.with.overflow", &[zero, value])
+                        .into_struct_value();
+
+                    let overflow = self
+                        .builder
+                        .build_extract_value(result, 1, "")
+                        .unwrap()
+                        .into_int_value();
+
+                    self.util_gen_runtime_check(
+                        overflow,
+                        unary_expr.span(),
+                        "attempt to negate with overflow",
+                    );
+
+                    self.builder
+                        .build_extract_value(result, 0, "")
+                        .unwrap()
+                        .into()
+                } else if ty_id.is_int() {
                     self.builder
                         .build_int_neg(value.into_int_value(), "")
                         .as_basic_value_enum()
@@ -1,7 +1,7 @@
-use crate::CodeGenerator;
-use cool_resolve::FrameId;
+use crate::{ArgAbi, CodeGenerator};
+use cool_resolve::{FrameId, TyId};
 use inkwell::basic_block::BasicBlock;
-use inkwell::values::{FunctionValue, InstructionValue};
+use inkwell::values::{FunctionValue, InstructionValue, PointerValue};
 
 #[derive(Clone, Copy, Debug)]
 pub struct JumpBlock<'a> {
@@ -13,15 +13,28 @@ pub struct JumpBlock<'a> {
 #[derive(Clone, Debug)]
 pub struct FnState<'a> {
     pub fn_value: FunctionValue<'a>,
+    pub frame_id: FrameId,
+    pub ret_ty_id: TyId,
+    pub ret_abi: ArgAbi<'a>,
+    pub sret_ptr: Option<PointerValue<'a>>,
     pub last_alloca: Option<InstructionValue<'a>>,
     pub jump_blocks: Vec<JumpBlock<'a>>,
 }
 
 impl<'a> FnState<'a> {
     #[inline]
-    pub fn new(fn_value: FunctionValue<'a>) -> Self {
+    pub fn new(
+        fn_value: FunctionValue<'a>,
+        frame_id: FrameId,
+        ret_ty_id: TyId,
+        ret_abi: ArgAbi<'a>,
+    ) -> Self {
         Self {
             fn_value,
+            frame_id,
+            ret_ty_id,
+            ret_abi,
+            sret_ptr: None,
             last_alloca: None,
             jump_blocks: vec![],
         }
@@ -1,23 +1,73 @@
-use crate::{mangle_item_path, BuilderExt, CodeGenerator, FnState, Value};
-use cool_ast::{ExternFnAst, FnAst};
-use inkwell::values::BasicValue;
+use crate::{
+    mangle_item_path, ArgAbi, BuilderExt, CodeGenerator, FnAbiInfo, FnState, LoadedValue, Value,
+};
+use cool_ast::{CallConv, ExternFnAst, FnAst, FnAttrs, InlineAttr};
+use cool_collections::SmallString;
+use cool_resolve::{BindingId, ItemId, TyId};
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::module::Linkage;
+use inkwell::types::{AnyType, BasicTypeEnum};
+use inkwell::values::{
+    AnyValue, BasicValue, BasicValueEnum, FunctionValue, InstructionOpcode, InstructionValue,
+    PointerValue,
+};
+use smallvec::SmallVec;
 
-impl CodeGenerator<'_> {
+/// Alignment of the stack slots used to reinterpret values passed in registers. Coerced types
+/// are at most 16 bytes wide, so the slots satisfy the alignment of both sides of the copy.
+const COERCION_SLOT_ALIGN: u32 = 16;
+
+// Calling convention ids from `llvm/IR/CallingConv.h`
+const LLVM_CALL_CONV_C: u32 = 0;
+const LLVM_CALL_CONV_FAST: u32 = 8;
+const LLVM_CALL_CONV_COLD: u32 = 9;
+
+impl<'a> CodeGenerator<'a> {
     pub fn add_extern_fn(&mut self, extern_fn_ast: &ExternFnAst) {
-        let fn_name = extern_fn_ast.item_id.last().unwrap().as_str();
+        let fn_name = extern_fn_ast
+            .attrs
+            .link_name
+            .unwrap_or_else(|| *extern_fn_ast.item_id.last().unwrap());
+
         let fn_ty = self.tys.get_fn_ty(extern_fn_ast.ty_id);
         let binding_id = self.resolve[extern_fn_ast.item_id].as_binding_id().unwrap();
-        let fn_value = self.module.add_function(fn_name, fn_ty, None);
+        let linkage = extern_fn_ast.attrs.is_weak.then_some(Linkage::ExternalWeak);
+        let fn_value = self.module.add_function(fn_name.as_str(), fn_ty, linkage);
+        self.util_add_c_abi_fn_attributes(fn_value, extern_fn_ast.ty_id);
+        self.util_apply_fn_attrs(fn_value, &extern_fn_ast.attrs);
 
         debug_assert!(!self.bindings.contains_key(&binding_id));
         self.bindings.insert(binding_id, fn_value.into());
     }
 
     pub fn add_fn(&mut self, fn_ast: &FnAst) {
-        let fn_name = mangle_item_path(&*fn_ast.item_id);
-        let fn_ty = self.tys.get_fn_ty(fn_ast.ty_id);
-        let binding_id = self.resolve[fn_ast.item_id].as_binding_id().unwrap();
-        let fn_value = self.module.add_function(&fn_name, fn_ty, None);
+        let linkage = fn_ast.attrs.is_weak.then_some(Linkage::WeakAny);
+        self.util_add_fn(fn_ast.item_id, fn_ast.ty_id, &fn_ast.attrs, linkage);
+    }
+
+    /// Declares a function that is defined by another module under its usual name.
+    pub fn add_fn_decl(&mut self, fn_decl: &ExternFnAst) {
+        let linkage = fn_decl.attrs.is_weak.then_some(Linkage::ExternalWeak);
+        self.util_add_fn(fn_decl.item_id, fn_decl.ty_id, &fn_decl.attrs, linkage);
+    }
+
+    fn util_add_fn(
+        &mut self,
+        item_id: ItemId,
+        ty_id: TyId,
+        attrs: &FnAttrs,
+        linkage: Option<Linkage>,
+    ) {
+        let fn_name = match attrs.link_name {
+            Some(link_name) => SmallString::from(link_name.as_str()),
+            None => mangle_item_path(&*item_id),
+        };
+
+        let fn_ty = self.tys.get_fn_ty(ty_id);
+        let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+        let fn_value = self.module.add_function(&fn_name, fn_ty, linkage);
+        self.util_add_c_abi_fn_attributes(fn_value, ty_id);
+        self.util_apply_fn_attrs(fn_value, attrs);
 
         debug_assert!(!self.bindings.contains_key(&binding_id));
         self.bindings.insert(binding_id, fn_value.into());
@@ -26,14 +76,91 @@ impl CodeGenerator<'_> {
     pub fn gen_fn(&mut self, fn_ast: &FnAst) {
         let binding_id = self.resolve[fn_ast.item_id].as_binding_id().unwrap();
         let fn_value = self.bindings[&binding_id].into_function_value();
-        self.fn_stack.push(FnState::new(fn_value));
+        let fn_abi = self.tys.get_fn_abi(fn_ast.ty_id).cloned();
+        let ret_abi = fn_abi.as_ref().map_or(ArgAbi::Direct, |fn_abi| fn_abi.ret);
+
+        self.fn_stack.push(FnState::new(
+            fn_value,
+            fn_ast.frame_id,
+            fn_ast.ty_id.get_fn().ret,
+            ret_abi,
+        ));
 
         let entry_block = self.context.append_basic_block(fn_value, "entry");
         self.builder.position_at_end(entry_block);
 
-        let mut param_value_iter = fn_value.get_param_iter();
+        match fn_abi {
+            Some(fn_abi) => self.util_gen_c_fn_params(fn_value, &fn_abi, &fn_ast.binding_ids),
+            None => self.util_gen_fn_params(fn_value.get_param_iter(), &fn_ast.binding_ids),
+        }
+
+        let ret_value = self.gen_loaded_expr(&fn_ast.body);
+
+        if !self.builder.current_block_diverges() {
+            self.util_gen_return(ret_value);
+        }
+
+        self.util_fix_unaligned_accesses(fn_value);
+        self.pass_manager.run_on(&fn_value);
+        self.fn_stack.pop();
+    }
+
+    /// Lowers the alignment of loads and stores through pointers to fields of packed structs,
+    /// including pointers derived from them, to 1.
+    pub(crate) fn util_fix_unaligned_accesses(&mut self, fn_value: FunctionValue<'a>) {
+        if self.unaligned_ptrs.is_empty() {
+            return;
+        }
+
+        let get_ptr_operand = |instruction: InstructionValue<'a>, index: u32| {
+            instruction
+                .get_operand(index)
+                .and_then(|operand| operand.left())
+                .filter(|operand| operand.is_pointer_value())
+                .map(|operand| operand.into_pointer_value())
+        };
 
-        for &binding_id in fn_ast.binding_ids.iter() {
+        for block in fn_value.get_basic_blocks() {
+            let mut next_instruction = block.get_first_instruction();
+
+            while let Some(instruction) = next_instruction {
+                next_instruction = instruction.get_next_instruction();
+
+                let ptr_operand_index = match instruction.get_opcode() {
+                    InstructionOpcode::GetElementPtr => {
+                        let is_unaligned = get_ptr_operand(instruction, 0)
+                            .is_some_and(|ptr| self.unaligned_ptrs.contains(&ptr));
+
+                        if is_unaligned {
+                            let ptr = instruction.as_any_value_enum().into_pointer_value();
+                            self.unaligned_ptrs.insert(ptr);
+                        }
+
+                        continue;
+                    }
+                    InstructionOpcode::Load => 0,
+                    InstructionOpcode::Store => 1,
+                    _ => continue,
+                };
+
+                let is_unaligned = get_ptr_operand(instruction, ptr_operand_index)
+                    .is_some_and(|ptr| self.unaligned_ptrs.contains(&ptr));
+
+                if is_unaligned {
+                    instruction.set_alignment(1).unwrap();
+                }
+            }
+        }
+    }
+
+    pub(crate) fn util_gen_fn_params<P>(
+        &mut self,
+        mut param_value_iter: P,
+        binding_ids: &[BindingId],
+    ) where
+        P: Iterator<Item = BasicValueEnum<'a>>,
+    {
+        for &binding_id in binding_ids.iter() {
             let param = self.resolve[binding_id];
 
             let param_value = if self.resolve.is_ty_zero_sized(param.ty_id) {
@@ -47,15 +174,209 @@ impl CodeGenerator<'_> {
             debug_assert!(!self.bindings.contains_key(&binding_id));
             self.bindings.insert(binding_id, param_value);
         }
+    }
 
-        let ret_value = self.gen_loaded_expr(&fn_ast.body);
-        let ret_value = ret_value.as_ref().map(|value| value as &dyn BasicValue);
+    fn util_gen_c_fn_params(
+        &mut self,
+        fn_value: FunctionValue<'a>,
+        fn_abi: &FnAbiInfo<'a>,
+        binding_ids: &[BindingId],
+    ) {
+        let mut param_value_iter = fn_value.get_param_iter();
 
-        if !self.builder.current_block_diverges() {
-            self.builder.build_return(ret_value);
+        if fn_abi.has_sret() {
+            let sret_ptr = param_value_iter.next().unwrap().into_pointer_value();
+            self.fn_state_mut().sret_ptr = Some(sret_ptr);
         }
 
-        self.pass_manager.run_on(&fn_value);
-        self.fn_stack.pop();
+        for (&binding_id, param_abi) in binding_ids.iter().zip(fn_abi.params.iter()) {
+            let param = self.resolve[binding_id];
+
+            let param_value = match param_abi {
+                ArgAbi::Ignore => Value::Void,
+                ArgAbi::Direct => {
+                    let value = param_value_iter.next().unwrap();
+                    Value::Memory(self.util_gen_named_init(value, param.symbol.as_str()))
+                }
+                ArgAbi::Coerce(_) => {
+                    let value = param_value_iter.next().unwrap();
+                    Value::Memory(self.util_gen_uncoerced_ptr(value, param.ty_id))
+                }
+                ArgAbi::Indirect { .. } => {
+                    // The caller passes a copy that the callee is free to modify
+                    Value::Memory(param_value_iter.next().unwrap().into_pointer_value())
+                }
+            };
+
+            debug_assert!(!self.bindings.contains_key(&binding_id));
+            self.bindings.insert(binding_id, param_value);
+        }
+    }
+
+    pub(crate) fn util_gen_return(&mut self, value: LoadedValue<'a>) {
+        let ret_ty_id = self.fn_state().ret_ty_id;
+
+        match self.fn_state().ret_abi {
+            ArgAbi::Coerce(coerced_ty) => {
+                let value = self.util_gen_coerced_value(value.unwrap(), ret_ty_id, coerced_ty);
+                self.builder.build_return(Some(&value));
+            }
+            ArgAbi::Indirect { .. } => {
+                let sret_ptr = self.fn_state().sret_ptr.unwrap();
+                self.builder.build_store(sret_ptr, value.unwrap());
+                self.builder.build_return(None);
+            }
+            ArgAbi::Ignore | ArgAbi::Direct => {
+                let value = value.as_ref().map(|value| value as &dyn BasicValue);
+                self.builder.build_return(value);
+            }
+        }
+    }
+
+    /// Reinterprets the memory of `value` as `coerced_ty`.
+    pub(crate) fn util_gen_coerced_value(
+        &mut self,
+        value: BasicValueEnum<'a>,
+        ty_id: TyId,
+        coerced_ty: BasicTypeEnum<'a>,
+    ) -> BasicValueEnum<'a> {
+        let value_ptr = self.util_gen_init(value);
+        let coerced_ptr = self.util_gen_coercion_slot(coerced_ty);
+        self.util_gen_coercion_copy(coerced_ptr, value_ptr, ty_id);
+        self.builder.build_load(coerced_ty, coerced_ptr, "")
+    }
+
+    /// Stores a value received as a coerced type to memory of type `ty_id`.
+    pub(crate) fn util_gen_uncoerced_ptr(
+        &mut self,
+        coerced_value: BasicValueEnum<'a>,
+        ty_id: TyId,
+    ) -> PointerValue<'a> {
+        let coerced_ptr = self.util_gen_coercion_slot(coerced_value.get_type());
+        self.builder.build_store(coerced_ptr, coerced_value);
+
+        let ptr = self.util_gen_alloca(self.tys[ty_id].unwrap());
+        self.util_gen_coercion_copy(ptr, coerced_ptr, ty_id);
+        ptr
+    }
+
+    fn util_gen_coercion_slot(&mut self, coerced_ty: BasicTypeEnum<'a>) -> PointerValue<'a> {
+        let ptr = self.util_gen_alloca(coerced_ty);
+
+        ptr.as_instruction_value()
+            .unwrap()
+            .set_alignment(COERCION_SLOT_ALIGN)
+            .unwrap();
+
+        ptr
+    }
+
+    /// Copies only the bytes of `ty_id`, as the coerced type can be wider.
+    fn util_gen_coercion_copy(
+        &mut self,
+        dst: PointerValue<'a>,
+        src: PointerValue<'a>,
+        ty_id: TyId,
+    ) {
+        let ty_def = self.resolve.get_ty_def(ty_id).unwrap();
+        let align = ty_def.align as u32;
+        let size = self.tys.isize_ty().const_int(ty_def.size, false);
+
+        self.builder
+            .build_memcpy(dst, align, src, align, size)
+            .unwrap();
+    }
+
+    fn util_apply_fn_attrs(&self, fn_value: FunctionValue<'a>, attrs: &FnAttrs) {
+        let mut fn_attributes = SmallVec::<[&str; 2]>::new();
+
+        match attrs.inline {
+            Some(InlineAttr::Always) => fn_attributes.push("alwaysinline"),
+            Some(InlineAttr::Never) => fn_attributes.push("noinline"),
+            None => (),
+        }
+
+        if attrs.is_cold {
+            fn_attributes.push("cold");
+        }
+
+        for name in fn_attributes {
+            fn_value.add_attribute(AttributeLoc::Function, self.mk_enum_attribute(name, 0));
+        }
+
+        if let Some(section) = attrs.section {
+            fn_value.set_section(Some(section.as_str()));
+        }
+
+        if let Some(call_conv) = attrs.call_conv {
+            let call_conv_id = match call_conv {
+                CallConv::C => LLVM_CALL_CONV_C,
+                CallConv::Fast => LLVM_CALL_CONV_FAST,
+                CallConv::Cold => LLVM_CALL_CONV_COLD,
+            };
+
+            fn_value.set_call_conventions(call_conv_id);
+        }
+    }
+
+    fn util_add_c_abi_fn_attributes(&self, fn_value: FunctionValue<'a>, fn_ty_id: TyId) {
+        for (loc, attribute) in self.util_get_c_abi_attributes(fn_ty_id) {
+            fn_value.add_attribute(loc, attribute);
+        }
+    }
+
+    /// Returns the `sret` and `byval` attributes of the parameters of a lowered C function.
+    /// They must be present on both the declaration and each call site.
+    pub(crate) fn util_get_c_abi_attributes(
+        &self,
+        fn_ty_id: TyId,
+    ) -> SmallVec<[(AttributeLoc, Attribute); 4]> {
+        let mut attributes = SmallVec::new();
+
+        let Some(fn_abi) = self.tys.get_fn_abi(fn_ty_id) else {
+            return attributes;
+        };
+
+        let fn_ty = fn_ty_id.get_fn();
+        let mut param_index = 0;
+
+        if fn_abi.has_sret() {
+            let ret_ty = self.tys[fn_ty.ret].unwrap();
+            attributes.push((
+                AttributeLoc::Param(0),
+                self.mk_type_attribute("sret", ret_ty),
+            ));
+            param_index += 1;
+        }
+
+        for (&param_ty_id, param_abi) in fn_ty.params.iter().zip(fn_abi.params.iter()) {
+            match param_abi {
+                ArgAbi::Ignore => continue,
+                ArgAbi::Indirect { byval: true } => {
+                    let param_ty = self.tys[param_ty_id].unwrap();
+                    let align = self.resolve.get_ty_def(param_ty_id).unwrap().align.max(8);
+                    let loc = AttributeLoc::Param(param_index);
+
+                    attributes.push((loc, self.mk_type_attribute("byval", param_ty)));
+                    attributes.push((loc, self.mk_enum_attribute("align", align)));
+                }
+                _ => (),
+            }
+
+            param_index += 1;
+        }
+
+        attributes
+    }
+
+    fn mk_type_attribute(&self, name: &str, ty: BasicTypeEnum<'a>) -> Attribute {
+        let kind_id = Attribute::get_named_enum_kind_id(name);
+        self.context
+            .create_type_attribute(kind_id, ty.as_any_type_enum())
+    }
+
+    fn mk_enum_attribute(&self, name: &str, value: u64) -> Attribute {
+        let kind_id = Attribute::get_named_enum_kind_id(name);
+        self.context.create_enum_attribute(kind_id, value)
     }
 }
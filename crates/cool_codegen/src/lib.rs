@@ -2,7 +2,9 @@ mod builder_ext;
 mod expr;
 mod fn_state;
 mod function;
+mod runtime_check;
 mod stmt;
+mod test_harness;
 mod ty;
 mod utils;
 mod value;
@@ -16,20 +18,29 @@ pub use self::ty::*;
 pub use self::utils::*;
 pub use self::value::*;
 use cool_ast::PackageAst;
-use cool_resolve::{BindingId, FrameId, ResolveContext};
+use cool_resolve::{BindingId, FrameId, ItemId, ResolveContext};
+use cool_span::SourceLocator;
 use inkwell::basic_block::BasicBlock;
 use inkwell::builder::Builder;
 use inkwell::context::Context;
 use inkwell::module::Module;
 use inkwell::passes::PassManager;
 use inkwell::targets::{InitializationConfig, Target, TargetData, TargetTriple};
-use inkwell::values::{FunctionValue, InstructionValue, IntValue};
+use inkwell::values::{FunctionValue, GlobalValue, InstructionValue, IntValue, PointerValue};
 use rustc_hash::{FxHashMap, FxHashSet};
 
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CodeGeneratorOptions {
+    pub overflow_checks: bool,
+    pub test_harness: bool,
+}
+
 pub struct CodeGenerator<'a> {
     context: &'a Context,
     package: &'a PackageAst,
     resolve: &'a ResolveContext,
+    source_locator: &'a dyn SourceLocator,
+    options: CodeGeneratorOptions,
     llvm_true: IntValue<'a>,
     llvm_false: IntValue<'a>,
     tys: GeneratedTys<'a>,
@@ -39,6 +50,8 @@ pub struct CodeGenerator<'a> {
     builder: Builder<'a>,
     fn_stack: Vec<FnState<'a>>,
     visited_defers: FxHashSet<FrameId>,
+    vtables: FxHashMap<(ItemId, ItemId), GlobalValue<'a>>,
+    unaligned_ptrs: FxHashSet<PointerValue<'a>>,
 }
 
 impl<'a> CodeGenerator<'a> {
@@ -57,14 +70,17 @@ impl<'a> CodeGenerator<'a> {
         context
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         context: &'a Context,
         target_triple: &TargetTriple,
         target_data: &TargetData,
         package: &'a PackageAst,
         resolve: &'a ResolveContext,
+        source_locator: &'a dyn SourceLocator,
         crate_name: &str,
         crate_root_file: &str,
+        options: CodeGeneratorOptions,
     ) -> Self {
         let llvm_true = context.i8_type().const_int(1, false);
         let llvm_false = context.i8_type().const_int(0, false);
@@ -82,12 +98,14 @@ impl<'a> CodeGenerator<'a> {
         pass_manager.initialize();
 
         let builder = context.create_builder();
-        let tys = GeneratedTys::new(context, target_data, resolve);
+        let tys = GeneratedTys::new(context, target_triple, target_data, resolve);
 
         Self {
             context,
             package,
             resolve,
+            source_locator,
+            options,
             llvm_true,
             llvm_false,
             tys,
@@ -97,6 +115,8 @@ impl<'a> CodeGenerator<'a> {
             builder,
             fn_stack: Default::default(),
             visited_defers: Default::default(),
+            vtables: Default::default(),
+            unaligned_ptrs: Default::default(),
         }
     }
 
@@ -105,6 +125,10 @@ impl<'a> CodeGenerator<'a> {
             self.add_extern_fn(extern_fn_ast);
         }
 
+        for fn_decl in self.package.declared_fns.iter() {
+            self.add_fn_decl(fn_decl);
+        }
+
         for fn_ast in self.package.fns.iter() {
             self.add_fn(fn_ast);
         }
@@ -113,6 +137,10 @@ impl<'a> CodeGenerator<'a> {
             self.gen_fn(fn_ast);
         }
 
+        if self.options.test_harness {
+            self.gen_test_harness();
+        }
+
         self.module
     }
 
@@ -134,6 +162,7 @@ impl<'a> CodeGenerator<'a> {
     }
 
     pub fn gen_return_defers(&mut self, return_frame_id: FrameId) {
+        let fn_frame_id = self.fn_state().frame_id;
         let mut current_frame_id = return_frame_id;
 
         loop {
@@ -144,6 +173,10 @@ impl<'a> CodeGenerator<'a> {
                 }
             }
 
+            if current_frame_id == fn_frame_id {
+                break;
+            }
+
             current_frame_id = match self.resolve.get_parent_frame(current_frame_id) {
                 Some(frame_id) => frame_id,
                 None => break,
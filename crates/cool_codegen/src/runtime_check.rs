@@ -0,0 +1,171 @@
+use crate::CodeGenerator;
+use cool_span::Span;
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::module::Linkage;
+use inkwell::types::FunctionType;
+use inkwell::values::{BasicMetadataValueEnum, FunctionValue, IntValue, PointerValue};
+
+const PANIC_HOOK: &str = "cool_panic";
+
+impl<'a> CodeGenerator<'a> {
+    /// Reports `message` at the location of `span` and aborts if `failed` is true.
+    pub(crate) fn util_gen_runtime_check(
+        &mut self,
+        failed: IntValue<'a>,
+        span: Span,
+        message: &str,
+    ) {
+        let error_block = self.append_block_after_current_block();
+        let continue_block = self.append_block_after(error_block);
+
+        self.builder
+            .build_conditional_branch(failed, error_block, continue_block);
+
+        self.builder.position_at_end(error_block);
+        self.util_gen_runtime_error(span, message);
+        self.builder.position_at_end(continue_block);
+    }
+
+    /// Reports `message` at the location of `span` and aborts.
+    pub(crate) fn util_gen_runtime_error(&mut self, span: Span, message: &str) {
+        let message_ptr = self
+            .builder
+            .build_global_string_ptr(message, "")
+            .as_pointer_value();
+
+        self.util_gen_panic(span, message_ptr);
+    }
+
+    /// Calls the panic hook with the location of `span` and `message`, a C string. The default
+    /// hook writes `file:line:column: message` to the standard error and aborts. A hook provided
+    /// by the package may return, in which case the program is aborted after the call.
+    pub(crate) fn util_gen_panic(&mut self, span: Span, message: PointerValue<'a>) {
+        let (path, position) = self.source_locator.locate(span.start);
+        let path = path.display().to_string();
+
+        let path_ptr = self
+            .builder
+            .build_global_string_ptr(&path, "")
+            .as_pointer_value();
+
+        let i32_ty = self.context.i32_type();
+        let line = i32_ty.const_int(position.line as u64, false);
+        let column = i32_ty.const_int(position.column as u64, false);
+
+        let panic_hook = self.get_or_add_panic_hook();
+
+        self.builder.build_call(
+            panic_hook,
+            &[path_ptr.into(), line.into(), column.into(), message.into()],
+            "",
+        );
+
+        let abort_fn = self.get_or_add_abort_fn();
+        self.builder.build_call(abort_fn, &[], "");
+        self.builder.build_unreachable();
+    }
+
+    /// Returns the panic hook, adding a weak default definition if the package does not provide
+    /// one through `@link_name("cool_panic")`. The default hook of test builds jumps back to the
+    /// test harness instead of aborting. The hook is not marked `noreturn` because the weak
+    /// definition can be replaced by one that returns.
+    fn get_or_add_panic_hook(&self) -> FunctionValue<'a> {
+        if let Some(panic_hook) = self.module.get_function(PANIC_HOOK) {
+            return panic_hook;
+        }
+
+        let i32_ty = self.context.i32_type();
+        let i8_ptr_ty = self.tys.i8_ptr_ty();
+
+        let panic_hook = self.module.add_function(
+            PANIC_HOOK,
+            self.tys.void_ty().fn_type(
+                &[
+                    i8_ptr_ty.into(),
+                    i32_ty.into(),
+                    i32_ty.into(),
+                    i8_ptr_ty.into(),
+                ],
+                false,
+            ),
+            Some(Linkage::WeakAny),
+        );
+
+        let cold_kind_id = Attribute::get_named_enum_kind_id("cold");
+        let cold = self.context.create_enum_attribute(cold_kind_id, 0);
+        panic_hook.add_attribute(AttributeLoc::Function, cold);
+
+        let dprintf_fn = self.get_or_add_libc_fn(
+            "dprintf",
+            i32_ty.fn_type(&[i32_ty.into(), i8_ptr_ty.into()], true),
+        );
+
+        let builder = self.context.create_builder();
+        builder.position_at_end(self.context.append_basic_block(panic_hook, ""));
+
+        let format_ptr = builder
+            .build_global_string_ptr("%s:%u:%u: %s\n", "")
+            .as_pointer_value();
+
+        let stderr = i32_ty.const_int(2, false);
+        let args = [stderr.into(), format_ptr.into()]
+            .into_iter()
+            .chain(
+                panic_hook
+                    .get_param_iter()
+                    .map(BasicMetadataValueEnum::from),
+            )
+            .collect::<Vec<_>>();
+
+        builder.build_call(dprintf_fn, &args, "");
+
+        // Test builds return to the harness, which reports the test as failed
+        if self.options.test_harness {
+            let longjmp_fn = self.get_or_add_libc_fn(
+                "longjmp",
+                self.tys
+                    .void_ty()
+                    .fn_type(&[i8_ptr_ty.into(), i32_ty.into()], false),
+            );
+            let noreturn_kind_id = Attribute::get_named_enum_kind_id("noreturn");
+            let noreturn = self.context.create_enum_attribute(noreturn_kind_id, 0);
+            longjmp_fn.add_attribute(AttributeLoc::Function, noreturn);
+
+            let jmp_buf_ptr = self.get_or_add_test_jmp_buf().as_pointer_value();
+            let jmp_buf_ptr = builder.build_pointer_cast(jmp_buf_ptr, i8_ptr_ty, "");
+            let one = i32_ty.const_int(1, false);
+            builder.build_call(longjmp_fn, &[jmp_buf_ptr.into(), one.into()], "");
+        } else {
+            builder.build_call(self.get_or_add_abort_fn(), &[], "");
+        }
+
+        builder.build_unreachable();
+
+        panic_hook
+    }
+
+    fn get_or_add_abort_fn(&self) -> FunctionValue<'a> {
+        if let Some(abort_fn) = self.module.get_function("abort") {
+            return abort_fn;
+        }
+
+        let abort_fn =
+            self.module
+                .add_function("abort", self.tys.void_ty().fn_type(&[], false), None);
+
+        let noreturn_kind_id = Attribute::get_named_enum_kind_id("noreturn");
+        let noreturn = self.context.create_enum_attribute(noreturn_kind_id, 0);
+        abort_fn.add_attribute(AttributeLoc::Function, noreturn);
+        abort_fn
+    }
+
+    pub(crate) fn get_or_add_libc_fn(
+        &self,
+        name: &str,
+        fn_ty: FunctionType<'a>,
+    ) -> FunctionValue<'a> {
+        self.module
+            .get_function(name)
+            .unwrap_or_else(|| self.module.add_function(name, fn_ty, None))
+    }
+}
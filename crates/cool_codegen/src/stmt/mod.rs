@@ -78,8 +78,18 @@ impl<'a> CodeGenerator<'a> {
             }
         }
 
+        let explicit_align = self.tys.get_explicit_align(ty.as_basic_type_enum());
         let pointer = alloca_builder.build_alloca(ty, name);
-        self.update_last_alloca(pointer.as_instruction_value().unwrap());
+        let alloca = pointer.as_instruction_value().unwrap();
+
+        // Packed types do not carry the alignment of structs with an explicit layout
+        if let Some(align) = explicit_align {
+            if alloca.get_alignment().unwrap() < align {
+                alloca.set_alignment(align).unwrap();
+            }
+        }
+
+        self.update_last_alloca(alloca);
         pointer
     }
 
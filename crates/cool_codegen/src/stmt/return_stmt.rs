@@ -1,6 +1,5 @@
 use crate::{BuilderExt, CodeGenerator, Value};
 use cool_ast::ReturnStmtAst;
-use inkwell::values::BasicValue;
 
 impl<'a> CodeGenerator<'a> {
     pub fn gen_return_stmt(&mut self, stmt: &ReturnStmtAst) -> Value<'a> {
@@ -11,8 +10,7 @@ impl<'a> CodeGenerator<'a> {
             return Value::Void;
         }
 
-        let value = value.as_ref().map(|value| value as &dyn BasicValue);
-        self.builder.build_return(value);
+        self.util_gen_return(value);
         Value::Void
     }
 }
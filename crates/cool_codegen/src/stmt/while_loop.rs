@@ -9,14 +9,8 @@ impl<'a> CodeGenerator<'a> {
         // Condition
         self.builder.position_at_end(cond_block);
 
-        let cond_value = {
-            let cond_value = self.gen_loaded_expr(&stmt.block.cond);
-            if self.builder.current_block_diverges() {
-                return;
-            }
-
-            let cond_value = cond_value.unwrap().into_int_value();
-            self.builder.build_bool(cond_value)
+        let Some((cond_value, optional_ptr)) = self.util_gen_cond(&stmt.block) else {
+            return;
         };
 
         let body_block = self.append_block_after(cond_block);
@@ -28,6 +22,7 @@ impl<'a> CodeGenerator<'a> {
         let first_frame_id = stmt.block.expr.as_block().unwrap().first_frame_id;
 
         self.builder.position_at_end(body_block);
+        self.util_gen_cond_binding(&stmt.block, optional_ptr);
         self.push_jump_block(JumpBlock {
             first_frame_id,
             break_block: end_block,
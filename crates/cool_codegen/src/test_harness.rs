@@ -0,0 +1,287 @@
+use crate::CodeGenerator;
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::module::Linkage;
+use inkwell::values::{FloatValue, GlobalValue, PointerValue};
+use inkwell::{AddressSpace, IntPredicate};
+
+const TEST_JMP_BUF: &str = "cool_test_jmp_buf";
+
+/// Size and alignment reserved for a `jmp_buf`, which is 200 bytes wide on x86_64 glibc.
+const JMP_BUF_SIZE: u32 = 256;
+const JMP_BUF_ALIGN: u32 = 16;
+
+// `CLOCK_MONOTONIC` from `time.h`
+const CLOCK_MONOTONIC: u64 = 1;
+
+impl<'a> CodeGenerator<'a> {
+    /// Adds a `main` function that runs every test of the package and prints a summary. Tests
+    /// run after setting a jump point that the panic hook returns to, so a failed test does not
+    /// stop the ones after it. If a program argument is given, only the tests whose name contains
+    /// it are run.
+    pub(crate) fn gen_test_harness(&mut self) {
+        // The program's own entry point is not used by test builds
+        if let Some(program_main) = self.module.get_function("main") {
+            program_main.as_global_value().set_name("cool_program_main");
+            program_main.set_linkage(Linkage::Internal);
+        }
+
+        let i32_ty = self.context.i32_type();
+        let i64_ty = self.context.i64_type();
+        let i8_ptr_ty = self.tys.i8_ptr_ty();
+        let timespec_ty = self
+            .context
+            .struct_type(&[i64_ty.into(), i64_ty.into()], false);
+
+        let main_fn = self.module.add_function(
+            "main",
+            i32_ty.fn_type(
+                &[
+                    i32_ty.into(),
+                    i8_ptr_ty.ptr_type(AddressSpace::default()).into(),
+                ],
+                false,
+            ),
+            None,
+        );
+
+        let printf_fn =
+            self.get_or_add_libc_fn("printf", i32_ty.fn_type(&[i8_ptr_ty.into()], true));
+        let fflush_fn =
+            self.get_or_add_libc_fn("fflush", i32_ty.fn_type(&[i8_ptr_ty.into()], false));
+        let strstr_fn = self.get_or_add_libc_fn(
+            "strstr",
+            i8_ptr_ty.fn_type(&[i8_ptr_ty.into(), i8_ptr_ty.into()], false),
+        );
+        let clock_gettime_fn = self.get_or_add_libc_fn(
+            "clock_gettime",
+            i32_ty.fn_type(
+                &[
+                    i32_ty.into(),
+                    timespec_ty.ptr_type(AddressSpace::default()).into(),
+                ],
+                false,
+            ),
+        );
+        let setjmp_fn =
+            self.get_or_add_libc_fn("_setjmp", i32_ty.fn_type(&[i8_ptr_ty.into()], false));
+
+        let returns_twice_kind_id = Attribute::get_named_enum_kind_id("returns_twice");
+        let returns_twice = self.context.create_enum_attribute(returns_twice_kind_id, 0);
+        setjmp_fn.add_attribute(AttributeLoc::Function, returns_twice);
+
+        let entry_block = self.context.append_basic_block(main_fn, "entry");
+        self.builder.position_at_end(entry_block);
+
+        // Arguments are followed by a null pointer, so the filter is null if there is none
+        let argv = main_fn.get_nth_param(1).unwrap().into_pointer_value();
+        let filter_ptr = unsafe {
+            self.builder
+                .build_gep(i8_ptr_ty, argv, &[i32_ty.const_int(1, false)], "")
+        };
+        let filter = self
+            .builder
+            .build_load(i8_ptr_ty, filter_ptr, "")
+            .into_pointer_value();
+
+        let passed_ptr = self.builder.build_alloca(i32_ty, "");
+        let failed_ptr = self.builder.build_alloca(i32_ty, "");
+        let filtered_ptr = self.builder.build_alloca(i32_ty, "");
+        let start_ptr = self.builder.build_alloca(timespec_ty, "");
+        let end_ptr = self.builder.build_alloca(timespec_ty, "");
+
+        for counter_ptr in [passed_ptr, failed_ptr, filtered_ptr] {
+            self.builder.build_store(counter_ptr, i32_ty.const_zero());
+        }
+
+        let jmp_buf_ptr = self.get_or_add_test_jmp_buf().as_pointer_value();
+        let jmp_buf_ptr = self.builder.build_pointer_cast(jmp_buf_ptr, i8_ptr_ty, "");
+        let clock_id = i32_ty.const_int(CLOCK_MONOTONIC, false);
+
+        let run_format = self.util_gen_c_str("test %s ... ");
+        let ok_format = self.util_gen_c_str("ok (%.3f ms)\n");
+        let failed_format = self.util_gen_c_str("FAILED (%.3f ms)\n");
+
+        for &item_id in self.package.tests.iter() {
+            let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+            let test_fn = self.bindings[&binding_id].into_function_value();
+            let test_name = self.util_gen_c_str(&item_id.to_string());
+
+            let check_name_block = self.append_block_after_current_block();
+            let run_block = self.append_block_after(check_name_block);
+            let passed_block = self.append_block_after(run_block);
+            let failed_block = self.append_block_after(passed_block);
+            let skip_block = self.append_block_after(failed_block);
+            let next_block = self.append_block_after(skip_block);
+
+            let has_filter = self.builder.build_is_not_null(filter, "");
+            self.builder
+                .build_conditional_branch(has_filter, check_name_block, run_block);
+
+            self.builder.position_at_end(check_name_block);
+            let found = self
+                .builder
+                .build_call(strstr_fn, &[test_name.into(), filter.into()], "")
+                .try_as_basic_value()
+                .unwrap_left()
+                .into_pointer_value();
+            let matches = self.builder.build_is_not_null(found, "");
+            self.builder
+                .build_conditional_branch(matches, run_block, skip_block);
+
+            self.builder.position_at_end(run_block);
+            self.builder
+                .build_call(printf_fn, &[run_format.into(), test_name.into()], "");
+            self.builder
+                .build_call(fflush_fn, &[i8_ptr_ty.const_null().into()], "");
+            self.builder
+                .build_call(clock_gettime_fn, &[clock_id.into(), start_ptr.into()], "");
+            let jump_value = self
+                .builder
+                .build_call(setjmp_fn, &[jmp_buf_ptr.into()], "")
+                .try_as_basic_value()
+                .unwrap_left()
+                .into_int_value();
+            let returned_from_hook = self.builder.build_int_compare(
+                IntPredicate::NE,
+                jump_value,
+                i32_ty.const_zero(),
+                "",
+            );
+            self.builder
+                .build_conditional_branch(returned_from_hook, failed_block, passed_block);
+
+            self.builder.position_at_end(passed_block);
+            let call = self.builder.build_call(test_fn, &[], "");
+            call.set_call_convention(test_fn.get_call_conventions());
+
+            for (block, format, counter_ptr) in [
+                (passed_block, ok_format, passed_ptr),
+                (failed_block, failed_format, failed_ptr),
+            ] {
+                self.builder.position_at_end(block);
+                self.builder
+                    .build_call(clock_gettime_fn, &[clock_id.into(), end_ptr.into()], "");
+                let elapsed_ms = self.util_gen_elapsed_ms(start_ptr, end_ptr);
+                self.builder
+                    .build_call(printf_fn, &[format.into(), elapsed_ms.into()], "");
+                self.util_gen_increment(counter_ptr);
+                self.builder.build_unconditional_branch(next_block);
+            }
+
+            self.builder.position_at_end(skip_block);
+            self.util_gen_increment(filtered_ptr);
+            self.builder.build_unconditional_branch(next_block);
+
+            self.builder.position_at_end(next_block);
+        }
+
+        let passed = self.builder.build_load(i32_ty, passed_ptr, "");
+        let failed = self
+            .builder
+            .build_load(i32_ty, failed_ptr, "")
+            .into_int_value();
+        let filtered = self.builder.build_load(i32_ty, filtered_ptr, "");
+
+        let has_failed =
+            self.builder
+                .build_int_compare(IntPredicate::NE, failed, i32_ty.const_zero(), "");
+        let result = self.builder.build_select(
+            has_failed,
+            self.util_gen_c_str("FAILED"),
+            self.util_gen_c_str("ok"),
+            "",
+        );
+
+        let summary_format =
+            self.util_gen_c_str("\ntest result: %s. %u passed; %u failed; %u filtered out\n");
+
+        self.builder.build_call(
+            printf_fn,
+            &[
+                summary_format.into(),
+                result.into(),
+                passed.into(),
+                failed.into(),
+                filtered.into(),
+            ],
+            "",
+        );
+
+        let exit_code = self.builder.build_int_z_extend(has_failed, i32_ty, "");
+        self.builder.build_return(Some(&exit_code));
+    }
+
+    /// Returns the buffer that the panic hook of test builds jumps back to.
+    pub(crate) fn get_or_add_test_jmp_buf(&self) -> GlobalValue<'a> {
+        if let Some(jmp_buf) = self.module.get_global(TEST_JMP_BUF) {
+            return jmp_buf;
+        }
+
+        let jmp_buf_ty = self.context.i8_type().array_type(JMP_BUF_SIZE);
+        let jmp_buf = self.module.add_global(jmp_buf_ty, None, TEST_JMP_BUF);
+        jmp_buf.set_linkage(Linkage::Internal);
+        jmp_buf.set_alignment(JMP_BUF_ALIGN);
+        jmp_buf.set_initializer(&jmp_buf_ty.const_zero());
+        jmp_buf
+    }
+
+    fn util_gen_c_str(&self, value: &str) -> PointerValue<'a> {
+        self.builder
+            .build_global_string_ptr(value, "")
+            .as_pointer_value()
+    }
+
+    fn util_gen_increment(&self, counter_ptr: PointerValue<'a>) {
+        let i32_ty = self.context.i32_type();
+        let counter = self
+            .builder
+            .build_load(i32_ty, counter_ptr, "")
+            .into_int_value();
+        let counter = self
+            .builder
+            .build_int_add(counter, i32_ty.const_int(1, false), "");
+        self.builder.build_store(counter_ptr, counter);
+    }
+
+    /// Returns the milliseconds between two `timespec` values as a double.
+    fn util_gen_elapsed_ms(
+        &self,
+        start_ptr: PointerValue<'a>,
+        end_ptr: PointerValue<'a>,
+    ) -> FloatValue<'a> {
+        let i64_ty = self.context.i64_type();
+        let f64_ty = self.context.f64_type();
+        let timespec_ty = self
+            .context
+            .struct_type(&[i64_ty.into(), i64_ty.into()], false);
+
+        let load_field = |ptr, index| {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(timespec_ty, ptr, index, "")
+                .unwrap();
+
+            self.builder
+                .build_load(i64_ty, field_ptr, "")
+                .into_int_value()
+        };
+
+        let secs = self
+            .builder
+            .build_int_sub(load_field(end_ptr, 0), load_field(start_ptr, 0), "");
+        let nanos =
+            self.builder
+                .build_int_sub(load_field(end_ptr, 1), load_field(start_ptr, 1), "");
+
+        let secs = self.builder.build_signed_int_to_float(secs, f64_ty, "");
+        let nanos = self.builder.build_signed_int_to_float(nanos, f64_ty, "");
+        let secs_ms = self
+            .builder
+            .build_float_mul(secs, f64_ty.const_float(1e3), "");
+        let nanos_ms = self
+            .builder
+            .build_float_div(nanos, f64_ty.const_float(1e6), "");
+
+        self.builder.build_float_add(secs_ms, nanos_ms, "")
+    }
+}
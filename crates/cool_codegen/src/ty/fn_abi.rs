@@ -0,0 +1,626 @@
+use cool_resolve::{FloatTy, FnTy, ResolveContext, TaggedUnionKind, TyId, ValueTy};
+use inkwell::context::Context;
+use inkwell::targets::TargetTriple;
+use inkwell::types::{BasicType, BasicTypeEnum};
+use smallvec::SmallVec;
+
+/// Calling conventions for which values passed to and returned from C functions are lowered.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AbiTarget {
+    /// x86-64 System V, used by Linux, macOS and the BSDs.
+    SysV64,
+    /// AArch64 AAPCS64.
+    AArch64,
+    /// Aggregates are passed as LLVM values.
+    Other,
+}
+
+impl AbiTarget {
+    pub fn from_triple(target_triple: &TargetTriple) -> Self {
+        let triple = target_triple.as_str().to_string_lossy();
+        let arch = triple.split('-').next().unwrap_or_default();
+
+        match arch {
+            "x86_64" if !triple.contains("windows") => Self::SysV64,
+            "aarch64" | "arm64" => Self::AArch64,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// How a parameter or the return value of a C function is passed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArgAbi<'a> {
+    /// Zero-sized values are not passed.
+    Ignore,
+    /// Passed as its own LLVM type.
+    Direct,
+    /// Passed in registers as a value of a different type with the same memory contents.
+    Coerce(BasicTypeEnum<'a>),
+    /// Passed as a pointer to a copy made by the caller, marked `byval` if the copy lives in
+    /// the argument area of the stack. Return values are written through a `sret` pointer.
+    Indirect { byval: bool },
+}
+
+impl ArgAbi<'_> {
+    #[inline]
+    pub fn is_indirect(&self) -> bool {
+        matches!(self, Self::Indirect { .. })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FnAbiInfo<'a> {
+    pub ret: ArgAbi<'a>,
+    pub params: SmallVec<[ArgAbi<'a>; 4]>,
+}
+
+impl<'a> FnAbiInfo<'a> {
+    /// Classifies the parameters and the return value of a C function. Returns `None` if the
+    /// target has no lowering rules, in which case the function type is used as is.
+    pub fn new(
+        context: &'a Context,
+        resolve: &ResolveContext,
+        target: AbiTarget,
+        fn_ty: &FnTy,
+    ) -> Option<Self> {
+        let classifier = AbiClassifier { context, resolve };
+
+        let fn_abi = match target {
+            AbiTarget::SysV64 => classifier.classify_sysv64(fn_ty),
+            AbiTarget::AArch64 => classifier.classify_aarch64(fn_ty),
+            AbiTarget::Other => return None,
+        };
+
+        Some(fn_abi)
+    }
+
+    #[inline]
+    pub fn has_sret(&self) -> bool {
+        self.ret.is_indirect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ScalarKind {
+    Int,
+    F32,
+    F64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Scalar {
+    offset: u64,
+    size: u64,
+    kind: ScalarKind,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum RegClass {
+    Int,
+    Sse,
+}
+
+struct AbiClassifier<'a, 'r> {
+    context: &'a Context,
+    resolve: &'r ResolveContext,
+}
+
+impl<'a> AbiClassifier<'a, '_> {
+    fn classify_sysv64(&self, fn_ty: &FnTy) -> FnAbiInfo<'a> {
+        let ret = match self.classify_sysv64_value(fn_ty.ret) {
+            SysV64Value::Ignore => ArgAbi::Ignore,
+            SysV64Value::Scalar { .. } => ArgAbi::Direct,
+            SysV64Value::Registers { ty, .. } => ArgAbi::Coerce(ty),
+            SysV64Value::Memory => ArgAbi::Indirect { byval: false },
+        };
+
+        let mut free_int_regs: u32 = if ret.is_indirect() { 5 } else { 6 };
+        let mut free_sse_regs: u32 = 8;
+
+        let params = fn_ty
+            .params
+            .iter()
+            .map(|&param| {
+                match self.classify_sysv64_value(param) {
+                    SysV64Value::Ignore => ArgAbi::Ignore,
+                    SysV64Value::Scalar { int_regs, sse_regs } => {
+                        free_int_regs = free_int_regs.saturating_sub(int_regs);
+                        free_sse_regs = free_sse_regs.saturating_sub(sse_regs);
+                        ArgAbi::Direct
+                    }
+                    SysV64Value::Registers {
+                        ty,
+                        int_regs,
+                        sse_regs,
+                    } => {
+                        // Aggregates that do not fit in the remaining registers are passed
+                        // entirely on the stack
+                        if int_regs <= free_int_regs && sse_regs <= free_sse_regs {
+                            free_int_regs -= int_regs;
+                            free_sse_regs -= sse_regs;
+                            ArgAbi::Coerce(ty)
+                        } else {
+                            ArgAbi::Indirect { byval: true }
+                        }
+                    }
+                    SysV64Value::Memory => ArgAbi::Indirect { byval: true },
+                }
+            })
+            .collect();
+
+        FnAbiInfo { ret, params }
+    }
+
+    fn classify_sysv64_value(&self, ty_id: TyId) -> SysV64Value<'a> {
+        let ty_def = self.resolve.get_ty_def(ty_id).unwrap();
+
+        if ty_def.is_zero_sized() {
+            return SysV64Value::Ignore;
+        }
+
+        if !self.is_aggregate(ty_id) {
+            let (int_regs, sse_regs) = match ty_id.get_value() {
+                ValueTy::Float(_) => (0, 1),
+                _ => ((ty_def.size as u32).div_ceil(8), 0),
+            };
+
+            return SysV64Value::Scalar { int_regs, sse_regs };
+        }
+
+        if ty_def.size > 16 {
+            return SysV64Value::Memory;
+        }
+
+        let scalars = self.collect_scalars(ty_id);
+
+        // Unaligned fields force the aggregate into memory
+        if scalars
+            .iter()
+            .any(|scalar| scalar.offset % scalar.size.min(8) != 0)
+        {
+            return SysV64Value::Memory;
+        }
+
+        let eightbyte_count = ty_def.size.div_ceil(8);
+        let mut tys = SmallVec::<[BasicTypeEnum; 2]>::new();
+        let mut int_regs = 0;
+        let mut sse_regs = 0;
+
+        for i in 0..eightbyte_count {
+            let start = i * 8;
+            let end = start + 8;
+
+            let eightbyte_scalars = scalars
+                .iter()
+                .filter(|scalar| scalar.offset < end && scalar.offset + scalar.size > start)
+                .collect::<SmallVec<[_; 4]>>();
+
+            let class = if eightbyte_scalars.is_empty() {
+                // Trailing padding is not passed
+                if i + 1 == eightbyte_count {
+                    break;
+                }
+
+                RegClass::Int
+            } else if eightbyte_scalars
+                .iter()
+                .any(|scalar| scalar.kind == ScalarKind::Int)
+            {
+                RegClass::Int
+            } else {
+                RegClass::Sse
+            };
+
+            let ty = match class {
+                RegClass::Int => {
+                    int_regs += 1;
+                    let size = (ty_def.size - start).min(8);
+                    self.context
+                        .custom_width_int_type((size * 8) as u32)
+                        .as_basic_type_enum()
+                }
+                RegClass::Sse => {
+                    sse_regs += 1;
+                    self.mk_sse_eightbyte_ty(&eightbyte_scalars)
+                }
+            };
+
+            tys.push(ty);
+        }
+
+        let ty = match tys.as_slice() {
+            [ty] => *ty,
+            tys => self.context.struct_type(tys, false).as_basic_type_enum(),
+        };
+
+        SysV64Value::Registers {
+            ty,
+            int_regs,
+            sse_regs,
+        }
+    }
+
+    fn mk_sse_eightbyte_ty(&self, scalars: &[&Scalar]) -> BasicTypeEnum<'a> {
+        match scalars {
+            [scalar] if scalar.kind == ScalarKind::F32 => {
+                self.context.f32_type().as_basic_type_enum()
+            }
+            [_, _] => self.context.f32_type().vec_type(2).as_basic_type_enum(),
+            _ => self.context.f64_type().as_basic_type_enum(),
+        }
+    }
+
+    fn classify_aarch64(&self, fn_ty: &FnTy) -> FnAbiInfo<'a> {
+        let ret = self.classify_aarch64_value(fn_ty.ret, true);
+
+        let params = fn_ty
+            .params
+            .iter()
+            .map(|&param| self.classify_aarch64_value(param, false))
+            .collect();
+
+        FnAbiInfo { ret, params }
+    }
+
+    fn classify_aarch64_value(&self, ty_id: TyId, is_ret: bool) -> ArgAbi<'a> {
+        let ty_def = self.resolve.get_ty_def(ty_id).unwrap();
+
+        if ty_def.is_zero_sized() {
+            return ArgAbi::Ignore;
+        }
+
+        if !self.is_aggregate(ty_id) {
+            return ArgAbi::Direct;
+        }
+
+        let scalars = self.collect_scalars(ty_id);
+
+        if let Some(ty) = self.get_homogeneous_float_aggregate_ty(&scalars, ty_def.size) {
+            return ArgAbi::Coerce(ty);
+        }
+
+        if ty_def.size > 16 {
+            return ArgAbi::Indirect { byval: false };
+        }
+
+        let ty = if ty_def.align == 16 {
+            self.context.i128_type().as_basic_type_enum()
+        } else if ty_def.size > 8 {
+            self.context.i64_type().array_type(2).as_basic_type_enum()
+        } else if is_ret {
+            self.context
+                .custom_width_int_type((ty_def.size * 8) as u32)
+                .as_basic_type_enum()
+        } else {
+            self.context.i64_type().as_basic_type_enum()
+        };
+
+        ArgAbi::Coerce(ty)
+    }
+
+    /// Aggregates of up to four floats of the same type are passed in floating-point
+    /// registers.
+    fn get_homogeneous_float_aggregate_ty(
+        &self,
+        scalars: &[Scalar],
+        size: u64,
+    ) -> Option<BasicTypeEnum<'a>> {
+        let first = scalars.first()?;
+
+        let float_ty = match first.kind {
+            ScalarKind::F32 => self.context.f32_type(),
+            ScalarKind::F64 => self.context.f64_type(),
+            ScalarKind::Int => return None,
+        };
+
+        let is_homogeneous = scalars.len() <= 4
+            && scalars.len() as u64 * first.size == size
+            && scalars.iter().enumerate().all(|(i, scalar)| {
+                scalar.kind == first.kind && scalar.offset == i as u64 * first.size
+            });
+
+        is_homogeneous.then(|| {
+            float_ty
+                .array_type(scalars.len() as u32)
+                .as_basic_type_enum()
+        })
+    }
+
+    fn is_aggregate(&self, ty_id: TyId) -> bool {
+        matches!(
+            ty_id.get_value(),
+            ValueTy::Array(_) | ValueTy::Variant(_) | ValueTy::Union(_),
+        ) || self
+            .resolve
+            .get_ty_def(ty_id)
+            .unwrap()
+            .get_aggregate_fields()
+            .is_some()
+    }
+
+    /// Returns the scalars stored in a value, sorted by offset. The variants of a variant
+    /// type and the fields of a union overlap.
+    fn collect_scalars(&self, ty_id: TyId) -> SmallVec<[Scalar; 8]> {
+        let mut scalars = SmallVec::new();
+        self.collect_scalars_at(ty_id, 0, &mut scalars);
+        scalars.sort_by_key(|scalar| scalar.offset);
+        scalars
+    }
+
+    fn collect_scalars_at(&self, ty_id: TyId, offset: u64, scalars: &mut SmallVec<[Scalar; 8]>) {
+        let ty_def = self.resolve.get_ty_def(ty_id).unwrap();
+
+        if ty_def.is_zero_sized() {
+            return;
+        }
+
+        match ty_id.get_value() {
+            ValueTy::Float(float_ty) => {
+                let kind = match float_ty {
+                    FloatTy::F32 => ScalarKind::F32,
+                    FloatTy::F64 => ScalarKind::F64,
+                };
+
+                scalars.push(Scalar {
+                    offset,
+                    size: ty_def.size,
+                    kind,
+                });
+            }
+            ValueTy::Array(array_ty) => {
+                let elem_size = self.resolve.get_ty_def(array_ty.elem).unwrap().size;
+
+                for i in 0..array_ty.len {
+                    self.collect_scalars_at(array_ty.elem, offset + i * elem_size, scalars);
+                }
+            }
+            ValueTy::Variant(variant_ty) => {
+                let mut largest_size = 0;
+
+                for &variant in variant_ty.variants() {
+                    largest_size = largest_size.max(self.resolve.get_ty_def(variant).unwrap().size);
+                    self.collect_scalars_at(variant, offset, scalars);
+                }
+
+                let tagged_union_ty = ty_def.kind.as_tagged_union().unwrap();
+
+                if let TaggedUnionKind::Basic { .. } = tagged_union_ty.kind {
+                    scalars.push(Scalar {
+                        offset: offset + largest_size,
+                        size: 1,
+                        kind: ScalarKind::Int,
+                    });
+                }
+            }
+            ValueTy::Union(_) => {
+                let untagged_union_ty = ty_def.kind.as_untagged_union().unwrap();
+
+                for field in untagged_union_ty.fields() {
+                    self.collect_scalars_at(field.ty_id, offset, scalars);
+                }
+            }
+            _ => {
+                match ty_def.get_aggregate_fields() {
+                    Some(fields) => {
+                        for field in fields.iter() {
+                            self.collect_scalars_at(field.ty_id, offset + field.offset, scalars);
+                        }
+                    }
+                    None => {
+                        scalars.push(Scalar {
+                            offset,
+                            size: ty_def.size,
+                            kind: ScalarKind::Int,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+enum SysV64Value<'a> {
+    Ignore,
+    Scalar {
+        int_regs: u32,
+        sse_regs: u32,
+    },
+    Registers {
+        ty: BasicTypeEnum<'a>,
+        int_regs: u32,
+        sse_regs: u32,
+    },
+    Memory,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cool_lexer::SymbolTable;
+    use cool_resolve::{FnAbi, PrimitiveTyData};
+    use inkwell::types::BasicTypeEnum;
+
+    fn primitives() -> PrimitiveTyData {
+        PrimitiveTyData {
+            i8_align: 1,
+            i16_align: 2,
+            i32_align: 4,
+            i64_align: 8,
+            i128_align: 16,
+            ptr_size: 8,
+            ptr_align: 8,
+            f32_align: 4,
+            f64_align: 8,
+        }
+    }
+
+    /// Classifies a C function taking `params` and returning `ret`, built from the type
+    /// constants of a fresh context.
+    fn classify<F>(target: AbiTarget, f: F) -> (FnAbiInfo<'static>, &'static Context)
+    where
+        F: FnOnce(&mut ResolveContext) -> (Vec<TyId>, TyId),
+    {
+        let context: &'static Context = Box::leak(Box::new(Context::create()));
+
+        let fn_abi = SymbolTable::default().enter(|| {
+            let mut resolve = ResolveContext::new(primitives());
+            let (params, ret) = f(&mut resolve);
+            let fn_ty_id = resolve.mk_fn(FnAbi::C, params, false, ret);
+            FnAbiInfo::new(context, &resolve, target, fn_ty_id.get_fn()).unwrap()
+        });
+
+        (fn_abi, context)
+    }
+
+    fn struct_ty<'a>(context: &'a Context, fields: &[BasicTypeEnum<'a>]) -> BasicTypeEnum<'a> {
+        context.struct_type(fields, false).as_basic_type_enum()
+    }
+
+    #[test]
+    fn sysv64_small_int_and_float_aggregates_are_coerced() {
+        let (fn_abi, context) = classify(AbiTarget::SysV64, |resolve| {
+            let tys = *resolve.ty_consts();
+            let ints = resolve.mk_tuple([tys.i32, tys.i32]);
+            let floats = resolve.mk_tuple([tys.f32, tys.f32]);
+            let doubles = resolve.mk_tuple([tys.f64, tys.f64]);
+            (vec![ints, floats, doubles], ints)
+        });
+
+        let f64_ty = context.f64_type().as_basic_type_enum();
+
+        assert_eq!(fn_abi.ret, ArgAbi::Coerce(context.i64_type().into()));
+        assert_eq!(fn_abi.params[0], ArgAbi::Coerce(context.i64_type().into()));
+        assert_eq!(
+            fn_abi.params[1],
+            ArgAbi::Coerce(context.f32_type().vec_type(2).into()),
+        );
+        assert_eq!(
+            fn_abi.params[2],
+            ArgAbi::Coerce(struct_ty(context, &[f64_ty, f64_ty])),
+        );
+    }
+
+    #[test]
+    fn sysv64_mixed_eightbytes() {
+        let (fn_abi, context) = classify(AbiTarget::SysV64, |resolve| {
+            let tys = *resolve.ty_consts();
+            let int_float = resolve.mk_tuple([tys.i32, tys.f32]);
+            let double_int = resolve.mk_tuple([tys.f64, tys.i32]);
+            let mixed = resolve.mk_tuple([tys.i32, tys.f32, tys.f64]);
+            (vec![int_float, double_int, mixed], tys.unit)
+        });
+
+        let f64_ty = context.f64_type().as_basic_type_enum();
+
+        assert_eq!(fn_abi.ret, ArgAbi::Ignore);
+        assert_eq!(fn_abi.params[0], ArgAbi::Coerce(context.i64_type().into()));
+        let double_int_ty =
+            ArgAbi::Coerce(struct_ty(context, &[f64_ty, context.i64_type().into()]));
+
+        assert_eq!(fn_abi.params[1], double_int_ty);
+        assert_eq!(fn_abi.params[2], double_int_ty);
+    }
+
+    #[test]
+    fn sysv64_large_aggregates_are_indirect() {
+        let (fn_abi, _) = classify(AbiTarget::SysV64, |resolve| {
+            let tys = *resolve.ty_consts();
+            let large = resolve.mk_tuple([tys.i64, tys.i64, tys.i64]);
+            (vec![large], large)
+        });
+
+        assert_eq!(fn_abi.ret, ArgAbi::Indirect { byval: false });
+        assert_eq!(fn_abi.params[0], ArgAbi::Indirect { byval: true });
+        assert!(fn_abi.has_sret());
+    }
+
+    #[test]
+    fn sysv64_aggregates_past_the_registers_are_indirect() {
+        let (fn_abi, context) = classify(AbiTarget::SysV64, |resolve| {
+            let tys = *resolve.ty_consts();
+            let pair = resolve.mk_tuple([tys.i64, tys.i64]);
+            (vec![pair; 4], tys.unit)
+        });
+
+        let i64_ty = context.i64_type().as_basic_type_enum();
+        let pair_ty = ArgAbi::Coerce(struct_ty(context, &[i64_ty, i64_ty]));
+
+        assert_eq!(fn_abi.params[..3], [pair_ty; 3]);
+        assert_eq!(fn_abi.params[3], ArgAbi::Indirect { byval: true });
+    }
+
+    #[test]
+    fn sysv64_empty_aggregates_are_ignored() {
+        let (fn_abi, _) = classify(AbiTarget::SysV64, |resolve| {
+            let tys = *resolve.ty_consts();
+            let empty = resolve.mk_tuple([tys.unit, tys.unit]);
+            (vec![tys.unit, empty, tys.i32], empty)
+        });
+
+        assert_eq!(fn_abi.ret, ArgAbi::Ignore);
+        assert_eq!(
+            fn_abi.params.as_slice(),
+            [ArgAbi::Ignore, ArgAbi::Ignore, ArgAbi::Direct],
+        );
+    }
+
+    #[test]
+    fn aarch64_homogeneous_float_aggregates() {
+        let (fn_abi, context) = classify(AbiTarget::AArch64, |resolve| {
+            let tys = *resolve.ty_consts();
+            let floats = resolve.mk_tuple([tys.f32, tys.f32, tys.f32]);
+            let doubles = resolve.mk_tuple([tys.f64, tys.f64, tys.f64, tys.f64]);
+            let mixed = resolve.mk_tuple([tys.f32, tys.f64]);
+            (vec![floats, doubles, mixed], doubles)
+        });
+
+        let doubles_ty = context.f64_type().array_type(4).as_basic_type_enum();
+
+        assert_eq!(fn_abi.ret, ArgAbi::Coerce(doubles_ty));
+        assert_eq!(
+            fn_abi.params[0],
+            ArgAbi::Coerce(context.f32_type().array_type(3).into()),
+        );
+        assert_eq!(fn_abi.params[1], ArgAbi::Coerce(doubles_ty));
+        assert_eq!(
+            fn_abi.params[2],
+            ArgAbi::Coerce(context.i64_type().array_type(2).into()),
+        );
+    }
+
+    #[test]
+    fn aarch64_int_aggregates() {
+        let (fn_abi, context) = classify(AbiTarget::AArch64, |resolve| {
+            let tys = *resolve.ty_consts();
+            let small = resolve.mk_tuple([tys.i8, tys.i8, tys.i8]);
+            let large = resolve.mk_tuple([tys.i64, tys.i64, tys.i64]);
+            let empty = resolve.mk_tuple([tys.unit]);
+            (vec![small, large, empty], small)
+        });
+
+        assert_eq!(
+            fn_abi.ret,
+            ArgAbi::Coerce(context.custom_width_int_type(24).into()),
+        );
+        assert_eq!(
+            fn_abi.params.as_slice(),
+            [
+                ArgAbi::Coerce(context.i64_type().into()),
+                ArgAbi::Indirect { byval: false },
+                ArgAbi::Ignore,
+            ],
+        );
+    }
+
+    #[test]
+    fn abi_target_from_triple() {
+        let target = |triple| AbiTarget::from_triple(&TargetTriple::create(triple));
+
+        assert_eq!(target("x86_64-unknown-linux-gnu"), AbiTarget::SysV64);
+        assert_eq!(target("x86_64-apple-darwin"), AbiTarget::SysV64);
+        assert_eq!(target("x86_64-pc-windows-msvc"), AbiTarget::Other);
+        assert_eq!(target("aarch64-unknown-linux-gnu"), AbiTarget::AArch64);
+        assert_eq!(target("arm64-apple-macosx"), AbiTarget::AArch64);
+    }
+}
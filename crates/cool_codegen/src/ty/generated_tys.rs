@@ -1,10 +1,11 @@
-use crate::{mangle_item_path, TyFieldMap};
+use crate::{mangle_item_path, AbiTarget, ArgAbi, FnAbiInfo, TyFieldMap};
 use cool_lexer::{sym, Symbol};
-use cool_resolve::{Field, ItemId, ResolveContext, TaggedUnionKind, TyId, ValueTy};
+use cool_resolve::{Field, FnAbi, FnTy, ItemId, ResolveContext, TaggedUnionKind, TyId, ValueTy};
 use inkwell::context::Context;
-use inkwell::targets::TargetData;
+use inkwell::targets::{TargetData, TargetTriple};
 use inkwell::types::{
-    BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType, IntType, PointerType, VoidType,
+    BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType, IntType, PointerType,
+    StructType, VoidType,
 };
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
@@ -13,8 +14,11 @@ use std::ops;
 #[derive(Clone, Debug)]
 pub struct GeneratedTys<'a> {
     fns: FxHashMap<TyId, FunctionType<'a>>,
+    fn_abis: FxHashMap<TyId, FnAbiInfo<'a>>,
+    abi_target: AbiTarget,
     tys: FxHashMap<TyId, Option<BasicTypeEnum<'a>>>,
     field_maps: FxHashMap<TyId, TyFieldMap>,
+    explicit_aligns: Vec<(StructType<'a>, u32)>,
     void_ty: VoidType<'a>,
     i8_ty: IntType<'a>,
     i8_ptr_ty: PointerType<'a>,
@@ -24,13 +28,17 @@ pub struct GeneratedTys<'a> {
 impl<'a> GeneratedTys<'a> {
     pub fn new(
         context: &'a Context,
+        target_triple: &TargetTriple,
         target_data: &TargetData,
         resolve: &'a ResolveContext,
     ) -> Self {
         let mut generated_tys = Self {
             fns: Default::default(),
+            fn_abis: Default::default(),
+            abi_target: AbiTarget::from_triple(target_triple),
             tys: Default::default(),
             field_maps: Default::default(),
+            explicit_aligns: Default::default(),
             void_ty: context.void_type(),
             i8_ty: context.i8_type(),
             i8_ptr_ty: context.i8_type().ptr_type(Default::default()),
@@ -121,26 +129,65 @@ impl<'a> GeneratedTys<'a> {
         ty_id: TyId,
         fields: &[Field],
     ) {
+        let ty_def = resolve.get_ty_def(ty_id).unwrap();
+        let layout = ty_def.kind.as_aggregate().unwrap().layout();
+
         let struct_decl = self.tys[&ty_id].unwrap().into_struct_type();
         let mut field_tys = Vec::<BasicTypeEnum>::new();
         let mut field_map = FxHashMap::<Symbol, u32>::default();
 
-        fields
-            .iter()
-            .flat_map(|field| {
-                self.insert_ty(context, resolve, field.ty_id)
-                    .map(|ty| (field.symbol, ty))
-            })
-            .enumerate()
-            .for_each(|(i, (symbol, ty))| {
+        if layout.is_auto() {
+            fields
+                .iter()
+                .flat_map(|field| {
+                    self.insert_ty(context, resolve, field.ty_id)
+                        .map(|ty| (field.symbol, ty))
+                })
+                .enumerate()
+                .for_each(|(i, (symbol, ty))| {
+                    field_tys.push(ty);
+                    field_map.insert(symbol, i as u32);
+                });
+
+            struct_decl.set_body(&field_tys, false);
+        } else {
+            // Structs with an explicit layout are emitted as packed structs with explicit padding,
+            // so the offsets of the fields always match the ones computed by the resolver
+            let mut offset = 0;
+
+            for field in fields.iter() {
+                let Some(ty) = self.insert_ty(context, resolve, field.ty_id) else {
+                    continue;
+                };
+
+                if field.offset > offset {
+                    field_tys.push(self.mk_padding_ty(field.offset - offset));
+                }
+
+                field_map.insert(field.symbol, field_tys.len() as u32);
                 field_tys.push(ty);
-                field_map.insert(symbol, i as u32);
-            });
+                offset = field.offset + resolve.get_ty_def(field.ty_id).unwrap().size;
+            }
+
+            if ty_def.size > offset {
+                field_tys.push(self.mk_padding_ty(ty_def.size - offset));
+            }
+
+            struct_decl.set_body(&field_tys, true);
+
+            if ty_def.align > 1 {
+                self.explicit_aligns
+                    .push((struct_decl, ty_def.align as u32));
+            }
+        }
 
-        struct_decl.set_body(&field_tys, false);
         self.field_maps.insert(ty_id, field_map.into());
     }
 
+    fn mk_padding_ty(&self, size: u64) -> BasicTypeEnum<'a> {
+        self.i8_ty.array_type(size as u32).as_basic_type_enum()
+    }
+
     fn insert_ty(
         &mut self,
         context: &'a Context,
@@ -153,17 +200,29 @@ impl<'a> GeneratedTys<'a> {
 
         let ty: Option<BasicTypeEnum> = match ty_id.get_value() {
             ValueTy::Fn(fn_ty) => {
-                let params = fn_ty
-                    .params
-                    .iter()
-                    .flat_map(|&param| self.insert_ty(context, resolve, param))
-                    .map(BasicMetadataTypeEnum::from)
-                    .collect::<Vec<_>>();
-
-                let fn_item_ty = self
-                    .insert_ty(context, resolve, fn_ty.ret)
-                    .map(|ty| ty.fn_type(&params, fn_ty.is_variadic))
-                    .unwrap_or_else(|| self.void_ty.fn_type(&params, fn_ty.is_variadic));
+                let fn_abi = (fn_ty.abi == FnAbi::C)
+                    .then(|| FnAbiInfo::new(context, resolve, self.abi_target, fn_ty))
+                    .flatten();
+
+                let fn_item_ty = match fn_abi {
+                    Some(fn_abi) => {
+                        let fn_item_ty = self.insert_c_fn_ty(context, resolve, fn_ty, &fn_abi);
+                        self.fn_abis.insert(ty_id, fn_abi);
+                        fn_item_ty
+                    }
+                    None => {
+                        let params = fn_ty
+                            .params
+                            .iter()
+                            .flat_map(|&param| self.insert_ty(context, resolve, param))
+                            .map(BasicMetadataTypeEnum::from)
+                            .collect::<Vec<_>>();
+
+                        self.insert_ty(context, resolve, fn_ty.ret)
+                            .map(|ty| ty.fn_type(&params, fn_ty.is_variadic))
+                            .unwrap_or_else(|| self.void_ty.fn_type(&params, fn_ty.is_variadic))
+                    }
+                };
 
                 self.fns.insert(ty_id, fn_item_ty);
                 Some(fn_item_ty.ptr_type(Default::default()).as_basic_type_enum())
@@ -174,7 +233,11 @@ impl<'a> GeneratedTys<'a> {
                     .map(|elem_ty| elem_ty.array_type(array_ty.len as u32))
                     .map(BasicTypeEnum::from)
             }
-            ValueTy::Tuple(_) | ValueTy::Struct(_) | ValueTy::Slice(_) => {
+            ValueTy::Tuple(_)
+            | ValueTy::Struct(_)
+            | ValueTy::Closure(_)
+            | ValueTy::Interface(_)
+            | ValueTy::Slice(_) => {
                 let fields = resolve
                     .get_ty_def(ty_id)
                     .unwrap()
@@ -200,6 +263,9 @@ impl<'a> GeneratedTys<'a> {
                 Some(ty)
             }
             ValueTy::Variant(_) => Some(self.insert_variant_ty(context, resolve, ty_id)),
+            ValueTy::Union(union_ty) => {
+                self.insert_union_ty(context, resolve, ty_id, union_ty.item_id)
+            }
             ValueTy::EmptyStruct(_) => None,
             ty => unimplemented!("{}", ty),
         };
@@ -208,6 +274,46 @@ impl<'a> GeneratedTys<'a> {
         ty
     }
 
+    /// Lowers the type of a C function according to the classification of its parameters and
+    /// return value. Values passed indirectly become pointers and a `sret` pointer is added
+    /// before the other parameters.
+    fn insert_c_fn_ty(
+        &mut self,
+        context: &'a Context,
+        resolve: &'a ResolveContext,
+        fn_ty: &FnTy,
+        fn_abi: &FnAbiInfo<'a>,
+    ) -> FunctionType<'a> {
+        let mut params = Vec::<BasicMetadataTypeEnum>::new();
+
+        if fn_abi.has_sret() {
+            params.push(self.i8_ptr_ty.into());
+        }
+
+        for (&param, param_abi) in fn_ty.params.iter().zip(fn_abi.params.iter()) {
+            let param_ty = match param_abi {
+                ArgAbi::Ignore => continue,
+                ArgAbi::Direct => self.insert_ty(context, resolve, param).unwrap(),
+                ArgAbi::Coerce(ty) => *ty,
+                ArgAbi::Indirect { .. } => self.i8_ptr_ty.as_basic_type_enum(),
+            };
+
+            params.push(param_ty.into());
+        }
+
+        match fn_abi.ret {
+            ArgAbi::Direct => {
+                self.insert_ty(context, resolve, fn_ty.ret)
+                    .unwrap()
+                    .fn_type(&params, fn_ty.is_variadic)
+            }
+            ArgAbi::Coerce(ty) => ty.fn_type(&params, fn_ty.is_variadic),
+            ArgAbi::Ignore | ArgAbi::Indirect { .. } => {
+                self.void_ty.fn_type(&params, fn_ty.is_variadic)
+            }
+        }
+    }
+
     fn insert_aggregate_ty(
         &mut self,
         context: &'a Context,
@@ -235,6 +341,31 @@ impl<'a> GeneratedTys<'a> {
         (!field_tys.is_empty()).then(|| context.struct_type(&field_tys, false).as_basic_type_enum())
     }
 
+    /// Unions are emitted as packed structs holding their bytes. Fields are accessed by casting
+    /// a pointer to the union.
+    fn insert_union_ty(
+        &mut self,
+        context: &'a Context,
+        resolve: &'a ResolveContext,
+        ty_id: TyId,
+        item_id: ItemId,
+    ) -> Option<BasicTypeEnum<'a>> {
+        let ty_def = resolve.get_ty_def(ty_id).unwrap();
+
+        if ty_def.is_zero_sized() {
+            return None;
+        }
+
+        let union_ty = context.opaque_struct_type(&mangle_item_path(&*item_id));
+        union_ty.set_body(&[self.mk_padding_ty(ty_def.size)], true);
+
+        if ty_def.align > 1 {
+            self.explicit_aligns.push((union_ty, ty_def.align as u32));
+        }
+
+        Some(union_ty.as_basic_type_enum())
+    }
+
     fn insert_variant_ty(
         &mut self,
         context: &'a Context,
@@ -292,6 +423,42 @@ impl<'a> GeneratedTys<'a> {
         self.fns[&ty_id]
     }
 
+    /// Returns how the parameters and return value are passed if `ty_id` is a C function type
+    /// lowered for the target.
+    #[inline]
+    pub fn get_fn_abi(&self, ty_id: TyId) -> Option<&FnAbiInfo<'a>> {
+        self.fn_abis.get(&ty_id)
+    }
+
+    /// Returns the alignment required by values of `ty` if it contains structs with an explicit
+    /// layout, whose LLVM types are packed and have no alignment of their own.
+    pub fn get_explicit_align(&self, ty: BasicTypeEnum<'a>) -> Option<u32> {
+        if self.explicit_aligns.is_empty() {
+            return None;
+        }
+
+        match ty {
+            BasicTypeEnum::StructType(struct_ty) => {
+                let struct_align = self
+                    .explicit_aligns
+                    .iter()
+                    .find(|(explicit_ty, _)| *explicit_ty == struct_ty)
+                    .map(|(_, align)| *align);
+
+                struct_ty
+                    .get_field_types()
+                    .into_iter()
+                    .flat_map(|field_ty| self.get_explicit_align(field_ty))
+                    .chain(struct_align)
+                    .max()
+            }
+            BasicTypeEnum::ArrayType(array_ty) => {
+                self.get_explicit_align(array_ty.get_element_type())
+            }
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn get_field_map(&self, ty_id: TyId) -> &TyFieldMap {
         &self.field_maps[&ty_id]
@@ -1,5 +1,7 @@
+mod fn_abi;
 mod generated_tys;
 mod ty_field_map;
 
+pub use self::fn_abi::*;
 pub use self::generated_tys::*;
 pub use self::ty_field_map::*;
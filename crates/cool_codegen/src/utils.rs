@@ -1,6 +1,7 @@
 use cool_collections::SmallString;
 use cool_lexer::sym;
 use cool_resolve::ItemPath;
+use std::fmt::Write;
 
 pub fn mangle_item_path<'a, P>(path: P) -> SmallString
 where
@@ -18,12 +19,84 @@ where
         return SmallString::new();
     };
 
-    let mut result = SmallString::from(first.as_str());
+    let mut result = SmallString::new();
+    push_mangled_symbol(&mut result, first.as_str());
 
     for other in others {
         result.push_str("__");
-        result.push_str(other.as_str());
+        push_mangled_symbol(&mut result, other.as_str());
     }
 
     result
 }
+
+/// Symbols of generic instances contain their type arguments, e.g. `Vec[*mut i32]`, so
+/// characters that are not valid in identifiers are escaped.
+fn push_mangled_symbol(result: &mut SmallString, symbol: &str) {
+    for c in symbol.chars() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => result.push(c),
+            ' ' => (),
+            '[' => result.push_str("$LB$"),
+            ']' => result.push_str("$RB$"),
+            '(' => result.push_str("$LP$"),
+            ')' => result.push_str("$RP$"),
+            ',' => result.push_str("$C$"),
+            '.' => result.push_str("$D$"),
+            '*' => result.push_str("$P$"),
+            _ => write!(result, "$u{:x}$", c as u32).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cool_lexer::{Symbol, SymbolTable};
+
+    fn mangle(path: &[&str]) -> String {
+        SymbolTable::default().enter(|| {
+            let path = path
+                .iter()
+                .map(|symbol| Symbol::insert(symbol))
+                .collect::<Vec<_>>();
+
+            mangle_item_path(path.as_slice()).to_string()
+        })
+    }
+
+    #[test]
+    fn mangle_plain_paths() {
+        assert_eq!(mangle(&["app", "math", "add"]), "app__math__add");
+        assert_eq!(mangle(&["app", "main"]), "main");
+    }
+
+    #[test]
+    fn mangle_generic_instances() {
+        assert_eq!(mangle(&["app", "first[i32]"]), "app__first$LB$i32$RB$");
+        assert_eq!(
+            mangle(&["app", "size[[3]i16]"]),
+            "app__size$LB$$LB$3$RB$i16$RB$",
+        );
+        assert_eq!(
+            mangle(&["app", "Pair[i32, *mut app.Node]"]),
+            "app__Pair$LB$i32$C$$P$mutapp$D$Node$RB$",
+        );
+        assert_eq!(
+            mangle(&["app", "apply[fn(i32) -> i32]"]),
+            "app__apply$LB$fn$LP$i32$RP$$u2d$$u3e$i32$RB$",
+        );
+    }
+
+    #[test]
+    fn mangle_distinguishes_instances() {
+        assert_ne!(
+            mangle(&["app", "Pair[i8, i64]"]),
+            mangle(&["app", "Pair[i64, i8]"]),
+        );
+        assert_ne!(
+            mangle(&["app", "first[i32]"]),
+            mangle(&["app", "first_i32"])
+        );
+    }
+}
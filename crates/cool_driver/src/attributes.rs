@@ -0,0 +1,261 @@
+use crate::{AttributeError, AttributeErrorKind};
+use cool_ast::{CallConv, FnAttrs, InlineAttr};
+use cool_lexer::{sym, LiteralKind, Symbol};
+use cool_parser::{Attribute, AttributeArg, Decl, DeclKind, Expr, Item};
+use cool_resolve::{LayoutKind, StructLayout};
+use cool_span::Section;
+
+/// Largest alignment supported by LLVM.
+const MAX_ALIGN: u64 = 1 << 29;
+
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct DeclAttrs {
+    pub fn_attrs: FnAttrs,
+    pub struct_layout: StructLayout,
+    pub is_test: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum AttributeTarget {
+    Fn,
+    GenericFn,
+    ExternFn,
+    Struct,
+    Module,
+    Other,
+}
+
+impl AttributeTarget {
+    fn for_decl(decl: &Decl) -> Self {
+        let DeclKind::Item(item_decl) = &decl.kind else {
+            return Self::Other;
+        };
+
+        match &item_decl.item {
+            Item::ExternFn(_) => Self::ExternFn,
+            Item::Module(_) => Self::Module,
+            Item::Struct(struct_item) if struct_item.has_body => Self::Struct,
+            Item::Const(const_item) => {
+                match &const_item.expr {
+                    Expr::Fn(fn_expr) if fn_expr.prototype.ty_params.is_some() => Self::GenericFn,
+                    Expr::Fn(_) => Self::Fn,
+                    _ => Self::Other,
+                }
+            }
+            _ => Self::Other,
+        }
+    }
+
+    fn accepts(&self, attribute: Symbol) -> bool {
+        match attribute {
+            sym::ATTR_INLINE | sym::ATTR_NOINLINE | sym::ATTR_SECTION => {
+                matches!(self, Self::Fn | Self::GenericFn)
+            }
+            sym::ATTR_LINK_NAME | sym::ATTR_WEAK => matches!(self, Self::Fn | Self::ExternFn),
+            sym::ATTR_COLD | sym::ATTR_CALLCONV => {
+                matches!(self, Self::Fn | Self::GenericFn | Self::ExternFn)
+            }
+            sym::ATTR_PACKED | sym::ATTR_ALIGN | sym::ATTR_LAYOUT => *self == Self::Struct,
+            sym::ATTR_TEST => matches!(self, Self::Fn | Self::Module),
+            _ => false,
+        }
+    }
+}
+
+/// Validates the attributes of a declaration. Only functions, structs and modules accept
+/// attributes.
+pub(crate) fn validate_decl_attributes(decl: &Decl) -> Result<DeclAttrs, AttributeError> {
+    let target = AttributeTarget::for_decl(decl);
+    let mut fn_attrs = FnAttrs::default();
+    let mut layout = StructLayout::default();
+    let mut is_test = false;
+    let mut seen = Vec::<Symbol>::new();
+
+    for attribute in decl.attributes.iter() {
+        let name = attribute.ident.symbol;
+
+        let error = |kind| {
+            Err(AttributeError {
+                span: attribute.span(),
+                kind,
+            })
+        };
+
+        if !is_known_attribute(name) {
+            return error(AttributeErrorKind::Unknown { name });
+        }
+
+        if !target.accepts(name) {
+            return error(AttributeErrorKind::NotApplicable { name });
+        }
+
+        if seen.contains(&name) {
+            return error(AttributeErrorKind::Duplicated { name });
+        }
+
+        seen.push(name);
+
+        match name {
+            sym::ATTR_INLINE | sym::ATTR_NOINLINE => {
+                expect_no_args(attribute)?;
+
+                if fn_attrs.inline.is_some() {
+                    return error(AttributeErrorKind::Conflicting { name });
+                }
+
+                fn_attrs.inline = Some(if name == sym::ATTR_INLINE {
+                    InlineAttr::Always
+                } else {
+                    InlineAttr::Never
+                });
+            }
+            sym::ATTR_COLD => {
+                expect_no_args(attribute)?;
+                fn_attrs.is_cold = true;
+            }
+            sym::ATTR_WEAK => {
+                expect_no_args(attribute)?;
+                fn_attrs.is_weak = true;
+            }
+            sym::ATTR_LINK_NAME => fn_attrs.link_name = Some(expect_str_arg(attribute)?),
+            sym::ATTR_SECTION => fn_attrs.section = Some(expect_str_arg(attribute)?),
+            sym::ATTR_CALLCONV => {
+                let call_conv = match expect_ident_arg(attribute)? {
+                    sym::ABI_C => CallConv::C,
+                    sym::CALLCONV_FAST => CallConv::Fast,
+                    sym::ATTR_COLD => CallConv::Cold,
+                    _ => return error(AttributeErrorKind::InvalidArgs { name }),
+                };
+
+                fn_attrs.call_conv = Some(call_conv);
+            }
+            sym::ATTR_PACKED | sym::ATTR_LAYOUT => {
+                let kind = if name == sym::ATTR_PACKED {
+                    expect_no_args(attribute)?;
+                    LayoutKind::Packed
+                } else if expect_ident_arg(attribute)? == sym::ABI_C {
+                    LayoutKind::C
+                } else {
+                    return error(AttributeErrorKind::InvalidArgs { name });
+                };
+
+                if layout.kind != LayoutKind::Auto {
+                    return error(AttributeErrorKind::Conflicting { name });
+                }
+
+                layout.kind = kind;
+            }
+            sym::ATTR_ALIGN => {
+                let align = expect_int_arg(attribute)?;
+
+                if !align.is_power_of_two() || align > MAX_ALIGN {
+                    return error(AttributeErrorKind::InvalidArgs { name });
+                }
+
+                layout.align = Some(align);
+            }
+            sym::ATTR_TEST => {
+                expect_no_args(attribute)?;
+                is_test = true;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(DeclAttrs {
+        fn_attrs,
+        struct_layout: layout,
+        is_test,
+    })
+}
+
+/// Validates the attributes of a method. Methods accept the same attributes as functions, except
+/// for `@test`.
+pub(crate) fn validate_method_attributes(decl: &Decl) -> Result<FnAttrs, AttributeError> {
+    let test_attribute = decl
+        .attributes
+        .iter()
+        .find(|attribute| attribute.ident.symbol == sym::ATTR_TEST);
+
+    if let Some(attribute) = test_attribute {
+        return Err(AttributeError {
+            span: attribute.span(),
+            kind: AttributeErrorKind::NotApplicable {
+                name: sym::ATTR_TEST,
+            },
+        });
+    }
+
+    validate_decl_attributes(decl).map(|attrs| attrs.fn_attrs)
+}
+
+fn is_known_attribute(name: Symbol) -> bool {
+    matches!(
+        name,
+        sym::ATTR_INLINE
+            | sym::ATTR_NOINLINE
+            | sym::ATTR_COLD
+            | sym::ATTR_LINK_NAME
+            | sym::ATTR_SECTION
+            | sym::ATTR_WEAK
+            | sym::ATTR_CALLCONV
+            | sym::ATTR_PACKED
+            | sym::ATTR_ALIGN
+            | sym::ATTR_LAYOUT
+            | sym::ATTR_TEST,
+    )
+}
+
+fn invalid_args(attribute: &Attribute) -> AttributeError {
+    AttributeError {
+        span: attribute.span(),
+        kind: AttributeErrorKind::InvalidArgs {
+            name: attribute.ident.symbol,
+        },
+    }
+}
+
+fn expect_no_args(attribute: &Attribute) -> Result<(), AttributeError> {
+    if !attribute.args.is_empty() {
+        return Err(invalid_args(attribute));
+    }
+
+    Ok(())
+}
+
+fn expect_str_arg(attribute: &Attribute) -> Result<Symbol, AttributeError> {
+    match attribute.args.as_slice() {
+        [AttributeArg::Literal(literal)]
+            if literal.prefix.is_none()
+                && literal.literal.kind == LiteralKind::Str
+                && !literal.literal.symbol.as_str().is_empty() =>
+        {
+            Ok(literal.literal.symbol)
+        }
+        _ => Err(invalid_args(attribute)),
+    }
+}
+
+fn expect_ident_arg(attribute: &Attribute) -> Result<Symbol, AttributeError> {
+    match attribute.args.as_slice() {
+        [AttributeArg::Ident(ident)] => Ok(ident.symbol),
+        _ => Err(invalid_args(attribute)),
+    }
+}
+
+fn expect_int_arg(attribute: &Attribute) -> Result<u64, AttributeError> {
+    match attribute.args.as_slice() {
+        [AttributeArg::Literal(literal)]
+            if literal.prefix.is_none() && literal.literal.kind.is_plain_int() =>
+        {
+            literal
+                .literal
+                .symbol
+                .as_str()
+                .replace('_', "")
+                .parse::<u64>()
+                .map_err(|_| invalid_args(attribute))
+        }
+        _ => Err(invalid_args(attribute)),
+    }
+}
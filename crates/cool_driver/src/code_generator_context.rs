@@ -1,8 +1,9 @@
 use inkwell::context::Context;
-use inkwell::targets::{TargetData, TargetTriple};
+use inkwell::targets::{TargetData, TargetMachine, TargetTriple};
 
 pub struct CodeGeneratorContext {
     pub context: Context,
     pub target_triple: TargetTriple,
     pub target_data: TargetData,
+    pub target_machine: TargetMachine,
 }
@@ -1,4 +1,4 @@
-use crate::ModulePathsError;
+use crate::{InterfaceError, ModulePathsError};
 use cool_ast::AstError;
 use cool_lexer::Symbol;
 use cool_parser::ParseError;
@@ -6,6 +6,8 @@ use cool_resolve::{ItemId, ItemPathBuf, ResolveError, TyId};
 use cool_span::Span;
 use derive_more::{Display, Error, From};
 use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
 
 pub type CompileResult<T> = Result<T, CompileErrorBundle>;
 
@@ -38,20 +40,30 @@ impl fmt::Display for CompileErrorBundle {
 #[derive(Clone, Error, From, Display, Debug)]
 pub enum CompileError {
     Ast(AstError),
+    Attribute(AttributeError),
     Define(DefineError),
+    Impl(ImplError),
     Import(ImportError),
     Init(InitError),
+    Interface(InterfaceError),
+    Internal(InternalError),
+    Jit(JitError),
     Module(ModuleError),
+    Output(OutputError),
     Parse(ParseError),
     Resolve(ResolveError),
+    Source(SourceError),
 }
 
 impl CompileError {
     pub fn span(&self) -> Option<Span> {
         match self {
             Self::Ast(e) => Some(e.span),
+            Self::Attribute(e) => Some(e.span),
             Self::Define(e) => e.span,
+            Self::Impl(e) => Some(e.span),
             Self::Import(e) => Some(e.span),
+            Self::Interface(e) => e.span,
             Self::Module(e) => e.span,
             Self::Parse(e) => Some(e.found.span),
             _ => None,
@@ -59,6 +71,29 @@ impl CompileError {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Display, Debug)]
+pub enum AttributeErrorKind {
+    #[display(fmt = "unknown attribute '@{name}'")]
+    Unknown { name: Symbol },
+    #[display(fmt = "attribute '@{name}' cannot be applied to this item")]
+    NotApplicable { name: Symbol },
+    #[display(fmt = "invalid arguments for attribute '@{name}'")]
+    InvalidArgs { name: Symbol },
+    #[display(fmt = "attribute '@{name}' is specified more than once")]
+    Duplicated { name: Symbol },
+    #[display(fmt = "attribute '@{name}' conflicts with a previous attribute")]
+    Conflicting { name: Symbol },
+    #[display(fmt = "link name '{link_name}' is already used by another function")]
+    DuplicateLinkName { link_name: Symbol },
+}
+
+#[derive(Clone, Error, Display, Debug)]
+#[display(fmt = "{kind}")]
+pub struct AttributeError {
+    pub span: Span,
+    pub kind: AttributeErrorKind,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, From, Display, Debug)]
 pub enum DefineErrorKind {
     Item(ItemId),
@@ -72,6 +107,12 @@ pub struct DefineError {
     pub kind: DefineErrorKind,
 }
 
+#[derive(Clone, Error, Display, Debug)]
+#[display(fmt = "impl blocks can only contain functions")]
+pub struct ImplError {
+    pub span: Span,
+}
+
 #[derive(Clone, Error, Display, Debug)]
 #[display(fmt = "failed to import '{path}'")]
 pub struct ImportError {
@@ -85,6 +126,53 @@ pub struct InitError {
     pub message: String,
 }
 
+#[derive(Clone, Error, Display, Debug)]
+#[display(fmt = "internal compiler error: {message}")]
+pub struct InternalError {
+    pub message: String,
+}
+
+/// Runs `f`, turning a panic into an internal error so that long-running sessions, such as the
+/// language server and the REPL, survive compiler bugs.
+pub fn catch_internal_error<T, F>(f: F) -> CompileResult<T>
+where
+    F: FnOnce() -> CompileResult<T>,
+{
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => {
+                match payload.downcast::<&str>() {
+                    Ok(message) => message.to_string(),
+                    Err(_) => "the compiler panicked".to_owned(),
+                }
+            }
+        };
+
+        Err(CompileError::from(InternalError { message }).into())
+    })
+}
+
+#[derive(Clone, Error, Display, Debug)]
+#[display(fmt = "failed to run program: {message}")]
+pub struct JitError {
+    pub message: String,
+}
+
+#[derive(Clone, Error, Display, Debug)]
+#[display(fmt = "failed to write '{}': {}", "path.display()", message)]
+pub struct OutputError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+#[derive(Clone, Error, Display, Debug)]
+#[display(fmt = "failed to read '{}': {}", "path.display()", message)]
+pub struct SourceError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
 #[derive(Clone, Error, Display, Debug)]
 #[display(fmt = "no file found for module '{module_name}'")]
 pub struct ModuleError {
@@ -0,0 +1,1389 @@
+use crate::{CompileError, CompileResult, OutputError, Package};
+use cool_ast::{CallConv, ExternFnAst, FnAttrs, GenericItem};
+use cool_collections::SmallString;
+use cool_lexer::{sym, Symbol};
+use cool_resolve::{
+    ConstValue, FnAbi, ItemId, ItemKind, ItemPathBuf, LayoutKind, ModuleId, Mutability,
+    ResolveContext, StructLayout, TyId, TyKind, TyShape, ValueTy,
+};
+use cool_span::{Section, Span};
+use derive_more::{Display, Error};
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::{fs, slice};
+
+/// Version of the interface format, which is bumped whenever the format changes.
+pub const INTERFACE_VERSION: u32 = 1;
+
+pub const INTERFACE_FILE_EXTENSION: &str = "cooli";
+
+/// Path of an item as the names of its symbols.
+pub type InterfacePath = Vec<String>;
+
+/// Exported items of a compiled library package, from which dependent packages are compiled
+/// without its sources. Struct and union layouts are stored as computed by the compiler, so they
+/// can be checked when the interface is imported.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PackageInterface {
+    pub version: u32,
+    pub package: String,
+    /// Source files the package was compiled from.
+    pub sources: Vec<PathBuf>,
+    /// Modules of the package except the root one, each listed after its parent.
+    pub modules: Vec<InterfaceModule>,
+    /// Exported structs and the private structs that exported items refer to.
+    pub structs: Vec<InterfaceStruct>,
+    /// Exported unions and the private unions that exported items refer to.
+    pub unions: Vec<InterfaceUnion>,
+    pub aliases: Vec<InterfaceAlias>,
+    pub fns: Vec<InterfaceFn>,
+    pub consts: Vec<InterfaceConst>,
+    /// Exported imports, which make items available under another path.
+    pub uses: Vec<InterfaceUse>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceModule {
+    pub path: InterfacePath,
+    pub is_exported: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceStruct {
+    pub path: InterfacePath,
+    pub is_exported: bool,
+    pub has_body: bool,
+    pub layout: InterfaceLayout,
+    pub size: u64,
+    pub align: u64,
+    /// Fields in the order they are stored in.
+    pub fields: Vec<InterfaceField>,
+    pub methods: Vec<InterfaceFn>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceUnion {
+    pub path: InterfacePath,
+    pub is_exported: bool,
+    pub size: u64,
+    pub align: u64,
+    pub fields: Vec<InterfaceField>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceField {
+    pub name: String,
+    pub ty: InterfaceTy,
+    pub offset: u64,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceLayout {
+    pub kind: InterfaceLayoutKind,
+    pub align: Option<u64>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum InterfaceLayoutKind {
+    Auto,
+    C,
+    Packed,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceAlias {
+    pub path: InterfacePath,
+    pub ty: InterfaceTy,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceFn {
+    pub path: InterfacePath,
+    pub ty: InterfaceTy,
+    /// Name of the function symbol if it is not the mangled path of the function.
+    pub link_name: Option<String>,
+    pub is_weak: bool,
+    pub is_cold: bool,
+    pub call_conv: Option<InterfaceCallConv>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum InterfaceCallConv {
+    C,
+    Fast,
+    Cold,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceConst {
+    pub path: InterfacePath,
+    pub ty: InterfaceTy,
+    pub value: InterfaceConstValue,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum InterfaceConstValue {
+    Unit,
+    Bool(bool),
+    Char(u32),
+    Int(u128),
+    /// Bits of the value, so that every value is restored exactly.
+    Float(u64),
+    Cstr(String),
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceUse {
+    pub module: InterfacePath,
+    pub symbol: String,
+    pub target: InterfacePath,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum InterfaceTy {
+    Unit,
+    Diverge,
+    /// Builtin type, e.g. `i32`.
+    Primitive(String),
+    /// Struct or union.
+    Named(InterfacePath),
+    Array {
+        elem: Box<InterfaceTy>,
+        len: u64,
+    },
+    Tuple(Vec<InterfaceTy>),
+    Ptr {
+        pointee: Box<InterfaceTy>,
+        is_mutable: bool,
+    },
+    ManyPtr {
+        pointee: Box<InterfaceTy>,
+        is_mutable: bool,
+    },
+    Slice {
+        elem: Box<InterfaceTy>,
+        is_mutable: bool,
+    },
+    Fn {
+        abi: String,
+        params: Vec<InterfaceTy>,
+        is_variadic: bool,
+        ret: Box<InterfaceTy>,
+    },
+    Closure {
+        params: Vec<InterfaceTy>,
+        ret: Box<InterfaceTy>,
+    },
+    Variant(Vec<InterfaceTy>),
+}
+
+#[derive(Clone, Error, Display, Debug)]
+pub enum InterfaceErrorKind {
+    #[display(fmt = "failed to read '{}': {}", "path.display()", message)]
+    Read { path: PathBuf, message: String },
+
+    #[display(
+        fmt = "'{}' was written by another version of the compiler",
+        "_0.display()"
+    )]
+    Version(#[error(not(source))] PathBuf),
+
+    #[display(fmt = "'{}' describes package '{}'", "path.display()", found)]
+    NameMismatch { path: PathBuf, found: String },
+
+    #[display(fmt = "item '{_0}' cannot be exported through an interface")]
+    UnsupportedItem(#[error(not(source))] ItemPathBuf),
+
+    #[display(fmt = "item '{item}' uses type '{ty_id}', which package interfaces cannot describe")]
+    UnsupportedTy { item: ItemPathBuf, ty_id: TyId },
+
+    #[display(fmt = "unknown item '{}'", "_0.join(\".\")")]
+    UnknownItem(#[error(not(source))] InterfacePath),
+
+    #[display(fmt = "layout of '{}' does not match the interface", "_0.join(\".\")")]
+    LayoutMismatch(#[error(not(source))] InterfacePath),
+}
+
+#[derive(Clone, Error, Display, Debug)]
+#[display(fmt = "interface of package '{package}': {kind}")]
+pub struct InterfaceError {
+    pub package: Symbol,
+    /// Declaration of the item that cannot be exported, if the error is about one.
+    pub span: Option<Span>,
+    #[error(source)]
+    pub kind: InterfaceErrorKind,
+}
+
+impl PackageInterface {
+    /// Describes the compiled package `name`. Generic items, interfaces and enums cannot be
+    /// described yet, so they must not be exported or used by exported items.
+    pub fn new(
+        resolve: &ResolveContext,
+        package: &Package,
+        name: &str,
+    ) -> Result<Self, InterfaceError> {
+        let package_symbol = Symbol::insert(name);
+        let error = |kind: InterfaceErrorKind| {
+            let span = match &kind {
+                InterfaceErrorKind::UnsupportedItem(item)
+                | InterfaceErrorKind::UnsupportedTy { item, .. } => {
+                    resolve
+                        .get_item_id_by_path(item.as_symbol_slice())
+                        .and_then(|item_id| {
+                            // Generic items are only stored as their definition
+                            package.get_item_span(item_id).or_else(|| {
+                                let generic_id = resolve[item_id].as_generic_id()?;
+                                package.generics.get(&generic_id).map(GenericItem::span)
+                            })
+                        })
+                }
+                _ => None,
+            };
+
+            InterfaceError {
+                package: package_symbol,
+                span,
+                kind,
+            }
+        };
+
+        let module_id = resolve
+            .get_item_id_by_path([package_symbol].as_slice())
+            .and_then(|item_id| resolve[item_id].as_module_id())
+            .ok_or_else(|| error(unknown_item(&[name.to_owned()])))?;
+
+        // Sources are compared against the interface by later builds, which may run elsewhere
+        let mut sources = package
+            .file_modules
+            .keys()
+            .map(|path| fs::canonicalize(path).unwrap_or_else(|_| path.clone()))
+            .collect::<Vec<_>>();
+
+        sources.sort();
+
+        let mut writer = InterfaceWriter {
+            resolve,
+            package,
+            package_symbol,
+            extern_fns: package
+                .extern_fns
+                .iter()
+                .map(|extern_fn| extern_fn.item_id)
+                .collect(),
+            written_tys: Default::default(),
+            referenced_tys: Default::default(),
+            interface: Self {
+                version: INTERFACE_VERSION,
+                package: package_symbol.as_str().to_owned(),
+                sources,
+                modules: Default::default(),
+                structs: Default::default(),
+                unions: Default::default(),
+                aliases: Default::default(),
+                fns: Default::default(),
+                consts: Default::default(),
+                uses: Default::default(),
+            },
+        };
+
+        writer.write_modules(module_id).map_err(error)?;
+
+        Ok(writer.interface)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, InterfaceErrorKind> {
+        let read_error = |message: String| {
+            InterfaceErrorKind::Read {
+                path: path.to_path_buf(),
+                message,
+            }
+        };
+
+        let source = fs::read_to_string(path).map_err(|e| read_error(e.to_string()))?;
+        let interface =
+            serde_json::from_str::<Self>(&source).map_err(|e| read_error(e.to_string()))?;
+
+        if interface.version != INTERFACE_VERSION {
+            return Err(InterfaceErrorKind::Version(path.to_path_buf()));
+        }
+
+        Ok(interface)
+    }
+
+    pub fn save(&self, path: &Path) -> CompileResult<()> {
+        let output_error = |message: String| {
+            CompileError::Output(OutputError {
+                path: path.to_path_buf(),
+                message,
+            })
+        };
+
+        let source = serde_json::to_string_pretty(self).map_err(|e| output_error(e.to_string()))?;
+        fs::write(path, source).map_err(|e| output_error(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Declares the items of the interface in the root module `module_id`. Functions are
+    /// defined by the compiled package, so they are added to the imported functions of
+    /// `package`.
+    pub fn import(
+        &self,
+        resolve: &mut ResolveContext,
+        package: &mut Package,
+        module_id: ModuleId,
+    ) -> Result<(), InterfaceError> {
+        let package_symbol = resolve[module_id].path().first();
+
+        let mut importer = InterfaceImporter { resolve, package };
+        importer.import(self).map_err(|kind| {
+            InterfaceError {
+                package: package_symbol,
+                span: None,
+                kind,
+            }
+        })
+    }
+}
+
+type InterfaceResult<T = ()> = Result<T, InterfaceErrorKind>;
+
+struct InterfaceWriter<'a> {
+    resolve: &'a ResolveContext,
+    package: &'a Package,
+    package_symbol: Symbol,
+    extern_fns: FxHashSet<ItemId>,
+    written_tys: FxHashSet<ItemId>,
+    /// Structs and unions of the package that exported items refer to.
+    referenced_tys: Vec<ItemId>,
+    interface: PackageInterface,
+}
+
+impl InterfaceWriter<'_> {
+    fn write_modules(&mut self, root_module_id: ModuleId) -> InterfaceResult {
+        let mut module_ids = VecDeque::from([root_module_id]);
+
+        while let Some(module_id) = module_ids.pop_front() {
+            let module = &self.resolve[module_id];
+
+            let mut elems = module.elems.iter().collect::<Vec<_>>();
+            elems.sort_by_key(|(symbol, _)| symbol.as_str());
+
+            for (&symbol, elem) in elems {
+                let is_defined_here = *elem.item_id == *module.child_path(symbol).as_symbol_slice();
+
+                if !is_defined_here {
+                    if elem.is_exported {
+                        self.write_use(module.item_id, symbol, elem.item_id)?;
+                    }
+
+                    continue;
+                }
+
+                match self.resolve[elem.item_id] {
+                    ItemKind::Module(child_module_id) => {
+                        self.interface.modules.push(InterfaceModule {
+                            path: interface_path(elem.item_id),
+                            is_exported: elem.is_exported,
+                        });
+
+                        module_ids.push_back(child_module_id);
+                    }
+                    ItemKind::Ty(ty_id) if elem.is_exported => {
+                        self.write_ty_item(elem.item_id, ty_id)?;
+                    }
+                    ItemKind::Binding(binding_id) if elem.is_exported => {
+                        let ty_id = self.resolve[binding_id].ty_id;
+
+                        match self.resolve.get_const_value(binding_id) {
+                            Some(value) => {
+                                let value = encode_const_value(value);
+                                self.write_const(elem.item_id, ty_id, value)?;
+                            }
+                            None => {
+                                let fn_item = self.encode_fn(elem.item_id, ty_id)?;
+                                self.interface.fns.push(fn_item);
+                            }
+                        }
+                    }
+                    ItemKind::Generic(_) if elem.is_exported => {
+                        return Err(unsupported_item(elem.item_id));
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        // Private types are only described if exported items refer to them
+        while let Some(item_id) = self.referenced_tys.pop() {
+            let ty_id = self.resolve[item_id].as_ty_id().unwrap();
+            self.write_ty_item(item_id, ty_id)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_ty_item(&mut self, item_id: ItemId, ty_id: TyId) -> InterfaceResult {
+        let is_own_ty = match ty_id.as_value() {
+            Some(ValueTy::Struct(struct_ty)) => struct_ty.item_id == item_id,
+            Some(ValueTy::EmptyStruct(struct_ty)) => struct_ty.item_id == item_id,
+            Some(ValueTy::Union(union_ty)) => union_ty.item_id == item_id,
+            Some(ValueTy::Interface(interface_ty)) => {
+                if interface_ty.item_id == item_id {
+                    return Err(unsupported_item(item_id));
+                }
+
+                false
+            }
+            _ => false,
+        };
+
+        if !is_own_ty {
+            let ty = self.encode_ty(ty_id, item_id)?;
+
+            self.interface.aliases.push(InterfaceAlias {
+                path: interface_path(item_id),
+                ty,
+            });
+
+            return Ok(());
+        }
+
+        if !self.written_tys.insert(item_id) {
+            return Ok(());
+        }
+
+        let ty_def = self
+            .resolve
+            .get_ty_def(ty_id)
+            .ok_or_else(|| unsupported_item(item_id))?;
+
+        let (size, align) = (ty_def.size, ty_def.align);
+        let is_exported = self.is_exported(item_id);
+
+        if let Some(union_ty) = ty_def.kind.as_untagged_union() {
+            let fields = union_ty.fields().to_vec();
+
+            let fields = fields
+                .iter()
+                .map(|field| {
+                    Ok(InterfaceField {
+                        name: field.symbol.as_str().to_owned(),
+                        ty: self.encode_ty(field.ty_id, item_id)?,
+                        offset: field.offset,
+                    })
+                })
+                .collect::<InterfaceResult<Vec<_>>>()?;
+
+            self.interface.unions.push(InterfaceUnion {
+                path: interface_path(item_id),
+                is_exported,
+                size,
+                align,
+                fields,
+            });
+
+            return Ok(());
+        }
+
+        let fields = ty_def
+            .get_aggregate_fields()
+            .map(|fields| fields.to_vec())
+            .unwrap_or_default();
+
+        let fields = fields
+            .iter()
+            .map(|field| {
+                Ok(InterfaceField {
+                    name: field.symbol.as_str().to_owned(),
+                    ty: self.encode_ty(field.ty_id, item_id)?,
+                    offset: field.offset,
+                })
+            })
+            .collect::<InterfaceResult<Vec<_>>>()?;
+
+        // Methods of generic struct instances are only generated where they are used
+        let mut methods = Vec::<InterfaceFn>::new();
+
+        if self.resolve.get_generic_instance_of(item_id).is_none() {
+            if let Some(method_table) = self.resolve.get_method_table(item_id) {
+                let mut method_item_ids = method_table
+                    .iter()
+                    .map(|&(_, method_item_id)| method_item_id)
+                    .filter(|&method_item_id| {
+                        self.resolve
+                            .get_method(method_item_id)
+                            .is_some_and(|method| method.is_exported)
+                    })
+                    .collect::<Vec<_>>();
+
+                method_item_ids
+                    .sort_by_key(|method_item_id| method_item_id.last().unwrap().as_str());
+
+                for method_item_id in method_item_ids {
+                    let binding_id = self.resolve[method_item_id].as_binding_id().unwrap();
+                    let ty_id = self.resolve[binding_id].ty_id;
+                    methods.push(self.encode_fn(method_item_id, ty_id)?);
+                }
+            }
+        }
+
+        let layout = self.resolve.get_struct_layout(item_id);
+
+        self.interface.structs.push(InterfaceStruct {
+            path: interface_path(item_id),
+            is_exported,
+            has_body: ty_id.is_struct(),
+            layout: InterfaceLayout {
+                kind: match layout.kind {
+                    LayoutKind::Auto => InterfaceLayoutKind::Auto,
+                    LayoutKind::C => InterfaceLayoutKind::C,
+                    LayoutKind::Packed => InterfaceLayoutKind::Packed,
+                },
+                align: layout.align,
+            },
+            size,
+            align,
+            fields,
+            methods,
+        });
+
+        Ok(())
+    }
+
+    fn write_const(
+        &mut self,
+        item_id: ItemId,
+        ty_id: TyId,
+        value: InterfaceConstValue,
+    ) -> InterfaceResult {
+        let ty = self.encode_ty(ty_id, item_id)?;
+
+        self.interface.consts.push(InterfaceConst {
+            path: interface_path(item_id),
+            ty,
+            value,
+        });
+
+        Ok(())
+    }
+
+    fn write_use(
+        &mut self,
+        module_item_id: ItemId,
+        symbol: Symbol,
+        item_id: ItemId,
+    ) -> InterfaceResult {
+        let is_supported = match self.resolve[item_id] {
+            ItemKind::Generic(_) => false,
+            ItemKind::Ty(ty_id) => !ty_id.is_interface(),
+            _ => true,
+        };
+
+        if !is_supported {
+            return Err(unsupported_item(item_id));
+        }
+
+        self.interface.uses.push(InterfaceUse {
+            module: interface_path(module_item_id),
+            symbol: symbol.as_str().to_owned(),
+            target: interface_path(item_id),
+        });
+
+        Ok(())
+    }
+
+    fn encode_fn(&mut self, item_id: ItemId, ty_id: TyId) -> InterfaceResult<InterfaceFn> {
+        if !ty_id.is_fn() {
+            return Err(unsupported_item(item_id));
+        }
+
+        let attrs = self
+            .package
+            .fn_attrs
+            .get(&item_id)
+            .copied()
+            .unwrap_or_default();
+
+        // External functions are linked by their own name rather than by their path
+        let link_name = if self.extern_fns.contains(&item_id) {
+            Some(attrs.link_name.unwrap_or(*item_id.last().unwrap()))
+        } else {
+            attrs.link_name
+        };
+
+        Ok(InterfaceFn {
+            path: interface_path(item_id),
+            ty: self.encode_ty(ty_id, item_id)?,
+            link_name: link_name.map(|link_name| link_name.as_str().to_owned()),
+            is_weak: attrs.is_weak,
+            is_cold: attrs.is_cold,
+            call_conv: attrs.call_conv.map(|call_conv| {
+                match call_conv {
+                    CallConv::C => InterfaceCallConv::C,
+                    CallConv::Fast => InterfaceCallConv::Fast,
+                    CallConv::Cold => InterfaceCallConv::Cold,
+                }
+            }),
+        })
+    }
+
+    /// Encodes the type of the item `item_id`, which is reported if the type is not supported.
+    fn encode_ty(&mut self, ty_id: TyId, item_id: ItemId) -> InterfaceResult<InterfaceTy> {
+        let encode_tys = |writer: &mut Self, ty_ids: &[TyId]| {
+            ty_ids
+                .iter()
+                .map(|&ty_id| writer.encode_ty(ty_id, item_id))
+                .collect::<InterfaceResult<Vec<_>>>()
+        };
+
+        let value_ty = match &*ty_id {
+            TyShape::Value(value_ty) => value_ty,
+            TyShape::Diverge => return Ok(InterfaceTy::Diverge),
+            _ => return Err(unsupported_ty(item_id, ty_id)),
+        };
+
+        let ty = match value_ty {
+            ValueTy::Unit => InterfaceTy::Unit,
+            ValueTy::Bool | ValueTy::Char | ValueTy::Int(_) | ValueTy::Float(_) => {
+                InterfaceTy::Primitive(value_ty.to_string())
+            }
+            ValueTy::Array(array_ty) => {
+                InterfaceTy::Array {
+                    elem: Box::new(self.encode_ty(array_ty.elem, item_id)?),
+                    len: array_ty.len,
+                }
+            }
+            ValueTy::Tuple(tuple_ty) => InterfaceTy::Tuple(encode_tys(self, tuple_ty.elems())?),
+            ValueTy::Struct(struct_ty) => self.encode_named_ty(struct_ty.item_id),
+            ValueTy::EmptyStruct(struct_ty) => self.encode_named_ty(struct_ty.item_id),
+            ValueTy::Union(union_ty) => self.encode_named_ty(union_ty.item_id),
+            ValueTy::Fn(fn_ty) => {
+                InterfaceTy::Fn {
+                    abi: fn_ty.abi.to_string(),
+                    params: encode_tys(self, &fn_ty.params)?,
+                    is_variadic: fn_ty.is_variadic,
+                    ret: Box::new(self.encode_ty(fn_ty.ret, item_id)?),
+                }
+            }
+            ValueTy::Closure(closure_ty) => {
+                InterfaceTy::Closure {
+                    params: encode_tys(self, &closure_ty.params)?,
+                    ret: Box::new(self.encode_ty(closure_ty.ret, item_id)?),
+                }
+            }
+            ValueTy::Ptr(ptr_ty) => {
+                InterfaceTy::Ptr {
+                    pointee: Box::new(self.encode_ty(ptr_ty.pointee, item_id)?),
+                    is_mutable: ptr_ty.is_mutable,
+                }
+            }
+            ValueTy::ManyPtr(many_ptr_ty) => {
+                InterfaceTy::ManyPtr {
+                    pointee: Box::new(self.encode_ty(many_ptr_ty.pointee, item_id)?),
+                    is_mutable: many_ptr_ty.is_mutable,
+                }
+            }
+            ValueTy::Slice(slice_ty) => {
+                InterfaceTy::Slice {
+                    elem: Box::new(self.encode_ty(slice_ty.elem, item_id)?),
+                    is_mutable: slice_ty.is_mutable,
+                }
+            }
+            ValueTy::Variant(variant_ty) => {
+                InterfaceTy::Variant(encode_tys(self, variant_ty.variants())?)
+            }
+            // Enums cannot be defined yet and interface objects have no stable layout
+            ValueTy::Enum(_) | ValueTy::Interface(_) => return Err(unsupported_ty(item_id, ty_id)),
+        };
+
+        Ok(ty)
+    }
+
+    fn encode_named_ty(&mut self, item_id: ItemId) -> InterfaceTy {
+        // Types of other packages are described by their own interfaces
+        if item_id.first() == Some(&self.package_symbol) && !self.written_tys.contains(&item_id) {
+            self.referenced_tys.push(item_id);
+        }
+
+        InterfaceTy::Named(interface_path(item_id))
+    }
+
+    /// Returns whether the item can be named from outside of its module. Generic instances are
+    /// not elements of any module, so they are never exported.
+    fn is_exported(&self, item_id: ItemId) -> bool {
+        let Some((&symbol, parent_path)) = item_id.split_last() else {
+            return false;
+        };
+
+        self.resolve
+            .get_item_id_by_path(parent_path)
+            .and_then(|parent_item_id| self.resolve[parent_item_id].as_module_id())
+            .and_then(|module_id| self.resolve[module_id].elems.get(&symbol))
+            .is_some_and(|elem| elem.is_exported && elem.item_id == item_id)
+    }
+}
+
+struct InterfaceImporter<'a> {
+    resolve: &'a mut ResolveContext,
+    package: &'a mut Package,
+}
+
+impl InterfaceImporter<'_> {
+    fn import(&mut self, interface: &PackageInterface) -> InterfaceResult {
+        for module in interface.modules.iter() {
+            let (parent_module_id, symbol) = self.get_parent_module(&module.path)?;
+            self.resolve
+                .insert_module(parent_module_id, module.is_exported, symbol)
+                .map_err(|_| unknown_item(&module.path))?;
+        }
+
+        for struct_item in interface.structs.iter() {
+            let (module_id, symbol) = self.get_parent_module(&struct_item.path)?;
+            let layout = StructLayout {
+                kind: match struct_item.layout.kind {
+                    InterfaceLayoutKind::Auto => LayoutKind::Auto,
+                    InterfaceLayoutKind::C => LayoutKind::C,
+                    InterfaceLayoutKind::Packed => LayoutKind::Packed,
+                },
+                align: struct_item.layout.align,
+            };
+
+            self.resolve
+                .declare_struct(
+                    module_id,
+                    struct_item.is_exported,
+                    symbol,
+                    struct_item.has_body,
+                    layout,
+                )
+                .map_err(|_| unknown_item(&struct_item.path))?;
+        }
+
+        for union_item in interface.unions.iter() {
+            let (module_id, symbol) = self.get_parent_module(&union_item.path)?;
+            self.resolve
+                .declare_union(module_id, union_item.is_exported, symbol)
+                .map_err(|_| unknown_item(&union_item.path))?;
+        }
+
+        self.define_tys(interface)?;
+
+        for alias in interface.aliases.iter() {
+            let (module_id, symbol) = self.get_parent_module(&alias.path)?;
+            let item_id = self
+                .resolve
+                .declare_alias(module_id, true, symbol)
+                .map_err(|_| unknown_item(&alias.path))?;
+
+            let ty_id = self.decode_ty(&alias.ty)?;
+            self.resolve.define_alias(item_id, ty_id);
+        }
+
+        for fn_item in interface.fns.iter() {
+            let (module_id, symbol) = self.get_parent_module(&fn_item.path)?;
+            let item_id = self
+                .resolve
+                .insert_global_binding(module_id, true, Mutability::Const, symbol)
+                .map_err(|_| unknown_item(&fn_item.path))?;
+
+            self.import_fn(item_id, fn_item)?;
+        }
+
+        for struct_item in interface.structs.iter() {
+            let struct_item_id = self.get_item_id(&struct_item.path)?;
+            let module_id = self.get_parent_module(&struct_item.path)?.0;
+
+            for method in struct_item.methods.iter() {
+                let symbol = Symbol::insert(method.path.last().map_or("", String::as_str));
+                let item_id = self
+                    .resolve
+                    .declare_method(struct_item_id, module_id, true, symbol)
+                    .map_err(|_| unknown_item(&method.path))?;
+
+                self.import_fn(item_id, method)?;
+            }
+        }
+
+        for const_item in interface.consts.iter() {
+            let (module_id, symbol) = self.get_parent_module(&const_item.path)?;
+            let item_id = self
+                .resolve
+                .insert_global_binding(module_id, true, Mutability::Const, symbol)
+                .map_err(|_| unknown_item(&const_item.path))?;
+
+            let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+            let ty_id = self.decode_ty(&const_item.ty)?;
+            let value = decode_const_value(&const_item.value);
+            self.resolve.define_const(binding_id, ty_id, value);
+        }
+
+        for use_item in interface.uses.iter() {
+            let module_id = self
+                .get_item_id(&use_item.module)
+                .ok()
+                .and_then(|item_id| self.resolve[item_id].as_module_id())
+                .ok_or_else(|| unknown_item(&use_item.module))?;
+
+            let path = use_item
+                .target
+                .iter()
+                .map(|symbol| Symbol::insert(symbol))
+                .collect::<ItemPathBuf>();
+
+            self.resolve
+                .insert_use(
+                    module_id,
+                    true,
+                    &path,
+                    Some(Symbol::insert(&use_item.symbol)),
+                )
+                .map_err(|_| unknown_item(&use_item.target))?;
+        }
+
+        Ok(())
+    }
+
+    /// Defines the declared structs and unions, retrying the ones whose fields are not defined
+    /// yet, and checks that their layouts match the interface.
+    fn define_tys(&mut self, interface: &PackageInterface) -> InterfaceResult {
+        let mut structs = interface
+            .structs
+            .iter()
+            .filter(|struct_item| struct_item.has_body)
+            .collect::<VecDeque<_>>();
+
+        let mut unions = interface.unions.iter().collect::<VecDeque<_>>();
+
+        loop {
+            let start_len = structs.len() + unions.len();
+
+            for _ in 0..structs.len() {
+                let struct_item = structs.pop_front().unwrap();
+                let item_id = self.get_item_id(&struct_item.path)?;
+                let fields = self.decode_fields(&struct_item.fields)?;
+
+                if self.resolve.define_struct(item_id, fields).is_err() {
+                    structs.push_back(struct_item);
+                }
+            }
+
+            for _ in 0..unions.len() {
+                let union_item = unions.pop_front().unwrap();
+                let item_id = self.get_item_id(&union_item.path)?;
+                let fields = self.decode_fields(&union_item.fields)?;
+
+                if self.resolve.define_union(item_id, fields).is_err() {
+                    unions.push_back(union_item);
+                }
+            }
+
+            // Arrays and tuples of the new types can only be defined after them
+            let ty_ids = self
+                .resolve
+                .iter_undefined_value_ty_ids()
+                .collect::<Vec<_>>();
+
+            for ty_id in ty_ids {
+                let _ = self.resolve.define_ty(ty_id);
+            }
+
+            if structs.len() + unions.len() == start_len {
+                break;
+            }
+        }
+
+        if let Some(struct_item) = structs.front() {
+            return Err(InterfaceErrorKind::LayoutMismatch(struct_item.path.clone()));
+        }
+
+        if let Some(union_item) = unions.front() {
+            return Err(InterfaceErrorKind::LayoutMismatch(union_item.path.clone()));
+        }
+
+        let tys = interface
+            .structs
+            .iter()
+            .map(|struct_item| {
+                (
+                    &struct_item.path,
+                    struct_item.size,
+                    struct_item.align,
+                    &struct_item.fields,
+                )
+            })
+            .chain(interface.unions.iter().map(|union_item| {
+                (
+                    &union_item.path,
+                    union_item.size,
+                    union_item.align,
+                    &union_item.fields,
+                )
+            }));
+
+        for (path, size, align, fields) in tys {
+            let item_id = self.get_item_id(path)?;
+            let ty_id = self.resolve[item_id].as_ty_id().unwrap();
+            let ty_def = self.resolve.get_ty_def(ty_id).unwrap();
+
+            let offsets = match &ty_def.kind {
+                TyKind::UntaggedUnion(union_ty) => union_ty.fields(),
+                TyKind::Aggregate(aggregate_ty) => aggregate_ty.fields(),
+                _ => &[],
+            };
+
+            let is_matching = ty_def.size == size
+                && ty_def.align == align
+                && offsets.len() == fields.len()
+                && offsets
+                    .iter()
+                    .zip(fields.iter())
+                    .all(|(field, interface_field)| {
+                        field.symbol.as_str() == interface_field.name
+                            && field.offset == interface_field.offset
+                    });
+
+            if !is_matching {
+                return Err(InterfaceErrorKind::LayoutMismatch(path.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decode_fields(&mut self, fields: &[InterfaceField]) -> InterfaceResult<Vec<(Symbol, TyId)>> {
+        fields
+            .iter()
+            .map(|field| Ok((Symbol::insert(&field.name), self.decode_ty(&field.ty)?)))
+            .collect()
+    }
+
+    fn import_fn(&mut self, item_id: ItemId, fn_item: &InterfaceFn) -> InterfaceResult {
+        let ty_id = self.decode_ty(&fn_item.ty)?;
+
+        if !ty_id.is_fn() {
+            return Err(unknown_item(&fn_item.path));
+        }
+
+        let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+        self.resolve.set_binding_ty(binding_id, ty_id);
+
+        let attrs = FnAttrs {
+            is_cold: fn_item.is_cold,
+            is_weak: fn_item.is_weak,
+            link_name: fn_item.link_name.as_deref().map(Symbol::insert),
+            call_conv: fn_item.call_conv.map(|call_conv| {
+                match call_conv {
+                    InterfaceCallConv::C => CallConv::C,
+                    InterfaceCallConv::Fast => CallConv::Fast,
+                    InterfaceCallConv::Cold => CallConv::Cold,
+                }
+            }),
+            ..Default::default()
+        };
+
+        if attrs.is_direct_call_only() {
+            self.resolve.mark_direct_call_only(binding_id);
+        }
+
+        self.package.fn_attrs.insert(item_id, attrs);
+        self.package.imported_fns.push(ExternFnAst {
+            item_id,
+            ty_id,
+            attrs,
+        });
+
+        Ok(())
+    }
+
+    fn decode_ty(&mut self, ty: &InterfaceTy) -> InterfaceResult<TyId> {
+        let ty_id = match ty {
+            InterfaceTy::Unit => self.resolve.ty_consts().unit,
+            InterfaceTy::Diverge => self.resolve.ty_consts().diverge,
+            InterfaceTy::Primitive(name) => {
+                let path = [sym::EMPTY, Symbol::insert(name)];
+
+                self.resolve
+                    .get_item_id_by_path(path.as_slice())
+                    .and_then(|item_id| self.resolve[item_id].as_ty_id())
+                    .ok_or_else(|| unknown_item(slice::from_ref(name)))?
+            }
+            InterfaceTy::Named(path) => {
+                let item_id = self.get_item_id(path)?;
+
+                self.resolve[item_id]
+                    .as_ty_id()
+                    .ok_or_else(|| unknown_item(path))?
+            }
+            InterfaceTy::Array { elem, len } => {
+                let elem = self.decode_ty(elem)?;
+                self.resolve.mk_array(*len, elem)
+            }
+            InterfaceTy::Tuple(elems) => {
+                let elems = self.decode_tys(elems)?;
+                self.resolve.mk_tuple(elems)
+            }
+            InterfaceTy::Ptr {
+                pointee,
+                is_mutable,
+            } => {
+                let pointee = self.decode_ty(pointee)?;
+                self.resolve.mk_ptr(pointee, *is_mutable)
+            }
+            InterfaceTy::ManyPtr {
+                pointee,
+                is_mutable,
+            } => {
+                let pointee = self.decode_ty(pointee)?;
+                self.resolve.mk_many_ptr(pointee, *is_mutable)
+            }
+            InterfaceTy::Slice { elem, is_mutable } => {
+                let elem = self.decode_ty(elem)?;
+                self.resolve.mk_slice(elem, *is_mutable)
+            }
+            InterfaceTy::Fn {
+                abi,
+                params,
+                is_variadic,
+                ret,
+            } => {
+                let abi = FnAbi::from_symbol(Symbol::insert(abi))
+                    .ok_or_else(|| unknown_item(slice::from_ref(abi)))?;
+                let params = self.decode_tys(params)?;
+                let ret = self.decode_ty(ret)?;
+                self.resolve.mk_fn(abi, params, *is_variadic, ret)
+            }
+            InterfaceTy::Closure { params, ret } => {
+                let params = self.decode_tys(params)?;
+                let ret = self.decode_ty(ret)?;
+                self.resolve.mk_closure(params, ret)
+            }
+            InterfaceTy::Variant(variants) => {
+                let variants = self.decode_tys(variants)?;
+                self.resolve.mk_variant(variants)
+            }
+        };
+
+        Ok(ty_id)
+    }
+
+    fn decode_tys(&mut self, tys: &[InterfaceTy]) -> InterfaceResult<Vec<TyId>> {
+        tys.iter().map(|ty| self.decode_ty(ty)).collect()
+    }
+
+    fn get_item_id(&self, path: &[String]) -> InterfaceResult<ItemId> {
+        let symbols = path
+            .iter()
+            .map(|symbol| Symbol::insert(symbol))
+            .collect::<Vec<_>>();
+
+        self.resolve
+            .get_item_id_by_path(symbols.as_slice())
+            .ok_or_else(|| unknown_item(path))
+    }
+
+    /// Returns the module that contains the item at `path` and the last symbol of the path.
+    fn get_parent_module(&self, path: &[String]) -> InterfaceResult<(ModuleId, Symbol)> {
+        let (symbol, parent_path) = path.split_last().ok_or_else(|| unknown_item(path))?;
+
+        let module_id = self
+            .get_item_id(parent_path)
+            .ok()
+            .and_then(|item_id| self.resolve[item_id].as_module_id())
+            .ok_or_else(|| unknown_item(path))?;
+
+        Ok((module_id, Symbol::insert(symbol)))
+    }
+}
+
+fn encode_const_value(value: &ConstValue) -> InterfaceConstValue {
+    match value {
+        ConstValue::Unit => InterfaceConstValue::Unit,
+        ConstValue::Bool(value) => InterfaceConstValue::Bool(*value),
+        ConstValue::Char(value) => InterfaceConstValue::Char(*value),
+        ConstValue::Int(value) => InterfaceConstValue::Int(*value),
+        ConstValue::Float(value) => InterfaceConstValue::Float(value.to_bits()),
+        ConstValue::Cstr(value) => InterfaceConstValue::Cstr(value.to_string()),
+    }
+}
+
+fn decode_const_value(value: &InterfaceConstValue) -> ConstValue {
+    match value {
+        InterfaceConstValue::Unit => ConstValue::Unit,
+        InterfaceConstValue::Bool(value) => ConstValue::Bool(*value),
+        InterfaceConstValue::Char(value) => ConstValue::Char(*value),
+        InterfaceConstValue::Int(value) => ConstValue::Int(*value),
+        InterfaceConstValue::Float(value) => ConstValue::Float(f64::from_bits(*value)),
+        InterfaceConstValue::Cstr(value) => ConstValue::Cstr(SmallString::from(value.as_str())),
+    }
+}
+
+fn interface_path(item_id: ItemId) -> InterfacePath {
+    item_id
+        .iter()
+        .map(|symbol| symbol.as_str().to_owned())
+        .collect()
+}
+
+#[inline]
+fn unsupported_item(item_id: ItemId) -> InterfaceErrorKind {
+    InterfaceErrorKind::UnsupportedItem(ItemPathBuf::from(&*item_id))
+}
+
+#[inline]
+fn unsupported_ty(item_id: ItemId, ty_id: TyId) -> InterfaceErrorKind {
+    InterfaceErrorKind::UnsupportedTy {
+        item: ItemPathBuf::from(&*item_id),
+        ty_id,
+    }
+}
+
+#[inline]
+fn unknown_item(path: &[String]) -> InterfaceErrorKind {
+    InterfaceErrorKind::UnknownItem(path.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        p1_parse, p2_define_tys, p3_define_fn_tys, p4_gen_ast, CompileOptions, PackageOptions,
+        Session,
+    };
+    use cool_resolve::PrimitiveTyData;
+    use std::{env, process};
+
+    // Enums cannot be defined yet, so the package has none
+    const LIB_SOURCE: &str = "
+Hidden :: struct { a: u8, b: i64 };
+
+export Point :: struct { x: i32, y: i32, hidden: Hidden } impl {
+    export new :: fn(x: i32, y: i32) -> Self {
+        Self { x = x, y = y, hidden = Hidden { a = 1, b = 2 } }
+    };
+
+    export sum :: fn(self: *Self) -> i32 { self.x + self.y };
+};
+
+@packed
+export Header :: struct { tag: u8, len: u32 };
+
+export Bits :: union { value: f32, bits: u32 };
+export Points :: type [4]Point;
+
+Buffer :: struct[T] { ptr: [*]mut T, len: usize };
+
+export LIMIT :: 340282366920938463463374607431768211455 as u128;
+export SCALE :: 1.5;
+export GREETING :: c\"hi\";
+
+export shapes :: module {
+    export Square :: struct { side: u32 };
+    export area :: fn(square: Square) -> u32 { square.side * square.side };
+};
+
+export use shapes.Square;
+
+export buffer :: fn(ptr: [*]mut i32) -> Buffer[i32] { Buffer[i32] { ptr = ptr, len = 0 } };
+";
+
+    const APP_SOURCE: &str = "
+use lib.Point;
+
+export main :: fn(ptr: [*]mut i32) -> u32 {
+    point := Point.new(1, 2);
+    sum := point.sum();
+    header := lib.Header { tag = 1, len = 2 };
+    bits := lib.Bits { bits = 3 };
+    points: lib.Points = [point, point, point, point];
+    limit: u128 = lib.LIMIT;
+    buffer := lib.buffer(ptr);
+    lib.shapes.area(lib.Square { side = sum as u32 })
+};
+";
+
+    /// Temporary directory holding the sources and interfaces of a test, removed when dropped.
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(test_name: &str) -> Self {
+            let dir = env::temp_dir().join(format!("cool_interface_{test_name}_{}", process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, file_name: &str, source: &str) -> PathBuf {
+            let path = self.0.join(file_name);
+            fs::write(&path, source).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn package_options(name: &str, root_file: PathBuf) -> CompileOptions {
+        CompileOptions {
+            crate_name: name.to_owned(),
+            crate_root_file: root_file,
+            dependencies: Vec::new(),
+            packages: Vec::new(),
+            overflow_checks: false,
+            test: false,
+        }
+    }
+
+    /// Runs the passes that precede code generation and returns the interface of `name`, which is
+    /// either the compiled package or one it imports.
+    fn describe(options: &CompileOptions, name: &str) -> Result<PackageInterface, InterfaceError> {
+        let primitives = PrimitiveTyData {
+            i8_align: 1,
+            i16_align: 2,
+            i32_align: 4,
+            i64_align: 8,
+            i128_align: 16,
+            ptr_size: 8,
+            ptr_align: 8,
+            f32_align: 4,
+            f64_align: 8,
+        };
+
+        Session::new(primitives).enter(|resolve| {
+            let package =
+                p1_parse(resolve, options).unwrap_or_else(|(_, errors)| panic!("{errors}"));
+            p2_define_tys(&package, resolve).unwrap_or_else(|errors| panic!("{errors}"));
+            p3_define_fn_tys(&package, resolve).unwrap_or_else(|errors| panic!("{errors}"));
+            p4_gen_ast(&package, resolve).unwrap_or_else(|errors| panic!("{errors}"));
+
+            PackageInterface::new(resolve, &package, name)
+        })
+    }
+
+    fn to_json(interface: &PackageInterface) -> String {
+        serde_json::to_string_pretty(interface).unwrap()
+    }
+
+    #[test]
+    fn imported_interface_describes_the_same_items() {
+        let dir = TestDir::new("round_trip");
+        let lib_root = dir.write("lib.cl", LIB_SOURCE);
+        let app_root = dir.write("app.cl", APP_SOURCE);
+        let interface_file = dir.0.join(format!("lib.{INTERFACE_FILE_EXTENSION}"));
+
+        let interface = describe(&package_options("lib", lib_root.clone()), "lib").unwrap();
+        interface.save(&interface_file).unwrap();
+
+        let loaded = PackageInterface::load(&interface_file).unwrap();
+        assert_eq!(to_json(&loaded), to_json(&interface));
+
+        let app_options = CompileOptions {
+            dependencies: vec!["lib".to_owned()],
+            packages: vec![PackageOptions {
+                name: "lib".to_owned(),
+                root_file: lib_root,
+                dependencies: Vec::new(),
+                interface_file: Some(interface_file),
+            }],
+            ..package_options("app", app_root)
+        };
+
+        // Describing the imported items again yields the interface they were imported from
+        let mut imported = describe(&app_options, "lib").unwrap();
+        imported.sources = interface.sources.clone();
+        assert_eq!(to_json(&imported), to_json(&interface));
+
+        let struct_paths = interface
+            .structs
+            .iter()
+            .map(|struct_item| struct_item.path.join("."))
+            .collect::<Vec<_>>();
+
+        // Private structs and generic instances are described because exported items use them
+        for path in ["lib.Point", "lib.Header", "lib.Hidden", "lib.Buffer[i32]"] {
+            assert!(struct_paths.iter().any(|struct_path| struct_path == path));
+        }
+
+        let limit = interface
+            .consts
+            .iter()
+            .find(|const_item| const_item.path.join(".") == "lib.LIMIT")
+            .unwrap();
+
+        assert!(matches!(limit.value, InterfaceConstValue::Int(value) if value == u128::MAX));
+    }
+
+    #[test]
+    fn items_are_written_in_path_order() {
+        let dir = TestDir::new("order");
+        let root_file = dir.write(
+            "lib.cl",
+            "
+export zeta :: fn() {};
+export alpha :: fn() {};
+export middle :: fn() {};
+export Z :: 3;
+export A :: 1;
+export M :: 2;
+",
+        );
+
+        let options = package_options("lib", root_file);
+        let interface = describe(&options, "lib").unwrap();
+
+        let fn_names = interface
+            .fns
+            .iter()
+            .map(|fn_item| fn_item.path.last().unwrap().as_str())
+            .collect::<Vec<_>>();
+
+        let const_names = interface
+            .consts
+            .iter()
+            .map(|const_item| const_item.path.last().unwrap().as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(fn_names, ["alpha", "middle", "zeta"]);
+        assert_eq!(const_names, ["A", "M", "Z"]);
+
+        // Separate compilations write the same interface
+        assert_eq!(
+            to_json(&describe(&options, "lib").unwrap()),
+            to_json(&interface)
+        );
+    }
+
+    #[test]
+    fn exported_generic_items_are_rejected() {
+        let dir = TestDir::new("generics");
+
+        for source in [
+            "export Buffer :: struct[T] { ptr: [*]mut T, len: usize };",
+            "export first :: fn[T](ptr: [*]T) -> T { ptr[0] };",
+        ] {
+            let root_file = dir.write("lib.cl", source);
+            let error = describe(&package_options("lib", root_file), "lib").unwrap_err();
+
+            assert!(matches!(error.kind, InterfaceErrorKind::UnsupportedItem(_)));
+            assert!(error.span.is_some());
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{CompileError, CompileResult, JitError};
+use inkwell::execution_engine::ExecutionEngine;
+use inkwell::module::Module;
+use inkwell::{support, OptimizationLevel};
+
+/// Runs the `main` function of a module in-process and returns its exit code. Symbols that are
+/// not defined by the module, such as the ones from libc, are resolved from the host process.
+pub fn run_jit(module: &Module, args: &[&str]) -> CompileResult<i32> {
+    let engine = create_jit_engine(module)?;
+
+    let main_fn = engine
+        .get_function_value("main")
+        .map_err(|_| jit_error("the program has no 'main' function".to_owned()))?;
+
+    let exit_code = unsafe { engine.run_function_as_main(main_fn, args) };
+    Ok(exit_code)
+}
+
+/// Creates a JIT execution engine that owns the module. Symbols that are not defined by its
+/// modules are resolved from the host process.
+pub(crate) fn create_jit_engine<'a>(module: &Module<'a>) -> CompileResult<ExecutionEngine<'a>> {
+    ExecutionEngine::link_in_mc_jit();
+
+    // An empty file name opens the host process itself. Returns true on failure
+    if support::load_library_permanently("") {
+        return Err(jit_error("failed to load the symbols of the host process".to_owned()).into());
+    }
+
+    module
+        .create_jit_execution_engine(OptimizationLevel::None)
+        .map_err(|message| jit_error(message.to_string()).into())
+}
+
+#[inline]
+pub(crate) fn jit_error(message: String) -> CompileError {
+    CompileError::Jit(JitError { message })
+}
@@ -1,15 +1,28 @@
+mod attributes;
 mod code_generator_context;
 mod error;
+mod interface;
+mod jit;
+mod manifest;
 mod options;
+mod output;
 mod package;
 mod passes;
 mod paths;
+mod repl;
+mod session;
 mod source_map;
 
 pub use self::code_generator_context::*;
 pub use self::error::*;
+pub use self::interface::*;
+pub use self::jit::*;
+pub use self::manifest::*;
 pub use self::options::*;
+pub use self::output::*;
 pub use self::package::*;
 pub use self::passes::*;
 pub use self::paths::*;
+pub use self::repl::*;
+pub use self::session::*;
 pub use self::source_map::*;
@@ -0,0 +1,573 @@
+use crate::{CompileOptions, PackageInterface, PackageOptions, INTERFACE_FILE_EXTENSION};
+use cool_lexer::{TokenKind, Tokenizer};
+use derive_more::{Display, Error};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use std::{fs, io};
+
+pub const MANIFEST_FILE_NAME: &str = "cool.toml";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize, Display, Debug)]
+pub enum OutputKind {
+    #[serde(rename = "exe")]
+    #[display(fmt = "exe")]
+    Exe,
+    #[serde(rename = "staticlib")]
+    #[display(fmt = "staticlib")]
+    StaticLib,
+    #[serde(rename = "object")]
+    #[display(fmt = "object")]
+    Object,
+}
+
+impl OutputKind {
+    #[inline]
+    pub fn is_library(&self) -> bool {
+        *self != Self::Exe
+    }
+
+    /// Returns the name of the file written for a package named `name`.
+    pub fn file_name(&self, name: &str) -> String {
+        match self {
+            Self::Exe => name.to_owned(),
+            Self::StaticLib => format!("lib{name}.a"),
+            Self::Object => format!("{name}.o"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub package: ManifestPackage,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, ManifestDependency>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestPackage {
+    pub name: String,
+    /// Root file of the package, relative to the manifest.
+    pub root: PathBuf,
+    pub kind: OutputKind,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestDependency {
+    /// Directory containing the manifest of the dependency, relative to the manifest that
+    /// declares it.
+    pub path: PathBuf,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let error = |kind| {
+            ManifestError {
+                path: path.to_path_buf(),
+                kind,
+            }
+        };
+
+        let source = fs::read_to_string(path).map_err(|e| error(ManifestErrorKind::Read(e)))?;
+        let manifest = toml::from_str::<Self>(&source)
+            .map_err(|e| error(ManifestErrorKind::Parse(Box::new(e))))?;
+
+        for name in [&manifest.package.name]
+            .into_iter()
+            .chain(manifest.dependencies.keys())
+        {
+            if !is_valid_package_name(name) {
+                return Err(error(ManifestErrorKind::InvalidName(name.clone())));
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+#[derive(Error, Display, Debug)]
+pub enum ManifestErrorKind {
+    #[display(fmt = "failed to read manifest: {_0}")]
+    Read(io::Error),
+
+    #[display(fmt = "invalid manifest: {_0}")]
+    Parse(Box<toml::de::Error>),
+
+    #[display(fmt = "'{_0}' is not a valid package name")]
+    InvalidName(#[error(not(source))] String),
+
+    #[display(fmt = "dependency '{name}' refers to package '{found}'")]
+    NameMismatch { name: String, found: String },
+
+    #[display(fmt = "dependency '{_0}' is not a library")]
+    NotLibrary(#[error(not(source))] String),
+
+    #[display(fmt = "package '{_0}' depends on itself")]
+    CyclicDependency(#[error(not(source))] String),
+
+    #[display(fmt = "multiple packages are named '{_0}'")]
+    DuplicatePackage(#[error(not(source))] String),
+}
+
+#[derive(Error, Display, Debug)]
+#[display(fmt = "'{}': {}", "path.display()", kind)]
+pub struct ManifestError {
+    pub path: PathBuf,
+    #[error(source)]
+    pub kind: ManifestErrorKind,
+}
+
+/// Package of a build plan.
+#[derive(Clone, Debug)]
+pub struct BuildPackage {
+    /// Directory containing the manifest.
+    pub dir: PathBuf,
+    pub manifest: Manifest,
+}
+
+impl BuildPackage {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.manifest.package.name
+    }
+
+    #[inline]
+    pub fn kind(&self) -> OutputKind {
+        self.manifest.package.kind
+    }
+
+    #[inline]
+    pub fn root_file(&self) -> PathBuf {
+        self.dir.join(&self.manifest.package.root)
+    }
+
+    #[inline]
+    pub fn output_dir(&self) -> PathBuf {
+        self.dir.join("target")
+    }
+
+    #[inline]
+    pub fn output_file(&self) -> PathBuf {
+        self.output_dir().join(self.kind().file_name(self.name()))
+    }
+
+    #[inline]
+    pub fn interface_file(&self) -> PathBuf {
+        self.output_dir()
+            .join(format!("{}.{}", self.name(), INTERFACE_FILE_EXTENSION))
+    }
+}
+
+/// Package described by a manifest together with every package it depends on.
+#[derive(Clone, Debug)]
+pub struct BuildPlan {
+    pub root: BuildPackage,
+    /// Direct and indirect dependencies, each listed after its own dependencies.
+    pub packages: Vec<BuildPackage>,
+}
+
+impl BuildPlan {
+    pub fn load(manifest_path: &Path) -> Result<Self, ManifestError> {
+        let manifest = Manifest::load(manifest_path)?;
+        let dir = manifest_path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+
+        let mut loader = PackageLoader::default();
+        loader.dirs.insert(
+            manifest.package.name.clone(),
+            canonical_dir(manifest_path, &dir)?,
+        );
+
+        let mut stack = vec![manifest.package.name.clone()];
+        loader.load_dependencies(manifest_path, &dir, &manifest, &mut stack)?;
+
+        Ok(Self {
+            root: BuildPackage { dir, manifest },
+            packages: loader.packages,
+        })
+    }
+
+    /// Returns the options that compile `package` from its sources. Its dependencies are
+    /// imported from their interfaces, so they must be built first.
+    pub fn compile_options(&self, package: &BuildPackage) -> CompileOptions {
+        let dependencies = self.get_dependencies(package);
+
+        let packages = self
+            .packages
+            .iter()
+            .filter(|dependency| dependencies.contains(dependency.name()))
+            .map(|dependency| {
+                PackageOptions {
+                    name: dependency.name().to_owned(),
+                    root_file: dependency.root_file(),
+                    dependencies: dependency.manifest.dependencies.keys().cloned().collect(),
+                    interface_file: Some(dependency.interface_file()),
+                }
+            })
+            .collect();
+
+        CompileOptions {
+            crate_name: package.name().to_owned(),
+            crate_root_file: package.root_file(),
+            dependencies: package.manifest.dependencies.keys().cloned().collect(),
+            packages,
+            overflow_checks: false,
+            test: false,
+        }
+    }
+
+    /// Returns the direct and indirect dependencies of `package` in the build order.
+    pub fn get_link_order(&self, package: &BuildPackage) -> Vec<&BuildPackage> {
+        let dependencies = self.get_dependencies(package);
+
+        self.packages
+            .iter()
+            .filter(|dependency| dependencies.contains(dependency.name()))
+            .collect()
+    }
+
+    /// Returns whether the outputs and the interface of `package` are newer than its manifest,
+    /// its sources and the interfaces of its dependencies.
+    pub fn is_up_to_date(&self, package: &BuildPackage) -> bool {
+        let interface_file = package.interface_file();
+
+        let Ok(interface) = PackageInterface::load(&interface_file) else {
+            return false;
+        };
+
+        let Some(built_time) = [interface_file, package.output_file()]
+            .iter()
+            .map(|path| modified_time(path))
+            .min()
+            .flatten()
+        else {
+            return false;
+        };
+
+        let manifest_file = package.dir.join(MANIFEST_FILE_NAME);
+
+        let dependency_interface_files = self
+            .get_link_order(package)
+            .into_iter()
+            .map(BuildPackage::interface_file);
+
+        [manifest_file]
+            .into_iter()
+            .chain(interface.sources)
+            .chain(dependency_interface_files)
+            .all(|path| modified_time(&path).is_some_and(|time| time <= built_time))
+    }
+
+    fn get_dependencies<'a>(&'a self, package: &'a BuildPackage) -> FxHashSet<&'a str> {
+        let mut dependencies = FxHashSet::<&str>::default();
+        let mut stack = package
+            .manifest
+            .dependencies
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        while let Some(name) = stack.pop() {
+            if !dependencies.insert(name) {
+                continue;
+            }
+
+            if let Some(dependency) = self.packages.iter().find(|package| package.name() == name) {
+                stack.extend(dependency.manifest.dependencies.keys().map(String::as_str));
+            }
+        }
+
+        dependencies
+    }
+}
+
+#[derive(Default, Debug)]
+struct PackageLoader {
+    /// Canonical directory of every package found so far.
+    dirs: FxHashMap<String, PathBuf>,
+    packages: Vec<BuildPackage>,
+}
+
+impl PackageLoader {
+    fn load_dependencies(
+        &mut self,
+        manifest_path: &Path,
+        dir: &Path,
+        manifest: &Manifest,
+        stack: &mut Vec<String>,
+    ) -> Result<(), ManifestError> {
+        let error = |kind| {
+            ManifestError {
+                path: manifest_path.to_path_buf(),
+                kind,
+            }
+        };
+
+        for (name, dependency) in manifest.dependencies.iter() {
+            let dependency_dir = dir.join(&dependency.path);
+            let dependency_manifest_path = dependency_dir.join(MANIFEST_FILE_NAME);
+            let dependency_canonical_dir =
+                canonical_dir(&dependency_manifest_path, &dependency_dir)?;
+
+            if let Some(loaded_dir) = self.dirs.get(name) {
+                if *loaded_dir != dependency_canonical_dir {
+                    return Err(error(ManifestErrorKind::DuplicatePackage(name.clone())));
+                }
+
+                if stack.contains(name) {
+                    return Err(error(ManifestErrorKind::CyclicDependency(name.clone())));
+                }
+
+                continue;
+            }
+
+            let dependency_manifest = Manifest::load(&dependency_manifest_path)?;
+
+            if dependency_manifest.package.name != *name {
+                return Err(error(ManifestErrorKind::NameMismatch {
+                    name: name.clone(),
+                    found: dependency_manifest.package.name,
+                }));
+            }
+
+            if !dependency_manifest.package.kind.is_library() {
+                return Err(error(ManifestErrorKind::NotLibrary(name.clone())));
+            }
+
+            self.dirs.insert(name.clone(), dependency_canonical_dir);
+
+            stack.push(name.clone());
+            self.load_dependencies(
+                &dependency_manifest_path,
+                &dependency_dir,
+                &dependency_manifest,
+                stack,
+            )?;
+            stack.pop();
+
+            self.packages.push(BuildPackage {
+                dir: dependency_dir,
+                manifest: dependency_manifest,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn canonical_dir(manifest_path: &Path, dir: &Path) -> Result<PathBuf, ManifestError> {
+    // An empty path stands for the working directory
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+
+    fs::canonicalize(dir).map_err(|e| {
+        ManifestError {
+            path: manifest_path.to_path_buf(),
+            kind: ManifestErrorKind::Read(e),
+        }
+    })
+}
+
+/// Package names are used as the first symbol of paths, so they must be identifiers.
+fn is_valid_package_name(name: &str) -> bool {
+    let mut tokenizer = Tokenizer::new(name, 0);
+    let token = tokenizer.next_token();
+
+    matches!(token.kind, TokenKind::Ident(_))
+        && token.span.end() as usize == name.len()
+        && tokenizer.next_token().kind == TokenKind::Eof
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cool_lexer::SymbolTable;
+    use std::{env, process};
+
+    /// Temporary directory holding the packages of a test, removed when dropped.
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(test_name: &str) -> Self {
+            let dir = env::temp_dir().join(format!("cool_manifest_{test_name}_{}", process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        /// Writes the manifest of a package in `package_dir` and returns its path.
+        fn add_package(
+            &self,
+            package_dir: &str,
+            name: &str,
+            kind: &str,
+            dependencies: &[(&str, &str)],
+        ) -> PathBuf {
+            let mut manifest = format!(
+                "[package]\nname = \"{name}\"\nroot = \"src/main.cl\"\nkind = \"{kind}\"\n\n\
+                 [dependencies]\n",
+            );
+
+            for (dependency, path) in dependencies {
+                manifest.push_str(&format!("{dependency} = {{ path = \"{path}\" }}\n"));
+            }
+
+            let dir = self.0.join(package_dir);
+            fs::create_dir_all(&dir).unwrap();
+
+            let manifest_path = dir.join(MANIFEST_FILE_NAME);
+            fs::write(&manifest_path, manifest).unwrap();
+            manifest_path
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn load_plan(manifest_path: &Path) -> Result<BuildPlan, ManifestErrorKind> {
+        SymbolTable::default()
+            .enter(|| BuildPlan::load(manifest_path))
+            .map_err(|error| error.kind)
+    }
+
+    fn names<'a>(packages: impl IntoIterator<Item = &'a BuildPackage>) -> Vec<&'a str> {
+        packages.into_iter().map(BuildPackage::name).collect()
+    }
+
+    #[test]
+    fn dependencies_are_listed_after_their_own() {
+        let dir = TestDir::new("order");
+        let manifest_path =
+            dir.add_package("app", "app", "exe", &[("net", "../net"), ("log", "../log")]);
+        dir.add_package(
+            "net",
+            "net",
+            "staticlib",
+            &[("log", "../log"), ("bytes", "../bytes")],
+        );
+        dir.add_package("log", "log", "object", &[("bytes", "../bytes")]);
+        dir.add_package("bytes", "bytes", "staticlib", &[]);
+
+        let plan = load_plan(&manifest_path).unwrap();
+        assert_eq!(plan.root.name(), "app");
+        assert_eq!(names(&plan.packages), ["bytes", "log", "net"]);
+
+        let net = plan.packages.iter().find(|p| p.name() == "net").unwrap();
+        assert_eq!(names(plan.get_link_order(net)), ["bytes", "log"]);
+        assert_eq!(
+            names(plan.get_link_order(&plan.root)),
+            ["bytes", "log", "net"]
+        );
+
+        let options = plan.compile_options(net);
+        assert_eq!(options.crate_name, "net");
+        assert_eq!(options.dependencies, ["bytes", "log"]);
+
+        let package_names = options
+            .packages
+            .iter()
+            .map(|package| package.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(package_names, ["bytes", "log"]);
+    }
+
+    #[test]
+    fn cyclic_dependencies_are_rejected() {
+        let dir = TestDir::new("cycle");
+        let manifest_path = dir.add_package("app", "app", "exe", &[("a", "../a")]);
+        dir.add_package("a", "a", "staticlib", &[("b", "../b")]);
+        dir.add_package("b", "b", "staticlib", &[("a", "../a")]);
+
+        assert!(matches!(
+            load_plan(&manifest_path),
+            Err(ManifestErrorKind::CyclicDependency(name)) if name == "a",
+        ));
+    }
+
+    #[test]
+    fn self_dependencies_are_rejected() {
+        let dir = TestDir::new("self");
+        let manifest_path = dir.add_package("a", "a", "staticlib", &[("a", ".")]);
+
+        assert!(matches!(
+            load_plan(&manifest_path),
+            Err(ManifestErrorKind::CyclicDependency(name)) if name == "a",
+        ));
+    }
+
+    #[test]
+    fn duplicate_packages_are_rejected() {
+        let dir = TestDir::new("duplicate");
+        let manifest_path =
+            dir.add_package("app", "app", "exe", &[("log", "../log"), ("net", "../net")]);
+        dir.add_package("log", "log", "staticlib", &[]);
+        dir.add_package("net", "net", "staticlib", &[("log", "../other_log")]);
+        dir.add_package("other_log", "log", "staticlib", &[]);
+
+        assert!(matches!(
+            load_plan(&manifest_path),
+            Err(ManifestErrorKind::DuplicatePackage(name)) if name == "log",
+        ));
+    }
+
+    #[test]
+    fn invalid_package_names_are_rejected() {
+        let dir = TestDir::new("names");
+
+        for name in ["my-app", "2d", "a b", ""] {
+            let manifest_path = dir.add_package("app", name, "exe", &[]);
+
+            assert!(matches!(
+                load_plan(&manifest_path),
+                Err(ManifestErrorKind::InvalidName(found)) if found == name,
+            ));
+        }
+
+        let manifest_path = dir.add_package("app", "app", "exe", &[("my-lib", "../lib")]);
+
+        assert!(matches!(
+            load_plan(&manifest_path),
+            Err(ManifestErrorKind::InvalidName(found)) if found == "my-lib",
+        ));
+    }
+
+    #[test]
+    fn dependencies_must_be_libraries_with_matching_names() {
+        let dir = TestDir::new("dependencies");
+        let manifest_path = dir.add_package("app", "app", "exe", &[("lib", "../lib")]);
+
+        dir.add_package("lib", "other", "staticlib", &[]);
+
+        assert!(matches!(
+            load_plan(&manifest_path),
+            Err(ManifestErrorKind::NameMismatch { name, found }) if name == "lib" && found == "other",
+        ));
+
+        dir.add_package("lib", "lib", "exe", &[]);
+
+        assert!(matches!(
+            load_plan(&manifest_path),
+            Err(ManifestErrorKind::NotLibrary(name)) if name == "lib",
+        ));
+    }
+}
@@ -4,4 +4,21 @@ use std::path::PathBuf;
 pub struct CompileOptions {
     pub crate_name: String,
     pub crate_root_file: PathBuf,
+    /// Names of the packages the crate can import.
+    pub dependencies: Vec<String>,
+    /// Packages compiled along with the crate, each listed after its own dependencies.
+    pub packages: Vec<PackageOptions>,
+    pub overflow_checks: bool,
+    /// Compile the `@test` functions and a `main` function that runs them instead of the
+    /// program's own.
+    pub test: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct PackageOptions {
+    pub name: String,
+    pub root_file: PathBuf,
+    pub dependencies: Vec<String>,
+    /// Interface of the compiled package, which is imported instead of parsing its sources.
+    pub interface_file: Option<PathBuf>,
 }
@@ -0,0 +1,90 @@
+use crate::{CodeGeneratorContext, CompileError, CompileResult, OutputError, OutputKind};
+use inkwell::module::Module;
+use inkwell::targets::FileType;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Writes a module to `dir` as the given kind of output and returns the path of the file. The
+/// object file is written first, then linked with the system C compiler for executables or
+/// archived with `ar` for static libraries. Executables are also linked with `link_inputs`, the
+/// outputs of the packages they depend on, which must come before their own dependencies.
+pub fn write_output(
+    codegen: &CodeGeneratorContext,
+    module: &Module,
+    kind: OutputKind,
+    dir: &Path,
+    name: &str,
+    link_inputs: &[PathBuf],
+) -> CompileResult<PathBuf> {
+    create_dir(dir)?;
+
+    let object_path = dir.join(format!("{name}.o"));
+    codegen
+        .target_machine
+        .write_to_file(module, FileType::Object, &object_path)
+        .map_err(|message| output_error(&object_path, message.to_string()))?;
+
+    let output_path = dir.join(kind.file_name(name));
+
+    match kind {
+        OutputKind::Exe => {
+            let mut command = Command::new("cc");
+            command
+                .arg("-o")
+                .arg(&output_path)
+                .arg(&object_path)
+                .args(link_inputs);
+
+            run_tool(&output_path, command)?;
+            Ok(output_path)
+        }
+        OutputKind::StaticLib => {
+            // Archives are updated in place, so a stale one would keep old members
+            if output_path.exists() {
+                fs::remove_file(&output_path)
+                    .map_err(|error| output_error(&output_path, error.to_string()))?;
+            }
+
+            let mut command = Command::new("ar");
+            command.arg("crs").arg(&output_path).arg(&object_path);
+            run_tool(&output_path, command)?;
+            Ok(output_path)
+        }
+        OutputKind::Object => Ok(object_path),
+    }
+}
+
+/// Writes the LLVM IR of a module to `dir` and returns the path of the file.
+pub fn write_llvm_ir(module: &Module, dir: &Path, name: &str) -> CompileResult<PathBuf> {
+    create_dir(dir)?;
+
+    let path = dir.join(format!("{name}.ll"));
+    module
+        .print_to_file(&path)
+        .map_err(|message| output_error(&path, message.to_string()))?;
+
+    Ok(path)
+}
+
+fn create_dir(dir: &Path) -> CompileResult<()> {
+    fs::create_dir_all(dir).map_err(|error| output_error(dir, error.to_string()).into())
+}
+
+fn run_tool(path: &Path, mut command: Command) -> CompileResult<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+
+    match command.status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(output_error(path, format!("'{program}' failed with {status}")).into()),
+        Err(error) => Err(output_error(path, format!("failed to run '{program}': {error}")).into()),
+    }
+}
+
+#[inline]
+fn output_error(path: &Path, message: String) -> CompileError {
+    CompileError::Output(OutputError {
+        path: path.to_path_buf(),
+        message,
+    })
+}
@@ -1,7 +1,13 @@
 use crate::SourceMap;
-use cool_parser::{AliasItem, ConstItem, EnumItem, ExternFnItem, StructItem, Ty};
+use cool_ast::{ExternFnAst, FnAttrs, GenericItemMap};
+use cool_parser::{
+    AliasItem, ConstItem, EnumItem, ExternFnItem, FnExpr, InterfaceItem, StaticAssertDecl,
+    StructItem, Ty, UnionItem,
+};
 use cool_resolve::{ItemId, ModuleId};
 use cool_span::{Section, Span};
+use rustc_hash::FxHashMap;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub struct DefineItem<I> {
@@ -21,9 +27,24 @@ impl<I> Section for DefineItem<I> {
 
 pub type Alias = DefineItem<AliasItem>;
 pub type Struct = DefineItem<StructItem>;
+pub type Union = DefineItem<UnionItem>;
 pub type Enum = DefineItem<EnumItem>;
+pub type Interface = DefineItem<InterfaceItem>;
 pub type ExternFn = DefineItem<ExternFnItem>;
 pub type Const = DefineItem<ConstItem>;
+pub type Method = DefineItem<FnExpr>;
+
+#[derive(Clone, Debug)]
+pub struct StaticAssert {
+    pub module_id: ModuleId,
+    pub decl: StaticAssertDecl,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TestFn {
+    pub span: Span,
+    pub item_id: ItemId,
+}
 
 #[derive(Clone, Default, Debug)]
 pub struct Package {
@@ -31,6 +52,35 @@ pub struct Package {
     pub aliases: Vec<Alias>,
     pub enums: Vec<Enum>,
     pub structs: Vec<Struct>,
+    pub unions: Vec<Union>,
+    pub interfaces: Vec<Interface>,
     pub extern_fns: Vec<ExternFn>,
     pub consts: Vec<Const>,
+    pub methods: Vec<Method>,
+    pub static_asserts: Vec<StaticAssert>,
+    pub tests: Vec<TestFn>,
+    pub generics: GenericItemMap,
+    pub fn_attrs: FxHashMap<ItemId, FnAttrs>,
+    /// Functions of packages imported from interfaces, which are defined by their objects.
+    pub imported_fns: Vec<ExternFnAst>,
+    /// Module defined by each parsed file.
+    pub file_modules: FxHashMap<PathBuf, ModuleId>,
+}
+
+impl Package {
+    /// Returns the span of the declaration of an item parsed from the sources of the package.
+    pub fn get_item_span(&self, item_id: ItemId) -> Option<Span> {
+        self.aliases
+            .iter()
+            .map(|item| (item.item_id, item.span))
+            .chain(self.structs.iter().map(|item| (item.item_id, item.span)))
+            .chain(self.unions.iter().map(|item| (item.item_id, item.span)))
+            .chain(self.enums.iter().map(|item| (item.item_id, item.span)))
+            .chain(self.interfaces.iter().map(|item| (item.item_id, item.span)))
+            .chain(self.extern_fns.iter().map(|item| (item.item_id, item.span)))
+            .chain(self.consts.iter().map(|item| (item.item_id, item.span)))
+            .chain(self.methods.iter().map(|item| (item.item_id, item.span)))
+            .find(|&(item_span_id, _)| item_span_id == item_id)
+            .map(|(_, span)| span)
+    }
 }
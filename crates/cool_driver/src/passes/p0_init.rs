@@ -1,10 +1,21 @@
-use crate::{CodeGeneratorContext, CompileError, CompileResult, InitError};
-use cool_resolve::{PrimitiveTyData, ResolveContext};
+use crate::{CodeGeneratorContext, CompileError, CompileResult, InitError, Session};
+use cool_resolve::PrimitiveTyData;
 use inkwell::context::Context;
-use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetTriple};
+use inkwell::targets::{
+    CodeModel, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple,
+};
 use inkwell::OptimizationLevel;
 
-pub fn p0_init(target_triple: &str) -> CompileResult<(CodeGeneratorContext, ResolveContext)> {
+/// Returns the target triple of the machine running the compiler, which code executed
+/// in-process must be generated for.
+pub fn host_target_triple() -> String {
+    TargetMachine::get_default_triple()
+        .as_str()
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn p0_init(target_triple: &str) -> CompileResult<(CodeGeneratorContext, Session)> {
     let context = Context::create();
 
     Target::initialize_all(&InitializationConfig {
@@ -58,7 +69,8 @@ pub fn p0_init(target_triple: &str) -> CompileResult<(CodeGeneratorContext, Reso
             context,
             target_triple,
             target_data,
+            target_machine,
         },
-        ResolveContext::new(primitives),
+        Session::new(primitives),
     ))
 }
@@ -1,13 +1,27 @@
+use crate::attributes::{validate_decl_attributes, validate_method_attributes, DeclAttrs};
 use crate::paths::ModulePaths;
 use crate::{
-    Alias, CompileError, CompileErrorBundle, CompileOptions, Const, Enum, ExternFn, ImportError,
-    ModuleError, Package, Struct,
+    Alias, AttributeError, AttributeErrorKind, CompileError, CompileErrorBundle, CompileOptions,
+    CompileResult, Const, Enum, ExternFn, ImplError, ImportError, Interface, InterfaceError,
+    InterfaceErrorKind, Method, ModuleError, Package, PackageInterface, SourceMap, StaticAssert,
+    Struct, TestFn, Union,
+};
+use cool_ast::{AstError, FnAttrs, GenericItem, TyDefError};
+use cool_lexer::{sym, Symbol, SymbolTable};
+use cool_parser::{
+    ConstItem, Decl, DeclKind, Expr, FnExpr, ImplBlock, Item, ItemDecl, ModuleContent, ModuleKind,
+    TyParamList,
+};
+use cool_resolve::{
+    GenericKind, ItemId, ItemPathBuf, ModuleId, Mutability, ResolveContext, ResolveError,
+    ResolveErrorKind,
 };
-use cool_lexer::Symbol;
-use cool_parser::{DeclKind, Item, ModuleContent, ModuleKind};
-use cool_resolve::{ItemPathBuf, ModuleId, Mutability, ResolveContext, ResolveErrorKind};
 use cool_span::{Section, Span};
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
 use std::collections::VecDeque;
+use std::path::Path;
 
 #[derive(Clone, Debug)]
 struct Import {
@@ -21,69 +35,297 @@ struct Import {
 pub fn p1_parse(
     resolve: &mut ResolveContext,
     options: &CompileOptions,
+) -> Result<Package, (Package, CompileErrorBundle)> {
+    p1_parse_with_source_map(resolve, options, SourceMap::default())
+}
+
+/// Parses the crate into a package that starts with the given source map, whose file
+/// overrides are used instead of the files on disk.
+pub fn p1_parse_with_source_map(
+    resolve: &mut ResolveContext,
+    options: &CompileOptions,
+    source_map: SourceMap,
 ) -> Result<Package, (Package, CompileErrorBundle)> {
     let mut errors = Vec::<CompileError>::new();
-    let mut package = Package::default();
+    let mut package = Package {
+        source_map,
+        ..Default::default()
+    };
+
+    // Dependencies come first, so every package is inserted after the packages it imports
+    let packages = options
+        .packages
+        .iter()
+        .map(|package| {
+            (
+                &package.name,
+                &package.root_file,
+                &package.dependencies,
+                package.interface_file.as_deref(),
+            )
+        })
+        .chain([(
+            &options.crate_name,
+            &options.crate_root_file,
+            &options.dependencies,
+            None,
+        )]);
+
+    let mut package_module_ids = FxHashMap::<&str, ModuleId>::default();
+    let mut file_modules = VecDeque::<(ModuleId, ModulePaths)>::new();
+
+    for (name, root_file, dependencies, interface_file) in packages {
+        let (module_id, module_paths) = match interface_file {
+            Some(_) => {
+                let Some(module_id) = insert_root_module(resolve, name, &mut errors) else {
+                    continue;
+                };
+
+                (module_id, None)
+            }
+            None => {
+                let Some((module_id, module_paths)) =
+                    insert_package(resolve, name, root_file, &mut errors)
+                else {
+                    continue;
+                };
+
+                (module_id, Some(module_paths))
+            }
+        };
+
+        for dependency in dependencies {
+            if let Some(&dependency_module_id) = package_module_ids.get(dependency.as_str()) {
+                resolve.insert_package_dependency(module_id, dependency_module_id);
+            }
+        }
+
+        package_module_ids.insert(name, module_id);
+
+        // Compiled packages are imported from their interfaces instead of being parsed
+        if let Some(interface_file) = interface_file {
+            if let Err(error) = import_interface(resolve, &mut package, module_id, interface_file) {
+                errors.push(error.into());
+            }
+        }
+
+        if let Some(module_paths) = module_paths {
+            file_modules.push_back((module_id, module_paths));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err((package, CompileErrorBundle { errors }));
+    }
+
+    let mut parser = PackageParser::new(resolve, &mut package, options.test);
+    parser.file_modules = file_modules;
+    parser.parse_file_modules();
+    parser.insert_imports();
+
+    match parser.finish() {
+        Ok(()) => Ok(package),
+        Err(errors) => Err((package, errors)),
+    }
+}
+
+/// Inserts the root module of a package, returning it along with the paths of its root file.
+fn insert_package(
+    resolve: &mut ResolveContext,
+    name: &str,
+    root_file: &Path,
+    errors: &mut Vec<CompileError>,
+) -> Option<(ModuleId, ModulePaths)> {
+    let symbol = Symbol::insert(name);
 
-    let crate_paths = match ModulePaths::for_root(&options.crate_root_file) {
-        Ok(crate_paths) => Some(crate_paths),
+    let module_paths = match ModulePaths::for_root(root_file) {
+        Ok(module_paths) => Some(module_paths),
         Err(error) => {
             errors.push(CompileError::from(ModuleError {
                 span: None,
-                module_name: Symbol::insert(&options.crate_name),
+                module_name: symbol,
                 error,
             }));
             None
         }
     };
 
-    let crate_symbol = Symbol::insert(&options.crate_name);
-    let crate_module_id = match resolve.insert_root_module(crate_symbol) {
-        Ok(crate_module_id) => Some(crate_module_id),
+    let module_id = insert_root_module(resolve, name, errors);
+    module_id.zip(module_paths)
+}
+
+fn insert_root_module(
+    resolve: &mut ResolveContext,
+    name: &str,
+    errors: &mut Vec<CompileError>,
+) -> Option<ModuleId> {
+    match resolve.insert_root_module(Symbol::insert(name)) {
+        Ok(module_id) => Some(module_id),
         Err(error) => {
             errors.push(error.into());
             None
         }
-    };
+    }
+}
 
-    let (crate_paths, crate_module_id) = match (crate_paths, crate_module_id) {
-        (Some(crate_paths), Some(crate_module_id)) => (crate_paths, crate_module_id),
-        _ => return Err((package, CompileErrorBundle { errors })),
+/// Declares the exported items of a compiled package from its interface file.
+fn import_interface(
+    resolve: &mut ResolveContext,
+    package: &mut Package,
+    module_id: ModuleId,
+    interface_file: &Path,
+) -> Result<(), InterfaceError> {
+    let symbol = resolve[module_id].path().first();
+    let error = |kind| {
+        InterfaceError {
+            package: symbol,
+            span: None,
+            kind,
+        }
     };
 
-    let mut file_modules = VecDeque::<(ModuleId, ModulePaths)>::new();
-    file_modules.push_back((crate_module_id, crate_paths));
+    let interface = PackageInterface::load(interface_file).map_err(error)?;
 
-    let mut imports = VecDeque::<Import>::new();
+    if interface.package != symbol.as_str() {
+        return Err(error(InterfaceErrorKind::NameMismatch {
+            path: interface_file.to_path_buf(),
+            found: interface.package,
+        }));
+    }
 
-    while let Some((module_id, module_paths)) = file_modules.pop_front() {
-        let module_content = match package.source_map.add_file(module_paths.path.clone()) {
-            Ok(source_file) => source_file,
-            Err(error) => {
-                errors.push(error.into());
-                continue;
+    interface.import(resolve, package, module_id)
+}
+
+/// Declares the items of a module that already exists, parsing the file modules it adds.
+pub(crate) fn p1_parse_decls(
+    resolve: &mut ResolveContext,
+    package: &mut Package,
+    module_id: ModuleId,
+    decls: Vec<Decl>,
+    child_dir: &Path,
+) -> CompileResult<()> {
+    let mut parser = PackageParser::new(resolve, package, false);
+    parser.parse_module(module_id, ModuleContent { decls }, child_dir);
+    parser.parse_file_modules();
+    parser.insert_imports();
+    parser.finish()
+}
+
+struct PackageParser<'a> {
+    resolve: &'a mut ResolveContext,
+    package: &'a mut Package,
+    errors: Vec<CompileError>,
+    file_modules: VecDeque<(ModuleId, ModulePaths)>,
+    imports: VecDeque<Import>,
+    symbol_names: FxHashMap<Symbol, bool>,
+    include_tests: bool,
+}
+
+impl<'a> PackageParser<'a> {
+    fn new(resolve: &'a mut ResolveContext, package: &'a mut Package, include_tests: bool) -> Self {
+        Self {
+            resolve,
+            package,
+            errors: Default::default(),
+            file_modules: Default::default(),
+            imports: Default::default(),
+            symbol_names: Default::default(),
+            include_tests,
+        }
+    }
+
+    /// Parses the queued file modules on the thread pool, one level of the module tree at a
+    /// time. Files are added to the source map and declared in the order they were queued, so
+    /// offsets and errors do not depend on scheduling.
+    fn parse_file_modules(&mut self) {
+        while !self.file_modules.is_empty() {
+            let file_modules = self.file_modules.drain(..).collect::<Vec<_>>();
+            let source_map = &mut self.package.source_map;
+
+            let sources = file_modules
+                .par_iter()
+                .map(|(_, module_paths)| source_map.read_file(&module_paths.path))
+                .collect::<Vec<_>>();
+
+            let first_file = source_map.files().len();
+            let mut read_file_modules = Vec::with_capacity(file_modules.len());
+
+            // Modules whose file cannot be read are left empty
+            for ((module_id, module_paths), source) in file_modules.into_iter().zip(sources) {
+                match source {
+                    Ok(source) => {
+                        source_map.add_source(module_paths.path.clone(), source);
+                        read_file_modules.push((module_id, module_paths));
+                    }
+                    Err(error) => self.errors.push(error.into()),
+                }
             }
-        };
 
+            let module_contents = SymbolTable::with_current(|symbols| {
+                source_map.files()[first_file..]
+                    .par_iter()
+                    .map(|file| symbols.enter(|| file.parse()))
+                    .collect::<Vec<_>>()
+            });
+
+            for ((module_id, module_paths), module_content) in
+                read_file_modules.into_iter().zip(module_contents)
+            {
+                self.package
+                    .file_modules
+                    .insert(module_paths.path.clone(), module_id);
+
+                match module_content {
+                    Ok(module_content) => {
+                        self.parse_module(module_id, module_content, &module_paths.child_dir);
+                    }
+                    Err(error) => self.errors.push(error.into()),
+                }
+            }
+        }
+    }
+
+    fn parse_module(
+        &mut self,
+        module_id: ModuleId,
+        module_content: ModuleContent,
+        child_dir: &Path,
+    ) {
         let mut modules = VecDeque::<(ModuleId, ModuleContent)>::new();
         modules.push_back((module_id, module_content));
 
         while let Some((module_id, module)) = modules.pop_front() {
             for decl in module.decls {
+                let link_name_span = get_link_name_span(&decl);
+
+                let DeclAttrs {
+                    fn_attrs,
+                    struct_layout,
+                    is_test,
+                } = validate_decl_attributes(&decl).unwrap_or_else(|error| {
+                    self.errors.push(error.into());
+                    DeclAttrs::default()
+                });
+
+                // Test functions and modules are left out of builds that do not run tests
+                if is_test && !self.include_tests {
+                    continue;
+                }
+
                 match decl.kind {
                     DeclKind::Item(item_decl) => {
                         let item_decl_span = item_decl.span();
 
                         match item_decl.item {
                             Item::Module(child_module) => {
-                                let child_module_id = match resolve.insert_module(
+                                let child_module_id = match self.resolve.insert_module(
                                     module_id,
                                     decl.is_exported,
                                     item_decl.ident.symbol,
                                 ) {
                                     Ok(child_module_id) => child_module_id,
                                     Err(error) => {
-                                        errors.push(error.into());
+                                        self.errors.push(error.into());
                                         continue;
                                     }
                                 };
@@ -94,12 +336,12 @@ pub fn p1_parse(
                                     }
                                     ModuleKind::External => {
                                         let child_module_paths = match ModulePaths::for_child(
-                                            &module_paths.child_dir,
+                                            child_dir,
                                             item_decl.ident.symbol.as_str(),
                                         ) {
                                             Ok(child_module_paths) => child_module_paths,
                                             Err(error) => {
-                                                errors.push(CompileError::from(ModuleError {
+                                                self.errors.push(CompileError::from(ModuleError {
                                                     span: Some(item_decl_span),
                                                     module_name: item_decl.ident.symbol,
                                                     error,
@@ -108,25 +350,25 @@ pub fn p1_parse(
                                             }
                                         };
 
-                                        file_modules
+                                        self.file_modules
                                             .push_back((child_module_id, child_module_paths));
                                     }
                                 }
                             }
                             Item::Alias(item) => {
-                                let item_id = match resolve.declare_alias(
+                                let item_id = match self.resolve.declare_alias(
                                     module_id,
                                     decl.is_exported,
                                     item_decl.ident.symbol,
                                 ) {
                                     Ok(item_id) => item_id,
                                     Err(error) => {
-                                        errors.push(error.into());
+                                        self.errors.push(error.into());
                                         continue;
                                     }
                                 };
 
-                                package.aliases.push(Alias {
+                                self.package.aliases.push(Alias {
                                     span: item_decl_span,
                                     module_id,
                                     item_id,
@@ -134,21 +376,139 @@ pub fn p1_parse(
                                     item,
                                 });
                             }
+                            Item::Struct(item) if item.ty_params.is_some() => {
+                                for (decl, _, _) in
+                                    collect_methods(&item.impl_blocks, &mut self.errors)
+                                {
+                                    if let Some(attribute) = decl.attributes.first() {
+                                        self.errors.push(CompileError::from(AttributeError {
+                                            span: attribute.span(),
+                                            kind: AttributeErrorKind::NotApplicable {
+                                                name: attribute.ident.symbol,
+                                            },
+                                        }));
+                                    }
+                                }
+
+                                let item_id = match declare_generic(
+                                    self.resolve,
+                                    module_id,
+                                    decl.is_exported,
+                                    item_decl.ident.symbol,
+                                    GenericKind::Struct {
+                                        has_body: item.has_body,
+                                        layout: struct_layout,
+                                    },
+                                    item.ty_params.as_ref().unwrap(),
+                                ) {
+                                    Ok(item_id) => item_id,
+                                    Err(error) => {
+                                        self.errors.push(error);
+                                        continue;
+                                    }
+                                };
+
+                                let generic_id = self.resolve[item_id].as_generic_id().unwrap();
+                                self.package
+                                    .generics
+                                    .insert(generic_id, GenericItem::Struct(item));
+                            }
                             Item::Struct(item) => {
-                                let item_id = match resolve.declare_struct(
+                                let item_id = match self.resolve.declare_struct(
                                     module_id,
                                     decl.is_exported,
                                     item_decl.ident.symbol,
                                     item.has_body,
+                                    struct_layout,
                                 ) {
                                     Ok(item_id) => item_id,
                                     Err(error) => {
-                                        errors.push(error.into());
+                                        self.errors.push(error.into());
                                         continue;
                                     }
                                 };
 
-                                package.structs.push(Struct {
+                                for (decl, item_decl, fn_expr) in
+                                    collect_methods(&item.impl_blocks, &mut self.errors)
+                                {
+                                    let method_item_id = match self.resolve.declare_method(
+                                        item_id,
+                                        module_id,
+                                        decl.is_exported,
+                                        item_decl.ident.symbol,
+                                    ) {
+                                        Ok(method_item_id) => method_item_id,
+                                        Err(error) => {
+                                            self.errors.push(error.into());
+                                            continue;
+                                        }
+                                    };
+
+                                    match validate_method_attributes(decl) {
+                                        Ok(method_attrs) => {
+                                            self.insert_fn_attrs(
+                                                get_link_name_span(decl)
+                                                    .unwrap_or(item_decl.span()),
+                                                method_item_id,
+                                                method_attrs,
+                                                None,
+                                            );
+                                        }
+                                        Err(error) => self.errors.push(error.into()),
+                                    }
+
+                                    self.package.methods.push(Method {
+                                        span: item_decl.span(),
+                                        module_id,
+                                        item_id: method_item_id,
+                                        ty: item_decl.ty.clone(),
+                                        item: fn_expr.clone(),
+                                    });
+                                }
+
+                                self.package.structs.push(Struct {
+                                    span: item_decl_span,
+                                    module_id,
+                                    item_id,
+                                    ty: item_decl.ty,
+                                    item,
+                                });
+                            }
+                            Item::Union(item) => {
+                                let item_id = match self.resolve.declare_union(
+                                    module_id,
+                                    decl.is_exported,
+                                    item_decl.ident.symbol,
+                                ) {
+                                    Ok(item_id) => item_id,
+                                    Err(error) => {
+                                        self.errors.push(error.into());
+                                        continue;
+                                    }
+                                };
+
+                                self.package.unions.push(Union {
+                                    span: item_decl_span,
+                                    module_id,
+                                    item_id,
+                                    ty: item_decl.ty,
+                                    item,
+                                });
+                            }
+                            Item::Interface(item) => {
+                                let item_id = match self.resolve.declare_interface(
+                                    module_id,
+                                    decl.is_exported,
+                                    item_decl.ident.symbol,
+                                ) {
+                                    Ok(item_id) => item_id,
+                                    Err(error) => {
+                                        self.errors.push(error.into());
+                                        continue;
+                                    }
+                                };
+
+                                self.package.interfaces.push(Interface {
                                     span: item_decl_span,
                                     module_id,
                                     item_id,
@@ -157,19 +517,19 @@ pub fn p1_parse(
                                 });
                             }
                             Item::Enum(item) => {
-                                let item_id = match resolve.declare_enum(
+                                let item_id = match self.resolve.declare_enum(
                                     module_id,
                                     decl.is_exported,
                                     item_decl.ident.symbol,
                                 ) {
                                     Ok(item_id) => item_id,
                                     Err(error) => {
-                                        errors.push(error.into());
+                                        self.errors.push(error.into());
                                         continue;
                                     }
                                 };
 
-                                package.enums.push(Enum {
+                                self.package.enums.push(Enum {
                                     span: item_decl_span,
                                     module_id,
                                     item_id,
@@ -178,7 +538,15 @@ pub fn p1_parse(
                                 });
                             }
                             Item::ExternFn(item) => {
-                                let item_id = match resolve.insert_global_binding(
+                                if let Some(ty_params) = item.prototype.ty_params.as_ref() {
+                                    self.errors.push(CompileError::from(AstError::new(
+                                        ty_params.span(),
+                                        TyDefError::TyParamsNotAllowed,
+                                    )));
+                                    continue;
+                                }
+
+                                let item_id = match self.resolve.insert_global_binding(
                                     module_id,
                                     decl.is_exported,
                                     Mutability::Const,
@@ -186,12 +554,19 @@ pub fn p1_parse(
                                 ) {
                                     Ok(item_id) => item_id,
                                     Err(error) => {
-                                        errors.push(error.into());
+                                        self.errors.push(error.into());
                                         continue;
                                     }
                                 };
 
-                                package.extern_fns.push(ExternFn {
+                                self.insert_fn_attrs(
+                                    link_name_span.unwrap_or(item_decl_span),
+                                    item_id,
+                                    fn_attrs,
+                                    Some(item_decl.ident.symbol),
+                                );
+
+                                self.package.extern_fns.push(ExternFn {
                                     span: item_decl_span,
                                     module_id,
                                     item_id,
@@ -200,7 +575,39 @@ pub fn p1_parse(
                                 });
                             }
                             Item::Const(item) => {
-                                let item_id = match resolve.insert_global_binding(
+                                if let Expr::Fn(fn_expr) = &item.expr {
+                                    if let Some(ty_params) = fn_expr.prototype.ty_params.as_ref() {
+                                        let item_id = match declare_generic(
+                                            self.resolve,
+                                            module_id,
+                                            decl.is_exported,
+                                            item_decl.ident.symbol,
+                                            GenericKind::Fn,
+                                            ty_params,
+                                        ) {
+                                            Ok(item_id) => item_id,
+                                            Err(error) => {
+                                                self.errors.push(error);
+                                                continue;
+                                            }
+                                        };
+
+                                        let generic_id =
+                                            self.resolve[item_id].as_generic_id().unwrap();
+                                        self.package.generics.insert(
+                                            generic_id,
+                                            GenericItem::Fn {
+                                                ty: item_decl.ty,
+                                                expr: fn_expr.clone(),
+                                                attrs: fn_attrs,
+                                            },
+                                        );
+
+                                        continue;
+                                    }
+                                }
+
+                                let item_id = match self.resolve.insert_global_binding(
                                     module_id,
                                     decl.is_exported,
                                     Mutability::Const,
@@ -208,12 +615,26 @@ pub fn p1_parse(
                                 ) {
                                     Ok(item_id) => item_id,
                                     Err(error) => {
-                                        errors.push(error.into());
+                                        self.errors.push(error.into());
                                         continue;
                                     }
                                 };
 
-                                package.consts.push(Const {
+                                if is_test {
+                                    self.package.tests.push(TestFn {
+                                        span: item_decl_span,
+                                        item_id,
+                                    });
+                                }
+
+                                self.insert_fn_attrs(
+                                    link_name_span.unwrap_or(item_decl_span),
+                                    item_id,
+                                    fn_attrs,
+                                    None,
+                                );
+
+                                self.package.consts.push(Const {
                                     span: item_decl_span,
                                     module_id,
                                     item_id,
@@ -223,6 +644,12 @@ pub fn p1_parse(
                             }
                         }
                     }
+                    DeclKind::StaticAssert(static_assert_decl) => {
+                        self.package.static_asserts.push(StaticAssert {
+                            module_id,
+                            decl: static_assert_decl,
+                        });
+                    }
                     DeclKind::Use(use_decl) => {
                         let path = use_decl
                             .path
@@ -233,7 +660,7 @@ pub fn p1_parse(
 
                         let alias = use_decl.alias.map(|alias| alias.symbol);
 
-                        imports.push_back(Import {
+                        self.imports.push_back(Import {
                             span: use_decl.span(),
                             module_id,
                             is_exported: decl.is_exported,
@@ -246,44 +673,175 @@ pub fn p1_parse(
         }
     }
 
-    let mut import_fail_count = 0_usize;
-    while let Some(import) = imports.pop_front() {
-        match resolve.insert_use(
-            import.module_id,
-            import.is_exported,
-            import.path.as_symbol_slice(),
-            import.alias,
-        ) {
-            Ok(_) => import_fail_count = 0,
-            Err(error) => {
-                import_fail_count += 1;
-
-                if error.kind == ResolveErrorKind::SymbolNotFound {
-                    imports.push_back(import);
-                } else {
-                    errors.push(CompileError::from(ImportError {
-                        span: import.span,
-                        path: import.path,
-                    }));
-                }
+    /// Records the attributes of a function. A function definition cannot share its symbol
+    /// name with another function, as LLVM would silently rename one of them. Extern
+    /// functions are named after their declaration unless they have a link name.
+    fn insert_fn_attrs(
+        &mut self,
+        span: Span,
+        item_id: ItemId,
+        fn_attrs: FnAttrs,
+        extern_name: Option<Symbol>,
+    ) {
+        let is_defined = extern_name.is_none();
+
+        if let Some(symbol_name) = fn_attrs.link_name.or(extern_name) {
+            let was_defined = self.symbol_names.get(&symbol_name).copied();
+
+            if was_defined.is_some_and(|was_defined| is_defined || was_defined) {
+                self.errors.push(CompileError::from(AttributeError {
+                    span,
+                    kind: AttributeErrorKind::DuplicateLinkName {
+                        link_name: symbol_name,
+                    },
+                }));
+            }
+
+            *self.symbol_names.entry(symbol_name).or_default() |= is_defined;
+        }
+
+        if fn_attrs.is_direct_call_only() {
+            let binding_id = self.resolve[item_id].as_binding_id().unwrap();
+            self.resolve.mark_direct_call_only(binding_id);
+        }
+
+        self.package.fn_attrs.insert(item_id, fn_attrs);
+    }
+
+    fn insert_imports(&mut self) {
+        let mut import_fail_count = 0_usize;
+        while let Some(import) = self.imports.pop_front() {
+            match self.resolve.insert_use(
+                import.module_id,
+                import.is_exported,
+                import.path.as_symbol_slice(),
+                import.alias,
+            ) {
+                Ok(_) => import_fail_count = 0,
+                Err(error) => {
+                    import_fail_count += 1;
 
-                if import_fail_count >= imports.len() {
-                    break;
+                    if error.kind == ResolveErrorKind::SymbolNotFound {
+                        self.imports.push_back(import);
+                    } else {
+                        self.errors.push(CompileError::from(ImportError {
+                            span: import.span,
+                            path: import.path,
+                        }));
+                    }
+
+                    if import_fail_count >= self.imports.len() {
+                        break;
+                    }
                 }
             }
         }
+
+        for import in self.imports.drain(..) {
+            self.errors.push(CompileError::from(ImportError {
+                span: import.span,
+                path: import.path,
+            }));
+        }
     }
 
-    for import in imports.drain(..) {
-        errors.push(CompileError::from(ImportError {
-            span: import.span,
-            path: import.path,
-        }));
+    fn finish(self) -> CompileResult<()> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CompileErrorBundle {
+                errors: self.errors,
+            })
+        }
     }
+}
+
+fn get_link_name_span(decl: &Decl) -> Option<Span> {
+    decl.attributes
+        .iter()
+        .find(|attribute| attribute.ident.symbol == sym::ATTR_LINK_NAME)
+        .map(|attribute| attribute.span())
+}
+
+/// Returns the functions declared in the impl blocks of a struct, reporting any other
+/// declarations.
+fn collect_methods<'a>(
+    impl_blocks: &'a [ImplBlock],
+    errors: &mut Vec<CompileError>,
+) -> Vec<(&'a Decl, &'a ItemDecl, &'a FnExpr)> {
+    let mut methods = Vec::<(&Decl, &ItemDecl, &FnExpr)>::new();
+
+    for decl in impl_blocks
+        .iter()
+        .flat_map(|impl_block| impl_block.decls.iter())
+    {
+        let fn_decl = decl.kind.as_item_decl().and_then(|item_decl| {
+            match &item_decl.item {
+                Item::Const(ConstItem {
+                    expr: Expr::Fn(fn_expr),
+                }) => Some((item_decl, fn_expr)),
+                _ => None,
+            }
+        });
+
+        let Some((item_decl, fn_expr)) = fn_decl else {
+            errors.push(CompileError::from(ImplError { span: decl.span }));
+            continue;
+        };
+
+        if let Some(ty_params) = fn_expr.prototype.ty_params.as_ref() {
+            errors.push(CompileError::from(AstError::new(
+                ty_params.span(),
+                TyDefError::TyParamsNotAllowed,
+            )));
+            continue;
+        }
 
-    if errors.is_empty() {
-        Ok(package)
-    } else {
-        Err((package, CompileErrorBundle { errors }))
+        if methods
+            .iter()
+            .any(|(_, method, _)| method.ident.symbol == item_decl.ident.symbol)
+        {
+            errors.push(CompileError::from(AstError::new(
+                item_decl.ident.span(),
+                ResolveError {
+                    symbol: item_decl.ident.symbol,
+                    kind: ResolveErrorKind::SymbolAlreadyDefined,
+                },
+            )));
+            continue;
+        }
+
+        methods.push((decl, item_decl, fn_expr));
     }
+
+    methods
+}
+
+fn declare_generic(
+    resolve: &mut ResolveContext,
+    module_id: ModuleId,
+    is_exported: bool,
+    symbol: Symbol,
+    kind: GenericKind,
+    ty_params: &TyParamList,
+) -> Result<ItemId, CompileError> {
+    let mut ty_param_symbols = SmallVec::<[Symbol; 2]>::new();
+
+    for ty_param in ty_params.params.iter() {
+        if ty_param_symbols.contains(&ty_param.ident.symbol) {
+            return Err(CompileError::from(AstError::new(
+                ty_param.span(),
+                ResolveError {
+                    symbol: ty_param.ident.symbol,
+                    kind: ResolveErrorKind::SymbolAlreadyDefined,
+                },
+            )));
+        }
+
+        ty_param_symbols.push(ty_param.ident.symbol);
+    }
+
+    resolve
+        .declare_generic(module_id, is_exported, symbol, kind, ty_param_symbols)
+        .map_err(CompileError::from)
 }
@@ -1,33 +1,58 @@
 use crate::{
-    Alias, CompileError, CompileErrorBundle, CompileResult, DefineError, DefineItem, Package,
-    Struct,
+    Alias, CompileError, CompileErrorBundle, CompileResult, Const, DefineError, DefineItem,
+    Package, Struct, Union,
 };
-use cool_ast::AstGenerator;
+use cool_ast::{AstGenerator, AstResult, GenericItem};
+use cool_parser::Expr;
 use cool_resolve::{ResolveContext, TyId};
 use smallvec::SmallVec;
 use std::collections::VecDeque;
 
 pub fn p2_define_tys(package: &Package, resolve: &mut ResolveContext) -> CompileResult<()> {
-    let mut ast = AstGenerator::new(resolve);
+    let mut ast = AstGenerator::new(resolve, &package.generics);
+    let mut errors = Vec::<CompileError>::new();
+
+    // Implementations must be known before any generic is instantiated with bounds
+    declare_interface_impls(&mut ast, package, &mut errors);
+
     let mut aliases = package.aliases.iter().collect::<VecDeque<_>>();
     let mut structs = package.structs.iter().collect::<VecDeque<_>>();
+    let mut unions = package.unions.iter().collect::<VecDeque<_>>();
+    let mut consts = package
+        .consts
+        .iter()
+        .filter(|const_item| !matches!(const_item.item.expr, Expr::Fn(_)))
+        .collect::<VecDeque<_>>();
     let mut ty_ids = VecDeque::<TyId>::new();
 
     loop {
         let mut made_progress = define_aliases(&mut ast, &mut aliases);
         made_progress |= define_structs(&mut ast, &mut structs);
+        made_progress |= define_unions(&mut ast, &mut unions);
+        made_progress |= define_generic_structs(&mut ast);
         made_progress |= define_ty_ids(&mut ast, &mut ty_ids);
+        made_progress |= define_consts(&mut ast, &mut consts);
 
         if !made_progress {
             break;
         }
     }
 
-    let mut errors = Vec::<CompileError>::new();
     report_undefinable_items(&mut errors, aliases);
     report_undefinable_items(&mut errors, structs);
+    report_undefinable_items(&mut errors, unions);
     report_undefinable_ty_ids(&mut errors, ty_ids);
 
+    // Constants can still be defined once function types are known, so their errors are only
+    // reported here if they could have caused the errors above
+    if !errors.is_empty() {
+        for const_item in consts {
+            if let Err(error) = define_const(&mut ast, const_item) {
+                errors.push(error.into());
+            }
+        }
+    }
+
     if !errors.is_empty() {
         return Err(CompileErrorBundle { errors });
     }
@@ -35,6 +60,34 @@ pub fn p2_define_tys(package: &Package, resolve: &mut ResolveContext) -> Compile
     Ok(())
 }
 
+fn declare_interface_impls(
+    ast: &mut AstGenerator,
+    package: &Package,
+    errors: &mut Vec<CompileError>,
+) {
+    for struct_item in package.structs.iter() {
+        if let Err(error) = ast.declare_interface_impls(
+            struct_item.module_id,
+            struct_item.item_id,
+            &struct_item.item.impl_blocks,
+        ) {
+            errors.push(error.into());
+        }
+    }
+
+    for (&generic_id, generic_item) in package.generics.iter() {
+        let GenericItem::Struct(struct_item) = generic_item else {
+            continue;
+        };
+
+        let module_id = ast.resolve[generic_id].module_id;
+
+        if let Err(error) = ast.resolve_impl_interfaces(module_id, &struct_item.impl_blocks) {
+            errors.push(error.into());
+        }
+    }
+}
+
 fn define_aliases(ast: &mut AstGenerator, aliases: &mut VecDeque<&Alias>) -> bool {
     let start_len = aliases.len();
 
@@ -92,6 +145,62 @@ fn define_structs(ast: &mut AstGenerator, structs: &mut VecDeque<&Struct>) -> bo
     structs.len() < start_len
 }
 
+fn define_unions(ast: &mut AstGenerator, unions: &mut VecDeque<&Union>) -> bool {
+    let start_len = unions.len();
+
+    for _ in 0..start_len {
+        let Some(union_item) = unions.pop_front() else {
+            break;
+        };
+
+        let Ok(fields) = union_item
+            .item
+            .fields
+            .iter()
+            .map(|field| {
+                ast.resolve_ty(union_item.module_id, &field.ty)
+                    .map(|ty_id| (field.ident.symbol, ty_id))
+            })
+            .collect::<Result<SmallVec<[_; 7]>, _>>()
+        else {
+            unions.push_back(union_item);
+            continue;
+        };
+
+        if ast
+            .resolve
+            .define_union(union_item.item_id, fields)
+            .is_err()
+        {
+            unions.push_back(union_item);
+        }
+    }
+
+    unions.len() < start_len
+}
+
+fn define_generic_structs(ast: &mut AstGenerator) -> bool {
+    let undefined_item_ids = ast
+        .resolve
+        .generic_instances()
+        .iter()
+        .map(|instance| instance.item_id)
+        .filter(|&item_id| {
+            ast.resolve[item_id]
+                .as_ty_id()
+                .is_some_and(|ty_id| ast.resolve.get_ty_def(ty_id).is_none())
+        })
+        .collect::<Vec<_>>();
+
+    let mut made_progress = false;
+
+    for item_id in undefined_item_ids {
+        made_progress |= ast.define_generic_struct(item_id).is_ok();
+    }
+
+    made_progress
+}
+
 fn define_ty_ids(ast: &mut AstGenerator, ty_ids: &mut VecDeque<TyId>) -> bool {
     ty_ids.clear();
     ty_ids.extend(ast.resolve.iter_undefined_value_ty_ids());
@@ -111,6 +220,32 @@ fn define_ty_ids(ast: &mut AstGenerator, ty_ids: &mut VecDeque<TyId>) -> bool {
     ty_ids.len() < start_len
 }
 
+fn define_consts(ast: &mut AstGenerator, consts: &mut VecDeque<&Const>) -> bool {
+    let start_len = consts.len();
+
+    for _ in 0..start_len {
+        let Some(const_item) = consts.pop_front() else {
+            break;
+        };
+
+        if define_const(ast, const_item).is_err() {
+            consts.push_back(const_item);
+        }
+    }
+
+    consts.len() < start_len
+}
+
+#[inline]
+pub(crate) fn define_const(ast: &mut AstGenerator, const_item: &Const) -> AstResult {
+    ast.define_const(
+        const_item.module_id,
+        const_item.item_id,
+        const_item.ty.as_ref(),
+        &const_item.item.expr,
+    )
+}
+
 fn report_undefinable_items<'a, I>(
     errors: &mut Vec<CompileError>,
     items: impl IntoIterator<Item = &'a DefineItem<I>>,
@@ -1,10 +1,12 @@
+use crate::passes::p2_define_tys::define_const;
 use crate::{CompileError, CompileErrorBundle, CompileResult, Package};
-use cool_ast::AstGenerator;
+use cool_ast::{AstError, AstGenerator, TyError, TyErrorKind};
 use cool_parser::Expr;
-use cool_resolve::ResolveContext;
+use cool_resolve::{FnAbi, ResolveContext};
+use cool_span::Section;
 
 pub fn p3_define_fn_tys(package: &Package, resolve: &mut ResolveContext) -> CompileResult<()> {
-    let mut ast = AstGenerator::new(resolve);
+    let mut ast = AstGenerator::new(resolve, &package.generics);
     let mut errors = Vec::<CompileError>::new();
 
     for extern_fn in package.extern_fns.iter() {
@@ -43,6 +45,95 @@ pub fn p3_define_fn_tys(package: &Package, resolve: &mut ResolveContext) -> Comp
         }
     }
 
+    // Tests are called by the harness without arguments
+    for test in package.tests.iter() {
+        let binding_id = ast.resolve[test.item_id].as_binding_id().unwrap();
+        let ty_id = ast.resolve[binding_id].ty_id;
+        let test_ty_id = ast.resolve.mk_fn(FnAbi::Cool, [], false, ast.tys().unit);
+
+        if !ty_id.is_infer() && ty_id != test_ty_id {
+            errors.push(CompileError::from(AstError::new(
+                test.span,
+                TyError {
+                    ty_id,
+                    kind: TyErrorKind::TyMismatch {
+                        expected_ty_id: test_ty_id,
+                    },
+                },
+            )));
+        }
+    }
+
+    for method in package.methods.iter() {
+        let frame_id = ast.resolve.add_method_frame(method.item_id);
+
+        let fn_expr_ty_id =
+            match ast.resolve_fn_prototype(frame_id, &method.ty, &method.item.prototype) {
+                Ok(fn_expr_ty_id) => fn_expr_ty_id,
+                Err(error) => {
+                    errors.push(error.into());
+                    continue;
+                }
+            };
+
+        let binding_id = ast.resolve[method.item_id].as_binding_id().unwrap();
+        ast.resolve.set_binding_ty(binding_id, fn_expr_ty_id);
+    }
+
+    // Constants left undefined by the previous pass are reported now that function types are known
+    for const_item in package.consts.iter() {
+        let binding_id = ast.resolve[const_item.item_id].as_binding_id().unwrap();
+
+        if matches!(const_item.item.expr, Expr::Fn(_))
+            || ast.resolve.get_const_value(binding_id).is_some()
+        {
+            continue;
+        }
+
+        if let Err(error) = define_const(&mut ast, const_item) {
+            errors.push(error.into());
+        }
+    }
+
+    for static_assert in package.static_asserts.iter() {
+        if let Err(error) = ast.check_static_assert(static_assert.module_id, &static_assert.decl) {
+            errors.push(error.into());
+        }
+    }
+
+    for interface in package.interfaces.iter() {
+        if let Err(error) =
+            ast.define_interface(interface.module_id, interface.item_id, &interface.item)
+        {
+            errors.push(error.into());
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(CompileErrorBundle { errors });
+    }
+
+    // Implementations of generic struct instances are checked when first used
+    for struct_item in package.structs.iter() {
+        let impl_blocks = struct_item
+            .item
+            .impl_blocks
+            .iter()
+            .filter(|impl_block| impl_block.interface.is_some());
+
+        let interface_item_ids = ast
+            .resolve_impl_interfaces(struct_item.module_id, &struct_item.item.impl_blocks)
+            .unwrap();
+
+        for (impl_block, interface_item_id) in impl_blocks.zip(interface_item_ids) {
+            if let Err(error) =
+                ast.define_interface_impl(impl_block.span(), struct_item.item_id, interface_item_id)
+            {
+                errors.push(error.into());
+            }
+        }
+    }
+
     if !errors.is_empty() {
         return Err(CompileErrorBundle { errors });
     }
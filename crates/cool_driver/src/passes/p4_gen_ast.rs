@@ -1,10 +1,32 @@
 use crate::{CompileError, CompileErrorBundle, CompileResult, Package};
-use cool_ast::{AstGenerator, ExternFnAst, FnAst, PackageAst};
+use cool_ast::{AstGenerator, ExternFnAst, FnAst, PackageAst, SourceIndex};
 use cool_parser::Expr;
 use cool_resolve::ResolveContext;
 
 pub fn p4_gen_ast(package: &Package, resolve: &mut ResolveContext) -> CompileResult<PackageAst> {
-    let mut ast = AstGenerator::new(resolve);
+    p4_gen_ast_from(package, resolve, 0, None)
+}
+
+/// Generates the package while recording the spans of its expressions and local bindings,
+/// including the ones of functions that failed to generate.
+pub fn p4_gen_ast_indexed(
+    package: &Package,
+    resolve: &mut ResolveContext,
+    source_index: &mut SourceIndex,
+) -> CompileResult<PackageAst> {
+    p4_gen_ast_from(package, resolve, 0, Some(source_index))
+}
+
+/// Generates the package and the generic function instances created after the first
+/// `instance_index` ones.
+pub(crate) fn p4_gen_ast_from(
+    package: &Package,
+    resolve: &mut ResolveContext,
+    mut instance_index: usize,
+    source_index: Option<&mut SourceIndex>,
+) -> CompileResult<PackageAst> {
+    let mut ast = AstGenerator::new(resolve, &package.generics);
+    ast.source_index = source_index;
     let mut errors = Vec::<CompileError>::new();
     let mut extern_fns = Vec::<ExternFnAst>::new();
     let mut fns = Vec::<FnAst>::new();
@@ -12,13 +34,15 @@ pub fn p4_gen_ast(package: &Package, resolve: &mut ResolveContext) -> CompileRes
     for extern_fn in package.extern_fns.iter() {
         let extern_fn_binding_id = ast.resolve[extern_fn.item_id].as_binding_id().unwrap();
         let extern_fn_ty_id = ast.resolve[extern_fn_binding_id].ty_id;
-        let extern_fn_ast = match ast.gen_extern_fn(extern_fn.item_id, extern_fn_ty_id) {
-            Ok(extern_fn_ast) => extern_fn_ast,
-            Err(error) => {
-                errors.push(error.into());
-                continue;
-            }
-        };
+        let extern_fn_attrs = package.fn_attrs[&extern_fn.item_id];
+        let extern_fn_ast =
+            match ast.gen_extern_fn(extern_fn.item_id, extern_fn_ty_id, extern_fn_attrs) {
+                Ok(extern_fn_ast) => extern_fn_ast,
+                Err(error) => {
+                    errors.push(error.into());
+                    continue;
+                }
+            };
 
         extern_fns.push(extern_fn_ast);
     }
@@ -27,24 +51,68 @@ pub fn p4_gen_ast(package: &Package, resolve: &mut ResolveContext) -> CompileRes
         if let Expr::Fn(fn_expr) = &const_item.item.expr {
             let fn_binding_id = ast.resolve[const_item.item_id].as_binding_id().unwrap();
             let fn_ty_id = ast.resolve[fn_binding_id].ty_id;
-            let fn_ast =
-                match ast.gen_fn(const_item.item_id, const_item.module_id, fn_ty_id, fn_expr) {
-                    Ok(fn_ast) => fn_ast,
-                    Err(error) => {
-                        errors.push(error.into());
-                        continue;
-                    }
-                };
+            let fn_attrs = package.fn_attrs[&const_item.item_id];
+            let fn_ast = match ast.gen_fn(
+                const_item.item_id,
+                const_item.module_id,
+                fn_ty_id,
+                fn_expr,
+                fn_attrs,
+            ) {
+                Ok(fn_ast) => fn_ast,
+                Err(error) => {
+                    errors.push(error.into());
+                    continue;
+                }
+            };
 
             fns.push(fn_ast);
         }
     }
 
+    for method in package.methods.iter() {
+        let fn_binding_id = ast.resolve[method.item_id].as_binding_id().unwrap();
+        let fn_ty_id = ast.resolve[fn_binding_id].ty_id;
+        let frame_id = ast.resolve.add_method_frame(method.item_id);
+        let fn_attrs = package
+            .fn_attrs
+            .get(&method.item_id)
+            .copied()
+            .unwrap_or_default();
+
+        match ast.gen_fn(method.item_id, frame_id, fn_ty_id, &method.item, fn_attrs) {
+            Ok(fn_ast) => fns.push(fn_ast),
+            Err(error) => errors.push(error.into()),
+        }
+    }
+
+    // Generic function instances can create further instances while being generated
+    while let Some(instance) = ast.resolve.generic_instances().get(instance_index).cloned() {
+        instance_index += 1;
+
+        // Skip struct instances and functions whose prototype failed to resolve. Methods of
+        // struct instances are only added once their prototype was resolved
+        let is_fn_instance = ast.resolve[instance.item_id]
+            .as_binding_id()
+            .is_some_and(|binding_id| !ast.resolve[binding_id].ty_id.is_infer());
+
+        if !is_fn_instance {
+            continue;
+        }
+
+        match ast.gen_generic_fn(&instance) {
+            Ok(fn_ast) => fns.push(fn_ast),
+            Err(error) => errors.push(error.into()),
+        }
+    }
+
     if errors.is_empty() {
         Ok(PackageAst {
             fns,
             extern_fns,
+            declared_fns: package.imported_fns.clone(),
             defer_stmts: ast.defer_stmts,
+            tests: package.tests.iter().map(|test| test.item_id).collect(),
         })
     } else {
         Err(CompileErrorBundle { errors })
@@ -1,11 +1,12 @@
-use crate::{CodeGeneratorContext, CompileOptions};
+use crate::{CodeGeneratorContext, CompileOptions, SourceMap};
 use cool_ast::PackageAst;
-use cool_codegen::CodeGenerator;
+use cool_codegen::{CodeGenerator, CodeGeneratorOptions};
 use cool_resolve::ResolveContext;
 use inkwell::module::Module;
 
 pub fn p5_gen_code<'a>(
     package: &'a PackageAst,
+    source_map: &'a SourceMap,
     codegen: &'a CodeGeneratorContext,
     resolve: &'a ResolveContext,
     options: &CompileOptions,
@@ -16,8 +17,13 @@ pub fn p5_gen_code<'a>(
         &codegen.target_data,
         package,
         resolve,
+        source_map,
         &options.crate_name,
         options.crate_root_file.to_str().unwrap(),
+        CodeGeneratorOptions {
+            overflow_checks: options.overflow_checks,
+            test_harness: options.test,
+        },
     );
 
     codegen.gen_module()
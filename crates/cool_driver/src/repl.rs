@@ -0,0 +1,328 @@
+use crate::jit::{create_jit_engine, jit_error};
+use crate::{
+    catch_internal_error, p1_parse_decls, p2_define_tys, p3_define_fn_tys, p4_gen_ast_from,
+    p5_gen_code, CodeGeneratorContext, CompileError, CompileOptions, CompileResult, Package,
+    Session, SourceMap,
+};
+use cool_ast::{AstGenerator, ExternFnAst, FnAttrs, GenericItemMap};
+use cool_lexer::{tk, Symbol, SymbolTable, TokenKind, TokenStream, Tokenizer};
+use cool_parser::{Decl, Expr, ParseResult, Parser};
+use cool_resolve::{FloatTy, IntTy, ModuleId, Mutability, TyId, ValueTy};
+use inkwell::execution_engine::ExecutionEngine;
+use std::mem;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub enum ReplOutput {
+    /// The input ends before the declaration or expression does.
+    Incomplete,
+    /// The input declared items or evaluated to a value that is not displayed.
+    Empty,
+    /// The value of the input expression, followed by its type.
+    Value(String),
+}
+
+enum ReplInput {
+    Decl(Decl),
+    Expr(Expr),
+}
+
+/// Interactive session that compiles every input to a separate module and adds it to a JIT.
+/// Items declared by an input stay available to the following ones, but items declared by an
+/// input that failed to compile cannot be used.
+pub struct Repl<'a> {
+    codegen: &'a CodeGeneratorContext,
+    session: Session,
+    options: CompileOptions,
+    module_id: ModuleId,
+    engine: ExecutionEngine<'a>,
+    source_map: SourceMap,
+    generics: GenericItemMap,
+    extern_fns: Vec<ExternFnAst>,
+    defined_fns: Vec<ExternFnAst>,
+    instance_count: usize,
+    expr_count: u32,
+}
+
+impl<'a> Repl<'a> {
+    /// Creates a session whose items live in a root module named after the crate. The crate
+    /// root file is only used to name the inputs in diagnostics.
+    pub fn new(
+        codegen: &'a CodeGeneratorContext,
+        mut session: Session,
+        options: CompileOptions,
+    ) -> CompileResult<Self> {
+        let module_id = session
+            .enter(|resolve| resolve.insert_root_module(Symbol::insert(&options.crate_name)))
+            .map_err(CompileError::from)?;
+
+        let module = codegen.context.create_module(&options.crate_name);
+        module.set_triple(&codegen.target_triple);
+        let engine = create_jit_engine(&module)?;
+
+        Ok(Self {
+            codegen,
+            session,
+            options,
+            module_id,
+            engine,
+            source_map: Default::default(),
+            generics: Default::default(),
+            extern_fns: Default::default(),
+            defined_fns: Default::default(),
+            instance_count: 0,
+            expr_count: 0,
+        })
+    }
+
+    #[inline]
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /// Returns the symbol table that must be entered to display the errors of the session.
+    #[inline]
+    pub fn symbols(&self) -> &Arc<SymbolTable> {
+        self.session.symbols()
+    }
+
+    /// Compiles and runs a declaration or an expression. Incomplete inputs are not consumed and
+    /// should be passed again once more source is available.
+    pub fn eval(&mut self, source: &str) -> CompileResult<ReplOutput> {
+        let symbols = Arc::clone(self.session.symbols());
+        symbols.enter(|| self.eval_source(source))
+    }
+
+    fn eval_source(&mut self, source: &str) -> CompileResult<ReplOutput> {
+        let input = match parse_input(source, self.source_map.end_offset()) {
+            Err(error) if error.found.kind == TokenKind::Eof => {
+                return Ok(ReplOutput::Incomplete);
+            }
+            input => {
+                self.source_map
+                    .add_source(self.options.crate_root_file.clone(), source.to_owned());
+
+                input.map_err(CompileError::from)?
+            }
+        };
+
+        let mut package = Package {
+            source_map: mem::take(&mut self.source_map),
+            generics: mem::take(&mut self.generics),
+            ..Default::default()
+        };
+
+        let output = catch_internal_error(|| self.eval_input(&mut package, input));
+
+        self.source_map = package.source_map;
+        self.generics = package.generics;
+        self.instance_count = self.session.resolve.generic_instances().len();
+        output
+    }
+
+    fn eval_input(&mut self, package: &mut Package, input: ReplInput) -> CompileResult<ReplOutput> {
+        let expr = match input {
+            ReplInput::Decl(decl) => {
+                p1_parse_decls(
+                    &mut self.session.resolve,
+                    package,
+                    self.module_id,
+                    vec![decl],
+                    Path::new("."),
+                )?;
+
+                None
+            }
+            ReplInput::Expr(expr) => Some(expr),
+        };
+
+        p2_define_tys(package, &mut self.session.resolve)?;
+        p3_define_fn_tys(package, &mut self.session.resolve)?;
+
+        let mut expr_fn = None;
+
+        if let Some(expr) = expr {
+            self.expr_count += 1;
+            let symbol = Symbol::insert(&format!("__repl_{}", self.expr_count));
+
+            let item_id = self
+                .session
+                .resolve
+                .insert_global_binding(self.module_id, false, Mutability::Const, symbol)
+                .map_err(CompileError::from)?;
+
+            let attrs = FnAttrs {
+                link_name: Some(symbol),
+                ..Default::default()
+            };
+
+            let mut expr_ty_id = None;
+            let mut ast = AstGenerator::new(&mut self.session.resolve, &package.generics);
+
+            let fn_ast = ast
+                .gen_expr_fn(item_id, self.module_id, &expr, attrs, |ty_id| {
+                    expr_ty_id = Some(ty_id);
+                    is_displayable(ty_id)
+                })
+                .map_err(CompileError::from)?;
+
+            let defer_stmts = mem::take(&mut ast.defer_stmts);
+            expr_fn = Some((fn_ast, defer_stmts, symbol, expr_ty_id.unwrap()));
+        }
+
+        let mut package_ast = p4_gen_ast_from(
+            package,
+            &mut self.session.resolve,
+            self.instance_count,
+            None,
+        )?;
+        let extern_fns = package_ast.extern_fns.clone();
+
+        let expr_fn = expr_fn.map(|(fn_ast, defer_stmts, symbol, expr_ty_id)| {
+            package_ast.defer_stmts.append(defer_stmts);
+            package_ast.fns.push(fn_ast);
+            (symbol, expr_ty_id)
+        });
+
+        package_ast
+            .extern_fns
+            .extend(self.extern_fns.iter().cloned());
+        package_ast.declared_fns = self.defined_fns.clone();
+
+        let module = p5_gen_code(
+            &package_ast,
+            &package.source_map,
+            self.codegen,
+            &self.session.resolve,
+            &self.options,
+        );
+
+        self.engine
+            .add_module(&module)
+            .map_err(|_| jit_error("the module was already added".to_owned()))?;
+
+        self.extern_fns.extend(extern_fns);
+        self.defined_fns
+            .extend(package_ast.fns.iter().map(|fn_ast| {
+                ExternFnAst {
+                    item_id: fn_ast.item_id,
+                    ty_id: fn_ast.ty_id,
+                    attrs: fn_ast.attrs,
+                }
+            }));
+
+        match expr_fn {
+            Some((symbol, expr_ty_id)) => self.call_expr_fn(symbol.as_str(), expr_ty_id),
+            None => Ok(ReplOutput::Empty),
+        }
+    }
+
+    fn call_expr_fn(&self, fn_name: &str, ty_id: TyId) -> CompileResult<ReplOutput> {
+        let value = unsafe {
+            match ty_id.as_value() {
+                Some(ValueTy::Bool) => (self.call::<u8>(fn_name)? != 0).to_string(),
+                Some(ValueTy::Char) => {
+                    let value = self.call::<u32>(fn_name)?;
+
+                    char::from_u32(value)
+                        .map(|value| format!("{value:?}"))
+                        .unwrap_or_else(|| format!("{value:#x}"))
+                }
+                Some(ValueTy::Int(int_ty)) => {
+                    match int_ty {
+                        IntTy::I8 => self.call::<i8>(fn_name)?.to_string(),
+                        IntTy::I16 => self.call::<i16>(fn_name)?.to_string(),
+                        IntTy::I32 => self.call::<i32>(fn_name)?.to_string(),
+                        IntTy::I64 => self.call::<i64>(fn_name)?.to_string(),
+                        IntTy::I128 => self.call::<i128>(fn_name)?.to_string(),
+                        IntTy::Isize => self.call::<isize>(fn_name)?.to_string(),
+                        IntTy::U8 => self.call::<u8>(fn_name)?.to_string(),
+                        IntTy::U16 => self.call::<u16>(fn_name)?.to_string(),
+                        IntTy::U32 => self.call::<u32>(fn_name)?.to_string(),
+                        IntTy::U64 => self.call::<u64>(fn_name)?.to_string(),
+                        IntTy::U128 => self.call::<u128>(fn_name)?.to_string(),
+                        IntTy::Usize => self.call::<usize>(fn_name)?.to_string(),
+                    }
+                }
+                Some(ValueTy::Float(FloatTy::F32)) => self.call::<f32>(fn_name)?.to_string(),
+                Some(ValueTy::Float(FloatTy::F64)) => self.call::<f64>(fn_name)?.to_string(),
+                Some(ValueTy::Ptr(_) | ValueTy::ManyPtr(_)) => {
+                    format!("{:#x}", self.call::<usize>(fn_name)?)
+                }
+                Some(ValueTy::Unit) | None => {
+                    self.call::<()>(fn_name)?;
+                    return Ok(ReplOutput::Empty);
+                }
+                Some(_) => {
+                    self.call::<()>(fn_name)?;
+                    "<value>".to_owned()
+                }
+            }
+        };
+
+        Ok(ReplOutput::Value(format!("{value}: {ty_id}")))
+    }
+
+    /// Calls a function without parameters that returns `T` under the C ABI.
+    unsafe fn call<T>(&self, fn_name: &str) -> CompileResult<T> {
+        let function = self
+            .engine
+            .get_function::<unsafe extern "C" fn() -> T>(fn_name)
+            .map_err(|error| jit_error(error.to_string()))?;
+
+        Ok(function.call())
+    }
+}
+
+/// Returns whether values of the type are returned as a single scalar that can be displayed.
+fn is_displayable(ty_id: TyId) -> bool {
+    ty_id.as_value().is_some_and(|ty| {
+        matches!(
+            ty,
+            ValueTy::Bool
+                | ValueTy::Char
+                | ValueTy::Int(_)
+                | ValueTy::Float(_)
+                | ValueTy::Ptr(_)
+                | ValueTy::ManyPtr(_),
+        )
+    })
+}
+
+fn parse_input(source: &str, offset: u32) -> ParseResult<ReplInput> {
+    let decl_error = match parse_source(source, offset, |parser| parser.parse_decl()) {
+        Ok(decl) => return Ok(ReplInput::Decl(decl)),
+        Err(error) => error,
+    };
+
+    let expr = parse_source(source, offset, |parser| {
+        let expr = parser.parse_expr()?;
+        parser.bump_if_eq(tk::SEMICOLON);
+        Ok(expr)
+    });
+
+    match expr {
+        Ok(expr) => Ok(ReplInput::Expr(expr)),
+        // Report the error of the parser that got further into the input
+        Err(expr_error) if expr_error.found.span.start > decl_error.found.span.start => {
+            Err(expr_error)
+        }
+        Err(_) => Err(decl_error),
+    }
+}
+
+fn parse_source<T, F>(source: &str, offset: u32, parse: F) -> ParseResult<T>
+where
+    F: FnOnce(&mut Parser) -> ParseResult<T>,
+{
+    let mut tokenizer = Tokenizer::new(source, offset);
+    let mut parser = Parser::new(TokenStream::new(&mut tokenizer));
+    let value = parse(&mut parser)?;
+
+    if parser.peek().kind != TokenKind::Eof {
+        return parser.peek_error(&[TokenKind::Eof]);
+    }
+
+    Ok(value)
+}
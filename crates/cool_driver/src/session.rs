@@ -0,0 +1,34 @@
+use cool_lexer::SymbolTable;
+use cool_resolve::{PrimitiveTyData, ResolveContext};
+use std::sync::Arc;
+
+/// Owns the symbol table and the resolve context used to compile a crate, so that compiling
+/// many crates in one process does not accumulate state. Symbols and ids obtained during the
+/// session are only meaningful while its symbol table is entered. The strings, types and paths
+/// they refer to are interned for the whole process, so using them afterwards is safe.
+pub struct Session {
+    pub resolve: ResolveContext,
+    symbols: Arc<SymbolTable>,
+}
+
+impl Session {
+    pub fn new(primitives: PrimitiveTyData) -> Self {
+        let symbols = Arc::new(SymbolTable::default());
+        let resolve = symbols.enter(|| ResolveContext::new(primitives));
+        Self { resolve, symbols }
+    }
+
+    #[inline]
+    pub fn symbols(&self) -> &Arc<SymbolTable> {
+        &self.symbols
+    }
+
+    /// Runs `f` with the symbol table of the session entered on the current thread.
+    pub fn enter<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut ResolveContext) -> R,
+    {
+        let Self { resolve, symbols } = self;
+        symbols.enter(|| f(resolve))
+    }
+}
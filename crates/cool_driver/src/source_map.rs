@@ -1,10 +1,10 @@
+use crate::{CompileError, CompileResult, SourceError};
 use cool_lexer::{TokenStream, Tokenizer};
 use cool_parser::{ModuleContent, ParseResult, Parser};
-use cool_span::{SourcePosition, Span};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
-use std::{fmt, str};
+use cool_span::{SourceLocator, SourcePosition, Span};
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, str};
 
 #[derive(Clone)]
 pub struct SourceFile {
@@ -15,6 +15,12 @@ pub struct SourceFile {
 }
 
 impl SourceFile {
+    pub fn parse(&self) -> ParseResult<ModuleContent> {
+        let mut tokenizer = Tokenizer::new(&self.source, self.span.start);
+        let mut parser = Parser::new(TokenStream::new(&mut tokenizer));
+        parser.parse_module_file()
+    }
+
     pub fn get_source_at_span(&self, span: Span) -> &str {
         let start = (span.start - self.span.start) as usize;
         let end = (span.end() - self.span.start) as usize;
@@ -55,37 +61,56 @@ impl fmt::Debug for SourceFile {
 #[derive(Clone, Default, Debug)]
 pub struct SourceMap {
     files: Vec<SourceFile>,
+    overrides: FxHashMap<PathBuf, String>,
 }
 
 impl SourceMap {
-    pub fn add_file(&mut self, path: PathBuf) -> ParseResult<ModuleContent> {
-        let file = File::open(&path).unwrap();
-        let mut buf_reader = BufReader::new(file);
+    /// Uses `source` instead of the contents of the file at `path` when the file is added, such
+    /// as for files with unsaved changes in an editor.
+    #[inline]
+    pub fn override_file(&mut self, path: PathBuf, source: String) {
+        self.overrides.insert(path, source);
+    }
+
+    /// Returns the source of a file, or its override if there is one. The file is not added.
+    pub fn read_file(&self, path: &Path) -> Result<String, SourceError> {
+        match self.overrides.get(path) {
+            Some(source) => Ok(source.clone()),
+            None => {
+                fs::read_to_string(path).map_err(|error| {
+                    SourceError {
+                        path: path.to_path_buf(),
+                        message: error.to_string(),
+                    }
+                })
+            }
+        }
+    }
+
+    pub fn add_file(&mut self, path: PathBuf) -> CompileResult<ModuleContent> {
+        let source = self.read_file(&path).map_err(CompileError::from)?;
+
+        self.add_source(path, source)
+            .parse()
+            .map_err(|error| CompileError::from(error).into())
+    }
 
-        let start_offset = self.files.last().map(|file| file.span.end()).unwrap_or(0);
+    /// Adds source code that does not necessarily come from a file, placing it after the
+    /// sources added before.
+    pub fn add_source(&mut self, path: PathBuf, source: String) -> &SourceFile {
+        let start_offset = self.end_offset();
 
         let mut line_offsets = Vec::<u32>::new();
         let mut line_offset = 0;
-        let mut source = String::new();
-
-        loop {
-            match buf_reader.read_line(&mut source) {
-                Ok(0) => break,
-                Ok(n) => {
-                    line_offsets.push(line_offset);
-                    line_offset += n as u32;
-                }
-                Err(error) => todo!("Error reading file: {error}"),
-            }
+
+        for line in source.split_inclusive('\n') {
+            line_offsets.push(line_offset);
+            line_offset += line.len() as u32;
         }
 
         let end_offset = start_offset + source.len() as u32;
         let span = Span::new(start_offset, end_offset);
 
-        let mut tokenizer = Tokenizer::new(&source, start_offset);
-        let mut parser = Parser::new(TokenStream::new(&mut tokenizer));
-        let module_content = parser.parse_module_file();
-
         self.files.push(SourceFile {
             path,
             span,
@@ -93,7 +118,18 @@ impl SourceMap {
             source,
         });
 
-        module_content
+        self.files.last().unwrap()
+    }
+
+    #[inline]
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// Returns the offset at which the next source will start.
+    #[inline]
+    pub fn end_offset(&self) -> u32 {
+        self.files.last().map(|file| file.span.end()).unwrap_or(0)
     }
 
     pub fn get_file_from_offset(&self, offset: u32) -> &SourceFile {
@@ -115,3 +151,10 @@ impl SourceMap {
             .get_source_at_span(span)
     }
 }
+
+impl SourceLocator for SourceMap {
+    fn locate(&self, offset: u32) -> (&Path, SourcePosition) {
+        let (file, position) = self.get_file_and_position_from_offset(offset);
+        (&file.path, position)
+    }
+}
@@ -0,0 +1,608 @@
+use crate::syntax_tree::{token_str, SyntaxGroup, SyntaxNode, SyntaxTree};
+use cool_lexer::{sym, tk, Token, TokenKind};
+
+const INDENT: &str = "    ";
+
+/// Writes a syntax tree in the canonical style.
+pub fn format_tree(source: &str, tree: &SyntaxTree) -> String {
+    let mut printer = Printer {
+        source,
+        out: String::new(),
+        line_indent: 0,
+        is_line_start: true,
+        prev: Prev::default(),
+    };
+
+    printer.write_nodes(&tree.nodes, GroupKind::Root, true, 0);
+    printer.write_comments(&tree.eof.trivia, 0, true);
+
+    let len = printer.out.trim_end().len();
+    printer.out.truncate(len);
+
+    if !printer.out.is_empty() {
+        printer.out.push('\n');
+    }
+
+    printer.out
+}
+
+/// Kind of the sequence of nodes being written, which decides how it is broken into lines.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum GroupKind {
+    Root,
+    /// Body of a `struct`, `union`, `enum`, `interface`, `impl` or `module`.
+    Decl,
+    FnBody,
+    MatchBody,
+    /// Tuples and parenthesized expressions, but not the arguments of a call.
+    Parens,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Item<'a> {
+    Node(&'a SyntaxNode),
+    /// Comma that is missing from the source.
+    Comma,
+    /// Comma that was removed from the source, still separating two elements.
+    RemovedComma,
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+struct Prev {
+    kind: Option<TokenKind>,
+    is_operand_end: bool,
+    is_prefix_op: bool,
+    /// The previous token is a bracket group prefixing a type, as in `[*]i8` or `[4]i32`.
+    is_ty_prefix: bool,
+    /// The previous token is the first half of `::` or `:=`.
+    is_compound_colon: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LineBreaks {
+    count: usize,
+    after_comment: bool,
+}
+
+struct Printer<'a> {
+    source: &'a str,
+    out: String,
+    line_indent: usize,
+    is_line_start: bool,
+    prev: Prev,
+}
+
+impl<'a> Printer<'a> {
+    fn write_nodes(
+        &mut self,
+        nodes: &[SyntaxNode],
+        kind: GroupKind,
+        is_broken: bool,
+        indent: usize,
+    ) {
+        let items = normalize_items(nodes, kind, is_broken);
+        let mut is_elem_start = true;
+        let mut has_spaced_colons = false;
+
+        for (i, &item) in items.iter().enumerate() {
+            let node = match item {
+                Item::Node(node) => node,
+                Item::Comma => {
+                    self.write_token(",", tk::COMMA, false);
+                    is_elem_start = true;
+                    continue;
+                }
+                Item::RemovedComma => {
+                    is_elem_start = true;
+                    continue;
+                }
+            };
+
+            let trivia = &node.first_token().trivia;
+
+            if i != 0 && !is_elem_start && is_broken {
+                is_elem_start =
+                    has_line_break(self.source, trivia) && ends_elem_on_line_break(&items[..i]);
+            }
+
+            if is_elem_start {
+                has_spaced_colons = count_single_colons(&items[i..]) >= 2;
+            }
+
+            let token_indent = if is_elem_start { indent } else { indent + 1 };
+            let allows_blank_line = is_elem_start && i != 0;
+            let line_breaks = self.write_comments(trivia, token_indent, allows_blank_line);
+
+            if (is_broken && (is_elem_start || i == 0))
+                || line_breaks.count != 0
+                || line_breaks.after_comment
+            {
+                let is_blank_line =
+                    (allows_blank_line || line_breaks.after_comment) && line_breaks.count >= 2;
+
+                self.newline(token_indent, is_blank_line);
+            }
+
+            let next = items.get(i + 1).and_then(|item| {
+                match item {
+                    Item::Node(node) => Some(*node),
+                    _ => None,
+                }
+            });
+
+            match node {
+                SyntaxNode::Token(token) => {
+                    let kind = token.kind();
+
+                    let is_compound_colon = kind == tk::COLON
+                        && next.is_some_and(|next| {
+                            (next.is_token(tk::COLON) || next.is_token(tk::EQ))
+                                && next.first_token().trivia.is_empty()
+                        });
+
+                    let has_space = if kind == tk::COLON && !self.prev.is_compound_colon {
+                        is_compound_colon || has_spaced_colons
+                    } else {
+                        self.has_space_before(kind)
+                    };
+
+                    self.write_token(self.token_str(&token.token), kind, has_space);
+                    self.prev.is_compound_colon = is_compound_colon;
+                }
+                SyntaxNode::Group(group) => {
+                    let is_call = group.open.kind() == tk::OPEN_PAREN
+                        && (self.prev.is_operand_end
+                            || self.prev.kind.is_some_and(is_call_keyword));
+
+                    let group_kind = if is_call {
+                        GroupKind::Other
+                    } else {
+                        group_kind(group, &items[..i])
+                    };
+
+                    let has_space = self.has_space_before(group.open.kind());
+                    self.write_group(group, group_kind, has_space);
+                }
+            }
+
+            is_elem_start = node.is_token(tk::COMMA) || node.is_token(tk::SEMICOLON);
+        }
+    }
+
+    fn write_group(&mut self, group: &SyntaxGroup, kind: GroupKind, has_space: bool) {
+        let is_ty_prefix = group.open.kind() == tk::OPEN_BRACKET
+            && !self.prev.is_operand_end
+            && !self.prev.kind.is_some_and(is_generic_keyword);
+
+        let open_kind = group.open.kind();
+        self.write_token(self.token_str(&group.open.token), open_kind, has_space);
+
+        let has_comments = group
+            .close
+            .trivia
+            .iter()
+            .any(|token| token.kind == TokenKind::Comment);
+
+        if group.nodes.is_empty() && !has_comments {
+            self.write_token(
+                self.token_str(&group.close.token),
+                group.close.kind(),
+                false,
+            );
+        } else {
+            let is_broken = match kind {
+                GroupKind::Decl | GroupKind::FnBody | GroupKind::MatchBody => true,
+                _ => {
+                    group
+                        .nodes
+                        .iter()
+                        .any(|node| has_line_break(self.source, &node.first_token().trivia))
+                        || has_line_break(self.source, &group.close.trivia)
+                        || (open_kind == tk::OPEN_BRACE
+                            && group.nodes.iter().any(|node| node.is_token(tk::SEMICOLON)))
+                }
+            };
+
+            let outer_indent = self.line_indent;
+            self.write_nodes(&group.nodes, kind, is_broken, outer_indent + 1);
+            self.write_comments(&group.close.trivia, outer_indent + 1, true);
+
+            if is_broken {
+                self.newline(outer_indent, false);
+            }
+
+            let close_kind = group.close.kind();
+            let has_space = close_kind == tk::CLOSE_BRACE && !self.is_line_start;
+            self.write_token(self.token_str(&group.close.token), close_kind, has_space);
+        }
+
+        self.prev.is_operand_end = true;
+        self.prev.is_ty_prefix = is_ty_prefix;
+    }
+
+    /// Writes the comments found in the trivia and returns the line breaks that follow the last
+    /// one.
+    fn write_comments(
+        &mut self,
+        trivia: &[Token],
+        indent: usize,
+        allows_blank_line: bool,
+    ) -> LineBreaks {
+        let mut line_breaks = LineBreaks {
+            count: 0,
+            after_comment: false,
+        };
+
+        for token in trivia {
+            if token.kind != TokenKind::Comment {
+                line_breaks.count += self.token_str(token).matches('\n').count();
+                continue;
+            }
+
+            if line_breaks.count == 0 && !self.is_line_start {
+                self.out.push(' ');
+            } else {
+                let is_blank_line =
+                    (allows_blank_line || line_breaks.after_comment) && line_breaks.count >= 2;
+
+                self.newline(indent, is_blank_line);
+                self.write_indent();
+            }
+
+            self.out.push_str(self.token_str(token).trim_end());
+            self.is_line_start = false;
+
+            line_breaks = LineBreaks {
+                count: 0,
+                after_comment: true,
+            };
+        }
+
+        line_breaks
+    }
+
+    fn write_token(&mut self, token_str: &str, kind: TokenKind, has_space: bool) {
+        let is_prefix_op = self.is_prefix_op(kind);
+
+        if self.is_line_start {
+            self.write_indent();
+        } else if has_space {
+            self.out.push(' ');
+        }
+
+        self.out.push_str(token_str);
+        self.is_line_start = false;
+
+        let is_operand_end = match kind {
+            TokenKind::Ident(_) | TokenKind::Literal(_) => true,
+            TokenKind::Keyword(symbol) => {
+                [sym::KW_CRATE, sym::KW_SELF, sym::KW_SUPER].contains(&symbol)
+            }
+            tk::CLOSE_PAREN | tk::CLOSE_BRACKET | tk::CLOSE_BRACE => true,
+            tk::STAR => self.prev.kind == Some(tk::DOT),
+            tk::QUESTION => !is_prefix_op,
+            _ => false,
+        };
+
+        self.prev = Prev {
+            kind: Some(kind),
+            is_operand_end,
+            is_prefix_op,
+            is_ty_prefix: false,
+            is_compound_colon: false,
+        };
+    }
+
+    fn write_indent(&mut self) {
+        for _ in 0..self.line_indent {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    fn newline(&mut self, indent: usize, is_blank_line: bool) {
+        self.line_indent = indent;
+
+        if self.out.is_empty() {
+            return;
+        }
+
+        let len = self.out.trim_end_matches(' ').len();
+        self.out.truncate(len);
+
+        if !self.is_line_start {
+            self.out.push('\n');
+        }
+
+        if is_blank_line && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+
+        self.is_line_start = true;
+    }
+
+    fn is_prefix_op(&self, kind: TokenKind) -> bool {
+        let is_unary_position = !self.prev.is_operand_end || self.prev.is_ty_prefix;
+
+        match kind {
+            tk::NOT => true,
+            tk::MINUS | tk::AND | tk::QUESTION => is_unary_position,
+            tk::STAR => is_unary_position && self.prev.kind != Some(tk::DOT),
+            _ => false,
+        }
+    }
+
+    fn has_space_before(&self, kind: TokenKind) -> bool {
+        let Some(prev_kind) = self.prev.kind else {
+            return false;
+        };
+
+        if self.prev.is_compound_colon {
+            return false;
+        }
+
+        match kind {
+            tk::COMMA
+            | tk::SEMICOLON
+            | tk::CLOSE_PAREN
+            | tk::CLOSE_BRACKET
+            | tk::DOT
+            | tk::DOT_DOT => return false,
+            tk::CLOSE_BRACE => return prev_kind != tk::OPEN_BRACE,
+            _ => (),
+        }
+
+        match prev_kind {
+            tk::DOT | tk::DOT_DOT | tk::OPEN_PAREN | tk::OPEN_BRACKET | tk::AT => return false,
+            TokenKind::Prefix(_) => return false,
+            tk::OPEN_BRACE | tk::COMMA | tk::SEMICOLON | tk::COLON => return true,
+            _ => (),
+        }
+
+        if self.prev.is_prefix_op {
+            return false;
+        }
+
+        if self.prev.is_ty_prefix {
+            return !matches!(
+                kind,
+                TokenKind::Ident(_)
+                    | TokenKind::Keyword(_)
+                    | tk::STAR
+                    | tk::QUESTION
+                    | tk::AND
+                    | tk::OPEN_BRACKET
+                    | tk::OPEN_PAREN
+            );
+        }
+
+        match kind {
+            tk::OPEN_PAREN => !self.prev.is_operand_end && !is_call_keyword(prev_kind),
+            tk::OPEN_BRACKET => !self.prev.is_operand_end && !is_generic_keyword(prev_kind),
+            tk::QUESTION => !self.prev.is_operand_end,
+            _ => true,
+        }
+    }
+
+    #[inline]
+    fn token_str(&self, token: &Token) -> &'a str {
+        token_str(self.source, token)
+    }
+}
+
+/// Adds the trailing comma of broken lists, removes the one of single-line lists and removes
+/// the commas after `match` arms whose body is a block.
+fn normalize_items(nodes: &[SyntaxNode], kind: GroupKind, is_broken: bool) -> Vec<Item<'_>> {
+    let mut items = nodes.iter().map(Item::Node).collect::<Vec<_>>();
+
+    if kind == GroupKind::Root {
+        return items;
+    }
+
+    let is_removable = |node: &SyntaxNode| {
+        node.first_token()
+            .trivia
+            .iter()
+            .all(|token| token.kind != TokenKind::Comment)
+    };
+
+    if kind == GroupKind::MatchBody {
+        let mut last_arm_is_block = false;
+
+        for i in 0..nodes.len() {
+            let is_block_arm =
+                i != 0 && nodes[i - 1].is_token(tk::FAT_ARROW) && nodes[i].is_group(tk::OPEN_BRACE);
+
+            if is_block_arm {
+                last_arm_is_block = true;
+
+                if let Some(comma) = nodes.get(i + 1) {
+                    if comma.is_token(tk::COMMA) && is_removable(comma) {
+                        items[i + 1] = Item::RemovedComma;
+                    }
+                }
+            } else if nodes[i].is_token(tk::FAT_ARROW) {
+                last_arm_is_block = false;
+            }
+        }
+
+        let has_trailing_comma = nodes.last().is_some_and(|node| node.is_token(tk::COMMA));
+
+        if !nodes.is_empty() && !last_arm_is_block && !has_trailing_comma {
+            items.push(Item::Comma);
+        }
+
+        return items;
+    }
+
+    let comma_count = nodes.iter().filter(|node| node.is_token(tk::COMMA)).count();
+    let has_trailing_comma = nodes.last().is_some_and(|node| node.is_token(tk::COMMA));
+
+    if is_broken {
+        // Fields of structs, unions and enums are the only declaration bodies without `;`.
+        let is_field_list = kind == GroupKind::Decl
+            && !nodes.is_empty()
+            && !nodes.iter().any(|node| node.is_token(tk::SEMICOLON));
+
+        if (comma_count != 0 || is_field_list) && !has_trailing_comma {
+            items.push(Item::Comma);
+        }
+    } else if has_trailing_comma && is_removable(nodes.last().unwrap()) {
+        let is_tuple = kind == GroupKind::Parens && comma_count == 1;
+
+        if !is_tuple {
+            items.pop();
+        }
+    }
+
+    items
+}
+
+fn group_kind(group: &SyntaxGroup, prev_items: &[Item]) -> GroupKind {
+    match group.open.kind() {
+        tk::OPEN_PAREN => return GroupKind::Parens,
+        tk::OPEN_BRACE => (),
+        _ => return GroupKind::Other,
+    }
+
+    for item in prev_items.iter().rev() {
+        let Item::Node(node) = item else {
+            break;
+        };
+
+        if node.is_token(tk::COMMA) || node.is_token(tk::SEMICOLON) {
+            break;
+        }
+
+        let TokenKind::Keyword(symbol) = node.first_token().kind() else {
+            continue;
+        };
+
+        match symbol {
+            sym::KW_STRUCT
+            | sym::KW_UNION
+            | sym::KW_ENUM
+            | sym::KW_INTERFACE
+            | sym::KW_IMPL
+            | sym::KW_MODULE => return GroupKind::Decl,
+            sym::KW_FN => return GroupKind::FnBody,
+            sym::KW_MATCH => return GroupKind::MatchBody,
+            sym::KW_CLOSURE
+            | sym::KW_DEFER
+            | sym::KW_ELSE
+            | sym::KW_FOR
+            | sym::KW_IF
+            | sym::KW_LOOP
+            | sym::KW_ORELSE
+            | sym::KW_WHILE => return GroupKind::Other,
+            _ => (),
+        }
+    }
+
+    GroupKind::Other
+}
+
+/// Returns whether a line break before the next node starts a new element, which happens after
+/// a block, as in `if` statements and `match` arms, and after an attribute.
+fn ends_elem_on_line_break(prev_items: &[Item]) -> bool {
+    let nodes = prev_items
+        .iter()
+        .rev()
+        .take(3)
+        .map(|item| {
+            match item {
+                Item::Node(node) => Some(*node),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let is_ident = |node: Option<&SyntaxNode>| {
+        node.is_some_and(|node| matches!(node.first_token().kind(), TokenKind::Ident(_)))
+    };
+
+    let is_at = |node: Option<&SyntaxNode>| node.is_some_and(|node| node.is_token(tk::AT));
+
+    match nodes.as_slice() {
+        [Some(node), ..] if node.is_group(tk::OPEN_BRACE) => true,
+        [last, before, ..] if is_ident(*last) && is_at(*before) => true,
+        [Some(last), before, before_before]
+            if last.is_group(tk::OPEN_PAREN) && is_ident(*before) && is_at(*before_before) =>
+        {
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Counts the colons of the element that are not part of `::` or `:=`. Item declarations with
+/// an explicit type have two and are written as `NAME : Ty : value`.
+fn count_single_colons(items: &[Item]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+
+    while let Some(Item::Node(node)) = items.get(i) {
+        if node.is_token(tk::COMMA) || node.is_token(tk::SEMICOLON) {
+            break;
+        }
+
+        if node.is_token(tk::COLON) {
+            let is_compound = matches!(
+                items.get(i + 1),
+                Some(Item::Node(next))
+                    if (next.is_token(tk::COLON) || next.is_token(tk::EQ))
+                        && next.first_token().trivia.is_empty()
+            );
+
+            if is_compound {
+                i += 1;
+            } else {
+                count += 1;
+            }
+        }
+
+        i += 1;
+    }
+
+    count
+}
+
+fn has_line_break(source: &str, trivia: &[Token]) -> bool {
+    trivia
+        .iter()
+        .any(|token| token.kind == TokenKind::Comment || token_str(source, token).contains('\n'))
+}
+
+fn is_call_keyword(kind: TokenKind) -> bool {
+    let TokenKind::Keyword(symbol) = kind else {
+        return false;
+    };
+
+    [
+        sym::KW_ALIGN_OF,
+        sym::KW_ASSERT,
+        sym::KW_CLOSURE,
+        sym::KW_FN,
+        sym::KW_OFFSET_OF,
+        sym::KW_PANIC,
+        sym::KW_SIZE_OF,
+        sym::KW_STATIC_ASSERT,
+        sym::KW_UNREACHABLE,
+    ]
+    .contains(&symbol)
+}
+
+fn is_generic_keyword(kind: TokenKind) -> bool {
+    let TokenKind::Keyword(symbol) = kind else {
+        return false;
+    };
+
+    [
+        sym::KW_ENUM,
+        sym::KW_FN,
+        sym::KW_INTERFACE,
+        sym::KW_STRUCT,
+        sym::KW_UNION,
+    ]
+    .contains(&symbol)
+}
@@ -0,0 +1,115 @@
+mod formatter;
+mod syntax_tree;
+
+pub use self::formatter::*;
+pub use self::syntax_tree::*;
+use cool_lexer::{TokenStream, Tokenizer};
+use cool_parser::{ParseError, Parser};
+use cool_span::Span;
+use derive_more::{Display, Error, From};
+
+#[derive(Clone, Error, From, Display, Debug)]
+pub enum FormatError {
+    Parse(ParseError),
+    UnbalancedDelimiter(UnbalancedDelimiter),
+    #[display(fmt = "formatting produced code that does not parse: {_0}")]
+    #[from(ignore)]
+    InvalidOutput(#[error(not(source))] String),
+}
+
+impl FormatError {
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::Parse(e) => Some(e.found.span),
+            Self::UnbalancedDelimiter(e) => Some(e.span),
+            Self::InvalidOutput(_) => None,
+        }
+    }
+}
+
+/// Formats the source of a module file. Sources that do not parse are rejected, since their
+/// structure cannot be trusted. A symbol table must be entered, which the errors must also be
+/// displayed with.
+pub fn format_source(source: &str) -> Result<String, FormatError> {
+    parse(source)?;
+
+    let tree = SyntaxTree::new(source)?;
+    let formatted = format_tree(source, &tree);
+
+    if let Err(error) = parse(&formatted) {
+        return Err(FormatError::InvalidOutput(error.to_string()));
+    }
+
+    Ok(formatted)
+}
+
+fn parse(source: &str) -> Result<(), ParseError> {
+    let mut tokenizer = Tokenizer::new(source, 0);
+    let mut parser = Parser::new(TokenStream::new(&mut tokenizer));
+    parser.parse_module_file().map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cool_lexer::SymbolTable;
+
+    #[track_caller]
+    fn assert_formats(source: &str, expected: &str) {
+        SymbolTable::default().enter(|| {
+            let formatted = format_source(source).unwrap();
+            assert_eq!(formatted, expected);
+            assert_eq!(format_source(&formatted).unwrap(), formatted);
+        });
+    }
+
+    #[test]
+    fn formatted_source_is_unchanged() {
+        let source = "\
+Point :: struct {
+    x: i32,
+    y: i32,
+};
+
+add :: fn(a: i32, b: i32) -> i32 {
+    sum := a + b;
+    return sum;
+};
+";
+
+        assert_formats(source, source);
+    }
+
+    #[test]
+    fn lists_get_trailing_commas() {
+        assert_formats(
+            "Point :: struct { x: i32, y: i32 };\n",
+            "Point :: struct {\n    x: i32,\n    y: i32,\n};\n",
+        );
+
+        assert_formats(
+            "f :: fn() {\n    g(1, 2,);\n};\n",
+            "f :: fn() {\n    g(1, 2);\n};\n",
+        );
+    }
+
+    #[test]
+    fn comments_are_kept() {
+        let source = "\
+// Point in the plane
+Point :: struct {
+    x: i32, // abscissa
+    // ordinate
+    y: i32,
+};
+";
+
+        assert_formats(source, source);
+    }
+
+    #[test]
+    fn comment_only_bodies_get_no_comma() {
+        let source = "Empty :: struct {\n    // nothing\n};\n";
+        assert_formats(source, source);
+    }
+}
@@ -0,0 +1,169 @@
+use cool_lexer::{tk, Token, TokenKind, Tokenizer};
+use cool_span::Span;
+use derive_more::{Display, Error};
+
+#[derive(Clone, Copy, Error, Display, Debug)]
+#[display(fmt = "unbalanced delimiter")]
+pub struct UnbalancedDelimiter {
+    pub span: Span,
+}
+
+/// Language token together with the whitespace and comments that precede it.
+#[derive(Clone, Debug)]
+pub struct SyntaxToken {
+    pub token: Token,
+    pub trivia: Vec<Token>,
+}
+
+impl SyntaxToken {
+    #[inline]
+    pub fn kind(&self) -> TokenKind {
+        self.token.kind
+    }
+}
+
+/// Tokens enclosed by a pair of matching delimiters.
+#[derive(Clone, Debug)]
+pub struct SyntaxGroup {
+    pub open: SyntaxToken,
+    pub nodes: Vec<SyntaxNode>,
+    pub close: SyntaxToken,
+}
+
+#[derive(Clone, Debug)]
+pub enum SyntaxNode {
+    Token(SyntaxToken),
+    Group(SyntaxGroup),
+}
+
+impl SyntaxNode {
+    /// Returns the token the node starts with, which holds the trivia before the node.
+    #[inline]
+    pub fn first_token(&self) -> &SyntaxToken {
+        match self {
+            Self::Token(token) => token,
+            Self::Group(group) => &group.open,
+        }
+    }
+
+    #[inline]
+    pub fn is_token(&self, kind: TokenKind) -> bool {
+        matches!(self, Self::Token(token) if token.kind() == kind)
+    }
+
+    #[inline]
+    pub fn is_group(&self, open_kind: TokenKind) -> bool {
+        matches!(self, Self::Group(group) if group.open.kind() == open_kind)
+    }
+}
+
+/// Lossless syntax tree of a source file: every byte of the source belongs to exactly one token
+/// or trivia token, so the source can be written back unchanged.
+#[derive(Clone, Debug)]
+pub struct SyntaxTree {
+    pub nodes: Vec<SyntaxNode>,
+    pub eof: SyntaxToken,
+}
+
+impl SyntaxTree {
+    pub fn new(source: &str) -> Result<Self, UnbalancedDelimiter> {
+        let mut tokenizer = Tokenizer::new(source, 0);
+        let mut groups = Vec::<(SyntaxToken, Vec<SyntaxNode>)>::new();
+        let mut nodes = Vec::<SyntaxNode>::new();
+
+        loop {
+            let mut trivia = Vec::<Token>::new();
+
+            let token = loop {
+                let token = tokenizer.next_token();
+
+                if token.kind.is_lang_part() {
+                    break token;
+                }
+
+                trivia.push(token);
+            };
+
+            let token = SyntaxToken { token, trivia };
+
+            match token.kind() {
+                tk::OPEN_BRACE | tk::OPEN_BRACKET | tk::OPEN_PAREN => {
+                    groups.push((token, nodes));
+                    nodes = Vec::new();
+                }
+                tk::CLOSE_BRACE | tk::CLOSE_BRACKET | tk::CLOSE_PAREN => {
+                    let Some((open, parent_nodes)) = groups.pop() else {
+                        return Err(UnbalancedDelimiter {
+                            span: token.token.span,
+                        });
+                    };
+
+                    if closing_delimiter(open.kind()) != token.kind() {
+                        return Err(UnbalancedDelimiter {
+                            span: token.token.span,
+                        });
+                    }
+
+                    let group = SyntaxGroup {
+                        open,
+                        nodes,
+                        close: token,
+                    };
+
+                    nodes = parent_nodes;
+                    nodes.push(SyntaxNode::Group(group));
+                }
+                TokenKind::Eof => {
+                    if let Some((open, _)) = groups.pop() {
+                        return Err(UnbalancedDelimiter {
+                            span: open.token.span,
+                        });
+                    }
+
+                    return Ok(Self { nodes, eof: token });
+                }
+                _ => nodes.push(SyntaxNode::Token(token)),
+            }
+        }
+    }
+
+    /// Writes the exact source the tree was built from.
+    pub fn write_source(&self, source: &str, out: &mut String) {
+        fn write_token(source: &str, token: &SyntaxToken, out: &mut String) {
+            for trivia in token.trivia.iter() {
+                out.push_str(token_str(source, trivia));
+            }
+
+            out.push_str(token_str(source, &token.token));
+        }
+
+        fn write_nodes(source: &str, nodes: &[SyntaxNode], out: &mut String) {
+            for node in nodes {
+                match node {
+                    SyntaxNode::Token(token) => write_token(source, token, out),
+                    SyntaxNode::Group(group) => {
+                        write_token(source, &group.open, out);
+                        write_nodes(source, &group.nodes, out);
+                        write_token(source, &group.close, out);
+                    }
+                }
+            }
+        }
+
+        write_nodes(source, &self.nodes, out);
+        write_token(source, &self.eof, out);
+    }
+}
+
+#[inline]
+pub fn token_str<'a>(source: &'a str, token: &Token) -> &'a str {
+    &source[(token.span.start as usize)..(token.span.end() as usize)]
+}
+
+fn closing_delimiter(open_kind: TokenKind) -> TokenKind {
+    match open_kind {
+        tk::OPEN_BRACE => tk::CLOSE_BRACE,
+        tk::OPEN_BRACKET => tk::CLOSE_BRACKET,
+        _ => tk::CLOSE_PAREN,
+    }
+}
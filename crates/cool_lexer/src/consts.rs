@@ -52,8 +52,8 @@ macro_rules! define_symbols {
                 pub const $extra: Symbol = unsafe { Symbol::new_unchecked($extra_idx) };
             )+
 
-            pub fn intern_symbols(symbols: &mut SymbolTable) {
-                let mut insert_checked = |expected_id, symbol| {
+            pub fn intern_symbols(symbols: &SymbolTable) {
+                let insert_checked = |expected_id, symbol| {
                     let actual_id = symbols.insert_str(symbol);
                     assert_eq!(actual_id, expected_id);
                     actual_id
@@ -115,86 +115,117 @@ define_symbols! {
     Keywords {
          1: align_of,
          2: as,
-         3: break,
-         4: continue,
-         5: crate,
-         6: defer,
-         7: else,
-         8: enum,
-         9: export,
-        10: extern,
-        11: false,
-        12: fn,
-        13: for,
-        14: if,
-        15: loop,
-        16: match,
-        17: module,
-        18: mut,
-        19: offset_of,
-        20: return,
-        21: self,
-        22: size_of,
-        23: struct,
-        24: super,
-        25: switch,
-        26: true,
-        27: type,
-        28: use,
-        29: while,
+         3: assert,
+         4: break,
+         5: closure,
+         6: continue,
+         7: crate,
+         8: defer,
+         9: else,
+        10: enum,
+        11: export,
+        12: extern,
+        13: false,
+        14: fn,
+        15: for,
+        16: if,
+        17: impl,
+        18: interface,
+        19: loop,
+        20: match,
+        21: module,
+        22: mut,
+        23: null,
+        24: offset_of,
+        25: orelse,
+        26: panic,
+        27: return,
+        28: self,
+        29: size_of,
+        30: static_assert,
+        31: struct,
+        32: super,
+        33: switch,
+        34: true,
+        35: type,
+        36: union,
+        37: unreachable,
+        38: use,
+        39: while,
     },
     Primitives {
-        30: i8,
-        31: i16,
-        32: i32,
-        33: i64,
-        34: i128,
-        35: isize,
-
-        36: u8,
-        37: u16,
-        38: u32,
-        39: u64,
-        40: u128,
-        41: usize,
-
-        42: f32,
-        43: f64,
-
-        44: char,
-        45: bool,
+        40: i8,
+        41: i16,
+        42: i32,
+        43: i64,
+        44: i128,
+        45: isize,
+
+        46: u8,
+        47: u16,
+        48: u32,
+        49: u64,
+        50: u128,
+        51: usize,
+
+        52: f32,
+        53: f64,
+
+        54: char,
+        55: bool,
     },
     Digits {
-        46: "0" as DIGIT_0,
-        47: "1" as DIGIT_1,
-        48: "2" as DIGIT_2,
-        49: "3" as DIGIT_3,
-        50: "4" as DIGIT_4,
-        51: "5" as DIGIT_5,
-        52: "6" as DIGIT_6,
-        53: "7" as DIGIT_7,
-        54: "8" as DIGIT_8,
-        55: "9" as DIGIT_9,
+        56: "0" as DIGIT_0,
+        57: "1" as DIGIT_1,
+        58: "2" as DIGIT_2,
+        59: "3" as DIGIT_3,
+        60: "4" as DIGIT_4,
+        61: "5" as DIGIT_5,
+        62: "6" as DIGIT_6,
+        63: "7" as DIGIT_7,
+        64: "8" as DIGIT_8,
+        65: "9" as DIGIT_9,
     },
     Extra {
-        56: "" as EMPTY,
-        57: "_" as WILDCARD,
-
-        58: "C" as ABI_C,
-        59: "Cool" as ABI_COOL,
-
-        60: "main" as MAIN,
-        61: "len" as LEN,
-        62: "ptr" as PTR,
-
-        63: "literal" as DIAG_LITERAL,
-        64: "identifier" as DIAG_IDENT,
-        65: "string literal" as DIAG_STR_LITERAL,
-        66: "punctuation" as DIAG_PUNCTUATION,
-        67: "binary operator" as DIAG_BIN_OP,
-
-        68: "variant_elem" as VARIANT_ELEM,
-        69: "variant_padding" as VARIANT_PADDING,
-        70: "variant_index" as VARIANT_INDEX,
+        66: "" as EMPTY,
+        67: "_" as WILDCARD,
+
+        68: "C" as ABI_C,
+        69: "Cool" as ABI_COOL,
+
+        70: "main" as MAIN,
+        71: "len" as LEN,
+        72: "ptr" as PTR,
+
+        73: "literal" as DIAG_LITERAL,
+        74: "identifier" as DIAG_IDENT,
+        75: "string literal" as DIAG_STR_LITERAL,
+        76: "punctuation" as DIAG_PUNCTUATION,
+        77: "binary operator" as DIAG_BIN_OP,
+
+        78: "variant_elem" as VARIANT_ELEM,
+        79: "variant_padding" as VARIANT_PADDING,
+        80: "variant_index" as VARIANT_INDEX,
+
+        81: "closure_fn" as CLOSURE_FN,
+        82: "closure_env" as CLOSURE_ENV,
+
+        83: "interface_data" as INTERFACE_DATA,
+        84: "interface_vtable" as INTERFACE_VTABLE,
+
+        85: "Self" as SELF_TY,
+
+        86: "inline" as ATTR_INLINE,
+        87: "noinline" as ATTR_NOINLINE,
+        88: "cold" as ATTR_COLD,
+        89: "link_name" as ATTR_LINK_NAME,
+        90: "section" as ATTR_SECTION,
+        91: "weak" as ATTR_WEAK,
+        92: "callconv" as ATTR_CALLCONV,
+        93: "fast" as CALLCONV_FAST,
+        94: "packed" as ATTR_PACKED,
+        95: "align" as ATTR_ALIGN,
+        96: "layout" as ATTR_LAYOUT,
+        97: "test" as ATTR_TEST,
     },
 }
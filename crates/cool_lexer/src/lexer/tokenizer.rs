@@ -81,6 +81,11 @@ impl<'a> Tokenizer<'a> {
                     kind: LiteralKind::Bool,
                     symbol,
                 })
+            } else if symbol.is_null_literal() {
+                TokenKind::Literal(Literal {
+                    kind: LiteralKind::Null,
+                    symbol,
+                })
             } else {
                 TokenKind::Keyword(symbol)
             }
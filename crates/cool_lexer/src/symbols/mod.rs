@@ -1,8 +1,8 @@
 mod symbol_table;
 
-pub(crate) use self::symbol_table::*;
+pub use self::symbol_table::*;
 pub use crate::consts::sym;
-use cool_collections::id_newtype;
+use cool_collections::{id_newtype, Id};
 use std::fmt;
 
 id_newtype!(Symbol; no_debug);
@@ -18,22 +18,33 @@ impl Symbol {
         *self == sym::KW_FALSE || *self == sym::KW_TRUE
     }
 
+    #[inline]
+    pub fn is_null_literal(&self) -> bool {
+        *self == sym::KW_NULL
+    }
+
     #[inline]
     pub fn is_known_suffix(&self) -> bool {
         *self >= sym::I8 && *self <= sym::F64
     }
 }
 
+// Formatting must not panic when no symbol table is entered, as symbols are often formatted
+// while reporting another failure.
 impl fmt::Debug for Symbol {
-    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "\"{}\"", self.as_str())
+        match self.try_as_str() {
+            Some(symbol_str) => write!(f, "\"{}\"", symbol_str),
+            None => write!(f, "Symbol({})", self.index()),
+        }
     }
 }
 
 impl fmt::Display for Symbol {
-    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.as_str())
+        match self.try_as_str() {
+            Some(symbol_str) => f.write_str(symbol_str),
+            None => write!(f, "<symbol {}>", self.index()),
+        }
     }
 }
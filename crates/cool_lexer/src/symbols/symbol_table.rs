@@ -1,22 +1,123 @@
 use crate::symbols::{sym, Symbol};
-use cool_arena::Arena;
+use cool_arena::{InternArena, SharedInterner};
 use cool_collections::{Id, SmallString};
-use once_cell::sync::Lazy;
+use rustc_hash::{FxHashMap, FxHasher};
+use std::cell::Cell;
 use std::fmt::Write;
-use std::sync::Mutex;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroU32;
+use std::ptr;
+use std::sync::{LazyLock, Mutex, RwLock};
 
-pub(crate) type SymbolTable<'a> = Arena<'a, Symbol, str>;
+const SHARD_COUNT: usize = 16;
 
-static SYMBOL_TABLE: Lazy<Mutex<SymbolTable<'static>>> = Lazy::new(|| {
-    let mut symbols = SymbolTable::new_leak();
-    sym::intern_symbols(&mut symbols);
-    Mutex::new(symbols)
-});
+/// Symbols interned during a compiler session. Symbols are resolved through the table entered
+/// by the current thread. Interning or getting the string of a symbol that is not predefined
+/// panics if no table is entered, since the symbol cannot be told apart from the ones of other
+/// tables.
+///
+/// The strings themselves are stored once for the whole process and shared by all tables, so
+/// they stay valid after the table is dropped.
+///
+/// Interning locks one of several shards, chosen by the hash of the string, so that threads
+/// interning different strings rarely wait for each other.
+pub struct SymbolTable {
+    shards: [Mutex<FxHashMap<&'static str, Symbol>>; SHARD_COUNT],
+    strs: RwLock<Vec<&'static str>>,
+}
+
+static SYMBOL_STRS: LazyLock<[SharedInterner<str>; SHARD_COUNT]> =
+    LazyLock::new(|| std::array::from_fn(|_| Mutex::new(InternArena::new_leak())));
+
+thread_local! {
+    static CURRENT_SYMBOL_TABLE: Cell<*const SymbolTable> = const { Cell::new(ptr::null()) };
+}
+
+impl SymbolTable {
+    /// Makes the table current on this thread while `f` runs.
+    pub fn enter<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        struct Guard(*const SymbolTable);
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                CURRENT_SYMBOL_TABLE.with(|table| table.set(self.0));
+            }
+        }
+
+        let _guard = Guard(CURRENT_SYMBOL_TABLE.with(|table| table.replace(self)));
+        f()
+    }
+
+    /// Runs `f` with the table that is current on this thread, so that it can be entered by
+    /// other threads.
+    pub fn with_current<F, R>(f: F) -> R
+    where
+        F: FnOnce(&SymbolTable) -> R,
+    {
+        Self::try_with_current(f).expect("no symbol table is entered on this thread")
+    }
+
+    fn try_with_current<F, R>(f: F) -> Option<R>
+    where
+        F: FnOnce(&SymbolTable) -> R,
+    {
+        let table = CURRENT_SYMBOL_TABLE.with(Cell::get);
+
+        // Safety: the table outlives the call to `enter` that made it current.
+        (!table.is_null()).then(|| f(unsafe { &*table }))
+    }
+
+    pub(crate) fn insert_str(&self, symbol_str: &str) -> Symbol {
+        let mut hasher = FxHasher::default();
+        symbol_str.hash(&mut hasher);
+
+        let shard_index = hasher.finish() as usize % SHARD_COUNT;
+        let mut shard = self.shards[shard_index].lock().unwrap();
+
+        if let Some(&symbol) = shard.get(symbol_str) {
+            return symbol;
+        }
+
+        let symbol_str = SYMBOL_STRS[shard_index]
+            .lock()
+            .unwrap()
+            .insert_str(symbol_str)
+            .into_ref();
+
+        let symbol = {
+            let mut strs = self.strs.write().unwrap();
+            strs.push(symbol_str);
+            Symbol::from(NonZeroU32::new(strs.len() as u32).unwrap())
+        };
+
+        shard.insert(symbol_str, symbol);
+        symbol
+    }
+
+    fn get(&self, symbol: Symbol) -> Option<&'static str> {
+        self.strs.read().unwrap().get(symbol.index()).copied()
+    }
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        let table = Self {
+            shards: Default::default(),
+            strs: Default::default(),
+        };
+
+        sym::intern_symbols(&table);
+        table
+    }
+}
 
 impl Symbol {
     #[inline]
     pub fn insert(symbol_str: &str) -> Symbol {
-        SYMBOL_TABLE.lock().unwrap().insert_str(symbol_str)
+        SymbolTable::with_current(|table| table.insert_str(symbol_str))
     }
 
     pub fn insert_u32(value: u32) -> Symbol {
@@ -29,12 +130,55 @@ impl Symbol {
         Self::insert(&value_str)
     }
 
+    /// Returns the string of the symbol, looked up in the symbol table entered on this thread.
     #[inline]
     pub fn as_str(&self) -> &'static str {
         if *self <= sym::WILDCARD {
             sym::ALL_REPRS[self.index()]
         } else {
-            SYMBOL_TABLE.lock().unwrap().get(*self).unwrap()
+            SymbolTable::with_current(|table| table.get(*self)).unwrap_or_else(|| {
+                panic!(
+                    "symbol {} was not interned into the entered table",
+                    self.index()
+                )
+            })
         }
     }
+
+    /// Returns the string of the symbol, or `None` if no symbol table is entered on this thread
+    /// or the symbol was not interned into it.
+    pub fn try_as_str(&self) -> Option<&'static str> {
+        if *self <= sym::WILDCARD {
+            Some(sym::ALL_REPRS[self.index()])
+        } else {
+            SymbolTable::try_with_current(|table| table.get(*self)).flatten()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbol_str_outlives_table() {
+        let (symbol, symbol_str) = SymbolTable::default().enter(|| {
+            let symbol = Symbol::insert("outlives_table");
+            (symbol, symbol.as_str())
+        });
+
+        assert_eq!(symbol_str, "outlives_table");
+        assert_eq!(symbol.try_as_str(), None);
+        assert_eq!(format!("{symbol}"), format!("<symbol {}>", symbol.index()));
+        assert_eq!(format!("{:?}", sym::KW_FN), "\"fn\"");
+    }
+
+    #[test]
+    fn tables_do_not_share_symbols() {
+        let first = SymbolTable::default().enter(|| Symbol::insert("first"));
+        let second = SymbolTable::default();
+
+        assert_eq!(second.enter(|| first.try_as_str()), None);
+        assert_eq!(second.enter(|| Symbol::insert("second")), first);
+    }
 }
@@ -28,6 +28,7 @@ pub enum LiteralKind {
     Bool,
     Char,
     Str,
+    Null,
 }
 
 impl LiteralKind {
@@ -66,6 +66,14 @@ Punctuation! {
     Shl: "<<",
     Shr: ">>",
 
+    // Wrapping and saturating arithmetic
+    PlusPercent: "+%",
+    MinusPercent: "-%",
+    StarPercent: "*%",
+    PlusOr: "+|",
+    MinusOr: "-|",
+    StarOr: "*|",
+
     PlusEq: "+=",
     MinusEq: "-=",
     StarEq: "*=",
@@ -76,6 +84,12 @@ Punctuation! {
     OrEq: "|=",
     ShlEq: "<<=",
     ShrEq: ">>=",
+    PlusPercentEq: "+%=",
+    MinusPercentEq: "-%=",
+    StarPercentEq: "*%=",
+    PlusOrEq: "+|=",
+    MinusOrEq: "-|=",
+    StarOrEq: "*|=",
 
     Eq: "=" from '=',
     EqEq: "==",
@@ -94,6 +108,8 @@ Punctuation! {
     Comma: "," from ',',
     Semicolon: ";" from ';',
     Colon: ":" from ':',
+    At: "@" from '@',
+    Question: "?" from '?',
 
     OpenBrace: "{" from '{',
     CloseBrace: "}" from '}',
@@ -112,6 +128,14 @@ impl Punctuation {
             (Self::Lt, Self::Lt) => Self::Shl,
             (Self::Gt, Self::Gt) => Self::Shr,
 
+            // Wrapping and saturating arithmetic
+            (Self::Plus, Self::Percent) => Self::PlusPercent,
+            (Self::Minus, Self::Percent) => Self::MinusPercent,
+            (Self::Star, Self::Percent) => Self::StarPercent,
+            (Self::Plus, Self::Or) => Self::PlusOr,
+            (Self::Minus, Self::Or) => Self::MinusOr,
+            (Self::Star, Self::Or) => Self::StarOr,
+
             // Assignment
             (Self::Plus, Self::Eq) => Self::PlusEq,
             (Self::Minus, Self::Eq) => Self::MinusEq,
@@ -122,6 +146,12 @@ impl Punctuation {
             (Self::Or, Self::Eq) => Self::OrEq,
             (Self::Shl, Self::Eq) => Self::ShlEq,
             (Self::Shr, Self::Eq) => Self::ShrEq,
+            (Self::PlusPercent, Self::Eq) => Self::PlusPercentEq,
+            (Self::MinusPercent, Self::Eq) => Self::MinusPercentEq,
+            (Self::StarPercent, Self::Eq) => Self::StarPercentEq,
+            (Self::PlusOr, Self::Eq) => Self::PlusOrEq,
+            (Self::MinusOr, Self::Eq) => Self::MinusOrEq,
+            (Self::StarOr, Self::Eq) => Self::StarOrEq,
 
             // Relational
             (Self::Eq, Self::Eq) => Self::EqEq,
@@ -0,0 +1,201 @@
+use cool_ast::{SourceIndex, SourceRef};
+use cool_driver::{
+    CompileError, CompileOptions, CompileResult, Package, Session, SourceFile, SourceMap,
+};
+use cool_lexer::Symbol;
+use cool_resolve::{BindingId, ItemId, ItemKind, ItemPathBuf, ModuleElem, Scope, TyId};
+use cool_span::Span;
+use rustc_hash::FxHashMap;
+use std::path::Path;
+
+/// Result of running the front-end passes on a crate, kept to answer editor queries.
+pub struct Analysis {
+    pub package: Package,
+    pub session: Session,
+    pub source_index: SourceIndex,
+    pub errors: Vec<CompileError>,
+    binding_spans: FxHashMap<BindingId, Span>,
+    ty_spans: FxHashMap<TyId, Span>,
+}
+
+impl Analysis {
+    /// Runs the passes up to the AST generation, collecting the errors of all of them.
+    pub fn new(options: &CompileOptions, source_map: SourceMap) -> CompileResult<Self> {
+        // Layouts follow the host, like the code of `cool_cli run` that the editor is checking
+        let (_, mut session) = cool_driver::p0_init(&cool_driver::host_target_triple())?;
+
+        let (package, source_index, errors) = session.enter(|resolve| {
+            let (package, mut errors) =
+                match cool_driver::p1_parse_with_source_map(resolve, options, source_map) {
+                    Ok(package) => (package, vec![]),
+                    Err((package, error_bundle)) => (package, error_bundle.errors),
+                };
+
+            if let Err(mut error_bundle) = cool_driver::p2_define_tys(&package, resolve) {
+                errors.append(&mut error_bundle.errors);
+            }
+
+            if let Err(mut error_bundle) = cool_driver::p3_define_fn_tys(&package, resolve) {
+                errors.append(&mut error_bundle.errors);
+            }
+
+            let mut source_index = SourceIndex::default();
+
+            if let Err(mut error_bundle) =
+                cool_driver::p4_gen_ast_indexed(&package, resolve, &mut source_index)
+            {
+                errors.append(&mut error_bundle.errors);
+            }
+
+            errors.sort_by_key(CompileError::span);
+            (package, source_index, errors)
+        });
+
+        let mut binding_spans = FxHashMap::<BindingId, Span>::default();
+        let mut ty_spans = FxHashMap::<TyId, Span>::default();
+
+        let item_spans = package
+            .aliases
+            .iter()
+            .map(|item| (item.item_id, item.span))
+            .chain(package.structs.iter().map(|item| (item.item_id, item.span)))
+            .chain(package.unions.iter().map(|item| (item.item_id, item.span)))
+            .chain(package.enums.iter().map(|item| (item.item_id, item.span)))
+            .chain(
+                package
+                    .interfaces
+                    .iter()
+                    .map(|item| (item.item_id, item.span)),
+            )
+            .chain(
+                package
+                    .extern_fns
+                    .iter()
+                    .map(|item| (item.item_id, item.span)),
+            )
+            .chain(package.consts.iter().map(|item| (item.item_id, item.span)))
+            .chain(package.methods.iter().map(|item| (item.item_id, item.span)));
+
+        for (item_id, span) in item_spans {
+            match session.resolve[item_id] {
+                ItemKind::Binding(binding_id) => {
+                    binding_spans.insert(binding_id, span);
+                }
+                ItemKind::Ty(ty_id) => {
+                    ty_spans.insert(ty_id, span);
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            package,
+            session,
+            source_index,
+            errors,
+            binding_spans,
+            ty_spans,
+        })
+    }
+
+    /// Runs `f` with the symbol table of the analysis entered, which is needed to intern and
+    /// display its symbols.
+    #[inline]
+    pub fn enter<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        self.session.symbols().enter(f)
+    }
+
+    pub fn has_parse_errors(&self) -> bool {
+        self.errors
+            .iter()
+            .any(|error| matches!(error, CompileError::Parse(_)))
+    }
+
+    pub fn get_file(&self, path: &Path) -> Option<&SourceFile> {
+        self.package
+            .source_map
+            .files()
+            .iter()
+            .find(|file| file.path == path)
+    }
+
+    /// Returns the span and the type of the innermost expression at the offset.
+    pub fn ty_at(&self, offset: u32) -> Option<(Span, TyId)> {
+        self.source_index
+            .find_expr(offset)
+            .map(|expr| (expr.span, expr.expr_id.ty_id))
+    }
+
+    /// Returns the span of the definition of the item or binding named at the offset. Modules
+    /// defined by a file point to the start of the file.
+    pub fn definition_at(&self, offset: u32) -> Option<Span> {
+        match self.source_index.find_source_ref(offset)? {
+            SourceRef::Binding(binding_id) => {
+                self.source_index
+                    .local_bindings
+                    .get(&binding_id)
+                    .or_else(|| self.binding_spans.get(&binding_id))
+                    .copied()
+            }
+            SourceRef::Ty(ty_id) => self.ty_spans.get(&ty_id).copied(),
+            SourceRef::Module(module_id) => {
+                let (path, _) = self
+                    .package
+                    .file_modules
+                    .iter()
+                    .find(|(_, &file_module_id)| file_module_id == module_id)?;
+
+                self.get_file(path)
+                    .map(|file| Span::new(file.span.start, 0))
+            }
+        }
+    }
+
+    /// Returns the members of the module at `module_path`, relative to the module defined by the
+    /// file, that can be accessed from the file. An empty path returns the members of the
+    /// module itself. Must be called with the analysis entered.
+    pub fn module_members(&self, path: &Path, module_path: &[&str]) -> Vec<(Symbol, ItemId)> {
+        let Some(&file_module_id) = self.package.file_modules.get(path) else {
+            return vec![];
+        };
+
+        let module_id = if module_path.is_empty() {
+            file_module_id
+        } else {
+            let module_path = module_path
+                .iter()
+                .map(|&symbol| Symbol::insert(symbol))
+                .collect::<ItemPathBuf>();
+
+            let Some(module_id) = self
+                .session
+                .resolve
+                .resolve_global(Scope::Module(file_module_id), &module_path)
+                .ok()
+                .and_then(|item_id| self.session.resolve[item_id].as_module_id())
+            else {
+                return vec![];
+            };
+
+            module_id
+        };
+
+        let file_module = &self.session.resolve[file_module_id];
+        let module = &self.session.resolve[module_id];
+        let is_visible =
+            |elem: &ModuleElem| elem.is_exported || file_module.item_id.is_child_of(module.item_id);
+
+        let mut members = module
+            .elems
+            .iter()
+            .filter(|(_, elem)| is_visible(elem))
+            .map(|(&symbol, elem)| (symbol, elem.item_id))
+            .collect::<Vec<_>>();
+
+        members.sort_by_key(|(symbol, _)| symbol.as_str());
+        members
+    }
+}
@@ -0,0 +1,13 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Language server for Cool, speaking LSP over the standard input and output.
+#[derive(Parser)]
+pub struct Args {
+    /// Name of the crate. Defaults to the name of the crate root file.
+    #[arg(long)]
+    pub crate_name: Option<String>,
+    /// Root file of the crate that open documents belong to. If not given, every open document
+    /// is analyzed as the root of its own crate.
+    pub crate_root_file: Option<PathBuf>,
+}
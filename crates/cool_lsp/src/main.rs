@@ -0,0 +1,35 @@
+mod analysis;
+mod args;
+mod position;
+mod server;
+
+use crate::args::Args;
+use crate::server::{Server, ServerResult};
+use clap::Parser as _;
+use lsp_server::Connection;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> ServerResult<()> {
+    let crate_root_file = args
+        .crate_root_file
+        .map(|crate_root_file| crate_root_file.canonicalize())
+        .transpose()?;
+
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = serde_json::to_value(Server::capabilities())?;
+    connection.initialize(capabilities)?;
+
+    Server::new(connection, args.crate_name, crate_root_file).run()?;
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use cool_driver::SourceFile;
+use cool_span::Span;
+use lsp_types::{Position, Range};
+
+/// Returns the byte index of an LSP position, whose character is counted in UTF-16 code units.
+pub fn position_to_index(text: &str, position: Position) -> Option<usize> {
+    let mut line_start = 0;
+
+    for _ in 0..position.line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+
+    let line = text[line_start..].split('\n').next().unwrap_or_default();
+    let mut utf16_len = 0;
+
+    for (i, c) in line.char_indices() {
+        if utf16_len >= position.character {
+            return Some(line_start + i);
+        }
+
+        utf16_len += c.len_utf16() as u32;
+    }
+
+    Some(line_start + line.len())
+}
+
+pub fn index_to_position(text: &str, index: usize) -> Position {
+    let index = index.min(text.len());
+    let before = &text[..index];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].encode_utf16().count() as u32,
+    }
+}
+
+/// Returns the offset of an LSP position in the source map, which is shared by all files.
+#[inline]
+pub fn position_to_offset(file: &SourceFile, position: Position) -> Option<u32> {
+    position_to_index(&file.source, position).map(|index| file.span.start + index as u32)
+}
+
+pub fn span_to_range(file: &SourceFile, span: Span) -> Range {
+    let start = (span.start - file.span.start) as usize;
+    let end = (span.end() - file.span.start) as usize;
+
+    Range {
+        start: index_to_position(&file.source, start),
+        end: index_to_position(&file.source, end),
+    }
+}
@@ -0,0 +1,427 @@
+use crate::analysis::Analysis;
+use crate::position::{position_to_index, position_to_offset, span_to_range};
+use cool_driver::{CompileOptions, SourceMap};
+use cool_resolve::{GenericKind, ItemKind, ValueTy};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as NotificationTrait, PublishDiagnostics,
+};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, Request as RequestTrait};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    Diagnostic, DiagnosticSeverity, GotoDefinitionParams, GotoDefinitionResponse, Hover,
+    HoverContents, HoverParams, HoverProviderCapability, Location, MarkupContent, MarkupKind,
+    OneOf, PublishDiagnosticsParams, Range, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use rustc_hash::FxHashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+pub type ServerResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+pub struct Server {
+    connection: Connection,
+    crate_name: Option<String>,
+    crate_root_file: Option<PathBuf>,
+    documents: FxHashMap<PathBuf, String>,
+    /// Latest analysis of each crate without parse errors, by crate root file.
+    analyses: FxHashMap<PathBuf, Analysis>,
+    /// Files of each crate that diagnostics were published for.
+    diagnostic_files: FxHashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl Server {
+    pub fn new(
+        connection: Connection,
+        crate_name: Option<String>,
+        crate_root_file: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            connection,
+            crate_name,
+            crate_root_file,
+            documents: Default::default(),
+            analyses: Default::default(),
+            diagnostic_files: Default::default(),
+        }
+    }
+
+    pub fn capabilities() -> ServerCapabilities {
+        ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            definition_provider: Some(OneOf::Left(true)),
+            completion_provider: Some(CompletionOptions {
+                trigger_characters: Some(vec![".".to_owned()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    pub fn run(&mut self) -> ServerResult<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        break;
+                    }
+
+                    let response = self.handle_request(request);
+                    self.connection.sender.send(response.into())?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> Response {
+        match request.method.as_str() {
+            HoverRequest::METHOD => self.dispatch::<HoverRequest, _>(request, Self::hover),
+            GotoDefinition::METHOD => {
+                self.dispatch::<GotoDefinition, _>(request, Self::goto_definition)
+            }
+            Completion::METHOD => self.dispatch::<Completion, _>(request, Self::completion),
+            _ => {
+                Response::new_err(
+                    request.id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request '{}'", request.method),
+                )
+            }
+        }
+    }
+
+    fn dispatch<R, F>(&self, request: Request, handler: F) -> Response
+    where
+        R: RequestTrait,
+        F: FnOnce(&Self, R::Params) -> R::Result,
+    {
+        let id = request.id.clone();
+
+        match request.extract::<R::Params>(R::METHOD) {
+            Ok((id, params)) => Response::new_ok(id, handler(self, params)),
+            Err(error) => Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string()),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> ServerResult<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = notification
+                    .extract::<<DidOpenTextDocument as NotificationTrait>::Params>(
+                        DidOpenTextDocument::METHOD,
+                    )?;
+
+                if let Ok(path) = params.text_document.uri.to_file_path() {
+                    self.documents
+                        .insert(path.clone(), params.text_document.text);
+                    self.analyze(&path)?;
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                let mut params = notification
+                    .extract::<<DidChangeTextDocument as NotificationTrait>::Params>(
+                    DidChangeTextDocument::METHOD,
+                )?;
+
+                // Documents are synced in full, so the last change contains the whole text
+                let (Ok(path), Some(change)) = (
+                    params.text_document.uri.to_file_path(),
+                    params.content_changes.pop(),
+                ) else {
+                    return Ok(());
+                };
+
+                self.documents.insert(path.clone(), change.text);
+                self.analyze(&path)?;
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = notification
+                    .extract::<<DidCloseTextDocument as NotificationTrait>::Params>(
+                        DidCloseTextDocument::METHOD,
+                    )?;
+
+                if let Ok(path) = params.text_document.uri.to_file_path() {
+                    self.documents.remove(&path);
+
+                    if self.crate_root_file.is_some() {
+                        self.analyze(&path)?;
+                    } else {
+                        self.analyses.remove(&path);
+                        self.publish_diagnostics(&path, FxHashMap::default())?;
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn crate_options(&self, path: &Path) -> CompileOptions {
+        let crate_root_file = self
+            .crate_root_file
+            .clone()
+            .unwrap_or_else(|| path.to_path_buf());
+
+        let crate_name = self.crate_name.clone().unwrap_or_else(|| {
+            crate_root_file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().replace('-', "_"))
+                .unwrap_or_default()
+        });
+
+        // Tests are analyzed like the rest of the crate
+        CompileOptions {
+            crate_name,
+            crate_root_file,
+            dependencies: Vec::new(),
+            packages: Vec::new(),
+            overflow_checks: false,
+            test: true,
+        }
+    }
+
+    /// Analyzes the crate of a document and publishes its diagnostics. The analysis is kept for
+    /// queries unless it has parse errors, which leave out the items of the file that failed to
+    /// parse.
+    fn analyze(&mut self, path: &Path) -> ServerResult<()> {
+        let options = self.crate_options(path);
+        let mut source_map = SourceMap::default();
+
+        for (path, text) in self.documents.iter() {
+            source_map.override_file(path.clone(), text.clone());
+        }
+
+        let analysis = match cool_driver::catch_internal_error(|| {
+            Analysis::new(&options, source_map)
+        }) {
+            Ok(analysis) => analysis,
+            Err(error_bundle) => {
+                eprintln!("{error_bundle}");
+
+                // Errors that prevent the analysis, such as compiler bugs, are shown on the crate
+                // root and the previous analysis is kept
+                let diagnostics = error_bundle
+                    .errors
+                    .iter()
+                    .map(|error| {
+                        Diagnostic {
+                            range: Range::default(),
+                            severity: Some(DiagnosticSeverity::ERROR),
+                            source: Some("cool".to_owned()),
+                            message: error.to_string(),
+                            ..Default::default()
+                        }
+                    })
+                    .collect();
+
+                let crate_root_file = options.crate_root_file;
+                let diagnostics = FxHashMap::from_iter([(crate_root_file.clone(), diagnostics)]);
+                return self.publish_diagnostics(&crate_root_file, diagnostics);
+            }
+        };
+
+        let mut diagnostics = FxHashMap::<PathBuf, Vec<Diagnostic>>::default();
+
+        for file in analysis.package.source_map.files() {
+            diagnostics.insert(file.path.clone(), vec![]);
+        }
+
+        analysis.enter(|| {
+            for error in analysis.errors.iter() {
+                let (path, range) = match error.span() {
+                    Some(span) => {
+                        let file = analysis.package.source_map.get_file_from_offset(span.start);
+                        (file.path.clone(), span_to_range(file, span))
+                    }
+                    None => (options.crate_root_file.clone(), Range::default()),
+                };
+
+                diagnostics.entry(path).or_default().push(Diagnostic {
+                    range,
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("cool".to_owned()),
+                    message: error.to_string(),
+                    ..Default::default()
+                });
+            }
+        });
+
+        self.publish_diagnostics(&options.crate_root_file, diagnostics)?;
+
+        if !analysis.has_parse_errors() || !self.analyses.contains_key(&options.crate_root_file) {
+            self.analyses.insert(options.crate_root_file, analysis);
+        }
+
+        Ok(())
+    }
+
+    /// Publishes the diagnostics of a crate, clearing the ones of the files that no longer have
+    /// any.
+    fn publish_diagnostics(
+        &mut self,
+        crate_root_file: &Path,
+        mut diagnostics: FxHashMap<PathBuf, Vec<Diagnostic>>,
+    ) -> ServerResult<()> {
+        let previous_files = self
+            .diagnostic_files
+            .insert(
+                crate_root_file.to_path_buf(),
+                diagnostics.keys().cloned().collect(),
+            )
+            .unwrap_or_default();
+
+        for path in previous_files {
+            diagnostics.entry(path).or_default();
+        }
+
+        for (path, diagnostics) in diagnostics {
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+
+            let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+            let notification = Notification::new(PublishDiagnostics::METHOD.to_owned(), params);
+            self.connection.sender.send(notification.into())?;
+        }
+
+        Ok(())
+    }
+
+    fn get_analysis(&self, path: &Path) -> Option<&Analysis> {
+        let crate_root_file = self.crate_root_file.as_deref().unwrap_or(path);
+        self.analyses.get(crate_root_file)
+    }
+
+    /// Returns the analysis of the crate of a document and the offset of a position in it.
+    fn get_analysis_and_offset(
+        &self,
+        uri: &Url,
+        position: lsp_types::Position,
+    ) -> Option<(&Analysis, u32)> {
+        let path = uri.to_file_path().ok()?;
+        let analysis = self.get_analysis(&path)?;
+        let file = analysis.get_file(&path)?;
+        let offset = position_to_offset(file, position)?;
+        Some((analysis, offset))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let params = params.text_document_position_params;
+        let (analysis, offset) =
+            self.get_analysis_and_offset(&params.text_document.uri, params.position)?;
+
+        let (span, ty_id) = analysis.ty_at(offset)?;
+        let file = analysis.package.source_map.get_file_from_offset(span.start);
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: analysis.enter(|| format!("```cool\n{ty_id}\n```")),
+            }),
+            range: Some(span_to_range(file, span)),
+        })
+    }
+
+    fn goto_definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let params = params.text_document_position_params;
+        let (analysis, offset) =
+            self.get_analysis_and_offset(&params.text_document.uri, params.position)?;
+
+        let span = analysis.definition_at(offset)?;
+        let file = analysis.package.source_map.get_file_from_offset(span.start);
+
+        Some(GotoDefinitionResponse::Scalar(Location {
+            uri: Url::from_file_path(&file.path).ok()?,
+            range: span_to_range(file, span),
+        }))
+    }
+
+    /// Completes the members of the module named by the path before the cursor, or the items of
+    /// the current module if there is no path.
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let params = params.text_document_position;
+        let path = params.text_document.uri.to_file_path().ok()?;
+        let analysis = self.get_analysis(&path)?;
+
+        // The document is used as is, since it can have parse errors while typing
+        let text = match self.documents.get(&path) {
+            Some(text) => text.as_str(),
+            None => analysis.get_file(&path)?.source.as_str(),
+        };
+
+        let index = position_to_index(text, params.position)?;
+        let module_path = module_path_before(&text[..index])?;
+
+        let items = analysis.enter(|| {
+            analysis
+                .module_members(&path, &module_path)
+                .into_iter()
+                .map(|(symbol, item_id)| {
+                    let (kind, detail) = match analysis.session.resolve[item_id] {
+                        ItemKind::Module(_) => (CompletionItemKind::MODULE, None),
+                        ItemKind::Ty(ty_id) => {
+                            (CompletionItemKind::STRUCT, Some(ty_id.to_string()))
+                        }
+                        ItemKind::Binding(binding_id) => {
+                            let ty_id = analysis.session.resolve[binding_id].ty_id;
+
+                            let kind = match ty_id.as_value() {
+                                Some(ValueTy::Fn(_)) => CompletionItemKind::FUNCTION,
+                                _ => CompletionItemKind::CONSTANT,
+                            };
+
+                            (kind, Some(ty_id.to_string()))
+                        }
+                        ItemKind::Generic(generic_id) => {
+                            match analysis.session.resolve[generic_id].kind {
+                                GenericKind::Struct { .. } => (CompletionItemKind::STRUCT, None),
+                                GenericKind::Fn => (CompletionItemKind::FUNCTION, None),
+                            }
+                        }
+                    };
+
+                    CompletionItem {
+                        label: symbol.as_str().to_owned(),
+                        kind: Some(kind),
+                        detail,
+                        ..Default::default()
+                    }
+                })
+                .collect()
+        });
+
+        Some(CompletionResponse::Array(items))
+    }
+}
+
+/// Returns the identifiers separated by dots that come before the one being typed at the end
+/// of the text. Returns `None` if the text ends with a dot that does not follow an identifier.
+fn module_path_before(text: &str) -> Option<Vec<&str>> {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    let mut rest = text.trim_end_matches(is_ident_char);
+    let mut module_path = Vec::<&str>::new();
+
+    while let Some(before_dot) = rest.strip_suffix('.') {
+        let ident_start = before_dot.trim_end_matches(is_ident_char).len();
+        let ident = &before_dot[ident_start..];
+
+        if ident.is_empty() {
+            return None;
+        }
+
+        module_path.push(ident);
+        rest = &before_dot[..ident_start];
+    }
+
+    module_path.reverse();
+    Some(module_path)
+}
@@ -0,0 +1,96 @@
+use crate::{Ident, LiteralExpr, ParseResult, Parser};
+use cool_lexer::{tk, TokenKind};
+use cool_span::{Section, Span};
+use smallvec::SmallVec;
+
+#[derive(Clone, Debug)]
+pub struct Attribute {
+    pub span: Span,
+    pub ident: Ident,
+    pub args: SmallVec<[AttributeArg; 1]>,
+}
+
+impl Section for Attribute {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum AttributeArg {
+    Ident(Ident),
+    Literal(LiteralExpr),
+}
+
+impl Section for AttributeArg {
+    fn span(&self) -> Span {
+        match self {
+            Self::Ident(ident) => ident.span(),
+            Self::Literal(literal) => literal.span(),
+        }
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_attributes(&mut self) -> ParseResult<Vec<Attribute>> {
+        let mut attributes = Vec::<Attribute>::new();
+
+        while self.peek().kind == tk::AT {
+            attributes.push(self.parse_attribute()?);
+        }
+
+        Ok(attributes)
+    }
+
+    pub fn parse_attribute(&mut self) -> ParseResult<Attribute> {
+        let start_token = self.bump_expect(&tk::AT)?;
+        let ident = self.parse_ident()?;
+        let mut args = SmallVec::new();
+
+        if self.bump_if_eq(tk::OPEN_PAREN).is_none() {
+            return Ok(Attribute {
+                span: start_token.span.to(ident.span),
+                ident,
+                args,
+            });
+        }
+
+        let end_token = match self.peek().kind {
+            tk::CLOSE_PAREN => self.bump_expect(&tk::CLOSE_PAREN)?,
+            _ => {
+                loop {
+                    args.push(self.parse_attribute_arg()?);
+
+                    if self.bump_if_eq(tk::COMMA).is_some() {
+                        if let Some(end_token) = self.bump_if_eq(tk::CLOSE_PAREN) {
+                            break end_token;
+                        }
+                    } else if let Some(end_token) = self.bump_if_eq(tk::CLOSE_PAREN) {
+                        break end_token;
+                    } else {
+                        return self.peek_error(&[tk::COMMA, tk::CLOSE_PAREN]);
+                    }
+                }
+            }
+        };
+
+        Ok(Attribute {
+            span: start_token.span.to(end_token.span),
+            ident,
+            args,
+        })
+    }
+
+    fn parse_attribute_arg(&mut self) -> ParseResult<AttributeArg> {
+        let arg = match self.peek().kind {
+            TokenKind::Ident(_) => AttributeArg::Ident(self.parse_ident()?),
+            TokenKind::Literal(_) | TokenKind::Prefix(_) => {
+                AttributeArg::Literal(self.parse_literal_expr()?)
+            }
+            _ => return self.peek_error(&[tk::DIAG_IDENT, tk::DIAG_LITERAL]),
+        };
+
+        Ok(arg)
+    }
+}
@@ -1,7 +1,11 @@
+mod attribute;
 mod item_decl;
+mod static_assert_decl;
 mod use_decl;
 
+pub use self::attribute::*;
 pub use self::item_decl::*;
+pub use self::static_assert_decl::*;
 pub use self::use_decl::*;
 use crate::{ParseResult, Parser};
 use cool_lexer::{tk, TokenKind};
@@ -11,6 +15,7 @@ use derive_more::From;
 #[derive(Clone, Debug)]
 pub struct Decl {
     pub span: Span,
+    pub attributes: Vec<Attribute>,
     pub is_exported: bool,
     pub kind: DeclKind,
 }
@@ -18,6 +23,7 @@ pub struct Decl {
 #[derive(Clone, From, Debug)]
 pub enum DeclKind {
     Item(ItemDecl),
+    StaticAssert(StaticAssertDecl),
     Use(UseDecl),
 }
 
@@ -35,6 +41,7 @@ impl Section for DeclKind {
     fn span(&self) -> Span {
         match self {
             Self::Item(decl) => decl.span(),
+            Self::StaticAssert(decl) => decl.span(),
             Self::Use(decl) => decl.span(),
         }
     }
@@ -42,6 +49,8 @@ impl Section for DeclKind {
 
 impl Parser<'_> {
     pub fn parse_decl(&mut self) -> ParseResult<Decl> {
+        let attributes = self.parse_attributes()?;
+
         let export_span = if self.peek().kind == tk::KW_EXPORT {
             Some(self.bump().span)
         } else {
@@ -50,19 +59,28 @@ impl Parser<'_> {
 
         let kind = match self.peek().kind {
             TokenKind::Ident(_) => DeclKind::Item(self.parse_item_decl()?),
+            tk::KW_STATIC_ASSERT if export_span.is_none() => {
+                DeclKind::StaticAssert(self.parse_static_assert_decl()?)
+            }
             tk::KW_USE => DeclKind::Use(self.parse_use_decl()?),
-            _ => self.peek_error(&[tk::KW_USE, tk::DIAG_IDENT])?,
+            _ => self.peek_error(&[tk::KW_STATIC_ASSERT, tk::KW_USE, tk::DIAG_IDENT])?,
         };
 
         let end_token = self.bump_expect(&tk::SEMICOLON)?;
 
-        let (is_exported, span) = match export_span {
-            Some(span) => (true, span.to(end_token.span)),
-            None => (false, kind.span().to(end_token.span)),
+        let (is_exported, start_span) = match export_span {
+            Some(span) => (true, span),
+            None => (false, kind.span()),
         };
 
+        let start_span = attributes
+            .first()
+            .map(|attribute| attribute.span)
+            .unwrap_or(start_span);
+
         Ok(Decl {
-            span,
+            span: start_span.to(end_token.span),
+            attributes,
             is_exported,
             kind,
         })
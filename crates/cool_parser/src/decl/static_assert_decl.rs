@@ -0,0 +1,41 @@
+use crate::{Expr, ParseResult, Parser};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct StaticAssertDecl {
+    pub span: Span,
+    pub cond: Expr,
+    pub has_trailing_comma: bool,
+}
+
+impl Section for StaticAssertDecl {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_static_assert_decl(&mut self) -> ParseResult<StaticAssertDecl> {
+        let start_token = self.bump_expect(&tk::KW_STATIC_ASSERT)?;
+        self.bump_expect(&tk::OPEN_PAREN)?;
+
+        let cond = self.parse_expr()?;
+
+        let (end_token, has_trailing_comma) = match self.bump_if_eq(tk::CLOSE_PAREN) {
+            Some(end_token) => (end_token, false),
+            None => {
+                self.bump_expect(&tk::COMMA)?;
+                let end_token = self.bump_expect(&tk::CLOSE_PAREN)?;
+                (end_token, true)
+            }
+        };
+
+        Ok(StaticAssertDecl {
+            span: start_token.span.to(end_token.span),
+            cond,
+            has_trailing_comma,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{BlockExpr, FnParamList, ParseResult, Parser, Ty};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct ClosureExpr {
+    pub span: Span,
+    pub param_list: FnParamList,
+    pub ret_ty: Option<Ty>,
+    pub body: Box<BlockExpr>,
+}
+
+impl Section for ClosureExpr {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_closure_expr(&mut self) -> ParseResult<ClosureExpr> {
+        let closure_kw = self.bump_expect(&tk::KW_CLOSURE)?;
+        let param_list = self.parse_fn_param_list()?;
+
+        let ret_ty = if self.bump_if_eq(tk::ARROW).is_some() {
+            Some(self.parse_ty()?)
+        } else {
+            None
+        };
+
+        let body = self.parse_block_expr()?;
+
+        Ok(ClosureExpr {
+            span: closure_kw.span.to(body.span()),
+            param_list,
+            ret_ty,
+            body: Box::new(body),
+        })
+    }
+}
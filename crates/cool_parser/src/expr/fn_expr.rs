@@ -38,7 +38,12 @@ impl Parser<'_> {
                 }
                 .into()
             }
-            None => ExternFnItem { prototype }.into(),
+            None => {
+                ExternFnItem {
+                    prototype: Box::new(prototype),
+                }
+                .into()
+            }
         };
 
         Ok(abstract_fn)
@@ -1,9 +1,11 @@
 mod access_expr;
 mod align_of_expr;
 mod array_expr;
+mod assert_expr;
 mod binary_expr;
 mod block_expr;
 mod cast_expr;
+mod closure_expr;
 mod cond_expr;
 mod fn_call_expr;
 mod fn_expr;
@@ -12,19 +14,24 @@ mod literal_expr;
 mod loop_expr;
 mod match_expr;
 mod offset_of_expr;
+mod or_else_expr;
+mod panic_expr;
 mod size_of_expr;
 mod stmt_expr;
 mod struct_expr;
 mod subscript_expr;
+mod try_expr;
 mod tuple_expr;
 mod unary_expr;
 
 pub use self::access_expr::*;
 pub use self::align_of_expr::*;
 pub use self::array_expr::*;
+pub use self::assert_expr::*;
 pub use self::binary_expr::*;
 pub use self::block_expr::*;
 pub use self::cast_expr::*;
+pub use self::closure_expr::*;
 pub use self::cond_expr::*;
 pub use self::fn_call_expr::*;
 pub use self::fn_expr::*;
@@ -33,10 +40,13 @@ pub use self::literal_expr::*;
 pub use self::loop_expr::*;
 pub use self::match_expr::*;
 pub use self::offset_of_expr::*;
+pub use self::or_else_expr::*;
+pub use self::panic_expr::*;
 pub use self::size_of_expr::*;
 pub use self::stmt_expr::*;
 pub use self::struct_expr::*;
 pub use self::subscript_expr::*;
+pub use self::try_expr::*;
 pub use self::tuple_expr::*;
 pub use self::unary_expr::*;
 use crate::{BinOp, ParseResult, Parser};
@@ -69,9 +79,11 @@ define_expr! {
     AlignOf,
     Array,
     ArrayRepeat,
+    Assert,
     Binary,
     Block,
     Cast,
+    Closure,
     Cond,
     Deref,
     Fn,
@@ -82,13 +94,17 @@ define_expr! {
     Loop,
     Match,
     OffsetOf,
+    OrElse,
+    Panic,
     Paren,
     Range,
     SizeOf,
     Stmt,
     Struct,
+    Try,
     Tuple,
     Unary,
+    Unreachable,
 }
 
 impl Expr {
@@ -126,6 +142,18 @@ impl Parser<'_> {
     }
 
     pub fn parse_expr_full(&mut self, allow_struct_expr: bool) -> ParseResult<Expr> {
+        let expr = self.parse_bin_expr(allow_struct_expr)?;
+
+        if self.peek().kind != tk::KW_ORELSE {
+            return Ok(expr);
+        }
+
+        Ok(self
+            .continue_parse_or_else_expr(expr, allow_struct_expr)?
+            .into())
+    }
+
+    fn parse_bin_expr(&mut self, allow_struct_expr: bool) -> ParseResult<Expr> {
         let expr = self.parse_primary_expr(allow_struct_expr)?;
 
         let (first_bin_op, second_expr) = match BinOp::from_token_kind(self.peek().kind) {
@@ -190,14 +218,18 @@ impl Parser<'_> {
 
     fn parse_primary_expr(&mut self, allow_struct_expr: bool) -> ParseResult<Expr> {
         let mut expr: Expr = match self.peek().kind {
-            TokenKind::Ident(_) => self.parse_ident_expr()?.into(),
+            TokenKind::Ident(_) | tk::KW_SELF => self.parse_ident_expr()?.into(),
             TokenKind::Prefix(_) | TokenKind::Literal(_) => self.parse_literal_expr()?.into(),
             tk::KW_ALIGN_OF => self.parse_align_of_expr()?.into(),
+            tk::KW_ASSERT => self.parse_assert_expr()?.into(),
+            tk::KW_CLOSURE => self.parse_closure_expr()?.into(),
             tk::KW_IF => self.parse_cond_expr()?.into(),
             tk::KW_LOOP => self.parse_loop_expr()?.into(),
             tk::KW_MATCH => self.parse_match_expr()?.into(),
             tk::KW_OFFSET_OF => self.parse_offset_of_expr()?.into(),
+            tk::KW_PANIC => self.parse_panic_expr()?.into(),
             tk::KW_SIZE_OF => self.parse_size_of_expr()?.into(),
+            tk::KW_UNREACHABLE => self.parse_unreachable_expr()?.into(),
             tk::MINUS | tk::NOT | tk::AND => self.parse_unary_expr()?.into(),
             tk::OPEN_BRACE => self.parse_block_expr()?.into(),
             tk::OPEN_BRACKET => self.parse_array_expr()?,
@@ -207,9 +239,14 @@ impl Parser<'_> {
                     tk::DIAG_IDENT,
                     tk::DIAG_LITERAL,
                     tk::KW_ALIGN_OF,
+                    tk::KW_ASSERT,
+                    tk::KW_CLOSURE,
                     tk::KW_OFFSET_OF,
+                    tk::KW_PANIC,
                     tk::KW_RETURN,
+                    tk::KW_SELF,
                     tk::KW_SIZE_OF,
+                    tk::KW_UNREACHABLE,
                     tk::OPEN_BRACE,
                     tk::OPEN_BRACKET,
                     tk::OPEN_PAREN,
@@ -228,6 +265,7 @@ impl Parser<'_> {
                         }
                         tk::OPEN_PAREN => self.continue_parse_fn_call_expr(Box::new(expr))?.into(),
                         tk::OPEN_BRACKET => self.continue_parse_subscript_expr(Box::new(expr))?,
+                        tk::QUESTION => self.continue_parse_try_expr(Box::new(expr))?.into(),
                         _ => break,
                     }
                 }
@@ -242,19 +280,34 @@ impl Parser<'_> {
                         tk::DOT => self.continue_parse_access_expr(Box::new(expr))?,
                         tk::KW_AS => self.continue_parse_cast_expr(Box::new(expr))?.into(),
                         tk::OPEN_BRACKET => self.continue_parse_subscript_expr(Box::new(expr))?,
+                        tk::QUESTION => self.continue_parse_try_expr(Box::new(expr))?.into(),
+                        _ => break,
+                    }
+                }
+                Expr::Index(_) => {
+                    match self.peek().kind {
+                        tk::DOT => self.continue_parse_access_expr(Box::new(expr))?,
+                        tk::KW_AS => self.continue_parse_cast_expr(Box::new(expr))?.into(),
+                        tk::OPEN_BRACE if allow_struct_expr => {
+                            self.continue_parse_struct_expr(Box::new(expr))?.into()
+                        }
+                        tk::OPEN_PAREN => self.continue_parse_fn_call_expr(Box::new(expr))?.into(),
+                        tk::OPEN_BRACKET => self.continue_parse_subscript_expr(Box::new(expr))?,
+                        tk::QUESTION => self.continue_parse_try_expr(Box::new(expr))?.into(),
                         _ => break,
                     }
                 }
                 Expr::Deref(_)
                 | Expr::FnCall(_)
-                | Expr::Index(_)
                 | Expr::Paren(_)
-                | Expr::Range(_) => {
+                | Expr::Range(_)
+                | Expr::Try(_) => {
                     match self.peek().kind {
                         tk::DOT => self.continue_parse_access_expr(Box::new(expr))?,
                         tk::KW_AS => self.continue_parse_cast_expr(Box::new(expr))?.into(),
                         tk::OPEN_PAREN => self.continue_parse_fn_call_expr(Box::new(expr))?.into(),
                         tk::OPEN_BRACKET => self.continue_parse_subscript_expr(Box::new(expr))?,
+                        tk::QUESTION => self.continue_parse_try_expr(Box::new(expr))?.into(),
                         _ => break,
                     }
                 }
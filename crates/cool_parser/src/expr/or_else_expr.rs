@@ -0,0 +1,32 @@
+use crate::{Expr, ParseResult, Parser};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct OrElseExpr {
+    pub lhs: Box<Expr>,
+    pub rhs: Box<Expr>,
+}
+
+impl Section for OrElseExpr {
+    #[inline]
+    fn span(&self) -> Span {
+        self.lhs.span().to(self.rhs.span())
+    }
+}
+
+impl Parser<'_> {
+    pub fn continue_parse_or_else_expr(
+        &mut self,
+        lhs: Expr,
+        allow_struct_expr: bool,
+    ) -> ParseResult<OrElseExpr> {
+        self.bump_expect(&tk::KW_ORELSE)?;
+        let rhs = self.parse_expr_full(allow_struct_expr)?;
+
+        Ok(OrElseExpr {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        })
+    }
+}
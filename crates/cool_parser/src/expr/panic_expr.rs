@@ -0,0 +1,63 @@
+use crate::{Expr, ParseResult, Parser};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct PanicExpr {
+    pub span: Span,
+    pub message: Box<Expr>,
+    pub has_trailing_comma: bool,
+}
+
+impl Section for PanicExpr {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UnreachableExpr {
+    pub span: Span,
+}
+
+impl Section for UnreachableExpr {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_panic_expr(&mut self) -> ParseResult<PanicExpr> {
+        let start_token = self.bump_expect(&tk::KW_PANIC)?;
+        self.bump_expect(&tk::OPEN_PAREN)?;
+
+        let message = self.parse_expr()?;
+
+        let (end_token, has_trailing_comma) = match self.bump_if_eq(tk::CLOSE_PAREN) {
+            Some(end_token) => (end_token, false),
+            None => {
+                self.bump_expect(&tk::COMMA)?;
+                let end_token = self.bump_expect(&tk::CLOSE_PAREN)?;
+                (end_token, true)
+            }
+        };
+
+        Ok(PanicExpr {
+            span: start_token.span.to(end_token.span),
+            message: Box::new(message),
+            has_trailing_comma,
+        })
+    }
+
+    pub fn parse_unreachable_expr(&mut self) -> ParseResult<UnreachableExpr> {
+        let start_token = self.bump_expect(&tk::KW_UNREACHABLE)?;
+        self.bump_expect(&tk::OPEN_PAREN)?;
+        let end_token = self.bump_expect(&tk::CLOSE_PAREN)?;
+
+        Ok(UnreachableExpr {
+            span: start_token.span.to(end_token.span),
+        })
+    }
+}
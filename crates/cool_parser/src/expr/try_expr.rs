@@ -0,0 +1,27 @@
+use crate::{Expr, ParseResult, Parser};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct TryExpr {
+    pub span: Span,
+    pub expr: Box<Expr>,
+}
+
+impl Section for TryExpr {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn continue_parse_try_expr(&mut self, expr: Box<Expr>) -> ParseResult<TryExpr> {
+        let end_token = self.bump_expect(&tk::QUESTION)?;
+
+        Ok(TryExpr {
+            span: expr.span().to(end_token.span),
+            expr,
+        })
+    }
+}
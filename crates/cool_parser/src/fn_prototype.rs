@@ -1,5 +1,5 @@
-use crate::{FnExternDecl, Ident, ParseResult, Parser, Ty};
-use cool_lexer::{tk, TokenKind};
+use crate::{FnExternDecl, Ident, ParseResult, Parser, Ty, TyParamList};
+use cool_lexer::{sym, tk, TokenKind};
 use cool_span::{Section, Span};
 
 #[derive(Clone, Debug)]
@@ -36,6 +36,7 @@ impl Section for FnParamList {
 pub struct FnPrototype {
     pub span: Span,
     pub extern_decl: Option<FnExternDecl>,
+    pub ty_params: Option<TyParamList>,
     pub param_list: FnParamList,
     pub ret_ty: Option<Ty>,
 }
@@ -62,7 +63,16 @@ impl Parser<'_> {
                     },
                 )
             }
-            _ => self.error(start_token, &[tk::KW_MUT, tk::DIAG_IDENT])?,
+            tk::KW_SELF => {
+                (
+                    false,
+                    Ident {
+                        span: start_token.span,
+                        symbol: sym::KW_SELF,
+                    },
+                )
+            }
+            _ => self.error(start_token, &[tk::KW_MUT, tk::KW_SELF, tk::DIAG_IDENT])?,
         };
 
         let ty = if self.bump_if_eq(tk::COLON).is_some() {
@@ -83,7 +93,7 @@ impl Parser<'_> {
         })
     }
 
-    fn parse_fn_param_list(&mut self) -> ParseResult<FnParamList> {
+    pub fn parse_fn_param_list(&mut self) -> ParseResult<FnParamList> {
         let start_token = self.bump_expect(&tk::OPEN_PAREN)?;
         let mut params = Vec::<FnParam>::new();
 
@@ -135,6 +145,13 @@ impl Parser<'_> {
         };
 
         let fn_kw = self.bump_expect(&tk::KW_FN)?;
+
+        let ty_params = if self.peek().kind == tk::OPEN_BRACKET {
+            Some(self.parse_ty_param_list()?)
+        } else {
+            None
+        };
+
         let param_list = self.parse_fn_param_list()?;
 
         let ret_ty = if self.bump_if_eq(tk::ARROW).is_some() {
@@ -160,6 +177,7 @@ impl Parser<'_> {
         Ok(FnPrototype {
             span,
             extern_decl,
+            ty_params,
             param_list,
             ret_ty,
         })
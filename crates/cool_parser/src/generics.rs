@@ -0,0 +1,113 @@
+use crate::{Ident, IdentPath, ParseResult, Parser, Ty};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct TyParam {
+    pub ident: Ident,
+    pub bound: Option<IdentPath>,
+}
+
+impl Section for TyParam {
+    #[inline]
+    fn span(&self) -> Span {
+        match self.bound.as_ref() {
+            Some(bound) => self.ident.span().to(bound.span()),
+            None => self.ident.span(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TyParamList {
+    pub span: Span,
+    pub params: Vec<TyParam>,
+    pub has_trailing_comma: bool,
+}
+
+impl Section for TyParamList {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TyArgList {
+    pub span: Span,
+    pub args: Vec<Ty>,
+    pub has_trailing_comma: bool,
+}
+
+impl Section for TyArgList {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_ty_param(&mut self) -> ParseResult<TyParam> {
+        let ident = self.parse_ident()?;
+
+        let bound = if self.bump_if_eq(tk::COLON).is_some() {
+            Some(self.parse_import_path()?)
+        } else {
+            None
+        };
+
+        Ok(TyParam { ident, bound })
+    }
+
+    pub fn parse_ty_param_list(&mut self) -> ParseResult<TyParamList> {
+        let start_token = self.bump_expect(&tk::OPEN_BRACKET)?;
+        let mut params = Vec::<TyParam>::new();
+
+        let (end_token, has_trailing_comma) = loop {
+            params.push(self.parse_ty_param()?);
+
+            match self.bump_if_eq(tk::CLOSE_BRACKET) {
+                Some(end_token) => break (end_token, false),
+                None => {
+                    self.bump_expect(&tk::COMMA)?;
+
+                    if let Some(end_token) = self.bump_if_eq(tk::CLOSE_BRACKET) {
+                        break (end_token, true);
+                    }
+                }
+            }
+        };
+
+        Ok(TyParamList {
+            span: start_token.span.to(end_token.span),
+            params,
+            has_trailing_comma,
+        })
+    }
+
+    pub fn parse_ty_arg_list(&mut self) -> ParseResult<TyArgList> {
+        let start_token = self.bump_expect(&tk::OPEN_BRACKET)?;
+        let mut args = Vec::<Ty>::new();
+
+        let (end_token, has_trailing_comma) = loop {
+            args.push(self.parse_ty()?);
+
+            match self.bump_if_eq(tk::CLOSE_BRACKET) {
+                Some(end_token) => break (end_token, false),
+                None => {
+                    self.bump_expect(&tk::COMMA)?;
+
+                    if let Some(end_token) = self.bump_if_eq(tk::CLOSE_BRACKET) {
+                        break (end_token, true);
+                    }
+                }
+            }
+        };
+
+        Ok(TyArgList {
+            span: start_token.span.to(end_token.span),
+            args,
+            has_trailing_comma,
+        })
+    }
+}
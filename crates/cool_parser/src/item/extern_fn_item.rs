@@ -3,7 +3,7 @@ use cool_span::{Section, Span};
 
 #[derive(Clone, Debug)]
 pub struct ExternFnItem {
-    pub prototype: FnPrototype,
+    pub prototype: Box<FnPrototype>,
 }
 
 impl Section for ExternFnItem {
@@ -0,0 +1,46 @@
+use crate::{Decl, IdentPath, ParseResult, Parser};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct ImplBlock {
+    pub span: Span,
+    pub interface: Option<IdentPath>,
+    pub decls: Vec<Decl>,
+}
+
+impl Section for ImplBlock {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_impl_block(&mut self) -> ParseResult<ImplBlock> {
+        let start_token = self.bump_expect(&tk::KW_IMPL)?;
+
+        let interface = if self.peek().kind != tk::OPEN_BRACE {
+            Some(self.parse_import_path()?)
+        } else {
+            None
+        };
+
+        self.bump_expect(&tk::OPEN_BRACE)?;
+
+        let mut decls = Vec::<Decl>::new();
+        let end_token = loop {
+            if self.peek().kind == tk::CLOSE_BRACE {
+                break self.bump();
+            }
+
+            decls.push(self.parse_decl()?);
+        };
+
+        Ok(ImplBlock {
+            span: start_token.span.to(end_token.span),
+            interface,
+            decls,
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{FnPrototype, Ident, ParseResult, Parser};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct InterfaceMethod {
+    pub ident: Ident,
+    pub prototype: FnPrototype,
+}
+
+impl Section for InterfaceMethod {
+    #[inline]
+    fn span(&self) -> Span {
+        self.ident.span().to(self.prototype.span())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InterfaceItem {
+    pub span: Span,
+    pub methods: Vec<InterfaceMethod>,
+}
+
+impl Section for InterfaceItem {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_interface_method(&mut self) -> ParseResult<InterfaceMethod> {
+        let ident = self.parse_ident()?;
+        self.bump_expect(&tk::COLON)?;
+        self.bump_expect(&tk::COLON)?;
+        let prototype = self.parse_fn_prototype()?;
+        self.bump_expect(&tk::SEMICOLON)?;
+
+        Ok(InterfaceMethod { ident, prototype })
+    }
+
+    pub fn parse_interface_item(&mut self) -> ParseResult<InterfaceItem> {
+        let start_token = self.bump_expect(&tk::KW_INTERFACE)?;
+        self.bump_expect(&tk::OPEN_BRACE)?;
+
+        let mut methods = Vec::<InterfaceMethod>::new();
+        let end_token = loop {
+            if self.peek().kind == tk::CLOSE_BRACE {
+                break self.bump();
+            }
+
+            methods.push(self.parse_interface_method()?);
+        };
+
+        Ok(InterfaceItem {
+            span: start_token.span.to(end_token.span),
+            methods,
+        })
+    }
+}
@@ -2,15 +2,21 @@ mod alias_item;
 mod const_item;
 mod enum_item;
 mod extern_fn_item;
+mod impl_block;
+mod interface_item;
 mod module_item;
 mod struct_item;
+mod union_item;
 
 pub use self::alias_item::*;
 pub use self::const_item::*;
 pub use self::enum_item::*;
 pub use self::extern_fn_item::*;
+pub use self::impl_block::*;
+pub use self::interface_item::*;
 pub use self::module_item::*;
 pub use self::struct_item::*;
+pub use self::union_item::*;
 use crate::{AbstractFn, ParseResult, Parser};
 use cool_lexer::tk;
 use cool_span::{Section, Span};
@@ -41,8 +47,10 @@ define_item! {
     Const,
     Enum,
     ExternFn,
+    Interface,
     Module,
     Struct,
+    Union,
 }
 
 impl From<AbstractFn> for Item {
@@ -63,15 +71,13 @@ impl Parser<'_> {
             tk::KW_TYPE => self.parse_alias_item()?.into(),
             tk::KW_ENUM => self.parse_enum_item()?.into(),
             tk::KW_STRUCT => self.parse_struct_item()?.into(),
+            tk::KW_INTERFACE => self.parse_interface_item()?.into(),
+            tk::KW_UNION => self.parse_union_item()?.into(),
             _ => {
-                self.peek_error(&[
-                    tk::KW_ENUM,
-                    tk::KW_EXTERN,
-                    tk::KW_FN,
-                    tk::KW_MODULE,
-                    tk::KW_STRUCT,
-                    tk::KW_TYPE,
-                ])?
+                ConstItem {
+                    expr: self.parse_expr()?,
+                }
+                .into()
             }
         };
 
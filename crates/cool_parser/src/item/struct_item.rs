@@ -1,4 +1,4 @@
-use crate::{Ident, ParseResult, Parser, Ty};
+use crate::{Ident, ImplBlock, ParseResult, Parser, Ty, TyParamList};
 use cool_lexer::tk;
 use cool_span::{Section, Span};
 
@@ -18,9 +18,11 @@ impl Section for StructField {
 #[derive(Clone, Debug)]
 pub struct StructItem {
     pub span: Span,
+    pub ty_params: Option<TyParamList>,
     pub has_body: bool,
     pub fields: Vec<StructField>,
     pub has_trailing_comma: bool,
+    pub impl_blocks: Vec<ImplBlock>,
 }
 
 impl Section for StructItem {
@@ -42,12 +44,28 @@ impl Parser<'_> {
     pub fn parse_struct_item(&mut self) -> ParseResult<StructItem> {
         let start_token = self.bump_expect(&tk::KW_STRUCT)?;
 
+        let ty_params = if self.peek().kind == tk::OPEN_BRACKET {
+            Some(self.parse_ty_param_list()?)
+        } else {
+            None
+        };
+
         if self.peek().kind != tk::OPEN_BRACE {
+            let impl_blocks = self.parse_impl_blocks()?;
+
+            let end_span = impl_blocks
+                .last()
+                .map(|impl_block| impl_block.span)
+                .or(ty_params.as_ref().map(|ty_params| ty_params.span))
+                .unwrap_or(start_token.span);
+
             return Ok(StructItem {
-                span: start_token.span,
+                span: start_token.span.to(end_span),
+                ty_params,
                 has_body: false,
                 fields: vec![],
                 has_trailing_comma: false,
+                impl_blocks,
             });
         }
 
@@ -75,11 +93,30 @@ impl Parser<'_> {
             }
         };
 
+        let impl_blocks = self.parse_impl_blocks()?;
+
+        let end_span = impl_blocks
+            .last()
+            .map(|impl_block| impl_block.span)
+            .unwrap_or(end_token.span);
+
         Ok(StructItem {
-            span: start_token.span.to(end_token.span),
+            span: start_token.span.to(end_span),
+            ty_params,
             has_body: true,
             fields,
             has_trailing_comma,
+            impl_blocks,
         })
     }
+
+    fn parse_impl_blocks(&mut self) -> ParseResult<Vec<ImplBlock>> {
+        let mut impl_blocks = Vec::<ImplBlock>::new();
+
+        while self.peek().kind == tk::KW_IMPL {
+            impl_blocks.push(self.parse_impl_block()?);
+        }
+
+        Ok(impl_blocks)
+    }
 }
@@ -0,0 +1,55 @@
+use crate::{ParseResult, Parser, StructField};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct UnionItem {
+    pub span: Span,
+    pub fields: Vec<StructField>,
+    pub has_trailing_comma: bool,
+}
+
+impl Section for UnionItem {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_union_item(&mut self) -> ParseResult<UnionItem> {
+        let start_token = self.bump_expect(&tk::KW_UNION)?;
+        self.bump_expect(&tk::OPEN_BRACE)?;
+
+        if let Some(end_token) = self.bump_if_eq(tk::CLOSE_BRACE) {
+            return Ok(UnionItem {
+                span: start_token.span.to(end_token.span),
+                fields: vec![],
+                has_trailing_comma: false,
+            });
+        }
+
+        let mut fields = Vec::<StructField>::new();
+
+        let (end_token, has_trailing_comma) = loop {
+            fields.push(self.parse_struct_field()?);
+
+            match self.bump_if_eq(tk::CLOSE_BRACE) {
+                Some(end_token) => break (end_token, false),
+                None => {
+                    self.bump_expect(&tk::COMMA)?;
+
+                    if let Some(end_token) = self.bump_if_eq(tk::CLOSE_BRACE) {
+                        break (end_token, true);
+                    }
+                }
+            }
+        };
+
+        Ok(UnionItem {
+            span: start_token.span.to(end_token.span),
+            fields,
+            has_trailing_comma,
+        })
+    }
+}
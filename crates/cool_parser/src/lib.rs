@@ -6,6 +6,7 @@ mod expr;
 mod expr_or_stmt;
 mod fn_extern_decl;
 mod fn_prototype;
+mod generics;
 mod ident;
 mod item;
 mod op;
@@ -22,6 +23,7 @@ pub use self::expr::*;
 pub use self::expr_or_stmt::*;
 pub use self::fn_extern_decl::*;
 pub use self::fn_prototype::*;
+pub use self::generics::*;
 pub use self::ident::*;
 pub use self::item::*;
 pub use self::op::*;
@@ -1,3 +1,4 @@
+use crate::{ArithmeticOp, BinOp, BitwiseOp};
 use cool_lexer::{Punctuation, TokenKind};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -13,6 +14,12 @@ pub enum AssignOp {
     Xor,
     Shl,
     Shr,
+    WrappingAdd,
+    WrappingSub,
+    WrappingMul,
+    SaturatingAdd,
+    SaturatingSub,
+    SaturatingMul,
 }
 
 impl AssignOp {
@@ -36,9 +43,40 @@ impl AssignOp {
             Punctuation::CaretEq => Self::Xor,
             Punctuation::ShlEq => Self::Shl,
             Punctuation::ShrEq => Self::Shr,
+            Punctuation::PlusPercentEq => Self::WrappingAdd,
+            Punctuation::MinusPercentEq => Self::WrappingSub,
+            Punctuation::StarPercentEq => Self::WrappingMul,
+            Punctuation::PlusOrEq => Self::SaturatingAdd,
+            Punctuation::MinusOrEq => Self::SaturatingSub,
+            Punctuation::StarOrEq => Self::SaturatingMul,
             _ => return None,
         };
 
         Some(assign_op)
     }
+
+    /// Returns the binary operation applied by a compound assignment.
+    pub fn bin_op(&self) -> Option<BinOp> {
+        let bin_op: BinOp = match self {
+            Self::Eq => return None,
+            Self::Add => ArithmeticOp::Add.into(),
+            Self::Sub => ArithmeticOp::Sub.into(),
+            Self::Mul => ArithmeticOp::Mul.into(),
+            Self::Div => ArithmeticOp::Div.into(),
+            Self::Rem => ArithmeticOp::Rem.into(),
+            Self::Or => BitwiseOp::Or.into(),
+            Self::And => BitwiseOp::And.into(),
+            Self::Xor => BitwiseOp::Xor.into(),
+            Self::Shl => BitwiseOp::Shl.into(),
+            Self::Shr => BitwiseOp::Shr.into(),
+            Self::WrappingAdd => ArithmeticOp::WrappingAdd.into(),
+            Self::WrappingSub => ArithmeticOp::WrappingSub.into(),
+            Self::WrappingMul => ArithmeticOp::WrappingMul.into(),
+            Self::SaturatingAdd => ArithmeticOp::SaturatingAdd.into(),
+            Self::SaturatingSub => ArithmeticOp::SaturatingSub.into(),
+            Self::SaturatingMul => ArithmeticOp::SaturatingMul.into(),
+        };
+
+        Some(bin_op)
+    }
 }
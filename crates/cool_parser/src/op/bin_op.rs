@@ -33,6 +33,12 @@ impl BinOp {
             Punctuation::Star => ArithmeticOp::Mul.into(),
             Punctuation::Slash => ArithmeticOp::Div.into(),
             Punctuation::Percent => ArithmeticOp::Rem.into(),
+            Punctuation::PlusPercent => ArithmeticOp::WrappingAdd.into(),
+            Punctuation::MinusPercent => ArithmeticOp::WrappingSub.into(),
+            Punctuation::StarPercent => ArithmeticOp::WrappingMul.into(),
+            Punctuation::PlusOr => ArithmeticOp::SaturatingAdd.into(),
+            Punctuation::MinusOr => ArithmeticOp::SaturatingSub.into(),
+            Punctuation::StarOr => ArithmeticOp::SaturatingMul.into(),
 
             // Relational
             Punctuation::EqEq => ComparisonOp::Eq.into(),
@@ -67,6 +73,8 @@ impl BinOp {
                     ArithmeticOp::Mul => BinOpPrecedence::High,
                     ArithmeticOp::Div => BinOpPrecedence::High,
                     ArithmeticOp::Rem => BinOpPrecedence::High,
+                    ArithmeticOp::WrappingMul => BinOpPrecedence::High,
+                    ArithmeticOp::SaturatingMul => BinOpPrecedence::High,
                     _ => BinOpPrecedence::Medium,
                 }
             }
@@ -84,6 +92,23 @@ pub enum ArithmeticOp {
     Mul,
     Div,
     Rem,
+    WrappingAdd,
+    WrappingSub,
+    WrappingMul,
+    SaturatingAdd,
+    SaturatingSub,
+    SaturatingMul,
+}
+
+impl ArithmeticOp {
+    /// Returns whether the operation is only defined for integers.
+    #[inline]
+    pub fn is_int_only(&self) -> bool {
+        !matches!(
+            self,
+            Self::Add | Self::Sub | Self::Mul | Self::Div | Self::Rem,
+        )
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -96,6 +121,14 @@ pub enum ComparisonOp {
     Ge,
 }
 
+impl ComparisonOp {
+    /// Returns whether the operation only checks for equality, which requires no ordering.
+    #[inline]
+    pub fn is_equality(&self) -> bool {
+        matches!(self, Self::Eq | Self::Ne)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum BitwiseOp {
     And,
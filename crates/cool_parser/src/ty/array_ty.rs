@@ -1,11 +1,11 @@
-use crate::{LiteralExpr, ParseResult, Parser, Ty};
+use crate::{Expr, ParseResult, Parser, Ty};
 use cool_lexer::{tk, Token};
 use cool_span::{Section, Span};
 
 #[derive(Clone, Debug)]
 pub struct ArrayTy {
     pub span: Span,
-    pub len: LiteralExpr,
+    pub len: Box<Expr>,
     pub elem: Box<Ty>,
 }
 
@@ -20,7 +20,7 @@ impl Parser<'_> {
     pub(crate) fn continue_parse_array_ty(&mut self, open_bracket: Token) -> ParseResult<ArrayTy> {
         debug_assert_eq!(open_bracket.kind, tk::OPEN_BRACKET);
 
-        let len = self.parse_literal_expr()?;
+        let len = Box::new(self.parse_expr()?);
         self.bump_expect(&tk::CLOSE_BRACKET)?;
         let elem = self.parse_ty()?;
 
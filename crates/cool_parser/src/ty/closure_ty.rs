@@ -0,0 +1,41 @@
+use crate::{FnTyParamList, ParseResult, Parser, Ty};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct ClosureTy {
+    pub span: Span,
+    pub param_list: FnTyParamList,
+    pub ret_ty: Option<Box<Ty>>,
+}
+
+impl Section for ClosureTy {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_closure_ty(&mut self) -> ParseResult<ClosureTy> {
+        let closure_kw = self.bump_expect(&tk::KW_CLOSURE)?;
+        let param_list = self.parse_fn_ty_param_list()?;
+
+        let ret_ty = if self.bump_if_eq(tk::ARROW).is_some() {
+            Some(Box::new(self.parse_ty()?))
+        } else {
+            None
+        };
+
+        let end_span = ret_ty
+            .as_ref()
+            .map(|ty| ty.span())
+            .unwrap_or(param_list.span);
+
+        Ok(ClosureTy {
+            span: closure_kw.span.to(end_span),
+            param_list,
+            ret_ty,
+        })
+    }
+}
@@ -33,7 +33,7 @@ impl Section for FnTy {
 }
 
 impl Parser<'_> {
-    fn parse_fn_ty_param_list(&mut self) -> ParseResult<FnTyParamList> {
+    pub fn parse_fn_ty_param_list(&mut self) -> ParseResult<FnTyParamList> {
         let start_token = self.bump_expect(&tk::OPEN_PAREN)?;
         let mut params = Vec::<Ty>::new();
 
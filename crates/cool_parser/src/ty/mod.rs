@@ -1,16 +1,20 @@
 mod array_ty;
+mod closure_ty;
 mod fn_ty;
 mod item_ty;
 mod many_ptr_ty;
+mod optional_ty;
 mod paren_ty;
 mod path_ty;
 mod ptr_ty;
 mod slice_ty;
 
 pub use self::array_ty::*;
+pub use self::closure_ty::*;
 pub use self::fn_ty::*;
 pub use self::item_ty::*;
 pub use self::many_ptr_ty::*;
+pub use self::optional_ty::*;
 pub use self::paren_ty::*;
 pub use self::path_ty::*;
 pub use self::ptr_ty::*;
@@ -42,9 +46,11 @@ macro_rules! define_ty {
 
 define_ty! {
     Array,
+    Closure,
     Fn,
     Item,
     ManyPtr,
+    Optional,
     Paren,
     Path,
     Ptr,
@@ -59,14 +65,17 @@ impl Parser<'_> {
             tk::KW_CRATE | tk::KW_SUPER | tk::KW_SELF | TokenKind::Ident(_) => {
                 self.parse_path_ty()?.into()
             }
+            tk::KW_CLOSURE => self.parse_closure_ty()?.into(),
             tk::KW_EXTERN | tk::KW_FN => self.parse_fn_ty()?.into(),
             tk::KW_MODULE | tk::KW_TYPE => self.parse_item_ty()?.into(),
             tk::OPEN_BRACKET => self.parse_array_or_slice_ty()?,
             tk::OPEN_PAREN => self.parse_paren_ty()?,
+            tk::QUESTION => self.parse_optional_ty()?.into(),
             tk::STAR => self.parse_ptr_ty()?.into(),
             _ => {
                 return self.peek_error(&[
                     tk::DIAG_IDENT,
+                    tk::KW_CLOSURE,
                     tk::KW_CRATE,
                     tk::KW_EXTERN,
                     tk::KW_FN,
@@ -76,6 +85,7 @@ impl Parser<'_> {
                     tk::KW_TYPE,
                     tk::OPEN_BRACKET,
                     tk::OPEN_PAREN,
+                    tk::QUESTION,
                     tk::STAR,
                 ]);
             }
@@ -0,0 +1,28 @@
+use crate::{ParseResult, Parser, Ty};
+use cool_lexer::tk;
+use cool_span::{Section, Span};
+
+#[derive(Clone, Debug)]
+pub struct OptionalTy {
+    pub span: Span,
+    pub inner: Box<Ty>,
+}
+
+impl Section for OptionalTy {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_optional_ty(&mut self) -> ParseResult<OptionalTy> {
+        let start_token = self.bump_expect(&tk::QUESTION)?;
+        let inner = Box::new(self.parse_ty()?);
+
+        Ok(OptionalTy {
+            span: start_token.span.to(inner.span()),
+            inner,
+        })
+    }
+}
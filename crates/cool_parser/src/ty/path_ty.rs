@@ -1,10 +1,11 @@
-use crate::{Ident, IdentVec, ParseResult, Parser};
+use crate::{Ident, IdentVec, ParseResult, Parser, TyArgList};
 use cool_lexer::{sym, tk, TokenKind};
 use cool_span::{Section, Span};
 
 #[derive(Clone, Debug)]
 pub struct PathTy {
     pub idents: IdentVec,
+    pub ty_args: Option<TyArgList>,
 }
 
 impl Section for PathTy {
@@ -15,11 +16,15 @@ impl Section for PathTy {
             .map(|ident| ident.span)
             .unwrap_or(Span::empty());
 
-        let end = self
-            .idents
-            .last()
-            .map(|ident| ident.span)
-            .unwrap_or(Span::empty());
+        let end = match self.ty_args.as_ref() {
+            Some(ty_args) => ty_args.span,
+            None => {
+                self.idents
+                    .last()
+                    .map(|ident| ident.span)
+                    .unwrap_or(Span::empty())
+            }
+        };
 
         start.to(end)
     }
@@ -55,6 +60,12 @@ impl Parser<'_> {
             idents.push(self.parse_ty_path_ident()?);
         }
 
-        Ok(PathTy { idents })
+        let ty_args = if self.peek().kind == tk::OPEN_BRACKET {
+            Some(self.parse_ty_arg_list()?)
+        } else {
+            None
+        };
+
+        Ok(PathTy { idents, ty_args })
     }
 }
@@ -0,0 +1,31 @@
+use cool_collections::SmallString;
+
+/// Value of a constant expression. Integers are stored as the bits of the value, sign-extended
+/// to 128 bits for signed types.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ConstValue {
+    Unit,
+    Bool(bool),
+    Char(u32),
+    Int(u128),
+    Float(f64),
+    Cstr(SmallString),
+}
+
+impl ConstValue {
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_int(&self) -> Option<u128> {
+        match self {
+            Self::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
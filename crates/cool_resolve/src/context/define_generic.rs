@@ -0,0 +1,205 @@
+use crate::{
+    Binding, EmptyStructTy, Frame, FrameId, ItemId, ItemKind, ModuleElem, ModuleId, Mutability,
+    ResolveContext, ResolveError, ResolveErrorKind, ResolveResult, Scope, StructLayout, StructTy,
+    TyId, ValueTy,
+};
+use cool_collections::id_newtype;
+use cool_lexer::Symbol;
+use smallvec::SmallVec;
+use std::fmt::Write;
+use std::ops;
+
+id_newtype!(GenericId);
+
+pub type TyArgs = SmallVec<[TyId; 2]>;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GenericKind {
+    Struct {
+        has_body: bool,
+        layout: StructLayout,
+    },
+    Fn,
+}
+
+#[derive(Clone, Debug)]
+pub struct Generic {
+    pub module_id: ModuleId,
+    pub item_id: ItemId,
+    pub kind: GenericKind,
+    pub ty_params: SmallVec<[Symbol; 2]>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GenericInstance {
+    pub generic_id: GenericId,
+    pub item_id: ItemId,
+    pub ty_args: TyArgs,
+}
+
+impl ResolveContext {
+    pub fn declare_generic<P>(
+        &mut self,
+        module_id: ModuleId,
+        is_exported: bool,
+        symbol: Symbol,
+        kind: GenericKind,
+        ty_params: P,
+    ) -> ResolveResult<ItemId>
+    where
+        P: IntoIterator<Item = Symbol>,
+    {
+        let module = &mut self.modules[module_id];
+        let item_path = module.child_path(symbol);
+
+        let item_id = self
+            .paths
+            .insert_slice_if_not_exists(item_path.as_symbol_slice())
+            .map(ItemId::from)
+            .ok_or(ResolveError {
+                symbol,
+                kind: ResolveErrorKind::SymbolAlreadyDefined,
+            })?;
+
+        let generic_id = self.generics.push(Generic {
+            module_id,
+            item_id,
+            kind,
+            ty_params: ty_params.into_iter().collect(),
+        });
+
+        self.items.insert(item_id, ItemKind::Generic(generic_id));
+
+        module.elems.insert(
+            symbol,
+            ModuleElem {
+                is_exported,
+                item_id,
+            },
+        );
+
+        Ok(item_id)
+    }
+
+    #[inline]
+    pub fn get_generic_instance(&self, generic_id: GenericId, ty_args: &[TyId]) -> Option<ItemId> {
+        self.generic_instance_ids
+            .get(&(generic_id, TyArgs::from_slice(ty_args)))
+            .map(|&index| self.generic_instances[index].item_id)
+    }
+
+    /// Declares a new instance of `generic_id` as a sibling of the generic item. The instance
+    /// is not reachable by path and is only ever looked up through its type arguments.
+    pub fn insert_generic_instance(&mut self, generic_id: GenericId, ty_args: &[TyId]) -> ItemId {
+        let generic = &self.generics[generic_id];
+
+        let mut instance_name = String::new();
+        write!(instance_name, "{}[", generic.item_id.last().unwrap()).unwrap();
+
+        for (i, ty_arg) in ty_args.iter().enumerate() {
+            if i != 0 {
+                instance_name.push_str(", ");
+            }
+
+            write!(instance_name, "{ty_arg}").unwrap();
+        }
+
+        instance_name.push(']');
+
+        let item_path = self.modules[generic.module_id].child_path(Symbol::insert(&instance_name));
+        let item_id = ItemId::from(self.paths.insert_slice(item_path.as_symbol_slice()));
+
+        let item_kind = match generic.kind {
+            GenericKind::Struct { has_body, layout } => {
+                let ty = if has_body {
+                    ValueTy::from(StructTy { item_id })
+                } else {
+                    ValueTy::from(EmptyStructTy { item_id })
+                };
+
+                if !layout.is_auto() {
+                    self.struct_layouts.insert(item_id, layout);
+                }
+
+                ItemKind::Ty(self.tys.insert_value(ty))
+            }
+            GenericKind::Fn => {
+                ItemKind::Binding(self.bindings.push(Binding {
+                    symbol: item_path.last(),
+                    mutability: Mutability::Const,
+                    ty_id: self.tys.consts().infer,
+                }))
+            }
+        };
+
+        self.items.insert(item_id, item_kind);
+
+        let ty_args = TyArgs::from_slice(ty_args);
+        self.generic_instance_ids
+            .insert((generic_id, ty_args.clone()), self.generic_instances.len());
+
+        self.push_generic_instance(GenericInstance {
+            generic_id,
+            item_id,
+            ty_args,
+        });
+
+        item_id
+    }
+
+    pub(crate) fn push_generic_instance(&mut self, instance: GenericInstance) {
+        self.generic_instance_indexes
+            .insert(instance.item_id, self.generic_instances.len());
+
+        self.generic_instances.push(instance);
+    }
+
+    /// Adds a frame in which the type parameters of `generic_id` refer to `ty_args`.
+    pub fn add_generic_frame(&mut self, generic_id: GenericId, ty_args: &[TyId]) -> FrameId {
+        let generic = &self.generics[generic_id];
+        let mut frame = Frame::new(Scope::Module(generic.module_id));
+
+        for (&symbol, &ty_id) in generic.ty_params.iter().zip(ty_args) {
+            frame.ty_params.insert(symbol, ty_id);
+        }
+
+        self.frames.push(frame)
+    }
+
+    pub fn resolve_ty_param(&self, mut scope: Scope, symbol: Symbol) -> Option<TyId> {
+        loop {
+            let Scope::Frame(frame_id) = scope else {
+                return None;
+            };
+
+            let frame = &self.frames[frame_id];
+
+            if let Some(&ty_id) = frame.ty_params.get(&symbol) {
+                return Some(ty_id);
+            }
+
+            scope = frame.parent;
+        }
+    }
+
+    #[inline]
+    pub fn generic_instances(&self) -> &[GenericInstance] {
+        &self.generic_instances
+    }
+
+    #[inline]
+    pub fn get_generic_instance_of(&self, item_id: ItemId) -> Option<&GenericInstance> {
+        self.generic_instance_indexes
+            .get(&item_id)
+            .map(|&index| &self.generic_instances[index])
+    }
+}
+
+impl ops::Index<GenericId> for ResolveContext {
+    type Output = Generic;
+
+    #[inline]
+    fn index(&self, generic_id: GenericId) -> &Self::Output {
+        &self.generics[generic_id]
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    Frame, FrameId, InterfaceTy, ItemId, ItemKind, ItemPath, ModuleElem, ModuleId, ResolveContext,
+    ResolveError, ResolveErrorKind, ResolveResult, Scope, TyId,
+};
+use cool_lexer::{sym, Symbol};
+use smallvec::SmallVec;
+
+#[derive(Clone, Copy, Debug)]
+pub struct InterfaceMethod {
+    pub symbol: Symbol,
+    pub ty_id: TyId,
+    pub is_receiver_mutable: bool,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct Interface {
+    pub methods: SmallVec<[InterfaceMethod; 4]>,
+}
+
+impl Interface {
+    #[inline]
+    pub fn get_method_index(&self, symbol: Symbol) -> Option<usize> {
+        self.methods
+            .iter()
+            .position(|method| method.symbol == symbol)
+    }
+
+    /// Returns whether interface objects can only be created from mutable pointers.
+    #[inline]
+    pub fn requires_mutable_data(&self) -> bool {
+        self.methods.iter().any(|method| method.is_receiver_mutable)
+    }
+}
+
+/// Methods that implement an interface for a struct, in the order of the interface methods.
+/// Empty until the implementation is checked.
+pub type InterfaceImpl = SmallVec<[ItemId; 4]>;
+
+impl ResolveContext {
+    pub fn declare_interface(
+        &mut self,
+        module_id: ModuleId,
+        is_exported: bool,
+        symbol: Symbol,
+    ) -> ResolveResult<ItemId> {
+        let module = &mut self.modules[module_id];
+        let item_path = module.child_path(symbol);
+
+        let item_id = self
+            .paths
+            .insert_slice_if_not_exists(item_path.as_symbol_slice())
+            .map(ItemId::from)
+            .ok_or(ResolveError {
+                symbol,
+                kind: ResolveErrorKind::SymbolAlreadyDefined,
+            })?;
+
+        let ty_id = self.tys.insert_value(InterfaceTy { item_id });
+        self.items.insert(item_id, ItemKind::Ty(ty_id));
+        self.interfaces.insert(item_id, Interface::default());
+
+        module.elems.insert(
+            symbol,
+            ModuleElem {
+                is_exported,
+                item_id,
+            },
+        );
+
+        Ok(item_id)
+    }
+
+    pub fn define_interface<M>(&mut self, item_id: ItemId, methods: M)
+    where
+        M: IntoIterator<Item = InterfaceMethod>,
+    {
+        self.interfaces
+            .get_mut(&item_id)
+            .expect("item is not an interface")
+            .methods = methods.into_iter().collect();
+    }
+
+    #[inline]
+    pub fn get_interface(&self, item_id: ItemId) -> Option<&Interface> {
+        self.interfaces.get(&item_id)
+    }
+
+    pub fn resolve_interface<'a, P>(&self, scope: Scope, path: P) -> ResolveResult<ItemId>
+    where
+        P: Into<ItemPath<'a>>,
+    {
+        let path: ItemPath = path.into();
+        let item_id = self.resolve_global(scope, path)?;
+
+        if !self.interfaces.contains_key(&item_id) {
+            return Err(ResolveError {
+                symbol: path.last(),
+                kind: ResolveErrorKind::SymbolNotInterface,
+            });
+        }
+
+        Ok(item_id)
+    }
+
+    /// Adds a frame in which `Self` refers to the interface object type of `item_id`.
+    pub fn add_interface_frame(&mut self, module_id: ModuleId, item_id: ItemId) -> FrameId {
+        let self_ty_id = self.items[&item_id].as_ty_id().unwrap();
+        let mut frame = Frame::new(Scope::Module(module_id));
+        frame.ty_params.insert(sym::SELF_TY, self_ty_id);
+        self.frames.push(frame)
+    }
+
+    /// Records that `struct_item_id` claims to implement `interface_item_id`.
+    pub fn declare_interface_impl(&mut self, struct_item_id: ItemId, interface_item_id: ItemId) {
+        self.interface_impls
+            .entry((struct_item_id, interface_item_id))
+            .or_default();
+    }
+
+    pub fn define_interface_impl<M>(
+        &mut self,
+        struct_item_id: ItemId,
+        interface_item_id: ItemId,
+        methods: M,
+    ) where
+        M: IntoIterator<Item = ItemId>,
+    {
+        self.interface_impls.insert(
+            (struct_item_id, interface_item_id),
+            methods.into_iter().collect(),
+        );
+    }
+
+    #[inline]
+    pub fn get_interface_impl(
+        &self,
+        struct_item_id: ItemId,
+        interface_item_id: ItemId,
+    ) -> Option<&InterfaceImpl> {
+        self.interface_impls
+            .get(&(struct_item_id, interface_item_id))
+    }
+
+    #[inline]
+    pub fn implements_interface(&self, struct_item_id: ItemId, interface_item_id: ItemId) -> bool {
+        self.interface_impls
+            .contains_key(&(struct_item_id, interface_item_id))
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    Binding, Frame, FrameId, GenericInstance, ItemId, ItemKind, ItemPathBuf, ModuleId, Mutability,
+    ResolveContext, ResolveError, ResolveErrorKind, ResolveResult, Scope,
+};
+use cool_collections::SmallVecMap;
+use cool_lexer::{sym, Symbol};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Method {
+    pub struct_item_id: ItemId,
+    pub module_id: ModuleId,
+    pub is_exported: bool,
+}
+
+pub type MethodTable = SmallVecMap<Symbol, ItemId, 4>;
+
+impl ResolveContext {
+    /// Declares a function in the impl block of `struct_item_id`. The method is a constant
+    /// binding whose type is defined later, like any other function item.
+    pub fn declare_method(
+        &mut self,
+        struct_item_id: ItemId,
+        module_id: ModuleId,
+        is_exported: bool,
+        symbol: Symbol,
+    ) -> ResolveResult<ItemId> {
+        let item_path = ItemPathBuf::from_base_and_symbol(&struct_item_id, symbol);
+
+        let item_id = self
+            .paths
+            .insert_slice_if_not_exists(item_path.as_symbol_slice())
+            .map(ItemId::from)
+            .ok_or(ResolveError {
+                symbol,
+                kind: ResolveErrorKind::SymbolAlreadyDefined,
+            })?;
+
+        let binding_id = self.bindings.push(Binding {
+            symbol,
+            mutability: Mutability::Const,
+            ty_id: self.tys.consts().infer,
+        });
+
+        self.items.insert(item_id, ItemKind::Binding(binding_id));
+
+        self.method_tables
+            .entry(struct_item_id)
+            .or_default()
+            .insert(symbol, item_id);
+
+        self.methods.insert(
+            item_id,
+            Method {
+                struct_item_id,
+                module_id,
+                is_exported,
+            },
+        );
+
+        Ok(item_id)
+    }
+
+    /// Looks up the method `symbol` of `struct_item_id` as seen from `scope`.
+    pub fn resolve_method(
+        &self,
+        scope: Scope,
+        struct_item_id: ItemId,
+        symbol: Symbol,
+    ) -> ResolveResult<ItemId> {
+        let item_id = self
+            .method_tables
+            .get(&struct_item_id)
+            .and_then(|method_table| method_table.get(&symbol))
+            .copied()
+            .ok_or(ResolveError {
+                symbol,
+                kind: ResolveErrorKind::SymbolNotFound,
+            })?;
+
+        let method = &self.methods[&item_id];
+
+        if !method.is_exported {
+            let current_module = &self.modules[self.resolve_parent_module(scope)];
+            let method_module = &self.modules[method.module_id];
+
+            if !current_module.item_id.is_child_of(method_module.item_id) {
+                return Err(ResolveError {
+                    symbol,
+                    kind: ResolveErrorKind::SymbolNotPublic,
+                });
+            }
+        }
+
+        Ok(item_id)
+    }
+
+    #[inline]
+    pub fn get_method(&self, item_id: ItemId) -> Option<&Method> {
+        self.methods.get(&item_id)
+    }
+
+    #[inline]
+    pub fn get_method_table(&self, struct_item_id: ItemId) -> Option<&MethodTable> {
+        self.method_tables.get(&struct_item_id)
+    }
+
+    /// Records a method of a generic struct instance as an instance of the generic, so that
+    /// its body is generated along with the other instances.
+    pub fn insert_generic_method_instance(&mut self, item_id: ItemId) {
+        let struct_item_id = self.methods[&item_id].struct_item_id;
+        let struct_instance = self
+            .get_generic_instance_of(struct_item_id)
+            .expect("struct is not a generic instance");
+
+        let instance = GenericInstance {
+            generic_id: struct_instance.generic_id,
+            item_id,
+            ty_args: struct_instance.ty_args.clone(),
+        };
+
+        self.push_generic_instance(instance);
+    }
+
+    /// Adds a frame in which `Self` refers to the struct the method belongs to, as well as the
+    /// type parameters of the struct if it is a generic instance.
+    pub fn add_method_frame(&mut self, item_id: ItemId) -> FrameId {
+        let method = self.methods[&item_id];
+        let self_ty_id = self.items[&method.struct_item_id].as_ty_id().unwrap();
+
+        let mut frame = match self.get_generic_instance_of(method.struct_item_id) {
+            Some(instance) => {
+                let generic = &self.generics[instance.generic_id];
+                let mut frame = Frame::new(Scope::Module(generic.module_id));
+
+                for (&symbol, &ty_id) in generic.ty_params.iter().zip(instance.ty_args.iter()) {
+                    frame.ty_params.insert(symbol, ty_id);
+                }
+
+                frame
+            }
+            None => Frame::new(Scope::Module(method.module_id)),
+        };
+
+        frame.ty_params.insert(sym::SELF_TY, self_ty_id);
+        self.frames.push(frame)
+    }
+}
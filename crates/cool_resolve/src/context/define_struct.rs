@@ -1,6 +1,6 @@
 use crate::{
     EmptyStructTy, ItemId, ItemKind, ModuleElem, ModuleId, ResolveContext, ResolveError,
-    ResolveErrorKind, ResolveResult, StructTy, TyDef, TyId, TyResult, ValueTy,
+    ResolveErrorKind, ResolveResult, StructLayout, StructTy, TyDef, TyId, TyResult, ValueTy,
 };
 use cool_lexer::Symbol;
 
@@ -11,6 +11,7 @@ impl ResolveContext {
         is_exported: bool,
         symbol: Symbol,
         has_body: bool,
+        layout: StructLayout,
     ) -> ResolveResult<ItemId> {
         let module = &mut self.modules[module_id];
         let item_path = module.child_path(symbol);
@@ -33,6 +34,10 @@ impl ResolveContext {
         let ty_id = self.tys.insert_value(ty);
         self.items.insert(item_id, ItemKind::Ty(ty_id));
 
+        if !layout.is_auto() {
+            self.struct_layouts.insert(item_id, layout);
+        }
+
         module.elems.insert(
             symbol,
             ModuleElem {
@@ -52,6 +57,15 @@ impl ResolveContext {
             .as_ty_id()
             .expect("item is not a struct");
 
-        self.tys.define_struct(struct_ty_id, fields)
+        let layout = self.get_struct_layout(item_id);
+        self.tys.define_struct(struct_ty_id, fields, layout)
+    }
+
+    #[inline]
+    pub fn get_struct_layout(&self, item_id: ItemId) -> StructLayout {
+        self.struct_layouts
+            .get(&item_id)
+            .copied()
+            .unwrap_or_default()
     }
 }
@@ -0,0 +1,50 @@
+use crate::{
+    ItemId, ItemKind, ModuleElem, ModuleId, ResolveContext, ResolveError, ResolveErrorKind,
+    ResolveResult, TyDef, TyId, TyResult, UnionTy,
+};
+use cool_lexer::Symbol;
+
+impl ResolveContext {
+    pub fn declare_union(
+        &mut self,
+        module_id: ModuleId,
+        is_exported: bool,
+        symbol: Symbol,
+    ) -> ResolveResult<ItemId> {
+        let module = &mut self.modules[module_id];
+        let item_path = module.child_path(symbol);
+
+        let item_id = self
+            .paths
+            .insert_slice_if_not_exists(item_path.as_symbol_slice())
+            .map(ItemId::from)
+            .ok_or(ResolveError {
+                symbol,
+                kind: ResolveErrorKind::SymbolAlreadyDefined,
+            })?;
+
+        let ty_id = self.tys.insert_value(UnionTy { item_id });
+        self.items.insert(item_id, ItemKind::Ty(ty_id));
+
+        module.elems.insert(
+            symbol,
+            ModuleElem {
+                is_exported,
+                item_id,
+            },
+        );
+
+        Ok(item_id)
+    }
+
+    pub fn define_union<F>(&mut self, item_id: ItemId, fields: F) -> TyResult<&TyDef>
+    where
+        F: IntoIterator<Item = (Symbol, TyId)>,
+    {
+        let union_ty_id = self.items[&item_id]
+            .as_ty_id()
+            .expect("item is not a union");
+
+        self.tys.define_union(union_ty_id, fields)
+    }
+}
@@ -1,6 +1,10 @@
 mod define_alias;
 mod define_enum;
+mod define_generic;
+mod define_interface;
+mod define_method;
 mod define_struct;
+mod define_union;
 mod resolve_binding;
 mod resolve_error;
 mod resolve_expr;
@@ -10,6 +14,9 @@ mod resolve_ty;
 
 pub use self::define_alias::*;
 pub use self::define_enum::*;
+pub use self::define_generic::*;
+pub use self::define_interface::*;
+pub use self::define_method::*;
 pub use self::define_struct::*;
 pub use self::resolve_binding::*;
 pub use self::resolve_error::*;
@@ -17,22 +24,40 @@ pub use self::resolve_expr::*;
 pub use self::resolve_global::*;
 pub use self::resolve_local::*;
 pub use self::resolve_ty::*;
-use crate::{Binding, Frame, ItemId, ItemKind, Module, PrimitiveTyData, TyContext};
-use bumpalo::Bump;
-use cool_arena::InternArena;
+use crate::{
+    Binding, ConstValue, Frame, ItemId, ItemKind, Module, PrimitiveTyData, StructLayout, TyContext,
+};
+use cool_arena::{InternArena, SharedInternArena, SharedInterner};
 use cool_collections::IdIndexedVec;
 use cool_lexer::{sym, Symbol};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use smallvec::SmallVec;
+use std::sync::{LazyLock, Mutex};
+
+static PATHS: LazyLock<SharedInterner<[Symbol]>> =
+    LazyLock::new(|| Mutex::new(InternArena::new_leak()));
 
 #[derive(Debug)]
 pub struct ResolveContext {
-    paths: InternArena<'static, [Symbol]>,
+    paths: SharedInternArena<[Symbol]>,
     items: FxHashMap<ItemId, ItemKind>,
     modules: IdIndexedVec<ModuleId, Module>,
+    package_dependencies: FxHashMap<Symbol, SmallVec<[Symbol; 4]>>,
     tys: TyContext,
     bindings: IdIndexedVec<BindingId, Binding>,
+    const_values: FxHashMap<BindingId, ConstValue>,
+    direct_call_fns: FxHashSet<BindingId>,
     frames: IdIndexedVec<FrameId, Frame>,
-    exprs: &'static Bump,
+    generics: IdIndexedVec<GenericId, Generic>,
+    generic_instances: Vec<GenericInstance>,
+    generic_instance_ids: FxHashMap<(GenericId, TyArgs), usize>,
+    generic_instance_indexes: FxHashMap<ItemId, usize>,
+    methods: FxHashMap<ItemId, Method>,
+    method_tables: FxHashMap<ItemId, MethodTable>,
+    interfaces: FxHashMap<ItemId, Interface>,
+    interface_impls: FxHashMap<(ItemId, ItemId), InterfaceImpl>,
+    struct_layouts: FxHashMap<ItemId, StructLayout>,
+    expr_count: u32,
 }
 
 impl ResolveContext {
@@ -45,13 +70,25 @@ impl ResolveContext {
 
     fn empty(primitives: PrimitiveTyData) -> Self {
         Self {
-            paths: InternArena::new_leak(),
+            paths: SharedInternArena::new(&PATHS),
             items: Default::default(),
             modules: Default::default(),
+            package_dependencies: Default::default(),
             tys: TyContext::new(primitives),
             bindings: Default::default(),
+            const_values: Default::default(),
+            direct_call_fns: Default::default(),
             frames: Default::default(),
-            exprs: Box::leak(Box::default()),
+            generics: Default::default(),
+            generic_instances: Default::default(),
+            generic_instance_ids: Default::default(),
+            generic_instance_indexes: Default::default(),
+            methods: Default::default(),
+            method_tables: Default::default(),
+            interfaces: Default::default(),
+            interface_impls: Default::default(),
+            struct_layouts: Default::default(),
+            expr_count: 0,
         }
     }
 
@@ -1,6 +1,6 @@
 use crate::{
-    Binding, Frame, Mutability, ResolveContext, ResolveError, ResolveErrorKind, ResolveResult,
-    Scope, TyId,
+    Binding, ConstValue, Frame, Mutability, ResolveContext, ResolveError, ResolveErrorKind,
+    ResolveResult, Scope, TyId,
 };
 use cool_collections::id_newtype;
 use cool_lexer::Symbol;
@@ -23,6 +23,33 @@ impl ResolveContext {
         }
     }
 
+    pub fn is_frame_within(&self, frame_id: FrameId, ancestor_frame_id: FrameId) -> bool {
+        let mut current_frame_id = frame_id;
+
+        loop {
+            if current_frame_id == ancestor_frame_id {
+                return true;
+            }
+
+            current_frame_id = match self.get_parent_frame(current_frame_id) {
+                Some(frame_id) => frame_id,
+                None => return false,
+            };
+        }
+    }
+
+    pub fn find_binding_frame(&self, frame_id: FrameId, symbol: Symbol) -> Option<FrameId> {
+        let mut current_frame_id = frame_id;
+
+        loop {
+            if self.frames[current_frame_id].bindings.contains_key(&symbol) {
+                return Some(current_frame_id);
+            }
+
+            current_frame_id = self.get_parent_frame(current_frame_id)?;
+        }
+    }
+
     pub fn insert_local_binding(
         &mut self,
         frame_id: FrameId,
@@ -54,6 +81,29 @@ impl ResolveContext {
         self.bindings[binding_id].ty_id = ty_id;
     }
 
+    #[inline]
+    pub fn define_const(&mut self, binding_id: BindingId, ty_id: TyId, value: ConstValue) {
+        self.bindings[binding_id].ty_id = ty_id;
+        self.const_values.insert(binding_id, value);
+    }
+
+    #[inline]
+    pub fn get_const_value(&self, binding_id: BindingId) -> Option<&ConstValue> {
+        self.const_values.get(&binding_id)
+    }
+
+    /// Marks a function that can only be called directly, because its calling convention is
+    /// not carried by function pointer types.
+    #[inline]
+    pub fn mark_direct_call_only(&mut self, binding_id: BindingId) {
+        self.direct_call_fns.insert(binding_id);
+    }
+
+    #[inline]
+    pub fn is_direct_call_only(&self, binding_id: BindingId) -> bool {
+        self.direct_call_fns.contains(&binding_id)
+    }
+
     #[inline]
     pub fn make_binding_mutable(&mut self, binding_id: BindingId) {
         self.bindings[binding_id].mutability = Mutability::Mutable
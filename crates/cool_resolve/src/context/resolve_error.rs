@@ -13,6 +13,8 @@ pub enum ResolveErrorKind {
     SymbolNotModule,
     SymbolNotTy,
     SymbolNotAbi,
+    SymbolNotInterface,
+    SymbolIsGeneric,
     TooManySuperKeywords,
 }
 
@@ -26,6 +28,8 @@ impl ResolveErrorKind {
             ResolveErrorKind::SymbolNotModule => "does not refer to a module",
             ResolveErrorKind::SymbolNotTy => "does not refer to a type",
             ResolveErrorKind::SymbolNotAbi => "does not refer to an abi",
+            ResolveErrorKind::SymbolNotInterface => "does not refer to an interface",
+            ResolveErrorKind::SymbolIsGeneric => "requires type arguments",
             ResolveErrorKind::TooManySuperKeywords => "path contains too many super keywords",
         }
     }
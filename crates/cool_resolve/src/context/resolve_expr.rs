@@ -1,18 +1,20 @@
 use crate::{ResolveContext, TyId};
-use cool_arena::InternedValue;
 use derive_more::Deref;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deref, Debug)]
-#[deref(forward)]
-pub struct ExprId(InternedValue<'static, ResolveExpr>);
+pub struct ExprId {
+    index: u32,
+    #[deref]
+    expr: ResolveExpr,
+}
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub enum ResolveExprKind {
     Lvalue { is_mutable: bool },
     Rvalue,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct ResolveExpr {
     pub ty_id: TyId,
     pub kind: ResolveExprKind,
@@ -54,7 +56,11 @@ impl ResolveExpr {
 impl ResolveContext {
     #[inline]
     pub fn add_expr(&mut self, expr: ResolveExpr) -> ExprId {
-        let expr: &'static _ = self.exprs.alloc(expr);
-        ExprId(InternedValue::from(expr))
+        self.expr_count += 1;
+
+        ExprId {
+            index: self.expr_count,
+            expr,
+        }
     }
 }
@@ -35,6 +35,19 @@ impl ResolveContext {
         Ok(module_id)
     }
 
+    /// Makes the root module of `dependency` importable by name from the package with the root
+    /// module `package`.
+    pub fn insert_package_dependency(&mut self, package: ModuleId, dependency: ModuleId) {
+        let package = self.modules[package].path().first();
+        let dependency = self.modules[dependency].path().first();
+
+        let dependencies = self.package_dependencies.entry(package).or_default();
+
+        if !dependencies.contains(&dependency) {
+            dependencies.push(dependency);
+        }
+    }
+
     pub fn insert_module(
         &mut self,
         parent: ModuleId,
@@ -178,8 +191,9 @@ impl ResolveContext {
             symbol => {
                 if module.elems.contains_key(&symbol) {
                     module.path().to_path_buf()
-                } else if self.paths.contains(&[symbol]) {
-                    // Check other crates
+                } else if self.is_package_visible(module.path().first(), symbol) {
+                    // Check the current package and its dependencies
+                    let _ = symbol_iter.next();
                     ItemPathBuf::from(symbol)
                 } else if self.paths.contains(&[sym::EMPTY, symbol]) {
                     // Check builtins
@@ -220,8 +234,18 @@ impl ResolveContext {
             })
     }
 
+    pub(crate) fn is_package_visible(&self, package: Symbol, symbol: Symbol) -> bool {
+        if symbol == package {
+            return true;
+        }
+
+        self.package_dependencies
+            .get(&package)
+            .is_some_and(|dependencies| dependencies.contains(&symbol))
+    }
+
     #[inline]
-    fn get_item_id_by_path<'a, P>(&self, path: P) -> Option<ItemId>
+    pub fn get_item_id_by_path<'a, P>(&self, path: P) -> Option<ItemId>
     where
         P: Into<ItemPath<'a>>,
     {
@@ -13,7 +13,8 @@ impl ResolveContext {
                     let resolved_symbol = frame
                         .bindings
                         .get(&symbol)
-                        .map(|&binding_id| ItemKind::Binding(binding_id));
+                        .map(|&binding_id| ItemKind::Binding(binding_id))
+                        .or_else(|| frame.ty_params.get(&symbol).copied().map(ItemKind::Ty));
 
                     match resolved_symbol {
                         Some(resolved_symbol) => return Ok(resolved_symbol),
@@ -21,10 +22,21 @@ impl ResolveContext {
                     }
                 }
                 Scope::Module(module_id) => {
-                    let resolved_elem = self.modules[module_id].elems.get(&symbol);
+                    let module = &self.modules[module_id];
+                    let resolved_item_id = module
+                        .elems
+                        .get(&symbol)
+                        .or_else(|| self.modules[ModuleId::for_builtins()].elems.get(&symbol))
+                        .map(|resolved_elem| resolved_elem.item_id)
+                        .or_else(|| {
+                            // Packages are referred to by name
+                            self.is_package_visible(module.path().first(), symbol)
+                                .then(|| self.get_item_id_by_path([symbol].as_slice()))
+                                .flatten()
+                        });
 
-                    match resolved_elem {
-                        Some(resolved_elem) => return Ok(self.items[&resolved_elem.item_id]),
+                    match resolved_item_id {
+                        Some(resolved_item_id) => return Ok(self.items[&resolved_item_id]),
                         None => {
                             return Err(ResolveError {
                                 symbol,
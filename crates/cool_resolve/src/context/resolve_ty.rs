@@ -1,7 +1,7 @@
 use crate::{
-    ArrayTy, FnAbi, FnTy, ItemId, ItemKind, ItemPath, ManyPtrTy, ModuleElem, ModuleId, PtrTy,
-    ResolveContext, ResolveError, ResolveErrorKind, ResolveResult, Scope, SliceTy, StructTy,
-    TupleTy, TyConsts, TyDef, TyId, TyResolutionMethod, VariantTy,
+    ArrayTy, ClosureTy, FnAbi, FnTy, ItemId, ItemKind, ItemPath, ManyPtrTy, ModuleElem, ModuleId,
+    PtrTy, ResolveContext, ResolveError, ResolveErrorKind, ResolveResult, Scope, SliceTy, StructTy,
+    TupleTy, TyConsts, TyDef, TyId, TyResolutionMethod, ValueTy, VariantTy,
 };
 use cool_lexer::{sym, Symbol};
 use smallvec::SmallVec;
@@ -43,6 +43,12 @@ impl ResolveContext {
         self.tys.insert_value(VariantTy::new(variants))
     }
 
+    #[inline]
+    pub fn mk_optional(&mut self, inner: TyId) -> TyId {
+        let unit = self.tys.consts().unit;
+        self.mk_variant([inner, unit])
+    }
+
     pub fn mk_fn<P>(&mut self, abi: FnAbi, params: P, is_variadic: bool, ret: TyId) -> TyId
     where
         P: IntoIterator<Item = TyId>,
@@ -55,6 +61,16 @@ impl ResolveContext {
         })
     }
 
+    pub fn mk_closure<P>(&mut self, params: P, ret: TyId) -> TyId
+    where
+        P: IntoIterator<Item = TyId>,
+    {
+        self.tys.insert_value(ClosureTy {
+            params: SmallVec::from_iter(params),
+            ret,
+        })
+    }
+
     pub fn mk_ptr(&mut self, pointee: TyId, is_mutable: bool) -> TyId {
         self.tys.insert_value(PtrTy {
             pointee,
@@ -98,7 +114,40 @@ impl ResolveContext {
         found_ty_id: TyId,
         expected_ty_id: TyId,
     ) -> Option<(TyId, TyResolutionMethod)> {
-        self.tys.resolve_ty_id(found_ty_id, expected_ty_id)
+        self.tys
+            .resolve_ty_id(found_ty_id, expected_ty_id)
+            .or_else(|| self.resolve_interface_ty_id(found_ty_id, expected_ty_id))
+    }
+
+    /// Resolves a pointer to a struct as an interface object if the struct implements the
+    /// interface. Interfaces with mutable receivers require a mutable pointer. Pointers to
+    /// zero-sized structs are not real pointers, so they cannot be used as object data.
+    fn resolve_interface_ty_id(
+        &self,
+        found_ty_id: TyId,
+        expected_ty_id: TyId,
+    ) -> Option<(TyId, TyResolutionMethod)> {
+        let interface_item_id = expected_ty_id.as_interface()?.item_id;
+        let ptr_ty = found_ty_id.as_ptr()?;
+
+        let struct_item_id = ptr_ty.pointee.as_struct()?.item_id;
+
+        if self.is_ty_zero_sized(ptr_ty.pointee)
+            || !self.implements_interface(struct_item_id, interface_item_id)
+        {
+            return None;
+        }
+
+        if !ptr_ty.is_mutable && self.interfaces[&interface_item_id].requires_mutable_data() {
+            return None;
+        }
+
+        Some((
+            expected_ty_id,
+            TyResolutionMethod::IntoInterface {
+                data_ty_id: found_ty_id,
+            },
+        ))
     }
 
     #[inline]
@@ -119,6 +168,34 @@ impl ResolveContext {
         }
     }
 
+    /// Returns whether values of the type can be compared with `==` and `!=`. Tuples, structs
+    /// and variants are equatable if all of their fields or variants are. Types that are not
+    /// value types, such as modules, items or the type of diverging expressions, are not
+    /// equatable.
+    pub fn is_ty_equatable(&self, ty_id: TyId) -> bool {
+        let Some(value_ty) = ty_id.as_value() else {
+            return false;
+        };
+
+        match value_ty {
+            ValueTy::Unit | ValueTy::Bool | ValueTy::EmptyStruct(_) => true,
+            ValueTy::Tuple(_) | ValueTy::Struct(_) => {
+                self.get_ty_def(ty_id)
+                    .and_then(TyDef::get_aggregate_fields)
+                    .is_some_and(|fields| {
+                        fields.iter().all(|field| self.is_ty_equatable(field.ty_id))
+                    })
+            }
+            ValueTy::Variant(variant_ty) => {
+                variant_ty
+                    .variants()
+                    .iter()
+                    .all(|&variant_ty_id| self.is_ty_equatable(variant_ty_id))
+            }
+            value_ty => value_ty.is_comparable(),
+        }
+    }
+
     #[inline]
     pub fn iter_value_ty_ids(&self) -> impl Iterator<Item = TyId> + '_ {
         self.tys.iter_value_ty_ids()
@@ -1,10 +1,11 @@
-use crate::{BindingId, ModuleId, TyId};
+use crate::{BindingId, GenericId, ModuleId, TyId};
 
 #[derive(Clone, Copy, Debug)]
 pub enum ItemKind {
     Module(ModuleId),
     Ty(TyId),
     Binding(BindingId),
+    Generic(GenericId),
 }
 
 impl From<ModuleId> for ItemKind {
@@ -28,6 +29,13 @@ impl From<BindingId> for ItemKind {
     }
 }
 
+impl From<GenericId> for ItemKind {
+    #[inline]
+    fn from(generic_id: GenericId) -> Self {
+        Self::Generic(generic_id)
+    }
+}
+
 impl ItemKind {
     #[inline]
     pub fn as_module_id(&self) -> Option<ModuleId> {
@@ -52,4 +60,12 @@ impl ItemKind {
             _ => None,
         }
     }
+
+    #[inline]
+    pub fn as_generic_id(&self) -> Option<GenericId> {
+        match self {
+            Self::Generic(generic_id) => Some(*generic_id),
+            _ => None,
+        }
+    }
 }
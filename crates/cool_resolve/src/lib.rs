@@ -1,4 +1,5 @@
 mod binding;
+mod const_value;
 mod context;
 mod item_id;
 mod item_kind;
@@ -8,6 +9,7 @@ mod scope;
 mod ty;
 
 pub use self::binding::*;
+pub use self::const_value::*;
 pub use self::context::*;
 pub use self::item_id::*;
 pub use self::item_kind::*;
@@ -1,4 +1,4 @@
-use crate::{BindingId, FrameId, ModuleId};
+use crate::{BindingId, FrameId, ModuleId, TyId};
 use cool_collections::SmallVecMap;
 use cool_lexer::Symbol;
 use derive_more::From;
@@ -13,6 +13,7 @@ pub enum Scope {
 pub struct Frame {
     pub parent: Scope,
     pub bindings: SmallVecMap<Symbol, BindingId, 2>,
+    pub ty_params: SmallVecMap<Symbol, TyId, 1>,
 }
 
 impl Frame {
@@ -21,6 +22,7 @@ impl Frame {
         Self {
             parent,
             bindings: Default::default(),
+            ty_params: Default::default(),
         }
     }
 }
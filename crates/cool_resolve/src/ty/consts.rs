@@ -16,6 +16,7 @@ pub struct TyConsts {
     // Items
     pub module: TyId,
     pub ty: TyId,
+    pub generic: TyId,
 
     // Non-number primitives
     pub unit: TyId,
@@ -77,6 +78,7 @@ impl TyConsts {
             // Items
             module: insert_ty_shape(TyShape::from(ItemTy::Module)),
             ty: insert_ty_shape(TyShape::from(ItemTy::Ty)),
+            generic: insert_ty_shape(TyShape::from(ItemTy::Generic)),
 
             // Non-number primitives
             unit: insert_ty_shape(TyShape::from(ValueTy::Unit)),
@@ -13,9 +13,35 @@ pub struct Field {
     pub ty_id: TyId,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum LayoutKind {
+    /// Fields are reordered to minimize padding.
+    #[default]
+    Auto,
+    /// Fields are laid out in declaration order, as a C compiler would.
+    C,
+    /// Fields are laid out in declaration order without padding.
+    Packed,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct StructLayout {
+    pub kind: LayoutKind,
+    /// Minimum alignment of the struct, which is also applied to its size.
+    pub align: Option<u64>,
+}
+
+impl StructLayout {
+    #[inline]
+    pub fn is_auto(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AggregateTy {
     fields: Arc<[Field]>,
+    layout: StructLayout,
 }
 
 impl AggregateTy {
@@ -32,10 +58,27 @@ impl AggregateTy {
     pub fn fields_arc(&self) -> &Arc<[Field]> {
         &self.fields
     }
+
+    #[inline]
+    pub fn layout(&self) -> StructLayout {
+        self.layout
+    }
 }
 
 impl TyContext {
     pub(crate) fn mk_aggregate_ty_def<F>(&mut self, ty_id: TyId, field_iter: F) -> TyResult<TyDef>
+    where
+        F: IntoIterator<Item = (Symbol, TyId)>,
+    {
+        self.mk_aggregate_ty_def_with_layout(ty_id, field_iter, StructLayout::default())
+    }
+
+    pub(crate) fn mk_aggregate_ty_def_with_layout<F>(
+        &mut self,
+        ty_id: TyId,
+        field_iter: F,
+        layout: StructLayout,
+    ) -> TyResult<TyDef>
     where
         F: IntoIterator<Item = (Symbol, TyId)>,
     {
@@ -62,7 +105,9 @@ impl TyContext {
             fields.push((field_symbol, field_ty_id, field_def));
         }
 
-        fields.sort_by_key(|(_, _, field_def)| Reverse(field_def.align));
+        if layout.kind == LayoutKind::Auto {
+            fields.sort_by_key(|(_, _, field_def)| Reverse(field_def.align));
+        }
 
         let mut offset = 0;
         let mut align = 1;
@@ -70,22 +115,33 @@ impl TyContext {
         let fields = fields
             .iter()
             .map(|(field_symbol, field_ty_id, field_def)| {
+                let field_align = match layout.kind {
+                    LayoutKind::Packed => 1,
+                    _ => field_def.align,
+                };
+
+                offset += compute_padding_for_align(offset, field_align);
+
                 let field = Field {
                     offset,
                     symbol: *field_symbol,
                     ty_id: *field_ty_id,
                 };
 
-                offset += field_def.size + compute_padding_for_align(offset, field_def.align);
-                align = align.max(field_def.align);
+                offset += field_def.size;
+                align = align.max(field_align);
                 field
             })
             .collect::<Arc<[_]>>();
 
+        if let Some(min_align) = layout.align {
+            align = align.max(min_align);
+        }
+
         Ok(TyDef {
             size: offset + compute_padding_for_align(offset, align),
             align,
-            kind: TyKind::Aggregate(AggregateTy { fields }),
+            kind: TyKind::Aggregate(AggregateTy { fields, layout }),
         })
     }
 }
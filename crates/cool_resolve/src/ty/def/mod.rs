@@ -1,10 +1,12 @@
 mod aggregate_ty;
 mod primitive_ty_data;
 mod tagged_union_ty;
+mod untagged_union_ty;
 
 pub use self::aggregate_ty::*;
 pub use self::primitive_ty_data::*;
 pub use self::tagged_union_ty::*;
+pub use self::untagged_union_ty::*;
 use cool_lexer::Symbol;
 use derive_more::From;
 use std::sync::Arc;
@@ -14,6 +16,7 @@ pub enum TyKind {
     Basic,
     Aggregate(AggregateTy),
     TaggedUnion(TaggedUnionTy),
+    UntaggedUnion(UntaggedUnionTy),
 }
 
 impl TyKind {
@@ -32,6 +35,14 @@ impl TyKind {
             _ => None,
         }
     }
+
+    #[inline]
+    pub fn as_untagged_union(&self) -> Option<&UntaggedUnionTy> {
+        match self {
+            Self::UntaggedUnion(untagged_union_ty) => Some(untagged_union_ty),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -51,6 +62,15 @@ impl TyDef {
         self.kind.as_aggregate()?.get_field(symbol)
     }
 
+    /// Returns a field of a struct-like type or of a union.
+    pub fn get_field(&self, symbol: Symbol) -> Option<&Field> {
+        match &self.kind {
+            TyKind::Aggregate(aggregate_ty) => aggregate_ty.get_field(symbol),
+            TyKind::UntaggedUnion(untagged_union_ty) => untagged_union_ty.get_field(symbol),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn is_zero_sized(&self) -> bool {
         self.size == 0
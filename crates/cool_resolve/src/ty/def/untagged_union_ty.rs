@@ -0,0 +1,75 @@
+use crate::{
+    compute_padding_for_align, Field, TyContext, TyDef, TyError, TyErrorKind, TyId, TyKind,
+    TyResult,
+};
+use cool_lexer::Symbol;
+use rustc_hash::FxHashSet;
+use std::sync::Arc;
+
+/// Layout of a C-like union. All fields are stored at offset 0.
+#[derive(Clone, Debug)]
+pub struct UntaggedUnionTy {
+    fields: Arc<[Field]>,
+}
+
+impl UntaggedUnionTy {
+    pub fn get_field(&self, symbol: Symbol) -> Option<&Field> {
+        self.fields.iter().find(|field| field.symbol == symbol)
+    }
+
+    #[inline]
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+}
+
+impl TyContext {
+    pub(crate) fn mk_untagged_union_ty_def<F>(
+        &mut self,
+        ty_id: TyId,
+        field_iter: F,
+    ) -> TyResult<TyDef>
+    where
+        F: IntoIterator<Item = (Symbol, TyId)>,
+    {
+        let mut fields = Vec::<Field>::new();
+        let mut used_fields = FxHashSet::<Symbol>::default();
+        let mut size = 0;
+        let mut align = 1;
+
+        for (field_symbol, field_ty_id) in field_iter {
+            if !used_fields.insert(field_symbol) {
+                return Err(TyError {
+                    ty_id,
+                    kind: TyErrorKind::UnionHasDuplicatedField {
+                        field: field_symbol,
+                    },
+                });
+            }
+
+            let Some(field_def) = self.get_def(field_ty_id) else {
+                return Err(TyError {
+                    ty_id,
+                    kind: TyErrorKind::CannotBeDefined,
+                });
+            };
+
+            size = size.max(field_def.size);
+            align = align.max(field_def.align);
+
+            fields.push(Field {
+                offset: 0,
+                symbol: field_symbol,
+                ty_id: field_ty_id,
+            });
+        }
+
+        Ok(TyDef {
+            size: size + compute_padding_for_align(size, align),
+            align,
+            kind: TyKind::UntaggedUnion(UntaggedUnionTy {
+                fields: fields.into(),
+            }),
+        })
+    }
+}
@@ -11,6 +11,7 @@ pub enum TyErrorKind {
     CannotBeDefined,
     StructHasInfiniteSize,
     StructHasDuplicatedField { field: Symbol },
+    UnionHasDuplicatedField { field: Symbol },
     EnumHasInvalidStorage { storage: TyId },
     EnumHasDuplicatedVariant { variant: Symbol },
 }
@@ -44,6 +45,9 @@ impl fmt::Display for TyError {
                     self.ty_id, field,
                 )
             }
+            TyErrorKind::UnionHasDuplicatedField { field } => {
+                write!(f, "union '{}' has duplicated field '{}'", self.ty_id, field,)
+            }
             TyErrorKind::EnumHasInvalidStorage { storage } => {
                 write!(
                     f,
@@ -9,13 +9,17 @@ pub use self::def::*;
 pub use self::error::*;
 pub use self::shape::*;
 pub use self::ty_id::*;
-use cool_arena::InternArena;
+use cool_arena::{InternArena, SharedInternArena, SharedInterner};
 use cool_lexer::{sym, Symbol};
 use rustc_hash::FxHashMap;
+use std::sync::{LazyLock, Mutex};
 
-pub(crate) type TyShapes = InternArena<'static, TyShape>;
+pub(crate) type TyShapes = SharedInternArena<TyShape>;
 pub(crate) type TyDefs = FxHashMap<TyId, TyDef>;
 
+static TY_SHAPES: LazyLock<SharedInterner<TyShape>> =
+    LazyLock::new(|| Mutex::new(InternArena::new_leak()));
+
 #[derive(Debug)]
 pub struct TyContext {
     shapes: TyShapes,
@@ -26,7 +30,7 @@ pub struct TyContext {
 
 impl TyContext {
     pub fn new(primitives: PrimitiveTyData) -> Self {
-        let mut shapes = TyShapes::new_leak();
+        let mut shapes = TyShapes::new(&TY_SHAPES);
         let mut defs = TyDefs::default();
         let consts = TyConsts::new(&mut shapes, &mut defs, &primitives);
 
@@ -72,6 +76,42 @@ impl TyContext {
             ValueTy::Fn(_) | ValueTy::Ptr(_) | ValueTy::ManyPtr(_) => {
                 TyDef::for_ptr(&self.primitives)
             }
+            ValueTy::Closure(closure_ty) => {
+                let env_ty_id = self.insert_value(PtrTy {
+                    pointee: self.consts.u8,
+                    is_mutable: true,
+                });
+
+                let fn_ty_id = self.insert_value(FnTy {
+                    abi: FnAbi::Cool,
+                    params: std::iter::once(env_ty_id)
+                        .chain(closure_ty.params.iter().copied())
+                        .collect(),
+                    is_variadic: false,
+                    ret: closure_ty.ret,
+                });
+
+                let fields = [(sym::CLOSURE_FN, fn_ty_id), (sym::CLOSURE_ENV, env_ty_id)];
+                self.mk_aggregate_ty_def(ty_id, fields)?
+            }
+            ValueTy::Interface(_) => {
+                let data_ty_id = self.insert_value(PtrTy {
+                    pointee: self.consts.u8,
+                    is_mutable: true,
+                });
+
+                let vtable_ty_id = self.insert_value(PtrTy {
+                    pointee: self.consts.u8,
+                    is_mutable: false,
+                });
+
+                let fields = [
+                    (sym::INTERFACE_DATA, data_ty_id),
+                    (sym::INTERFACE_VTABLE, vtable_ty_id),
+                ];
+
+                self.mk_aggregate_ty_def(ty_id, fields)?
+            }
             ValueTy::Slice(slice_ty) => {
                 let fields = [
                     (
@@ -118,7 +158,12 @@ impl TyContext {
         Ok(self.defs.entry(ty_id).or_insert(def))
     }
 
-    pub fn define_struct<F>(&mut self, struct_ty_id: TyId, fields: F) -> TyResult<&TyDef>
+    pub fn define_struct<F>(
+        &mut self,
+        struct_ty_id: TyId,
+        fields: F,
+        layout: StructLayout,
+    ) -> TyResult<&TyDef>
     where
         F: IntoIterator<Item = (Symbol, TyId)>,
     {
@@ -133,10 +178,29 @@ impl TyContext {
             return Ok(&self.defs[&struct_ty_id]);
         }
 
-        let def = self.mk_aggregate_ty_def(struct_ty_id, fields)?;
+        let def = self.mk_aggregate_ty_def_with_layout(struct_ty_id, fields, layout)?;
         Ok(self.defs.entry(struct_ty_id).or_insert(def))
     }
 
+    pub fn define_union<F>(&mut self, union_ty_id: TyId, fields: F) -> TyResult<&TyDef>
+    where
+        F: IntoIterator<Item = (Symbol, TyId)>,
+    {
+        if !union_ty_id.is_union() {
+            return Err(TyError {
+                ty_id: union_ty_id,
+                kind: TyErrorKind::CannotBeDefined,
+            });
+        }
+
+        if self.defs.contains_key(&union_ty_id) {
+            return Ok(&self.defs[&union_ty_id]);
+        }
+
+        let def = self.mk_untagged_union_ty_def(union_ty_id, fields)?;
+        Ok(self.defs.entry(union_ty_id).or_insert(def))
+    }
+
     #[inline]
     pub fn consts(&self) -> &TyConsts {
         &self.consts
@@ -7,4 +7,7 @@ pub enum ItemTy {
 
     #[display(fmt = "type")]
     Ty,
+
+    #[display(fmt = "generic")]
+    Generic,
 }
@@ -75,3 +75,29 @@ impl fmt::Display for FnTy {
         }
     }
 }
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ClosureTy {
+    pub params: SmallVec<[TyId; 2]>,
+    pub ret: TyId,
+}
+
+impl fmt::Display for ClosureTy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "closure(")?;
+
+        if let Some((first, others)) = self.params.split_first() {
+            write!(f, "{}", first)?;
+
+            for other in others {
+                write!(f, ", {}", other)?;
+            }
+        }
+
+        if self.ret.is_unit() {
+            write!(f, ")")
+        } else {
+            write!(f, ") -> {}", self.ret)
+        }
+    }
+}
@@ -1,19 +1,23 @@
 mod array_ty;
 mod enum_ty;
 mod fn_ty;
+mod interface_ty;
 mod primitive_ty;
 mod ptr_ty;
 mod struct_ty;
 mod tuple_ty;
+mod union_ty;
 mod variant_ty;
 
 pub use self::array_ty::*;
 pub use self::enum_ty::*;
 pub use self::fn_ty::*;
+pub use self::interface_ty::*;
 pub use self::primitive_ty::*;
 pub use self::ptr_ty::*;
 pub use self::struct_ty::*;
 pub use self::tuple_ty::*;
+pub use self::union_ty::*;
 pub use self::variant_ty::*;
 use crate::TyShape;
 use derive_more::{Display, From};
@@ -119,8 +123,11 @@ define_value_ty! {
         Tuple,
         Struct,
         EmptyStruct,
+        Union,
         Enum,
         Fn,
+        Closure,
+        Interface,
         Ptr,
         ManyPtr,
         Slice,
@@ -153,7 +160,7 @@ impl ValueTy {
     pub fn is_comparable(&self) -> bool {
         matches!(
             self,
-            Self::Int(_) | Self::Float(_) | Self::Ptr(_) | Self::ManyPtr(_),
+            Self::Char | Self::Int(_) | Self::Float(_) | Self::Ptr(_) | Self::ManyPtr(_),
         )
     }
 
@@ -0,0 +1,7 @@
+use crate::ItemId;
+use derive_more::Display;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Display, Debug)]
+pub struct UnionTy {
+    pub item_id: ItemId,
+}
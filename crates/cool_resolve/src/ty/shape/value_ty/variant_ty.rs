@@ -57,6 +57,16 @@ impl VariantTy {
             .find(|(_, variant_ty_id)| **variant_ty_id == ty_id)
             .map(|(index, _)| index as u32)
     }
+
+    /// Returns the wrapped type if this is an optional type, i.e. `(T | ())`.
+    #[must_use]
+    pub fn get_optional_inner(&self) -> Option<TyId> {
+        match *self.variants.as_slice() {
+            [first, second] if second.is_unit() => Some(first),
+            [first, second] if first.is_unit() => Some(second),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for VariantTy {
@@ -11,6 +11,7 @@ pub enum TyResolutionMethod {
     Direct,
     DropConst,
     WrapInVariant { wrapped_ty_id: TyId },
+    IntoInterface { data_ty_id: TyId },
 }
 
 impl TyContext {
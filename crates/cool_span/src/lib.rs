@@ -1,7 +1,9 @@
 mod section;
+mod source_locator;
 mod source_position;
 mod span;
 
 pub use self::section::*;
+pub use self::source_locator::*;
 pub use self::source_position::*;
 pub use self::span::*;
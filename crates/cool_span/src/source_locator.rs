@@ -0,0 +1,8 @@
+use crate::SourcePosition;
+use std::path::Path;
+
+/// Maps offsets to the files and positions they belong to, so that code generation can embed
+/// source locations without depending on how files are loaded.
+pub trait SourceLocator {
+    fn locate(&self, offset: u32) -> (&Path, SourcePosition);
+}
@@ -0,0 +1,24 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Compiles every test program in a directory and compares the diagnostics, the emitted IR and
+/// the output of the program against the expected ones.
+#[derive(Parser)]
+pub struct Args {
+    /// Overwrite the expected `.stderr`, `.ll`, `.stdout` and `.run.stderr` files with the actual output.
+    #[arg(long)]
+    pub bless: bool,
+    /// Compile a single test program and run it, without comparing anything. Used to capture
+    /// the output of the programs in a separate process.
+    #[arg(long, hide = true)]
+    pub run: Option<PathBuf>,
+    #[arg(long, hide = true)]
+    pub overflow_checks: bool,
+    #[arg(long, hide = true)]
+    pub test: bool,
+    /// Directory containing the test programs. Defaults to the `tests/ui` directory of the
+    /// repository.
+    pub dir: Option<PathBuf>,
+    /// Only run the tests whose path contains this string.
+    pub filter: Option<String>,
+}
@@ -0,0 +1,115 @@
+use cool_driver::{CompileError, CompileOptions, SourceMap};
+use inkwell::module::Module;
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub location: Option<DiagnosticLocation>,
+    pub message: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct DiagnosticLocation {
+    pub path: PathBuf,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Diagnostic {
+    fn new(source_map: &SourceMap, error: &CompileError) -> Self {
+        let location = error.span().map(|span| {
+            let (file, position) = source_map.get_file_and_position_from_offset(span.start);
+
+            DiagnosticLocation {
+                path: file.path.clone(),
+                line: position.line,
+                column: position.column,
+            }
+        });
+
+        Self {
+            location,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = &self.location {
+            write!(
+                f,
+                "{}:{}:{}: ",
+                location.path.display(),
+                location.line,
+                location.column,
+            )?;
+        }
+
+        write!(f, "error: {}", self.message)
+    }
+}
+
+/// Runs every compiler pass on a crate and passes the generated module to `on_module`. Returns
+/// the diagnostics of all passes, sorted by location, if any of them failed.
+pub fn compile<F, T>(options: &CompileOptions, on_module: F) -> Result<T, Vec<Diagnostic>>
+where
+    F: FnOnce(&Module) -> T,
+{
+    // Programs are run in-process, so they are compiled for the host
+    let target_triple = cool_driver::host_target_triple();
+
+    let (codegen, mut session) = cool_driver::p0_init(&target_triple).map_err(|error_bundle| {
+        error_bundle
+            .errors
+            .iter()
+            .map(|error| Diagnostic::new(&SourceMap::default(), error))
+            .collect::<Vec<_>>()
+    })?;
+
+    session.enter(|resolve| {
+        let (package, mut errors) = match cool_driver::p1_parse(resolve, options) {
+            Ok(package) => (package, vec![]),
+            Err((package, error_bundle)) => (package, error_bundle.errors),
+        };
+
+        if let Err(mut error_bundle) = cool_driver::p2_define_tys(&package, resolve) {
+            errors.append(&mut error_bundle.errors);
+        }
+
+        if let Err(mut error_bundle) = cool_driver::p3_define_fn_tys(&package, resolve) {
+            errors.append(&mut error_bundle.errors);
+        }
+
+        let package_ast = match cool_driver::p4_gen_ast(&package, resolve) {
+            Ok(package_ast) => Some(package_ast),
+            Err(mut error_bundle) => {
+                errors.append(&mut error_bundle.errors);
+                None
+            }
+        };
+
+        let package_ast = match package_ast {
+            Some(package_ast) if errors.is_empty() => package_ast,
+            _ => {
+                errors.sort_by_key(CompileError::span);
+
+                return Err(errors
+                    .iter()
+                    .map(|error| Diagnostic::new(&package.source_map, error))
+                    .collect());
+            }
+        };
+
+        let module = cool_driver::p5_gen_code(
+            &package_ast,
+            &package.source_map,
+            &codegen,
+            resolve,
+            options,
+        );
+
+        Ok(on_module(&module))
+    })
+}
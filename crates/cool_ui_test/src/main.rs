@@ -0,0 +1,102 @@
+mod args;
+mod compile;
+mod runner;
+mod ui_test;
+
+use crate::args::Args;
+use crate::runner::{run_program, TestRunner};
+use crate::ui_test::{find_tests, UiTest};
+use clap::Parser as _;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    if let Some(path) = args.run {
+        return match run_program(path, args.overflow_checks, args.test) {
+            Ok(exit_code) => ExitCode::from(exit_code as u8),
+            Err(error) => {
+                eprint!("{error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let dir = args
+        .dir
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/ui"));
+
+    let dir = match dir.canonicalize() {
+        Ok(dir) => dir,
+        Err(error) => {
+            println!("{}: '{}': {error}.", "Error".red(), dir.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let paths = match find_tests(&dir) {
+        Ok(paths) => paths,
+        Err(error) => {
+            println!("{}: {error}.", "Error".red());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let runner = TestRunner::new(dir.clone(), args.bless);
+    let mut failed = Vec::<(PathBuf, Vec<String>)>::new();
+    let mut passed = 0;
+    let mut filtered = 0;
+
+    for path in paths {
+        let name = path.strip_prefix(&dir).unwrap().display().to_string();
+
+        if args
+            .filter
+            .as_ref()
+            .is_some_and(|filter| !name.contains(filter))
+        {
+            filtered += 1;
+            continue;
+        }
+
+        let failures = match UiTest::parse(path.clone()) {
+            Ok(test) => runner.run(&test),
+            Err(error) => vec![error],
+        };
+
+        if failures.is_empty() {
+            println!("test {name} ... {}", "ok".green());
+            passed += 1;
+        } else {
+            println!("test {name} ... {}", "FAILED".red());
+            failed.push((path, failures));
+        }
+    }
+
+    for (path, failures) in failed.iter() {
+        println!("\n---- {} ----", path.strip_prefix(&dir).unwrap().display());
+
+        for failure in failures {
+            println!("{failure}");
+        }
+    }
+
+    let result = if failed.is_empty() {
+        "ok".green()
+    } else {
+        "FAILED".red()
+    };
+
+    println!(
+        "\ntest result: {result}. {passed} passed; {} failed; {filtered} filtered out",
+        failed.len(),
+    );
+
+    if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
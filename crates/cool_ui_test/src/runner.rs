@@ -0,0 +1,286 @@
+use crate::compile::{compile, Diagnostic};
+use crate::ui_test::{Directives, UiTest};
+use cool_driver::CompileOptions;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+pub struct TestRunner {
+    dir: PathBuf,
+    bless: bool,
+}
+
+impl TestRunner {
+    #[inline]
+    pub fn new(dir: PathBuf, bless: bool) -> Self {
+        Self { dir, bless }
+    }
+
+    /// Runs a test and returns the reasons why it failed, if any.
+    pub fn run(&self, test: &UiTest) -> Vec<String> {
+        let mut failures = Vec::<String>::new();
+        let options = compile_options(test);
+
+        let (diagnostics, ir) = match compile(&options, |module| module.print_to_string()) {
+            Ok(ir) => (vec![], Some(ir.to_string())),
+            Err(diagnostics) => (diagnostics, None),
+        };
+
+        self.check_annotations(test, &diagnostics, &mut failures);
+
+        let stderr = diagnostics
+            .iter()
+            .map(|diagnostic| format!("{diagnostic}\n"))
+            .collect::<String>();
+
+        self.check_output(&test.path.with_extension("stderr"), &stderr, &mut failures);
+
+        let ir_path = test.path.with_extension("ll");
+
+        if test.directives.emit_ir || ir_path.exists() {
+            self.check_output(&ir_path, ir.as_deref().unwrap_or_default(), &mut failures);
+        }
+
+        if test.directives.run {
+            match ir {
+                Some(_) => self.check_run(test, &mut failures),
+                None => failures.push("cannot run a program that failed to compile".to_owned()),
+            }
+        }
+
+        failures
+    }
+
+    fn check_annotations(
+        &self,
+        test: &UiTest,
+        diagnostics: &[Diagnostic],
+        failures: &mut Vec<String>,
+    ) {
+        let mut found = vec![false; test.annotations.len()];
+
+        for diagnostic in diagnostics {
+            let Some(location) = diagnostic
+                .location
+                .as_ref()
+                .filter(|location| location.path == test.path)
+            else {
+                continue;
+            };
+
+            let annotation_index =
+                test.annotations
+                    .iter()
+                    .enumerate()
+                    .position(|(i, annotation)| {
+                        !found[i]
+                            && annotation.line == location.line
+                            && diagnostic.message.contains(&annotation.message)
+                    });
+
+            match annotation_index {
+                Some(i) => found[i] = true,
+                None => {
+                    failures.push(format!(
+                        "unexpected error at line {}: {}",
+                        location.line,
+                        first_line(&diagnostic.message),
+                    ));
+                }
+            }
+        }
+
+        for (annotation, _) in test
+            .annotations
+            .iter()
+            .zip(found)
+            .filter(|(_, found)| !found)
+        {
+            failures.push(format!(
+                "expected error at line {} not found: {}",
+                annotation.line, annotation.message,
+            ));
+        }
+    }
+
+    fn check_run(&self, test: &UiTest, failures: &mut Vec<String>) {
+        let mut command = Command::new(env::current_exe().unwrap());
+        command.arg("--run").arg(&test.path);
+
+        if test.directives.overflow_checks {
+            command.arg("--overflow-checks");
+        }
+
+        if test.directives.test {
+            command.arg("--test");
+        }
+
+        let output = match command.output() {
+            Ok(output) => output,
+            Err(error) => {
+                failures.push(format!("failed to run the program: {error}"));
+                return;
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        // The test harness exits with an error when a test fails, which its summary records
+        if !test.directives.test && !output.status.success() {
+            failures.push(format!("program exited with {}\n{stderr}", output.status));
+        }
+
+        let stdout = if test.directives.test {
+            remove_test_times(&stdout)
+        } else {
+            stdout.into_owned()
+        };
+
+        self.check_output(&test.path.with_extension("stdout"), &stdout, failures);
+        self.check_output(&test.path.with_extension("run.stderr"), &stderr, failures);
+    }
+
+    /// Compares the output against the expected one, or overwrites the expected one in bless
+    /// mode. An empty output is expected when the file does not exist, and empty files are
+    /// removed when blessing.
+    fn check_output(&self, expected_path: &Path, actual: &str, failures: &mut Vec<String>) {
+        let actual = self.normalize(actual);
+
+        if self.bless {
+            let result = if !actual.is_empty() {
+                fs::write(expected_path, actual)
+            } else if expected_path.exists() {
+                fs::remove_file(expected_path)
+            } else {
+                Ok(())
+            };
+
+            if let Err(error) = result {
+                failures.push(format!(
+                    "failed to bless '{}': {error}",
+                    expected_path.display(),
+                ));
+            }
+
+            return;
+        }
+
+        let expected = fs::read_to_string(expected_path).unwrap_or_default();
+
+        if expected != actual {
+            failures.push(format!(
+                "'{}' does not match the actual output:\n{}",
+                self.normalize(&expected_path.display().to_string()),
+                diff(&expected, &actual),
+            ));
+        }
+    }
+
+    /// Replaces the test directory in paths, so expected files do not depend on where the
+    /// repository is checked out.
+    fn normalize(&self, output: &str) -> String {
+        let dir = format!("{}/", self.dir.display());
+        output.replace(&dir, "$DIR/")
+    }
+}
+
+/// Compiles and runs a test program in the current process, forwarding its exit code.
+pub fn run_program(path: PathBuf, overflow_checks: bool, test: bool) -> Result<i32, String> {
+    let test = UiTest {
+        path,
+        directives: Directives {
+            overflow_checks,
+            test,
+            ..Default::default()
+        },
+        annotations: vec![],
+    };
+
+    let options = compile_options(&test);
+
+    let program_name = options.crate_root_file.to_string_lossy().into_owned();
+
+    compile(&options, |module| {
+        cool_driver::run_jit(module, &[&program_name])
+            .map_err(|error_bundle| error_bundle.to_string())
+    })
+    .map_err(|diagnostics| {
+        diagnostics
+            .iter()
+            .map(|diagnostic| format!("{diagnostic}\n"))
+            .collect::<String>()
+    })?
+}
+
+fn compile_options(test: &UiTest) -> CompileOptions {
+    let crate_name = test
+        .path
+        .file_stem()
+        .unwrap()
+        .to_string_lossy()
+        .replace('-', "_");
+
+    CompileOptions {
+        crate_name,
+        crate_root_file: test.path.clone(),
+        dependencies: Vec::new(),
+        packages: Vec::new(),
+        overflow_checks: test.directives.overflow_checks,
+        test: test.directives.test,
+    }
+}
+
+/// Removes the durations that the test harness prints after the result of each test.
+fn remove_test_times(output: &str) -> String {
+    output
+        .lines()
+        .map(|line| {
+            match line.rfind(" (") {
+                Some(i) if line.starts_with("test ") && line.ends_with(" ms)") => &line[..i],
+                _ => line,
+            }
+        })
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+#[inline]
+fn first_line(message: &str) -> &str {
+    message.lines().next().unwrap_or_default()
+}
+
+/// Lists the expected lines prefixed with `-` and the actual lines prefixed with `+`, skipping
+/// the lines they have in common at the start and the end.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    let prefix_len = expected
+        .iter()
+        .zip(actual.iter())
+        .take_while(|(expected, actual)| expected == actual)
+        .count();
+
+    let suffix_len = expected[prefix_len..]
+        .iter()
+        .rev()
+        .zip(actual[prefix_len..].iter().rev())
+        .take_while(|(expected, actual)| expected == actual)
+        .count();
+
+    let expected = &expected[prefix_len..(expected.len() - suffix_len)];
+    let actual = &actual[prefix_len..(actual.len() - suffix_len)];
+
+    let mut diff = format!("@@ line {} @@\n", prefix_len + 1);
+
+    for line in expected {
+        diff.push_str(&format!("-{line}\n"));
+    }
+
+    for line in actual {
+        diff.push_str(&format!("+{line}\n"));
+    }
+
+    diff
+}
@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Test program along with the directives and expected errors written in its comments.
+#[derive(Clone, Debug)]
+pub struct UiTest {
+    pub path: PathBuf,
+    pub directives: Directives,
+    pub annotations: Vec<Annotation>,
+}
+
+/// Options set with `//@ <name>` comments.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Directives {
+    /// `//@ overflow-checks`: compile with overflow checks.
+    pub overflow_checks: bool,
+    /// `//@ emit-ir`: compare the generated IR against the `.ll` file.
+    pub emit_ir: bool,
+    /// `//@ run`: run the program and compare its output against the `.stdout` file and the
+    /// `.run.stderr` file.
+    pub run: bool,
+    /// `//@ test`: compile the `@test` functions. Combined with `//@ run`, the test harness is
+    /// run instead of `main`, and failing tests do not fail the UI test.
+    pub test: bool,
+}
+
+/// Error expected by a `//~ ERROR <message>` comment. Each `^` after `//~` moves the expected
+/// error one line up.
+#[derive(Clone, Debug)]
+pub struct Annotation {
+    pub line: u32,
+    pub message: String,
+}
+
+impl UiTest {
+    pub fn parse(path: PathBuf) -> Result<Self, String> {
+        let source = fs::read_to_string(&path)
+            .map_err(|error| format!("failed to read '{}': {error}", path.display()))?;
+
+        let mut directives = Directives::default();
+        let mut annotations = Vec::<Annotation>::new();
+
+        for (i, line) in source.lines().enumerate() {
+            let line_number = i as u32 + 1;
+
+            if let Some(directive) = line.trim_start().strip_prefix("//@") {
+                match directive.trim() {
+                    "overflow-checks" => directives.overflow_checks = true,
+                    "emit-ir" => directives.emit_ir = true,
+                    "run" => directives.run = true,
+                    "test" => directives.test = true,
+                    directive => {
+                        return Err(format!(
+                            "line {line_number}: unknown directive '{directive}'",
+                        ));
+                    }
+                }
+
+                continue;
+            }
+
+            let Some((_, annotation)) = line.split_once("//~") else {
+                continue;
+            };
+
+            let message = annotation.trim_start_matches('^');
+            let offset = (annotation.len() - message.len()) as u32;
+
+            let message = message
+                .trim()
+                .strip_prefix("ERROR")
+                .filter(|message| message.is_empty() || message.starts_with(' '))
+                .ok_or_else(|| {
+                    format!("line {line_number}: annotations must start with 'ERROR'")
+                })?;
+
+            if offset >= line_number {
+                return Err(format!(
+                    "line {line_number}: annotation points before the file"
+                ));
+            }
+
+            annotations.push(Annotation {
+                line: line_number - offset,
+                message: message.trim().to_owned(),
+            });
+        }
+
+        Ok(Self {
+            path,
+            directives,
+            annotations,
+        })
+    }
+}
+
+/// Returns the test programs in a directory and its subdirectories, sorted by path. Module
+/// directories, the ones containing a `@module.cl` file, are not searched.
+pub fn find_tests(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut tests = Vec::<PathBuf>::new();
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let entries = fs::read_dir(&dir)
+            .map_err(|error| format!("failed to read '{}': {error}", dir.display()))?;
+
+        for entry in entries {
+            let path = entry
+                .map_err(|error| format!("failed to read '{}': {error}", dir.display()))?
+                .path();
+
+            if path.is_dir() {
+                if !path.join("@module.cl").exists() {
+                    dirs.push(path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "cl") {
+                tests.push(path);
+            }
+        }
+    }
+
+    tests.sort();
+    Ok(tests)
+}
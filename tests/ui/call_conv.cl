@@ -0,0 +1,9 @@
+@callconv(fast)
+add_one :: fn(x: i32) -> i32 {
+    x + 1
+};
+
+export main :: fn() {
+    x := add_one(1);
+    f := add_one; //~ ERROR function 'add_one' has a non-default calling convention and can only be called directly
+};
@@ -0,0 +1,60 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Counter :: struct {
+    count: i32,
+    step: i32,
+};
+
+apply :: fn(f: closure(i32) -> i32, x: i32) -> i32 {
+    f(x)
+};
+
+make_doubler :: fn() -> closure(i32) -> i32 {
+    closure(x: i32) -> i32 { x * 2 }
+};
+
+export main :: fn() {
+    // Immutable bindings are captured by value
+    offset := 10;
+    add_offset := closure(x: i32) -> i32 { x + offset };
+    printf(c"%d %d\n", add_offset(1), apply(add_offset, 5));
+
+    // Mutable bindings are captured by pointer, so mutations are visible after the call
+    mut total := 0;
+    add := closure(x: i32) -> i32 {
+        total += x;
+        total
+    };
+    add(3);
+    add(4);
+    printf(c"%d %d\n", total, apply(add, 5));
+
+    total = 100;
+    printf(c"%d\n", add(1));
+
+    // Aggregates captured by pointer see field updates from both sides
+    mut counter := Counter { count = 0, step = 2 };
+    tick := closure() {
+        counter.count += counter.step;
+    };
+    tick();
+    counter.step = 5;
+    tick();
+    printf(c"%d\n", counter.count);
+
+    // Nested closures capture through the enclosing closure
+    mut calls := 0;
+    outer := closure(x: i32) -> i32 {
+        inner := closure(y: i32) -> i32 {
+            calls += 1;
+            y + offset
+        };
+        inner(x) + inner(x)
+    };
+    printf(c"%d %d\n", outer(1), calls);
+
+    doubler := make_doubler();
+    printf(c"%d\n", apply(doubler, 21));
+};
@@ -0,0 +1,60 @@
+Callback :: struct {
+    f: closure(i32) -> i32,
+};
+
+make :: fn() -> closure(i32) -> i32 {
+    closure(x: i32) -> i32 { x + 1 }
+};
+
+make_bound :: fn() -> closure(i32) -> i32 {
+    f := closure(x: i32) -> i32 { x * 2 };
+    f
+};
+
+make_callback :: fn() -> Callback {
+    Callback { f = make() }
+};
+
+make_capturing :: fn(y: i32) -> closure(i32) -> i32 {
+    closure(x: i32) -> i32 { x + y }
+    //~^ ERROR values of type 'closure(i32) -> i32' may hold a closure that captures locals and cannot outlive its function
+};
+
+return_capturing :: fn(y: i32) -> closure(i32) -> i32 {
+    f := closure(x: i32) -> i32 { x + y };
+    return f;
+    //~^ ERROR may hold a closure that captures locals
+};
+
+return_mutable :: fn() -> closure(i32) -> i32 {
+    mut f := closure(x: i32) -> i32 { x };
+    f
+    //~^ ERROR may hold a closure that captures locals
+};
+
+pass_through :: fn(f: closure(i32) -> i32) -> closure(i32) -> i32 {
+    f
+};
+
+call_pass_through :: fn(y: i32) -> closure(i32) -> i32 {
+    pass_through(make());
+    pass_through(closure(x: i32) -> i32 { x + y })
+    //~^ ERROR may hold a closure that captures locals
+};
+
+reassign_param :: fn(mut f: closure(i32) -> i32, y: i32) -> closure(i32) -> i32 {
+    f = closure(x: i32) -> i32 { x + y };
+    f
+    //~^ ERROR may hold a closure that captures locals
+};
+
+store :: fn(callback: *mut Callback, y: i32) {
+    callback.f = make();
+    callback.f = closure(x: i32) -> i32 { x - y };
+    //~^ ERROR may hold a closure that captures locals
+};
+
+local :: fn(y: i32) {
+    mut callback := Callback { f = closure(x: i32) -> i32 { x + y } };
+    callback.f = closure(x: i32) -> i32 { x * y };
+};
@@ -0,0 +1,43 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+LEN :: 4;
+DOUBLE_LEN :: LEN * 2;
+I8_MIN : i8 : (-127) - 1;
+I8_MAX : i8 : 127;
+U8_MAX : u8 : 255;
+I64_MIN : i64 : (-9223372036854775807) - 1;
+MASK : u32 : (1 << 31) | 1;
+
+static_assert(LEN == 4);
+static_assert(DOUBLE_LEN == 8);
+static_assert(I8_MAX +% 1 == I8_MIN);
+static_assert(I8_MAX +| 1 == I8_MAX);
+static_assert(I8_MIN -| 1 == I8_MIN);
+static_assert(I8_MIN *| 2 == I8_MIN);
+static_assert(I8_MIN *% (-1) == I8_MIN);
+static_assert(U8_MAX +% 1 == 0);
+static_assert(U8_MAX +| 1 == U8_MAX);
+static_assert((0 as u8) -| 1 == 0);
+static_assert((0 as u8) -% 1 == U8_MAX);
+static_assert(I64_MIN -| 1 == I64_MIN);
+static_assert(I64_MIN / 2 == (-4611686018427387904));
+static_assert(((-7) / 2 == (-3)) && ((-7) % 2 == (-1)));
+static_assert(MASK >> 31 == 1);
+static_assert(((-16) as i8) >> 2 == (-4));
+static_assert((1 as u8) << 7 == 128);
+static_assert((300 as u8) == 44);
+static_assert(((-1) as i8) as u8 == 255);
+static_assert((200 as u8) as i8 == (-56));
+static_assert(((-1) as i32) as u64 == 18446744073709551615);
+static_assert((65535 as u16) as i16 as i64 == (-1));
+static_assert((2.9 as i32) == 2 && ((-2.9) as i32) == (-2));
+static_assert((1000.0 as u8) == 255 && ((-1.0) as u8) == 0);
+static_assert(size_of([DOUBLE_LEN]i32) == 32);
+
+export main :: fn() {
+    values: [LEN + 1]i32 = [1, 2, 3, 4, 5];
+    doubled: [DOUBLE_LEN]u8 = [0, 0, 0, 0, 0, 0, 0, 9];
+    printf(c"%d %d %d\n", values[4], doubled[7] as i32, size_of([LEN + 1]i32) as i32);
+};
@@ -0,0 +1,19 @@
+I8_OVERFLOW : i8 : 127 + 1; //~ ERROR constant evaluation overflowed type 'i8'
+U16_OVERFLOW : u16 : 65535 * 2; //~ ERROR constant evaluation overflowed type 'u16'
+I32_UNDERFLOW : i32 : (-2147483647) - 2; //~ ERROR constant evaluation overflowed type 'i32'
+U64_UNDERFLOW : u64 : 0 - 1; //~ ERROR constant evaluation overflowed type 'u64'
+DIV_BY_ZERO : i32 : 1 / 0; //~ ERROR division by zero in constant expression
+REM_BY_ZERO : u8 : 1 % 0; //~ ERROR division by zero in constant expression
+I8_MIN : i8 : (-127) - 1;
+MIN_DIV : i8 : I8_MIN / (-1); //~ ERROR constant evaluation overflowed type 'i8'
+MIN_REM : i8 : I8_MIN % (-1); //~ ERROR constant evaluation overflowed type 'i8'
+SHL_WIDTH : u32 : 1 << 32; //~ ERROR constant evaluation overflowed type 'u32'
+SHR_WIDTH : i8 : 1 >> 8; //~ ERROR constant evaluation overflowed type 'i8'
+CAST_OVERFLOW : u8 : (300 as u8) * 6; //~ ERROR constant evaluation overflowed type 'u8'
+NEGATIVE_LEN : i32 : -1;
+
+static_assert(I8_MIN == 0); //~ ERROR static assertion failed
+
+export main :: fn() {
+    values: [NEGATIVE_LEN]i32 = []; //~ ERROR array length must be a non-negative integer
+};
@@ -0,0 +1,52 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Point :: struct {
+    x: i32,
+    y: i32,
+};
+
+Shape :: struct {
+    origin: Point,
+    closed: bool,
+};
+
+print_bool :: fn(value: bool) {
+    if value {
+        printf(c"true\n");
+    } else {
+        printf(c"false\n");
+    }
+};
+
+export main :: fn() {
+    a := Point { x = 1, y = 2 };
+    b := Point { x = 1, y = 2 };
+    c := Point { x = 1, y = 3 };
+    print_bool(a == b);
+    print_bool(a == c);
+    print_bool(a != c);
+
+    s := Shape { origin = a, closed = true };
+    t := Shape { origin = b, closed = false };
+    print_bool(s == t);
+    print_bool(s.origin == t.origin);
+
+    print_bool((1, 'a') == (1, 'a'));
+    print_bool((1, 'a') == (1, 'b'));
+
+    five: i32 = 5;
+    six: i32 = 6;
+    int_variant: (i32 | bool) = five;
+    bool_variant: (i32 | bool) = true;
+    print_bool(int_variant == five);
+    print_bool(int_variant == six);
+    print_bool(int_variant == bool_variant);
+    print_bool(bool_variant == true);
+
+    point_variant: (Point | ()) = a;
+    print_bool(point_variant == b);
+    print_bool(point_variant == c);
+    print_bool(point_variant != ());
+};
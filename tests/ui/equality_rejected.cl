@@ -0,0 +1,21 @@
+Wrapper :: struct {
+    f: closure(i32),
+};
+
+m :: module {};
+
+modules :: fn() -> bool {
+    m == m //~ ERROR expressions of type 'module' are not comparable
+};
+
+items :: fn() -> bool {
+    Wrapper == Wrapper //~ ERROR expressions of type 'type' are not comparable
+};
+
+diverging :: fn() -> bool {
+    unreachable() == unreachable() //~ ERROR expressions of type '<diverge>' are not comparable
+};
+
+closures :: fn(a: Wrapper, b: Wrapper) -> bool {
+    a == b //~ ERROR are not comparable
+};
@@ -0,0 +1,42 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Pair :: struct[A, B] {
+    first: A,
+    second: B,
+} impl {
+    get_second :: fn(self: *Self) -> B {
+        self.second
+    };
+};
+
+IntBool :: type Pair[i32, bool];
+NestedPair :: type Pair[IntBool, i8];
+IntPtr :: type *i32;
+Shorts :: type [3]i16;
+SmallLarge :: type Pair[i8, i64];
+LargeSmall :: type Pair[i64, i8];
+
+first :: fn[T](values: [2]T) -> T {
+    values[0]
+};
+
+size :: fn[T]() -> usize {
+    size_of(T)
+};
+
+export main :: fn() {
+    // Each instance is a separate function whose mangled name escapes its type arguments,
+    // e.g. `size[[3]i16]` becomes `size$LB$$LB$3$RB$i16$RB$`
+    small: i8 = 3;
+    printf(c"%d %d\n", first[i32]([1, 2]), first[i8]([small, small]) as i32);
+    printf(c"%d %d %d\n", size[i32]() as i32, size[IntPtr]() as i32, size[Shorts]() as i32);
+    printf(c"%d %d\n", size[SmallLarge]() as i32, size[LargeSmall]() as i32);
+
+    pair := IntBool { first = 7, second = true };
+    nested := NestedPair { first = pair, second = small };
+    is_set: i32 = if pair.get_second() { 1 } else { 0 };
+    printf(c"%d %d\n", is_set, nested.get_second() as i32);
+    printf(c"%d\n", nested.first.first);
+};
@@ -0,0 +1,24 @@
+Pair :: struct[A, B] {
+    first: A,
+    second: B,
+};
+
+single :: fn() {
+    size_of(Pair[i32]); //~ ERROR type argument count mismatch
+};
+
+first :: fn[T](values: [2]T) -> T {
+    values[0]
+};
+
+two_args :: fn[A, B](a: A, b: B) -> A {
+    a
+};
+
+too_few :: fn() -> i32 {
+    two_args[i32](1, 2) //~ ERROR type argument count mismatch
+};
+
+not_generic :: fn() -> i32 {
+    too_few[i32]() //~ ERROR expected 'usize', found 'type'
+};
@@ -0,0 +1,7 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+export main :: fn() {
+    printf(c"Hello, world!\n");
+};
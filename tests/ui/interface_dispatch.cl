@@ -0,0 +1,64 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Shape :: interface {
+    area :: fn(self: *Self) -> i32;
+    scale :: fn(self: *mut Self, factor: i32);
+};
+
+Square :: struct {
+    side: i32,
+} impl Shape {
+    area :: fn(self: *Self) -> i32 {
+        self.side * self.side
+    };
+
+    scale :: fn(self: *mut Self, factor: i32) {
+        self.side *= factor;
+    };
+};
+
+Rect :: struct {
+    width: i32,
+    height: i32,
+} impl Shape {
+    area :: fn(self: *Self) -> i32 {
+        self.width * self.height
+    };
+
+    scale :: fn(self: *mut Self, factor: i32) {
+        self.width *= factor;
+        self.height *= factor;
+    };
+};
+
+total_area :: fn(shapes: [3]Shape) -> i32 {
+    mut total := 0;
+    mut i: usize = 0;
+
+    while i < 3 {
+        total += shapes[i].area();
+        i += 1;
+    }
+
+    total
+};
+
+export main :: fn() {
+    mut square := Square { side = 3 };
+    mut rect := Rect { width = 2, height = 5 };
+    mut big := Square { side = 4 };
+
+    square_shape: Shape = &mut square;
+    rect_shape: Shape = &mut rect;
+    big_shape: Shape = &mut big;
+
+    printf(c"%d %d %d\n", square_shape.area(), rect_shape.area(), big_shape.area());
+    printf(c"%d\n", total_area([square_shape, rect_shape, big_shape]));
+
+    // Calls through the vtable mutate the underlying structs
+    rect_shape.scale(2);
+    square_shape.scale(3);
+    printf(c"%d %d %d %d\n", rect.width, rect.height, square.side, total_area([square_shape, rect_shape, big_shape]));
+};
@@ -0,0 +1,5 @@
+@link_name("on_event")
+first :: fn() {};
+
+@link_name("on_event") //~ ERROR link name 'on_event' is already used by another function
+second :: fn() {};
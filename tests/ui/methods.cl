@@ -0,0 +1,51 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Counter :: struct {
+    value: i32,
+} impl {
+    new :: fn(value: i32) -> Counter {
+        Counter { value = value }
+    };
+
+    get :: fn(self: *Self) -> i32 {
+        self.value
+    };
+
+    add :: fn(self: *mut Self, amount: i32) {
+        self.value += amount;
+    };
+
+    doubled :: fn(self: Self) -> i32 {
+        self.value * 2
+    };
+};
+
+Other :: struct {
+    value: i32,
+} impl {
+    // Methods with the same name on different structs do not clash
+    get :: fn(self: *Self) -> i32 {
+        -self.value
+    };
+};
+
+bump :: fn(counter: *mut Counter) {
+    counter.add(10);
+};
+
+export main :: fn() {
+    mut counter := Counter.new(1);
+    other := Other { value = 5 };
+
+    // Receivers are passed by pointer or by value depending on the method
+    counter.add(2);
+    printf(c"%d %d %d\n", counter.get(), counter.doubled(), other.get());
+
+    // Method calls through pointers pass the pointer as the receiver
+    counter_ptr := &mut counter;
+    counter_ptr.add(4);
+    bump(counter_ptr);
+    printf(c"%d %d\n", counter_ptr.get(), counter.value);
+};
@@ -0,0 +1,31 @@
+Counter :: struct {
+    value: i32,
+} impl {
+    get :: fn(self: *Self) -> i32 {
+        self.value
+    };
+
+    get :: fn(self: *Self) -> i32 { //~ ERROR 'get' was already defined
+        0
+    };
+
+    add :: fn(self: *mut Self, amount: i32) {
+        self.value += amount;
+    };
+
+    zero :: fn() -> Counter {
+        Counter { value = 0 }
+    };
+};
+
+immutable_receiver :: fn(counter: *Counter) {
+    counter.add(1); //~ ERROR expected '*mut methods_rejected.Counter', found '*methods_rejected.Counter'
+};
+
+missing_method :: fn(counter: Counter) -> i32 {
+    counter.missing() //~ ERROR type 'methods_rejected.Counter' has no field 'missing'
+};
+
+no_receiver :: fn(counter: Counter) -> Counter {
+    counter.zero() //~ ERROR method 'zero' of type 'methods_rejected.Counter' has no receiver
+};
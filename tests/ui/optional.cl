@@ -0,0 +1,72 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Point :: struct {
+    x: i32,
+    y: i32,
+};
+
+find :: fn(values: [4]i32, target: i32) -> ?usize {
+    mut i: usize = 0;
+
+    while i < 4 {
+        if values[i] == target {
+            return i;
+        }
+
+        i += 1;
+    }
+
+    null
+};
+
+origin :: fn(is_set: bool) -> ?Point {
+    if is_set {
+        Point { x = 1, y = 2 }
+    } else {
+        null
+    }
+};
+
+first_char :: fn(text: ?[*]i8) -> i32 {
+    if text as p {
+        p[0] as i32
+    } else {
+        -1
+    }
+};
+
+export main :: fn() {
+    values := [5, 7, 9, 11];
+
+    if find(values, 9) as index {
+        printf(c"found at %d\n", index as i32);
+    }
+
+    if find(values, 4) as index {
+        printf(c"unexpected %d\n", index as i32);
+    } else {
+        printf(c"not found\n");
+    }
+
+    missing := find(values, 4) orelse 99;
+    present := find(values, 11) orelse 99;
+    printf(c"%d %d\n", missing as i32, present as i32);
+
+    if origin(true) as point {
+        printf(c"%d %d\n", point.x, point.y);
+    }
+
+    fallback := origin(false) orelse Point { x = 3, y = 4 };
+    printf(c"%d %d\n", fallback.x, fallback.y);
+
+    mut maybe: ?i32 = null;
+    printf(c"%d\n", maybe orelse 0);
+    answer: i32 = 42;
+    maybe = answer;
+    printf(c"%d\n", maybe orelse 0);
+
+    no_text: ?[*]i8 = null;
+    printf(c"%d %d\n", first_char(c"A"), first_char(no_text));
+};
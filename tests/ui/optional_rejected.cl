@@ -0,0 +1,16 @@
+or_else_non_optional :: fn(value: i32) -> i32 {
+    value orelse 0 //~ ERROR expected '<variant>', found 'i32'
+};
+
+null_non_optional :: fn() {
+    value: i32 = null; //~ ERROR expected 'i32', found '()'
+};
+
+unwrap_non_optional :: fn(value: i32) -> i32 {
+    if value as inner {
+        //~^ ERROR 'inner' could not be found
+        inner
+    } else {
+        0
+    }
+};
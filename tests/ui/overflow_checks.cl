@@ -0,0 +1,57 @@
+//@ overflow-checks
+//@ test
+//@ run
+
+add :: fn(a: i8, b: i8) -> i8 {
+    a + b
+};
+
+div :: fn(a: i32, b: i32) -> i32 {
+    a / b
+};
+
+shl :: fn(a: u32, bits: u32) -> u32 {
+    a << bits
+};
+
+@test
+in_range :: fn() {
+    assert(add(100, 27) == 127);
+    assert(div((-2147483647) - 1, 1) == (-2147483647) - 1);
+    assert(shl(1, 31) == 2147483648);
+};
+
+@test
+add_overflows :: fn() {
+    add(100, 28);
+};
+
+@test
+divide_by_zero :: fn() {
+    div(1, 0);
+};
+
+@test
+divide_min_by_minus_one :: fn() {
+    div((-2147483647) - 1, -1);
+};
+
+@test
+shift_by_bit_width :: fn() {
+    shl(1, 32);
+};
+
+// Saturating operators clamp to the bounds of the type instead of trapping
+@test
+saturating_ops_clamp :: fn() {
+    max: i8 = 120;
+    min: i8 = -120;
+    zero: u8 = 0;
+
+    assert(max +| 10 == 127);
+    assert(min -| 10 == (-127) - 1);
+    assert(max *| 2 == 127);
+    assert(min *| 2 == (-127) - 1);
+    assert(zero -| 1 == 0);
+    assert(max +| 5 == 125);
+};
@@ -0,0 +1,13 @@
+@packed
+Header :: struct {
+    tag: u8,
+    len: u32,
+};
+
+len_ptr :: fn(header: *Header) -> *u32 {
+    &header.len //~ ERROR expression may be unaligned inside a packed struct and is not addressable
+};
+
+tag_ptr :: fn(header: *Header) -> *u8 {
+    &header.tag
+};
@@ -0,0 +1,51 @@
+//@ test
+//@ run
+
+// `unreachable` diverges, so the function needs no value after the branches
+sign :: fn(value: i32) -> i32 {
+    if value > 0 {
+        return 1;
+    } else if value < 0 {
+        return -1;
+    } else if value == 0 {
+        return 0;
+    }
+
+    unreachable()
+};
+
+// Diverging branches take the type of the other ones
+checked_half :: fn(value: i32) -> i32 {
+    if value % 2 == 0 {
+        value / 2
+    } else {
+        unreachable()
+    }
+};
+
+@test
+assertion_holds :: fn() {
+    assert(sign(-5) == -1);
+    assert(checked_half(8) == 4);
+};
+
+// A failing test returns to the harness, which keeps running the tests after it
+@test
+assertion_fails :: fn() {
+    assert(sign(5) == -1);
+};
+
+@test
+explicit_panic :: fn() {
+    panic(c"something went wrong");
+};
+
+@test
+reaches_unreachable :: fn() {
+    checked_half(3);
+};
+
+@test
+runs_after_failures :: fn() {
+    assert(sign(0) == 0);
+};
@@ -0,0 +1,36 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Point :: struct {
+    x: i32,
+    y: i32,
+    z: i32,
+};
+
+export main :: fn() {
+    values: [4]i32 = [1, 2, 3, 4];
+    p: [*]i32 = (&values[0]) as [*]i32;
+    q: [*]i32 = p + 3;
+    d: isize = (q + 1) - p;
+    e: isize = q - (p + 1);
+    printf(c"%d %d %d\n", d as i32, e as i32, (10 - 7) as i32);
+
+    // Offsets and differences count elements rather than bytes
+    points: [3]Point = [
+        Point { x = 1, y = 2, z = 3 },
+        Point { x = 4, y = 5, z = 6 },
+        Point { x = 7, y = 8, z = 9 },
+    ];
+    begin: [*]Point = (&points[0]) as [*]Point;
+    mut end := begin;
+    end += 3;
+    mut last := end;
+    last -= 1;
+    printf(c"%d %d %d\n", (end - begin) as i32, (last - begin) as i32, last[0].z);
+
+    wide: [2]i64 = [10, 20];
+    first: [*]i64 = (&wide[0]) as [*]i64;
+    second := first + 1;
+    printf(c"%d %d\n", (second - first) as i32, (first - second) as i32);
+};
@@ -0,0 +1,14 @@
+Empty :: struct {};
+
+f :: fn(begin: [*]Empty, end: [*]Empty) -> isize {
+    end - begin //~ ERROR cannot subtract pointers of type '[*]ptr_diff_rejected.Empty' to zero-sized elements
+};
+
+g :: fn(begin: [*](), end: [*]()) -> isize {
+    end - begin //~ ERROR cannot subtract pointers of type '[*]()' to zero-sized elements
+};
+
+// Offsets are allowed and leave the pointer unchanged
+h :: fn(begin: [*]Empty) -> [*]Empty {
+    begin + 1
+};
@@ -0,0 +1,20 @@
+Point :: struct {
+    x: i32,
+    y: i32,
+};
+
+missing :: fn() {
+    p := Point { x = 1 }; //~ ERROR missing field initializers for type
+};
+
+duplicate :: fn() {
+    p := Point { x = 1, x = 2, y = 3 }; //~ ERROR field 'x' is initialized more than once
+};
+
+unknown :: fn() {
+    p := Point { x = 1, z = 2, y = 3 }; //~ ERROR has no field 'z'
+};
+
+not_struct :: fn() {
+    p := i32 { x = 1 }; //~ ERROR struct literals must start with a struct or union type
+};
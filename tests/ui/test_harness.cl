@@ -0,0 +1,27 @@
+//@ test
+//@ run
+
+add :: fn(a: i32, b: i32) -> i32 {
+    a + b
+};
+
+// Modules marked as tests are only compiled in test builds
+@test
+tests :: module {
+    use super.add;
+
+    @test
+    adds_numbers :: fn() {
+        assert(add(2, 3) == 5);
+    };
+
+    @test
+    fails :: fn() {
+        assert(add(2, 2) == 5);
+    };
+};
+
+// The program's entry point is not run by test builds
+export main :: fn() {
+    assert(false);
+};
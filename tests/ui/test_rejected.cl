@@ -0,0 +1,26 @@
+//@ test
+
+// The harness calls tests without arguments and ignores their result
+@test
+with_params :: fn(value: i32) { //~ ERROR expected 'fn()', found 'fn(i32)'
+    assert(value == 0);
+};
+
+@test
+with_result :: fn() -> i32 { //~ ERROR expected 'fn()', found 'fn() -> i32'
+    0
+};
+
+Counter :: struct {
+    value: i32,
+} impl {
+    @test //~ ERROR attribute '@test' cannot be applied to this item
+    get :: fn(self: *Self) -> i32 {
+        self.value
+    };
+};
+
+@test //~ ERROR attribute '@test' cannot be applied to this item
+Point :: struct {
+    x: i32,
+};
@@ -0,0 +1,52 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+NotFound :: struct {};
+
+Invalid :: struct {
+    code: i32,
+};
+
+Overflow :: struct {};
+
+lookup :: fn(key: i32) -> (i32 | NotFound | Invalid) {
+    if key < 0 {
+        return Invalid { code = key };
+    }
+
+    if key > 9 {
+        return NotFound {};
+    }
+
+    value: i32 = key * 10;
+    value
+};
+
+// Errors of `lookup` are propagated into a wider error set
+double :: fn(key: i32) -> (i32 | NotFound | Invalid | Overflow) {
+    value: i32 = lookup(key)?;
+
+    if value > 50 {
+        return Overflow {};
+    }
+
+    result: i32 = value * 2;
+    result
+};
+
+describe :: fn(key: i32) {
+    match double(key) {
+        i32 as value => { printf(c"%d: ok %d\n", key, value); }
+        NotFound => { printf(c"%d: not found\n", key); }
+        Invalid as error => { printf(c"%d: invalid %d\n", key, error.code); }
+        Overflow => { printf(c"%d: overflow\n", key); }
+    }
+};
+
+export main :: fn() {
+    describe(2);
+    describe(12);
+    describe(-3);
+    describe(7);
+};
@@ -0,0 +1,21 @@
+NotFound :: struct {};
+
+Invalid :: struct {};
+
+lookup :: fn(key: i32) -> (i32 | NotFound | Invalid) {
+    NotFound {}
+};
+
+try_non_variant :: fn(value: i32) -> (i32 | NotFound) {
+    value? //~ ERROR expected '<variant>', found 'i32'
+};
+
+try_unreturnable_error :: fn() -> (i32 | NotFound) {
+    value: i32 = lookup(1)?; //~ ERROR expected '(try_rejected.NotFound | i32)', found 'try_rejected.Invalid'
+    value
+};
+
+try_ambiguous :: fn() -> i32 {
+    lookup(1)?; //~ ERROR cannot determine the success variant
+    0
+};
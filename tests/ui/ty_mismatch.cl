@@ -0,0 +1,7 @@
+add :: fn(a: i32, b: i32) -> i32 {
+    a + b
+};
+
+add_flag :: fn() -> i32 {
+    add(1, true) //~ ERROR expected 'i32', found 'bool'
+};
@@ -0,0 +1,41 @@
+//@ run
+
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Bits :: union {
+    value: f32,
+    bits: u32,
+    bytes: [4]u8,
+};
+
+// Unions take the size and alignment of their widest and most aligned fields
+Mixed :: union {
+    byte: u8,
+    wide: u64,
+    triple: [3]u32,
+};
+
+static_assert(size_of(Bits) == 4);
+static_assert(align_of(Bits) == 4);
+static_assert(size_of(Mixed) == 16);
+static_assert(align_of(Mixed) == 8);
+
+Event :: struct {
+    kind: u8,
+    data: Bits,
+};
+
+export main :: fn() {
+    // Fields share their storage, so reading another field reinterprets the bytes
+    mut bits := Bits { value = 1.0 };
+    printf(c"%x\n", bits.bits);
+
+    bits.bits = 1077936128;
+    printf(c"%d\n", bits.value as i32);
+
+    bits.bytes[0] = 1;
+    printf(c"%x\n", bits.bits);
+
+    event := Event { kind = 1, data = Bits { bits = 65 } };
+    printf(c"%d %d\n", event.kind as i32, event.data.bytes[0] as i32);
+};
@@ -0,0 +1,17 @@
+Bits :: union {
+    value: f32,
+    bits: u32,
+};
+
+empty :: fn() -> Bits {
+    Bits {} //~ ERROR union 'union_rejected.Bits' must be initialized with exactly one field
+};
+
+both :: fn() -> Bits {
+    Bits { value = 1.0, bits = 2 } //~ ERROR union 'union_rejected.Bits' must be initialized with exactly one field
+};
+
+missing :: fn() -> u32 {
+    bits := Bits { bits = 2 };
+    bits.other //~ ERROR type 'union_rejected.Bits' has no field 'other'
+};
@@ -0,0 +1,4 @@
+f :: fn() {
+    x := undefined_value;
+    //~^ ERROR 'undefined_value' could not be found
+};
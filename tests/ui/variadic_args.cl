@@ -0,0 +1,18 @@
+printf :: extern fn(format: [*]i8, ...) -> i32;
+
+Point :: struct {
+    x: i32,
+    y: i32,
+};
+
+scalars :: fn(p: *Point) {
+    printf(c"%d %f %c %p\n", 1, 2.5, 'a', p);
+};
+
+struct_arg :: fn(p: Point) {
+    printf(c"%d\n", p); //~ ERROR values of type 'variadic_args.Point' cannot be passed as variadic arguments
+};
+
+tuple_arg :: fn() {
+    printf(c"%d\n", (1, 2)); //~ ERROR cannot be passed as variadic arguments
+};